@@ -1,3 +1,4 @@
+use chrono::NaiveTime;
 pub use theme::Theme;
 
 pub mod theme;
@@ -20,7 +21,12 @@ impl Default for Appearance {
 #[derive(Debug, Clone)]
 pub enum Selected {
     Static(Theme),
-    Dynamic { light: Theme, dark: Theme },
+    Dynamic {
+        light: Theme,
+        dark: Theme,
+    },
+    /// Themes by the time of day they start at, earliest first.
+    Scheduled(Vec<(NaiveTime, Theme)>),
 }
 
 impl Default for Selected {
@@ -32,11 +38,35 @@ impl Default for Selected {
 impl Selected {
     pub fn is_dynamic(&self) -> bool {
         match self {
-            Selected::Static(_) => false,
+            Selected::Static(_) | Selected::Scheduled(_) => false,
             Selected::Dynamic { .. } => true,
         }
     }
 
+    /// The scheduled theme at `time`.  Before the earliest start, the
+    /// latest theme of the previous day is still in effect.
+    pub fn scheduled(&self, time: NaiveTime) -> Option<&Theme> {
+        self.slot(time).map(|(_, theme)| theme)
+    }
+
+    /// When the scheduled theme in effect at `time` started, which only
+    /// changes when the next theme is due.
+    pub fn scheduled_slot(&self, time: NaiveTime) -> Option<NaiveTime> {
+        self.slot(time).map(|(start, _)| *start)
+    }
+
+    fn slot(&self, time: NaiveTime) -> Option<&(NaiveTime, Theme)> {
+        let Selected::Scheduled(schedule) = self else {
+            return None;
+        };
+
+        schedule
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= time)
+            .or_else(|| schedule.last())
+    }
+
     pub fn dynamic(light: Theme, dark: Theme) -> Selected {
         Selected::Dynamic { light, dark }
     }
@@ -44,4 +74,45 @@ impl Selected {
     pub fn specific(theme: Theme) -> Selected {
         Selected::Static(theme)
     }
+
+    pub fn scheduled_by(mut schedule: Vec<(NaiveTime, Theme)>) -> Selected {
+        schedule.sort_by_key(|(start, _)| *start);
+
+        Selected::Scheduled(schedule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scheduled() {
+        let theme = |name: &str| Theme {
+            name: name.to_string(),
+            ..Theme::default()
+        };
+        let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+
+        let selected = Selected::scheduled_by(vec![
+            (time(19), theme("dark")),
+            (time(7), theme("light")),
+        ]);
+
+        let name = |hour| {
+            selected
+                .scheduled(time(hour))
+                .map(|theme| theme.name.as_str())
+        };
+
+        assert_eq!(name(3), Some("dark"));
+        assert_eq!(name(7), Some("light"));
+        assert_eq!(name(12), Some("light"));
+        assert_eq!(name(19), Some("dark"));
+        assert_eq!(name(23), Some("dark"));
+
+        assert_eq!(selected.scheduled_slot(time(3)), Some(time(19)));
+        assert_eq!(selected.scheduled_slot(time(12)), Some(time(7)));
+        assert_eq!(Selected::default().scheduled_slot(time(12)), None);
+    }
 }
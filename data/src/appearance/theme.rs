@@ -13,6 +13,8 @@ use tokio::fs;
 
 use crate::config::buffer;
 
+pub mod import;
+
 const DEFAULT_THEME_NAME: &str = "Ferra";
 const DEFAULT_THEME_CONTENT: &str =
     include_str!("../../../assets/themes/ferra.toml");
@@ -590,10 +592,10 @@ pub fn nickname_color(
 
 pub fn nickname_alpha(
     color: Color,
-    is_away: Option<buffer::Away>,
+    is_away: Option<buffer::AwayAppearance>,
     background_color: Color,
 ) -> Color {
-    if let Some(buffer::Away::Dimmed(dimmed)) = is_away {
+    if let Some(buffer::AwayAppearance::Dimmed(dimmed)) = is_away {
         dimmed.transform_color(color, background_color)
     } else {
         color
@@ -631,6 +633,83 @@ pub fn adapt_nickname_color(
     }
 }
 
+/// Minimum contrast ratio of text against its background (WCAG 2 AA).
+pub const MINIMUM_CONTRAST: f32 = 4.5;
+
+/// WCAG 2 contrast ratio of `foreground` drawn over `background`, from 1.0
+/// up to 21.0.
+pub fn contrast_ratio(foreground: Color, background: Color) -> f32 {
+    let foreground = Color::from_rgb(
+        foreground.r * foreground.a + background.r * (1.0 - foreground.a),
+        foreground.g * foreground.a + background.g * (1.0 - foreground.a),
+        foreground.b * foreground.a + background.b * (1.0 - foreground.a),
+    );
+
+    let (lighter, darker) = {
+        let foreground = relative_luminance(foreground);
+        let background = relative_luminance(background);
+
+        (foreground.max(background), foreground.min(background))
+    };
+
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Moves the lightness of `foreground` away from `background` until their
+/// contrast ratio reaches `minimum`, keeping its hue and saturation.  Colors
+/// that already reach it are returned as is.
+pub fn fix_contrast(
+    foreground: Color,
+    background: Color,
+    minimum: f32,
+) -> Color {
+    if contrast_ratio(foreground, background) >= minimum {
+        return foreground;
+    }
+
+    let hsl = to_hsl(alpha_color(foreground, 1.0));
+    let lighten = relative_luminance(background) < 0.18;
+
+    let mut fixed = foreground;
+
+    for step in 1..=100u8 {
+        let offset = f32::from(step) / 100.0;
+        let lightness = if lighten {
+            (hsl.lightness + offset).min(1.0)
+        } else {
+            (hsl.lightness - offset).max(0.0)
+        };
+
+        fixed = alpha_color(
+            from_hsl(Okhsl::new(hsl.hue, hsl.saturation, lightness)),
+            1.0,
+        );
+
+        if contrast_ratio(fixed, background) >= minimum
+            || lightness == 0.0
+            || lightness == 1.0
+        {
+            break;
+        }
+    }
+
+    fixed
+}
+
+fn relative_luminance(color: Color) -> f32 {
+    let linear = |channel: f32| {
+        if channel <= 0.04045 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    };
+
+    0.2126 * linear(color.r)
+        + 0.7152 * linear(color.g)
+        + 0.0722 * linear(color.b)
+}
+
 pub fn to_hsl(color: Color) -> Okhsl {
     let mut hsl = Okhsl::from_color(to_rgb(color));
     if hsl.saturation.is_nan() {
@@ -1134,3 +1213,30 @@ mod binary {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contrast() {
+        assert!(
+            (contrast_ratio(Color::BLACK, Color::WHITE) - 21.0).abs() < 0.01
+        );
+        assert!(
+            (contrast_ratio(Color::WHITE, Color::WHITE) - 1.0).abs() < 0.01
+        );
+
+        let background = hex_to_color("#1F1F1F").unwrap();
+        let foreground = hex_to_color("#3A3A3A").unwrap();
+
+        let fixed = fix_contrast(foreground, background, MINIMUM_CONTRAST);
+        assert!(contrast_ratio(fixed, background) >= MINIMUM_CONTRAST);
+
+        let background = Color::WHITE;
+        let foreground = hex_to_color("#DDDD00").unwrap();
+
+        let fixed = fix_contrast(foreground, background, MINIMUM_CONTRAST);
+        assert!(contrast_ratio(fixed, background) >= MINIMUM_CONTRAST);
+    }
+}
@@ -0,0 +1,746 @@
+//! Converts color schemes made for other clients and terminals into
+//! [`Styles`].
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use iced_core::Color;
+use thiserror::Error;
+use tokio::fs;
+
+use super::{OptionalTextStyle, Styles, TextStyle, hex_to_color};
+use crate::Config;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// base16 and base24 YAML schemes
+    Base16,
+    /// iTerm2 `.itermcolors`
+    Iterm2,
+    /// Alacritty TOML color configuration
+    Alacritty,
+    /// kitty color configuration
+    Kitty,
+    /// `[color]` section of `weechat.conf`
+    Weechat,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Base16 => write!(f, "base16"),
+            Format::Iterm2 => write!(f, "iTerm2"),
+            Format::Alacritty => write!(f, "Alacritty"),
+            Format::Kitty => write!(f, "kitty"),
+            Format::Weechat => write!(f, "WeeChat"),
+        }
+    }
+}
+
+impl Format {
+    /// Guesses the format from the file name, falling back to the content.
+    pub fn detect(path: &Path, content: &str) -> Option<Self> {
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        if file_name == "weechat.conf" {
+            return Some(Format::Weechat);
+        }
+
+        match path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("yaml" | "yml") => return Some(Format::Base16),
+            Some("itermcolors") => return Some(Format::Iterm2),
+            Some("toml") => return Some(Format::Alacritty),
+            _ => (),
+        }
+
+        if content.contains("<plist") {
+            Some(Format::Iterm2)
+        } else if content.contains("base00") {
+            Some(Format::Base16)
+        } else if content.contains("[colors") {
+            Some(Format::Alacritty)
+        } else if content.contains("chat_bg") || content.contains("[color]") {
+            Some(Format::Weechat)
+        } else if content.lines().any(|line| line.starts_with("color0")) {
+            Some(Format::Kitty)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("unrecognized color scheme format")]
+    UnknownFormat,
+    #[error("color scheme has no {0} color")]
+    MissingColor(&'static str),
+    #[error("invalid TOML: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("failed to read color scheme: {0}")]
+    Read(#[from] std::io::Error),
+    #[error(transparent)]
+    Save(#[from] super::Error),
+}
+
+/// A theme imported into the themes directory.
+#[derive(Debug, Clone)]
+pub struct Imported {
+    pub name: String,
+    pub path: PathBuf,
+    pub styles: Styles,
+}
+
+/// Converts the color scheme at `path` and saves it as `<name>.toml` in
+/// [`Config::themes_dir`].  The name defaults to the one given by the
+/// scheme or the file name.
+pub async fn import_file(
+    path: PathBuf,
+    name: Option<String>,
+) -> Result<Imported, Error> {
+    let content = fs::read_to_string(&path).await?;
+
+    let format = Format::detect(&path, &content).ok_or(Error::UnknownFormat)?;
+
+    let (scheme_name, styles) = import(format, &content)?;
+
+    let name = name
+        .or(scheme_name)
+        .or_else(|| {
+            path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .map(ToString::to_string)
+        })
+        .map_or_else(|| "imported".to_string(), |name| file_name(&name));
+
+    let path = Config::themes_dir().join(format!("{name}.toml"));
+
+    styles.save(path.clone()).await?;
+
+    Ok(Imported { name, path, styles })
+}
+
+/// Converts `content` into styles, along with the name of the scheme when
+/// it has one.
+pub fn import(
+    format: Format,
+    content: &str,
+) -> Result<(Option<String>, Styles), Error> {
+    match format {
+        Format::Base16 => {
+            let (name, palette) = base16(content)?;
+
+            Ok((name, palette.styles()))
+        }
+        Format::Iterm2 => Ok((None, iterm2(content)?.styles())),
+        Format::Alacritty => Ok((None, alacritty(content)?.styles())),
+        Format::Kitty => Ok((None, kitty(content)?.styles())),
+        Format::Weechat => Ok((None, weechat(content))),
+    }
+}
+
+/// Colors of a terminal: the default background and foreground, and the 16
+/// ANSI colors.
+#[derive(Debug, Clone, Copy)]
+struct Palette {
+    background: Color,
+    foreground: Color,
+    selection: Option<Color>,
+    ansi: [Color; 16],
+}
+
+impl Palette {
+    /// Default xterm colors, used for the colors a scheme leaves out.
+    fn xterm() -> Self {
+        let ansi = [
+            "#000000", "#CD0000", "#00CD00", "#CDCD00", "#0000EE", "#CD00CD",
+            "#00CDCD", "#E5E5E5", "#7F7F7F", "#FF0000", "#00FF00", "#FFFF00",
+            "#5C5CFF", "#FF00FF", "#00FFFF", "#FFFFFF",
+        ]
+        .map(|hex| hex_to_color(hex).unwrap_or(Color::BLACK));
+
+        Self {
+            background: ansi[0],
+            foreground: ansi[7],
+            selection: None,
+            ansi,
+        }
+    }
+
+    fn styles(&self) -> Styles {
+        let Palette {
+            background,
+            foreground,
+            selection,
+            ansi,
+        } = *self;
+
+        let is_dark = luminance(background) < 0.5;
+        let shade = |amount: f32| {
+            mix(
+                background,
+                if is_dark { Color::BLACK } else { Color::WHITE },
+                amount,
+            )
+        };
+        let tint = |amount: f32| mix(background, foreground, amount);
+        let text = |color: Color| TextStyle {
+            color,
+            font_style: None,
+        };
+        let optional = |color: Color| OptionalTextStyle {
+            color: Some(color),
+            font_style: None,
+        };
+
+        let mut styles = Styles::default();
+
+        styles.general.background = tint(0.04);
+        styles.general.border = tint(0.2);
+        styles.general.horizontal_rule = tint(0.08);
+        styles.general.scrollbar = Some(tint(0.08));
+        styles.general.unread_indicator = ansi[3];
+        styles.general.highlight_indicator = Some(ansi[11]);
+
+        styles.text.primary = text(foreground);
+        styles.text.secondary = text(mix(foreground, background, 0.35));
+        styles.text.tertiary = text(ansi[5]);
+        styles.text.success = text(ansi[2]);
+        styles.text.error = text(ansi[1]);
+        styles.text.warning = optional(ansi[3]);
+        styles.text.info = optional(ansi[6]);
+        styles.text.debug = optional(ansi[13]);
+        styles.text.trace = optional(ansi[7]);
+
+        styles.buffer.background = background;
+        styles.buffer.background_text_input = shade(0.12);
+        styles.buffer.background_title_bar = shade(0.12);
+        styles.buffer.border = tint(0.2);
+        styles.buffer.border_selected = tint(0.4);
+        styles.buffer.timestamp = text(mix(foreground, background, 0.5));
+        styles.buffer.action = text(ansi[2]);
+        styles.buffer.topic = text(mix(foreground, background, 0.35));
+        styles.buffer.highlight = mix(background, ansi[3], 0.25);
+        styles.buffer.code = text(ansi[13]);
+        styles.buffer.nickname = text(ansi[4]);
+        styles.buffer.nickname_offline = optional(ansi[8]);
+        styles.buffer.url = text(ansi[6]);
+        styles.buffer.selection = selection.unwrap_or_else(|| tint(0.2));
+        styles.buffer.server_messages.default = text(ansi[3]);
+
+        styles.buttons.primary.background = tint(0.04);
+        styles.buttons.primary.background_hover = background;
+        styles.buttons.primary.background_selected = shade(0.12);
+        styles.buttons.primary.background_selected_hover = shade(0.25);
+        styles.buttons.secondary.background = tint(0.08);
+        styles.buttons.secondary.background_hover = tint(0.12);
+        styles.buttons.secondary.background_selected =
+            mix(background, ansi[2], 0.35);
+        styles.buttons.secondary.background_selected_hover =
+            mix(background, ansi[2], 0.45);
+
+        // mIRC colors by their closest ANSI color
+        let formatting = &mut styles.formatting;
+        formatting.white = Some(ansi[15]);
+        formatting.black = Some(ansi[0]);
+        formatting.blue = Some(ansi[4]);
+        formatting.green = Some(ansi[2]);
+        formatting.red = Some(ansi[9]);
+        formatting.brown = Some(ansi[1]);
+        formatting.magenta = Some(ansi[5]);
+        formatting.orange = Some(ansi[3]);
+        formatting.yellow = Some(ansi[11]);
+        formatting.lightgreen = Some(ansi[10]);
+        formatting.cyan = Some(ansi[6]);
+        formatting.lightcyan = Some(ansi[14]);
+        formatting.lightblue = Some(ansi[12]);
+        formatting.pink = Some(ansi[13]);
+        formatting.grey = Some(ansi[8]);
+        formatting.lightgrey = Some(ansi[7]);
+
+        styles
+    }
+}
+
+fn base16(content: &str) -> Result<(Option<String>, Palette), Error> {
+    let mut name = None;
+    let mut bases = HashMap::new();
+
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        let key = key.trim();
+        let value = value
+            .split(" #")
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .trim_matches(['"', '\'']);
+
+        if matches!(key, "scheme" | "name") && !value.is_empty() {
+            name = Some(value.to_string());
+        } else if let Some(base) = key.strip_prefix("base")
+            && let Ok(base) = u8::from_str_radix(base, 16)
+            && let Some(color) = parse_hex(value)
+        {
+            bases.insert(base, color);
+        }
+    }
+
+    let base = |index: u8| bases.get(&index).copied();
+    let required = |index: u8, name: &'static str| {
+        base(index).ok_or(Error::MissingColor(name))
+    };
+
+    let background = required(0x00, "base00")?;
+    let foreground = required(0x05, "base05")?;
+    let red = required(0x08, "base08")?;
+    let yellow = required(0x0A, "base0A")?;
+    let green = required(0x0B, "base0B")?;
+    let cyan = required(0x0C, "base0C")?;
+    let blue = required(0x0D, "base0D")?;
+    let magenta = required(0x0E, "base0E")?;
+
+    // base24 adds bright variants, base16 reuses the normal ones
+    let ansi = [
+        background,
+        red,
+        green,
+        yellow,
+        blue,
+        magenta,
+        cyan,
+        foreground,
+        base(0x03).unwrap_or(foreground),
+        base(0x12).unwrap_or(red),
+        base(0x14).unwrap_or(green),
+        base(0x13).unwrap_or(yellow),
+        base(0x16).unwrap_or(blue),
+        base(0x17).unwrap_or(magenta),
+        base(0x15).unwrap_or(cyan),
+        base(0x07).unwrap_or(foreground),
+    ];
+
+    Ok((
+        name,
+        Palette {
+            background,
+            foreground,
+            selection: base(0x02),
+            ansi,
+        },
+    ))
+}
+
+fn iterm2(content: &str) -> Result<Palette, Error> {
+    let mut colors = HashMap::new();
+    let mut depth = 0;
+    let mut key = None;
+    let mut component = None;
+    let mut rgb = [0.0; 3];
+
+    // Each piece starts with a tag followed by its text, if any
+    for piece in content.split('<').skip(1) {
+        let Some((tag, text)) = piece.split_once('>') else {
+            continue;
+        };
+
+        if tag.starts_with(['?', '!']) || tag.ends_with('/') {
+            continue;
+        }
+
+        match tag {
+            "dict" => {
+                depth += 1;
+                rgb = [0.0; 3];
+            }
+            "/dict" => {
+                if depth == 2
+                    && let Some(key) = key.take()
+                {
+                    colors.insert(key, Color::from_rgb(rgb[0], rgb[1], rgb[2]));
+                }
+
+                depth -= 1;
+            }
+            "key" if depth == 1 => key = Some(text.trim().to_string()),
+            "key" => component = Some(text.trim().to_string()),
+            "real" | "integer" if depth == 2 => {
+                let value = text.trim().parse::<f32>().unwrap_or_default();
+
+                match component.as_deref() {
+                    Some("Red Component") => rgb[0] = value,
+                    Some("Green Component") => rgb[1] = value,
+                    Some("Blue Component") => rgb[2] = value,
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    terminal_palette(
+        colors.get("Background Color").copied(),
+        colors.get("Foreground Color").copied(),
+        colors.get("Selection Color").copied(),
+        |index| colors.get(&format!("Ansi {index} Color")).copied(),
+    )
+}
+
+fn alacritty(content: &str) -> Result<Palette, Error> {
+    const NAMES: [&str; 8] = [
+        "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+    ];
+
+    let table = content.parse::<toml::Table>()?;
+
+    let color = |section: &str, name: &str| {
+        table
+            .get("colors")?
+            .get(section)?
+            .get(name)?
+            .as_str()
+            .and_then(parse_hex)
+    };
+
+    terminal_palette(
+        color("primary", "background"),
+        color("primary", "foreground"),
+        color("selection", "background"),
+        |index| {
+            let section = if index < 8 { "normal" } else { "bright" };
+
+            color(section, NAMES[index % 8])
+        },
+    )
+}
+
+fn kitty(content: &str) -> Result<Palette, Error> {
+    let colors = content
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.trim().split_once(char::is_whitespace)?;
+
+            Some((key, parse_hex(value.trim())?))
+        })
+        .collect::<HashMap<_, _>>();
+
+    terminal_palette(
+        colors.get("background").copied(),
+        colors.get("foreground").copied(),
+        colors.get("selection_background").copied(),
+        |index| colors.get(format!("color{index}").as_str()).copied(),
+    )
+}
+
+/// WeeChat colors are named terminal colors, so the xterm palette stands
+/// in for the terminal.
+fn weechat(content: &str) -> Styles {
+    let mut section = "";
+    let mut options = HashMap::new();
+
+    for line in content.lines().map(str::trim) {
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            section = name;
+        } else if section == "color"
+            && let Some((key, value)) = line.split_once('=')
+        {
+            options
+                .insert(key.trim(), value.trim().trim_matches('"').to_string());
+        }
+    }
+
+    let mut palette = Palette::xterm();
+    let color = |option: &str| {
+        options
+            .get(option)
+            .and_then(|value| weechat_color(value, &palette.ansi))
+    };
+
+    if let Some(background) = color("chat_bg") {
+        palette.background = background;
+    }
+
+    if let Some(foreground) = color("chat") {
+        palette.foreground = foreground;
+    }
+
+    let mut styles = palette.styles();
+
+    let color = |option: &str| {
+        options
+            .get(option)
+            .and_then(|value| weechat_color(value, &palette.ansi))
+    };
+    let set_text = |style: &mut TextStyle, option: &str| {
+        if let Some(color) = color(option) {
+            style.color = color;
+        }
+    };
+    let set_optional = |style: &mut OptionalTextStyle, option: &str| {
+        if let Some(color) = color(option) {
+            style.color = Some(color);
+        }
+    };
+
+    set_text(&mut styles.buffer.timestamp, "chat_time");
+    set_text(&mut styles.buffer.nickname, "chat_nick");
+    set_text(&mut styles.buffer.topic, "chat_channel");
+    set_text(&mut styles.buffer.action, "chat_prefix_action");
+    set_text(
+        &mut styles.buffer.server_messages.default,
+        "chat_prefix_network",
+    );
+    set_text(&mut styles.text.error, "chat_prefix_error");
+    set_optional(&mut styles.buffer.server_messages.join, "chat_prefix_join");
+    set_optional(&mut styles.buffer.server_messages.part, "chat_prefix_quit");
+    set_optional(&mut styles.buffer.server_messages.quit, "chat_prefix_quit");
+    set_optional(&mut styles.buffer.nickname_offline, "chat_nick_offline");
+
+    if let Some(highlight) = color("chat_highlight_bg") {
+        styles.buffer.highlight = mix(palette.background, highlight, 0.5);
+    }
+
+    if let Some(separator) = color("separator") {
+        styles.general.border = separator;
+        styles.buffer.border = separator;
+    }
+
+    styles
+}
+
+/// Parses a WeeChat color such as `lightcyan`, `*214` or `default`.
+fn weechat_color(value: &str, ansi: &[Color; 16]) -> Option<Color> {
+    // Attributes: bold, reverse, italic, underline and keep
+    let value = value.trim_start_matches(['*', '!', '/', '_', '|']);
+
+    let index = match value {
+        "black" => 0,
+        "red" => 1,
+        "green" => 2,
+        "brown" => 3,
+        "blue" => 4,
+        "magenta" => 5,
+        "cyan" => 6,
+        "gray" => 7,
+        "darkgray" => 8,
+        "lightred" => 9,
+        "lightgreen" => 10,
+        "yellow" => 11,
+        "lightblue" => 12,
+        "lightmagenta" => 13,
+        "lightcyan" => 14,
+        "white" => 15,
+        _ => value.parse::<u8>().ok()?,
+    };
+
+    Some(xterm_256(index, ansi))
+}
+
+fn xterm_256(index: u8, ansi: &[Color; 16]) -> Color {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    match index {
+        0..=15 => ansi[usize::from(index)],
+        16..=231 => {
+            let index = usize::from(index - 16);
+
+            Color::from_rgb8(
+                LEVELS[index / 36],
+                LEVELS[index / 6 % 6],
+                LEVELS[index % 6],
+            )
+        }
+        _ => {
+            let level = 8 + (index - 232) * 10;
+
+            Color::from_rgb8(level, level, level)
+        }
+    }
+}
+
+fn terminal_palette(
+    background: Option<Color>,
+    foreground: Option<Color>,
+    selection: Option<Color>,
+    ansi: impl Fn(usize) -> Option<Color>,
+) -> Result<Palette, Error> {
+    let xterm = Palette::xterm();
+
+    Ok(Palette {
+        background: background.ok_or(Error::MissingColor("background"))?,
+        foreground: foreground.ok_or(Error::MissingColor("foreground"))?,
+        selection,
+        ansi: std::array::from_fn(|index| {
+            ansi(index).unwrap_or(xterm.ansi[index])
+        }),
+    })
+}
+
+/// Parses `#rrggbb`, `0xrrggbb` or `rrggbb`.
+fn parse_hex(value: &str) -> Option<Color> {
+    let hex = value
+        .strip_prefix('#')
+        .or_else(|| value.strip_prefix("0x"))
+        .unwrap_or(value);
+
+    if hex.len() != 6 {
+        return None;
+    }
+
+    hex_to_color(&format!("#{hex}"))
+}
+
+fn mix(from: Color, to: Color, amount: f32) -> Color {
+    Color::from_rgb(
+        from.r + (to.r - from.r) * amount,
+        from.g + (to.g - from.g) * amount,
+        from.b + (to.b - from.b) * amount,
+    )
+}
+
+fn luminance(color: Color) -> f32 {
+    0.299 * color.r + 0.587 * color.g + 0.114 * color.b
+}
+
+/// Keeps the name usable as a file name.
+fn file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | ' ') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base16() {
+        let scheme = r#"
+scheme: "Tomorrow Night"
+author: "Chris Kempson"
+base00: "1d1f21" # background
+base01: "282a2e"
+base02: "373b41"
+base03: "969896"
+base04: "b4b7b4"
+base05: "c5c8c6"
+base06: "e0e0e0"
+base07: "ffffff"
+base08: "cc6666"
+base09: "de935f"
+base0A: "f0c674"
+base0B: "b5bd68"
+base0C: "8abeb7"
+base0D: "81a2be"
+base0E: "b294bb"
+base0F: "a3685a"
+"#;
+
+        let (name, styles) = import(Format::Base16, scheme).unwrap();
+
+        assert_eq!(name.as_deref(), Some("Tomorrow Night"));
+        assert_eq!(styles.buffer.background, parse_hex("1d1f21").unwrap());
+        assert_eq!(styles.text.primary.color, parse_hex("c5c8c6").unwrap());
+        assert_eq!(styles.formatting.red, parse_hex("cc6666"));
+
+        assert!(matches!(
+            import(Format::Base16, "base00: \"1d1f21\""),
+            Err(Error::MissingColor("base05"))
+        ));
+    }
+
+    #[test]
+    fn terminals() {
+        let iterm2 = r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0">
+<dict>
+	<key>Ansi 1 Color</key>
+	<dict>
+		<key>Blue Component</key>
+		<real>0.0</real>
+		<key>Green Component</key>
+		<real>0.0</real>
+		<key>Red Component</key>
+		<real>1</real>
+	</dict>
+	<key>Background Color</key>
+	<dict>
+		<key>Blue Component</key>
+		<real>0.0</real>
+		<key>Green Component</key>
+		<real>0.0</real>
+		<key>Red Component</key>
+		<real>0.0</real>
+	</dict>
+	<key>Foreground Color</key>
+	<dict>
+		<key>Blue Component</key>
+		<real>1</real>
+		<key>Green Component</key>
+		<real>1</real>
+		<key>Red Component</key>
+		<real>1</real>
+	</dict>
+</dict>
+</plist>"#;
+
+        let palette = super::iterm2(iterm2).unwrap();
+        assert_eq!(palette.background, Color::BLACK);
+        assert_eq!(palette.foreground, Color::WHITE);
+        assert_eq!(palette.ansi[1], Color::from_rgb(1.0, 0.0, 0.0));
+
+        let alacritty = r##"
+[colors.primary]
+background = "0x1d1f21"
+foreground = "#c5c8c6"
+
+[colors.normal]
+red = "#cc6666"
+"##;
+
+        let palette = super::alacritty(alacritty).unwrap();
+        assert_eq!(palette.background, parse_hex("1d1f21").unwrap());
+        assert_eq!(palette.ansi[1], parse_hex("cc6666").unwrap());
+
+        let kitty = "# comment\nbackground #1d1f21\nforeground  #c5c8c6\ncolor1 #cc6666\n";
+
+        let palette = super::kitty(kitty).unwrap();
+        assert_eq!(palette.foreground, parse_hex("c5c8c6").unwrap());
+        assert_eq!(palette.ansi[1], parse_hex("cc6666").unwrap());
+    }
+
+    #[test]
+    fn weechat() {
+        let conf = "[look]\nbuffer_time_format = \"%H:%M\"\n\n[color]\nchat_bg = 235\nchat_nick = lightcyan\nchat_time = *white\n";
+
+        let styles = super::weechat(conf);
+        let ansi = Palette::xterm().ansi;
+
+        assert_eq!(styles.buffer.background, Color::from_rgb8(38, 38, 38));
+        assert_eq!(styles.buffer.nickname.color, ansi[14]);
+        assert_eq!(styles.buffer.timestamp.color, ansi[15]);
+    }
+}
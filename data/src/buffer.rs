@@ -5,6 +5,7 @@ use chrono::Locale;
 use iced_core::Color as IcedColor;
 use serde::{Deserialize, Deserializer, Serialize};
 
+pub mod switcher;
 pub mod timestamp;
 
 pub use self::timestamp::Timestamp;
@@ -33,10 +34,14 @@ pub enum Upstream {
 pub enum Internal {
     #[strum(serialize = "File Transfers")]
     FileTransfers,
+    #[strum(serialize = "Scheduled Messages")]
+    ScheduledMessages,
     Logs,
     Highlights,
     #[strum(serialize = "Channel Discovery")]
     ChannelDiscovery(Option<Server>),
+    #[strum(serialize = "Server Notices")]
+    ServerNotices(Server),
 }
 
 impl Buffer {
@@ -111,6 +116,7 @@ impl Upstream {
 impl Internal {
     pub const ALL: &'static [Self] = &[
         Self::FileTransfers,
+        Self::ScheduledMessages,
         Self::Logs,
         Self::Highlights,
         Self::ChannelDiscovery(None),
@@ -119,9 +125,13 @@ impl Internal {
     pub fn key(&self) -> String {
         match self {
             Internal::FileTransfers => "file-transfers",
+            Internal::ScheduledMessages => "scheduled-messages",
             Internal::Logs => "logs",
             Internal::Highlights => "highlights",
             Internal::ChannelDiscovery(_) => "channel-discovery",
+            Internal::ServerNotices(server) => {
+                return format!("server-notices:{server}");
+            }
         }
         .to_string()
     }
@@ -0,0 +1,157 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use super::Upstream;
+
+const RECENT_LEN: usize = 64;
+
+/// Upstream buffers in the order they were last focused, most recent first.
+#[derive(Debug, Clone, Default)]
+pub struct Recent(VecDeque<Upstream>);
+
+impl Recent {
+    pub fn push(&mut self, buffer: Upstream) {
+        self.0.retain(|recent| *recent != buffer);
+        self.0.push_front(buffer);
+        self.0.truncate(RECENT_LEN);
+    }
+
+    fn position(&self, buffer: &Upstream) -> Option<usize> {
+        self.0.iter().position(|recent| recent == buffer)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub buffer: Upstream,
+    pub has_unread: bool,
+    pub has_highlight: bool,
+}
+
+/// Orders the entries offered by the buffer switcher: buffers with
+/// highlights first, then buffers with unread messages, each by how recently
+/// they were focused.  The current buffer goes last, since switching to it
+/// would do nothing.
+pub fn rank(
+    mut entries: Vec<Entry>,
+    recent: &Recent,
+    current: Option<&Upstream>,
+) -> Vec<Entry> {
+    entries.sort_by_key(|entry| {
+        (
+            Some(&entry.buffer) == current,
+            !entry.has_highlight,
+            !entry.has_unread,
+            recent.position(&entry.buffer).unwrap_or(usize::MAX),
+        )
+    });
+
+    entries
+}
+
+impl Entry {
+    /// Marks buffers with highlights or unread messages.
+    pub fn marker(&self) -> Option<&'static str> {
+        if self.has_highlight {
+            Some("!")
+        } else if self.has_unread {
+            Some("•")
+        } else {
+            None
+        }
+    }
+
+    /// Name of the buffer, which is what the switcher searches.
+    pub fn name(&self) -> String {
+        match &self.buffer {
+            Upstream::Server(server) => server.to_string(),
+            Upstream::Channel(server, channel) => {
+                format!("{channel} ({server})")
+            }
+            Upstream::Query(server, query) => format!("{query} ({server})"),
+        }
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.marker() {
+            Some(marker) => write!(f, "{marker} {}", self.name()),
+            None => write!(f, "{}", self.name()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::ServerName;
+    use crate::{Server, isupport, target};
+
+    fn channel(name: &str) -> Upstream {
+        Upstream::Channel(
+            Server::from(ServerName::from("libera")),
+            target::Channel::from_str(
+                name,
+                isupport::DEFAULT_CHANTYPES,
+                isupport::CaseMap::default(),
+            ),
+        )
+    }
+
+    fn entry(name: &str, has_unread: bool, has_highlight: bool) -> Entry {
+        Entry {
+            buffer: channel(name),
+            has_unread,
+            has_highlight,
+        }
+    }
+
+    #[test]
+    fn rank_entries() {
+        let mut recent = Recent::default();
+        recent.push(channel("#b"));
+        recent.push(channel("#c"));
+        recent.push(channel("#a"));
+
+        let entries = vec![
+            entry("#a", false, false),
+            entry("#b", false, false),
+            entry("#c", false, false),
+            entry("#d", true, false),
+            entry("#e", true, true),
+            entry("#f", false, false),
+        ];
+
+        let ranked = rank(entries, &recent, Some(&channel("#a")))
+            .into_iter()
+            .map(|entry| entry.buffer)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            ranked,
+            vec![
+                channel("#e"),
+                channel("#d"),
+                channel("#c"),
+                channel("#b"),
+                channel("#f"),
+                channel("#a"),
+            ]
+        );
+    }
+
+    #[test]
+    fn name_leaves_out_marker() {
+        let highlighted = entry("#halloy", true, true);
+        let unread = entry("#halloy", true, false);
+
+        assert_eq!(highlighted.name(), "#halloy (libera)");
+        assert_eq!(highlighted.to_string(), "! #halloy (libera)");
+        assert_eq!(unread.to_string(), "• #halloy (libera)");
+        assert_eq!(
+            entry("#halloy", false, false).to_string(),
+            "#halloy (libera)"
+        );
+    }
+}
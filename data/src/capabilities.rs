@@ -35,11 +35,14 @@ pub enum Capability {
     Multiline,
     MultiPrefix,
     Metadata,
+    PreAway,
     ReadMarker,
     Sasl,
     ServerTime,
     Setname,
     UserhostInNames,
+    WebPush,
+    ZncPlayback,
 }
 
 impl FromStr for Capability {
@@ -64,10 +67,13 @@ impl FromStr for Capability {
             "draft/message-redaction" => Ok(Self::MessageRedaction),
             "multi-prefix" => Ok(Self::MultiPrefix),
             "draft/metadata-2" => Ok(Self::Metadata),
+            "draft/pre-away" => Ok(Self::PreAway),
             "server-time" => Ok(Self::ServerTime),
             "setname" => Ok(Self::Setname),
             "soju.im/bouncer-networks" => Ok(Self::BouncerNetworks),
             "userhost-in-names" => Ok(Self::UserhostInNames),
+            "soju.im/webpush" => Ok(Self::WebPush),
+            "znc.in/playback" => Ok(Self::ZncPlayback),
             _ if cap.starts_with("sasl") => Ok(Self::Sasl),
             _ => Err("unknown capability"),
         }
@@ -430,6 +436,26 @@ impl Capabilities {
             requested.push("draft/metadata-2");
         }
 
+        if self.pending.contains_key("draft/pre-away")
+            && !self.acknowledged(Capability::PreAway)
+        {
+            requested.push("draft/pre-away");
+        }
+
+        if self.pending.contains_key("soju.im/webpush")
+            && config.webpush.enabled
+            && !self.acknowledged(Capability::WebPush)
+        {
+            requested.push("soju.im/webpush");
+        }
+
+        if self.pending.contains_key("znc.in/playback")
+            && config.znc.playback
+            && !self.acknowledged(Capability::ZncPlayback)
+        {
+            requested.push("znc.in/playback");
+        }
+
         for (cap, val) in self.pending.drain() {
             self.listed.insert(cap, val);
         }
@@ -475,6 +501,12 @@ impl Capabilities {
             .flatten()
     }
 
+    // ZNC advertises its own vendor-prefixed capabilities regardless of which
+    // modules are loaded
+    pub fn listed_by_znc(&self) -> bool {
+        self.listed.keys().any(|cap| cap.starts_with("znc.in/"))
+    }
+
     pub fn contains_multiline_limits(&self) -> bool {
         self.multiline_limits().is_some()
     }
@@ -30,6 +30,7 @@ use crate::isupport::{
     ChatHistoryState, ChatHistorySubcommand, MessageReference, WhoToken,
     WhoXPollParameters, find_target_limit, format_optional_message_reference,
 };
+use crate::message::broadcast::netsplit;
 use crate::message::source;
 use crate::rate_limit::{BackoffInterval, TokenBucket, TokenPriority};
 use crate::target::{self, Target};
@@ -38,7 +39,7 @@ use crate::user::{ChannelUsers, Nick, NickRef};
 use crate::{
     Server, User, buffer, channel_discovery, compression, config, ctcp, dcc,
     environment, file_transfer, fileupload, history, isupport, message,
-    metadata, mode, server,
+    metadata, mode, server, webpush, znc,
 };
 
 pub mod on_connect;
@@ -77,6 +78,12 @@ pub enum Broadcast {
         channels: Vec<target::Channel>,
         sent_time: DateTime<Utc>,
     },
+    Netsplit {
+        kind: netsplit::Kind,
+        split: netsplit::Split,
+        users: Vec<(User, Vec<target::Channel>)>,
+        sent_time: DateTime<Utc>,
+    },
     Nickname {
         old_user: User,
         new_nick: Nick,
@@ -132,6 +139,9 @@ pub enum Message {
         Result<(), Error>,
     ),
     RequestNewerChatHistory(Server, Target, DateTime<Utc>, bool),
+    ZncPlayback(Server, Option<DateTime<Utc>>),
+    ZncPlaybackTimestampUpdated(Server, DateTime<Utc>, Result<(), znc::Error>),
+    WebPushSubscribed(Server, Result<webpush::Keys, webpush::Error>),
 }
 
 #[derive(Debug)]
@@ -178,6 +188,8 @@ pub enum Event {
     AddToSidebar(target::Query),
     Disconnect(Option<String>),
     UpdateIcon,
+    ZncPlayback,
+    WebPushSubscribe,
 }
 
 struct ChatHistoryRequest {
@@ -230,6 +242,11 @@ pub struct Client {
     http_client: Option<Arc<reqwest::Client>>, // Only Some if config.proxy.is_some()
     registry: metadata::ServerRegistry,
     monitored_users: HashMap<User, MonitoredUser>,
+    znc_version_request: VersionRequest,
+    znc_list: Option<znc::List>,
+    znc_last_seen: Option<DateTime<Utc>>,
+    connection_only_away: bool,
+    netsplits: netsplit::Tracker,
 }
 
 impl fmt::Debug for Client {
@@ -303,6 +320,11 @@ impl Client {
             channel_discovery_manager: channel_discovery::Manager::new(),
             registry: metadata::ServerRegistry::new(),
             monitored_users: HashMap::new(),
+            znc_version_request: VersionRequest::default(),
+            znc_list: None,
+            znc_last_seen: None,
+            connection_only_away: false,
+            netsplits: netsplit::Tracker::default(),
         }
     }
 
@@ -342,6 +364,28 @@ impl Client {
         Ok(())
     }
 
+    fn end_capability_negotiation(
+        &mut self,
+        config: &config::Config,
+    ) -> Result<()> {
+        self.registration_step = RegistrationStep::End;
+
+        // Declare away status before registration completes, so that bouncers
+        // do not briefly mark the user as present
+        if self.capabilities.acknowledged(Capability::PreAway)
+            && let Some(message) =
+                config.buffer.nickname.away.on_connect.as_ref()
+        {
+            self.connection_only_away =
+                config::buffer::Away::is_connection_only(message);
+            self.handle.try_send(command!("AWAY", message))?;
+        }
+
+        self.handle.try_send(command!("CAP", "END"))?;
+
+        Ok(())
+    }
+
     pub fn update_config(
         &mut self,
         config: Arc<config::Server>,
@@ -704,6 +748,11 @@ impl Client {
 
         if matches!(priority, TokenPriority::User) {
             match &message.command {
+                Command::AWAY(comment) => {
+                    self.connection_only_away = comment
+                        .as_deref()
+                        .is_some_and(config::buffer::Away::is_connection_only);
+                }
                 Command::LIST(..) => {
                     self.channel_discovery_manager.status =
                         Some(channel_discovery::Status::Requested(Utc::now()));
@@ -1043,6 +1092,8 @@ impl Client {
             };
         }
 
+        let znc_list_entry = self.follow_znc_list(&message);
+
         match &message.command {
             Command::BATCH(batch, params) => {
                 let mut chars = batch.chars();
@@ -1329,6 +1380,18 @@ impl Client {
                     }]);
                 }
             }
+            Command::WEBPUSH(subcommand, params)
+                if subcommand == "REGISTER" =>
+            {
+                if let Some(endpoint) = params.first() {
+                    log::info!(
+                        "[{}] Registered push endpoint {endpoint}",
+                        self.server
+                    );
+                }
+
+                return Ok(vec![]);
+            }
             Command::BOUNCER(subcommand, params) if subcommand == "NETWORK" => {
                 if !self.is_primary() {
                     // we should only be receiving bouncer communication on the primary channel. Just for
@@ -1388,8 +1451,7 @@ impl Client {
                         }
                     } else {
                         // If none requested, end negotiation
-                        self.registration_step = RegistrationStep::End;
-                        self.handle.try_send(command!("CAP", "END"))?;
+                        self.end_capability_negotiation(config)?;
                     }
                 }
             }
@@ -1415,8 +1477,7 @@ impl Client {
                             sasl.command()
                         ))?;
                     } else {
-                        self.registration_step = RegistrationStep::End;
-                        self.handle.try_send(command!("CAP", "END"))?;
+                        self.end_capability_negotiation(config)?;
                     }
                 }
             }
@@ -1430,8 +1491,7 @@ impl Client {
 
                 // End if we didn't move to sasl or already ended
                 if self.registration_step < RegistrationStep::Sasl {
-                    self.registration_step = RegistrationStep::End;
-                    self.handle.try_send(command!("CAP", "END"))?;
+                    self.end_capability_negotiation(config)?;
                 }
             }
             Command::CAP(_, sub, a, b) if sub == "NEW" => {
@@ -1547,7 +1607,46 @@ impl Client {
                     });
                 }
             }
+            Command::PRIVMSG(_, text) | Command::NOTICE(_, text)
+                if matches!(self.znc_version_request, VersionRequest::Sent)
+                    && self.is_from_znc_module(&message, znc::STATUS)
+                    && znc::is_version_reply(text) =>
+            {
+                self.znc_version_request = VersionRequest::Need(false);
+
+                self.features.enable_supported(text);
+
+                return self.znc_detected();
+            }
+            Command::PRIVMSG(_, _) | Command::NOTICE(_, _)
+                if znc_list_entry.is_some() =>
+            {
+                if let Some(znc::ListEntry::Buffer(buffer)) = &znc_list_entry
+                    && !buffer.starts_with(&self.config.znc.status_prefix)
+                    && let Ok(query) = target::Query::parse(
+                        buffer,
+                        self.chantypes(),
+                        self.statusmsg(),
+                        self.casemapping(),
+                    )
+                {
+                    return Ok(vec![Event::AddToSidebar(query)]);
+                }
+
+                return Ok(vec![]);
+            }
             Command::PRIVMSG(_, text) | Command::NOTICE(_, text) => {
+                if self.features.znc {
+                    let server_time = message.server_time_or_now();
+
+                    if self
+                        .znc_last_seen
+                        .is_none_or(|last_seen| last_seen < server_time)
+                    {
+                        self.znc_last_seen = Some(server_time);
+                    }
+                }
+
                 if let Some(user) = message.user(self.casemapping()) {
                     let is_echo = user.nickname() == self.nickname();
 
@@ -1886,6 +1985,17 @@ impl Client {
                     self.casemapping(),
                 ));
             }
+            Command::Numeric(RPL_YOUREOPER, _) => {
+                // Subscribe to the configured server notices
+                if let Some(snomask) = self.config.oper.snomask.as_ref() {
+                    self.handle.try_send(command!(
+                        "MODE",
+                        self.nickname().to_string(),
+                        "+s",
+                        snomask
+                    ))?;
+                }
+            }
             Command::Numeric(RPL_MYINFO, args) => {
                 let server_version = ok!(args.get(2));
 
@@ -1901,6 +2011,21 @@ impl Client {
                     channel.users.remove(&user);
                 });
 
+                // Users lost in a netsplit are reported together once the
+                // split has settled, or as plain QUITs if too few users quit
+                // for it to be one (see `Client::netsplits`)
+                if let Some(split) = netsplit::Split::parse(comment.as_deref())
+                {
+                    self.netsplits.quit(
+                        user,
+                        channels,
+                        split,
+                        message.server_time_or_now(),
+                    );
+
+                    return Ok(vec![]);
+                }
+
                 return Ok(vec![Event::Broadcast(Broadcast::Quit {
                     user,
                     comment: comment.clone(),
@@ -2005,7 +2130,15 @@ impl Client {
                         user.update_bot(true);
                     }
 
-                    channel.users.insert(user);
+                    channel.users.insert(user.clone());
+
+                    if self.netsplits.join(
+                        user,
+                        target_channel,
+                        message.server_time_or_now(),
+                    ) {
+                        return Ok(vec![]);
+                    }
                 }
             }
             Command::KICK(channel, victim, reason) => {
@@ -2349,8 +2482,9 @@ impl Client {
                 }
             }
             // RPL_UNAWAY is a reply to "/AWAY <msg>" from the server
-            // for the client/user itself.
-            Command::Numeric(RPL_NOWAWAY, _) => {
+            // for the client/user itself.  When only this connection is away
+            // the user is still present via other connections to the bouncer.
+            Command::Numeric(RPL_NOWAWAY, _) if !self.connection_only_away => {
                 let user = User::from(self.nickname().to_owned());
 
                 for channel in self.chanmap.values_mut() {
@@ -3134,8 +3268,7 @@ impl Client {
             }
             Command::Numeric(RPL_SASLSUCCESS, _) => {
                 self.sasl_succeeded = true;
-                self.registration_step = RegistrationStep::End;
-                self.handle.try_send(command!("CAP", "END"))?;
+                self.end_capability_negotiation(config)?;
             }
             Command::Numeric(ERR_SASLFAIL | ERR_SASLTOOLONG, _) => {
                 log::warn!("[{}] SASL authentication failed", self.server);
@@ -3156,8 +3289,7 @@ impl Client {
                     ))]);
                 }
 
-                self.registration_step = RegistrationStep::End;
-                self.handle.try_send(command!("CAP", "END"))?;
+                self.end_capability_negotiation(config)?;
             }
             Command::Numeric(RPL_TRYAGAIN, args) => {
                 let command = ok!(args.get(1));
@@ -3299,6 +3431,37 @@ impl Client {
                         .try_send(command!("BOUNCER", "LISTNETWORKS"))?;
                 }
 
+                // Set away status now if it could not be declared during
+                // registration (connection-only away requires pre-away)
+                if !self.capabilities.acknowledged(Capability::PreAway)
+                    && let Some(message) =
+                        config.buffer.nickname.away.on_connect.as_ref()
+                {
+                    if config::buffer::Away::is_connection_only(message) {
+                        log::warn!(
+                            "[{}] not marking this connection as away: the \
+                             server does not support draft/pre-away",
+                            self.server
+                        );
+                    } else {
+                        self.handle.try_send(command!("AWAY", message))?;
+                    }
+                }
+
+                // Identify as an IRC operator
+                if self.config.oper.on_connect
+                    && let Some(message) = self.oper_command(None)
+                {
+                    self.handle.try_send(message)?;
+                }
+
+                // ZNC does not identify itself during registration, so ask
+                // *status for its version when it advertises its capabilities
+                if self.capabilities.listed_by_znc() {
+                    self.znc_version_request = VersionRequest::Sent;
+                    self.handle.try_send(znc::version(&self.config.znc))?;
+                }
+
                 // request metadata
                 if self.capabilities.acknowledged(Capability::Metadata) {
                     let mut requested = config
@@ -3391,6 +3554,11 @@ impl Client {
                         .ok()
                         .map(Event::AddToSidebar)
                     })
+                    .chain(
+                        (self.capabilities.acknowledged(Capability::WebPush)
+                            && self.config.webpush.endpoint.is_some())
+                        .then_some(Event::WebPushSubscribe),
+                    )
                     .chain(iter::once(Event::OnConnect(on_connect(
                         self.handle.clone(),
                         self.config.clone(),
@@ -4201,6 +4369,193 @@ impl Client {
             .unwrap_or_default()
     }
 
+    fn is_from_znc_module(
+        &self,
+        message: &message::Encoded,
+        module: &str,
+    ) -> bool {
+        message.user(self.casemapping()).is_some_and(|user| {
+            user.nickname()
+                == Nick::from_str(
+                    &self.config.znc.module_nick(module),
+                    self.casemapping(),
+                )
+        })
+    }
+
+    /// Follows the table `*playback` sends in response to `LIST`, returning
+    /// the entry the message holds while the table is being received.
+    fn follow_znc_list(
+        &mut self,
+        message: &message::Encoded,
+    ) -> Option<znc::ListEntry> {
+        if self.znc_list.is_none()
+            || !self.is_from_znc_module(message, znc::PLAYBACK)
+        {
+            return None;
+        }
+
+        let (Command::PRIVMSG(_, text) | Command::NOTICE(_, text)) =
+            &message.command
+        else {
+            return None;
+        };
+
+        let entry = znc::parse_list_entry(text);
+
+        if self
+            .znc_list
+            .as_mut()
+            .is_some_and(|list| list.is_finished_by(entry.as_ref()))
+        {
+            self.znc_list = None;
+        }
+
+        entry
+    }
+
+    fn znc_detected(&mut self) -> Result<Vec<Event>> {
+        log::info!("[{}] ZNC detected", self.server);
+
+        for command in &self.config.znc.status_commands {
+            self.handle
+                .try_send(znc::status(&self.config.znc, command))?;
+        }
+
+        if let Some(identifier) = self.config.znc.clientbuffer.as_ref() {
+            self.handle
+                .try_send(znc::add_client(&self.config.znc, identifier))?;
+
+            // Queries are not restored by ZNC on connect, so list the
+            // buffers it holds for this client to add them to the sidebar
+            if self.capabilities.acknowledged(Capability::ZncPlayback) {
+                self.znc_list = Some(znc::List::default());
+                self.handle.try_send(znc::list(&self.config.znc))?;
+            }
+        }
+
+        if self.capabilities.acknowledged(Capability::ZncPlayback) {
+            Ok(vec![Event::ZncPlayback])
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    fn send_znc_playback(&mut self, since: Option<DateTime<Utc>>) {
+        // Messages arriving from here on move it forward
+        self.znc_last_seen = self.znc_last_seen.max(since);
+
+        if self.capabilities.acknowledged(Capability::ZncPlayback) {
+            self.send(
+                None,
+                znc::play(&self.config.znc, since).into(),
+                TokenPriority::High,
+            );
+        }
+    }
+
+    pub fn load_znc_playback_timestamp(
+        &self,
+    ) -> impl Future<Output = Message> + use<> {
+        let server = self.server.clone();
+
+        async move {
+            let since = znc::load_playback_timestamp(server.clone())
+                .await
+                .ok()
+                .flatten();
+
+            Message::ZncPlayback(server, since)
+        }
+        .boxed()
+    }
+
+    pub fn overwrite_znc_playback_timestamp(
+        &self,
+        timestamp: DateTime<Utc>,
+    ) -> impl Future<Output = Message> + use<> {
+        let server = self.server.clone();
+
+        async move {
+            let result =
+                znc::overwrite_playback_timestamp(server.clone(), timestamp)
+                    .await;
+
+            Message::ZncPlaybackTimestampUpdated(server, timestamp, result)
+        }
+        .boxed()
+    }
+
+    pub fn znc_last_seen(&self) -> Option<DateTime<Utc>> {
+        self.znc_last_seen
+    }
+
+    pub fn subscribe_webpush(&self) -> impl Future<Output = Message> + use<> {
+        let server = self.server.clone();
+        let vapid =
+            isupport::get_vapid(&self.isupport).map(ToString::to_string);
+        let endpoint = self
+            .config
+            .webpush
+            .endpoint
+            .as_ref()
+            .map(|base| webpush::endpoint(base, &server));
+
+        async move {
+            let result =
+                webpush::subscribe(server.clone(), vapid, endpoint).await;
+
+            Message::WebPushSubscribed(server, result)
+        }
+        .boxed()
+    }
+
+    fn register_webpush(&mut self, keys: &webpush::Keys) {
+        let Some(base) = self.config.webpush.endpoint.as_ref() else {
+            return;
+        };
+
+        let endpoint = webpush::endpoint(base, &self.server);
+
+        if isupport::get_vapid(&self.isupport).is_none() {
+            log::warn!(
+                "[{}] Server doesn't advertise a VAPID key, halloy-push will \
+                 reject its push messages",
+                self.server
+            );
+        }
+
+        match webpush::register(&endpoint, keys) {
+            Ok(message) => {
+                self.send(None, message.into(), TokenPriority::High);
+            }
+            Err(error) => {
+                log::warn!(
+                    "[{}] Failed to register push endpoint {endpoint}: {error}",
+                    self.server
+                );
+            }
+        }
+    }
+
+    fn oper_command(&self, password: Option<&str>) -> Option<proto::Message> {
+        let nickname = self.nickname().to_string();
+        let name = self.config.oper.name.as_deref().unwrap_or(&nickname);
+        let password = password.or(self.config.oper.password.as_deref())?;
+
+        Some(command!("OPER", name, password))
+    }
+
+    fn oper(&mut self, password: Option<&str>) -> bool {
+        let Some(message) = self.oper_command(password) else {
+            return false;
+        };
+
+        self.send(None, message.into(), TokenPriority::High);
+
+        true
+    }
+
     pub fn load_chathistory_targets_timestamp(
         &self,
         server_time: DateTime<Utc>,
@@ -4463,6 +4818,26 @@ impl Client {
             .as_nickref()
     }
 
+    pub fn netsplits(&mut self, now: Instant) -> Vec<Broadcast> {
+        let (events, quits) = self.netsplits.drain(now);
+
+        events
+            .into_iter()
+            .map(|event| Broadcast::Netsplit {
+                kind: event.kind,
+                split: event.split,
+                users: event.users,
+                sent_time: event.sent_time,
+            })
+            .chain(quits.into_iter().map(|quit| Broadcast::Quit {
+                user: quit.user,
+                comment: Some(quit.comment),
+                channels: quit.channels,
+                sent_time: quit.sent_time,
+            }))
+            .collect()
+    }
+
     pub fn tick(&mut self, now: Instant) -> Result<()> {
         match self.notification_blackout {
             NotificationBlackout::Blackout(instant) => {
@@ -4925,7 +5300,9 @@ fn continue_chathistory_between(
             | Event::BouncerNetwork(_, _)
             | Event::AddToSidebar(_)
             | Event::Disconnect(_)
-            | Event::UpdateIcon => None,
+            | Event::UpdateIcon
+            | Event::ZncPlayback
+            | Event::WebPushSubscribe => None,
         });
 
     start_message_reference.map(|start_message_reference| {
@@ -4974,7 +5351,9 @@ fn continue_chathistory_targets(
             | Event::BouncerNetwork(_, _)
             | Event::AddToSidebar(_)
             | Event::Disconnect(_)
-            | Event::UpdateIcon => None,
+            | Event::UpdateIcon
+            | Event::ZncPlayback
+            | Event::WebPushSubscribe => None,
         });
 
     start_timestamp.map(|start_timestamp| {
@@ -5267,6 +5646,10 @@ impl Map {
             return None;
         }
 
+        if let Some(location) = client.config.filehost.backend.location() {
+            return Some(location);
+        }
+
         client.config.filehost.override_url.as_deref().or(
             if server.is_bouncer_network() {
                 server.parent().as_ref().and_then(|p| self.get_filehost(p))
@@ -5297,7 +5680,9 @@ impl Map {
     ) -> Option<fileupload::Auth> {
         let client = self.client(server)?;
 
-        if !client.config.filehost.enabled {
+        if !client.config.filehost.enabled
+            || client.config.filehost.backend != filehost::Backend::Soju
+        {
             return None;
         }
 
@@ -5327,6 +5712,12 @@ impl Map {
         }
     }
 
+    pub fn get_filehost_backend(&self, server: &Server) -> filehost::Backend {
+        self.client(server)
+            .map(|client| client.config.filehost.backend.clone())
+            .unwrap_or_default()
+    }
+
     pub fn get_use_tls(&self, server: &Server) -> bool {
         self.client(server).is_none_or(|c| c.config.use_tls)
     }
@@ -5615,6 +6006,83 @@ impl Map {
         })
     }
 
+    pub fn send_znc_playback(
+        &mut self,
+        server: &Server,
+        since: Option<DateTime<Utc>>,
+    ) {
+        if let Some(client) = self.client_mut(server) {
+            client.send_znc_playback(since);
+        }
+    }
+
+    pub fn subscribe_webpush(
+        &self,
+        server: &Server,
+    ) -> Option<impl Future<Output = Message> + use<>> {
+        self.client(server).map(Client::subscribe_webpush)
+    }
+
+    pub fn register_webpush(&mut self, server: &Server, keys: &webpush::Keys) {
+        if let Some(client) = self.client_mut(server) {
+            client.register_webpush(keys);
+        }
+    }
+
+    /// Sends OPER using the configured operator credentials, with `password`
+    /// overriding the configured password. Returns `false` when no password
+    /// is available.
+    pub fn oper(&mut self, server: &Server, password: Option<&str>) -> bool {
+        self.client_mut(server)
+            .is_some_and(|client| client.oper(password))
+    }
+
+    pub fn load_znc_playback_timestamp(
+        &self,
+        server: &Server,
+    ) -> Option<impl Future<Output = Message> + use<>> {
+        self.client(server).map(Client::load_znc_playback_timestamp)
+    }
+
+    pub fn overwrite_znc_playback_timestamp(
+        &self,
+        server: &Server,
+    ) -> Option<impl Future<Output = Message> + use<>> {
+        self.client(server).and_then(|client| {
+            client.znc_last_seen().map(|timestamp| {
+                client.overwrite_znc_playback_timestamp(timestamp)
+            })
+        })
+    }
+
+    /// Record the last message seen on every connected ZNC server, so
+    /// playback resumes from it next time.
+    pub fn save_znc_playback_timestamps(
+        &self,
+    ) -> impl Future<Output = ()> + use<> {
+        let timestamps = self
+            .connected_servers()
+            .filter_map(|server| {
+                let timestamp = self.client(server)?.znc_last_seen()?;
+
+                Some((server.clone(), timestamp))
+            })
+            .collect::<Vec<_>>();
+
+        async move {
+            for (server, timestamp) in timestamps {
+                if let Err(error) =
+                    znc::overwrite_playback_timestamp(server.clone(), timestamp)
+                        .await
+                {
+                    log::warn!(
+                        "failed to update ZNC playback timestamp for {server} to {timestamp}: {error}"
+                    );
+                }
+            }
+        }
+    }
+
     pub fn get_server_supports_detach(&self, server: &Server) -> bool {
         self.client(server)
             .is_some_and(|client| client.features.detach)
@@ -5715,6 +6183,22 @@ impl Map {
         Ok(())
     }
 
+    pub fn netsplits(&mut self, now: Instant) -> Vec<(Server, Broadcast)> {
+        self.0
+            .iter_mut()
+            .filter_map(|(server, state)| match state {
+                State::Disconnected => None,
+                State::Ready(client) => Some(
+                    client
+                        .netsplits(now)
+                        .into_iter()
+                        .map(|broadcast| (server.clone(), broadcast)),
+                ),
+            })
+            .flatten()
+            .collect()
+    }
+
     pub fn get_registry(&self, server: &Server) -> &dyn metadata::Registry {
         self.0
             .get(server)
@@ -106,16 +106,20 @@ pub fn on_connect(
                                 None
                             }
                             // We don't handle hop, clear-buffer, sysinfo,
-                            // channel_discovery, connect, or reconnect when
-                            // called on connection.
+                            // channel_discovery, connect, reconnect, oper
+                            // (see oper.on_connect), workspace, or schedule
+                            // when called on connection.
                             command::Internal::ClearBuffer
                             | command::Internal::ChannelDiscovery
                             | command::Internal::Connect(_)
                             | command::Internal::Exec(_)
                             | command::Internal::Hop(_, _)
+                            | command::Internal::Oper(_)
                             | command::Internal::SysInfo
                             | command::Internal::Reconnect
-                            | command::Internal::Upload(_) => None,
+                            | command::Internal::Schedule(_, _)
+                            | command::Internal::Upload(_)
+                            | command::Internal::Workspace(_) => None,
                         },
                     }
                 }
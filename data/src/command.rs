@@ -18,7 +18,9 @@ use crate::isupport::{
 };
 use crate::message::{self, formatting};
 use crate::user::{ChannelUsers, NickRef};
-use crate::{Config, Message, Server, Target, Url, User, ctcp, target};
+use crate::{
+    Config, Message, Server, Target, Url, User, ctcp, schedule, target,
+};
 
 pub mod alias;
 
@@ -42,12 +44,26 @@ pub enum Internal {
     Hop(Option<String>, Option<String>),
     ChannelDiscovery,
     Delay(u64),
+    /// Send a line to the current buffer at the given time.
+    Schedule(DateTime<Utc>, String),
     SysInfo,
     Detach(Vec<target::Channel>),
     Connect(String),
     Reconnect,
     Upload(String),
     Exec(String),
+    /// Identify as an IRC operator using the configured credentials,
+    /// optionally overriding the configured password.
+    Oper(Option<String>),
+    Workspace(Workspace),
+}
+
+/// Named pane layouts managed with `/workspace`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Workspace {
+    Switch(String),
+    Save(String),
+    Delete(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -88,6 +104,7 @@ pub enum Irc {
     Chathistory(String, Vec<String>),
     Monitor(String, Option<String>),
     Invite(String, String),
+    Oper(String, String),
     Redact {
         target: String,
         msgid: message::Id,
@@ -274,6 +291,8 @@ pub enum Kind {
     Hop,
     Notice,
     Delay,
+    Schedule,
+    Later,
     Clear,
     List,
     ClearTopic,
@@ -284,6 +303,8 @@ pub enum Kind {
     Upload,
     MassMessage,
     Exec,
+    Oper,
+    Workspace,
     Raw,
 }
 
@@ -321,6 +342,8 @@ impl FromStr for Kind {
             "invite" => Ok(Kind::Invite),
             "hop" | "rejoin" => Ok(Kind::Hop),
             "delay" => Ok(Kind::Delay),
+            "schedule" => Ok(Kind::Schedule),
+            "later" => Ok(Kind::Later),
             "clear" => Ok(Kind::Clear),
             "list" => Ok(Kind::List),
             "cleartopic" | "ct" => Ok(Kind::ClearTopic),
@@ -331,6 +354,8 @@ impl FromStr for Kind {
             "upload" => Ok(Kind::Upload),
             "massmessage" | "mm" => Ok(Kind::MassMessage),
             "exec" => Ok(Kind::Exec),
+            "oper" => Ok(Kind::Oper),
+            "workspace" => Ok(Kind::Workspace),
             _ => Err(()),
         }
     }
@@ -1695,6 +1720,27 @@ fn parse_command(
                     Err(Error::NotPositiveInteger)
                 }
             }),
+            Kind::Schedule => {
+                validated::<2, 0, true>(args, |[when, text], _| {
+                    let at = schedule::parse_when(&when, Local::now())
+                        .ok_or(Error::InvalidScheduleTime)?;
+
+                    if at <= Utc::now() {
+                        return Err(Error::ScheduleTimeInPast);
+                    }
+
+                    Ok(Command::Internal(Internal::Schedule(at, text)))
+                })
+            }
+            Kind::Later => validated::<2, 0, true>(args, |[delay, text], _| {
+                let delay = schedule::parse_delay(&delay)
+                    .ok_or(Error::InvalidScheduleDelay)?;
+
+                Ok(Command::Internal(Internal::Schedule(
+                    Utc::now() + delay,
+                    text,
+                )))
+            }),
             Kind::Connect => validated::<0, 1, false>(args, |_, [server]| {
                 if let Some(server) = server {
                     if let Ok(url) = Url::from_str(&server)
@@ -1751,6 +1797,43 @@ fn parse_command(
                     Ok(Command::Internal(Internal::Exec(command.to_string())))
                 }
             }
+            Kind::Oper => {
+                validated::<0, 2, false>(args, |_, [name, password]| {
+                    Ok(match (name, password) {
+                        (Some(name), Some(password)) => {
+                            Command::Irc(Irc::Oper(name, password), None)
+                        }
+                        (password, _) => {
+                            Command::Internal(Internal::Oper(password))
+                        }
+                    })
+                })
+            }
+            Kind::Workspace => {
+                let raw = raw.trim();
+                let (action, name) = raw
+                    .split_once(' ')
+                    .map_or((raw, ""), |(action, name)| (action, name.trim()));
+
+                let workspace = match action.to_lowercase().as_str() {
+                    "save" | "delete" if name.is_empty() => {
+                        return Err(Error::MissingWorkspaceName);
+                    }
+                    "save" => Workspace::Save(name.to_string()),
+                    "delete" => Workspace::Delete(name.to_string()),
+                    _ => Workspace::Switch(raw.to_string()),
+                };
+
+                if raw.is_empty() {
+                    Err(Error::IncorrectArgCount {
+                        min: 1,
+                        max: 2,
+                        actual: 0,
+                    })
+                } else {
+                    Ok(Command::Internal(Internal::Workspace(workspace)))
+                }
+            }
         },
         Err(()) => Ok(unknown()),
     }
@@ -1822,7 +1905,7 @@ fn get_combined_arg(
     (!combined_arg.is_empty()).then_some(combined_arg)
 }
 
-fn validated_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
+pub(crate) fn validated_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
     // Allow no timestamp= prefix if we can parse the remainder as a timestamp.
     let timestamp = timestamp.strip_prefix("timestamp=").unwrap_or(timestamp);
 
@@ -2040,6 +2123,7 @@ impl TryFrom<Irc> for proto::Command {
             Irc::Invite(nickname, channel) => {
                 proto::Command::INVITE(nickname, channel)
             }
+            Irc::Oper(name, password) => proto::Command::OPER(name, password),
             Irc::Redact {
                 target,
                 msgid,
@@ -2135,8 +2219,21 @@ pub enum Error {
     InvalidChathistoryTimestamp,
     #[error("too large (maximum limit: {maximum_limit})")]
     ChathistoryLimitTooLarge { maximum_limit: u16 },
+    #[error(
+        "invalid time (expected a delay such as 10m or 1h30m, a time such as \
+         14:30, or a date and time such as 2026-10-20T14:30)"
+    )]
+    InvalidScheduleTime,
+    #[error("invalid delay (expected a delay such as 10m or 1h30m)")]
+    InvalidScheduleDelay,
+    #[error("time is in the past")]
+    ScheduleTimeInPast,
+    #[error("only messages and IRC commands can be scheduled")]
+    NotSchedulable,
     #[error("exec is not enabled by the user")]
     ExecDisabled,
+    #[error("missing workspace name")]
+    MissingWorkspaceName,
     #[error("/{command} is not available{context}")]
     CommandNotAvailable {
         command: &'static str,
@@ -2195,7 +2292,9 @@ fn fmt_channel_name_requirements(chantypes: &[char]) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{AutoFormat, Command, Error, Internal, isupport, parse};
+    use super::{
+        AutoFormat, Command, Error, Internal, Irc, Workspace, isupport, parse,
+    };
     use crate::Config;
     use crate::capabilities::Capabilities;
     use crate::features::Features;
@@ -2319,6 +2418,87 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parse_oper() {
+        let config = Config::default();
+
+        let parse = |input| {
+            parse(
+                input,
+                None,
+                None,
+                AutoFormat::default(),
+                true,
+                &isupport::DEFAULT,
+                &Capabilities::default(),
+                &Features::default(),
+                None,
+                &config,
+            )
+            .unwrap()
+        };
+
+        assert!(matches!(
+            parse("/oper"),
+            Command::Internal(Internal::Oper(None))
+        ));
+        assert!(matches!(
+            parse("/oper hunter2"),
+            Command::Internal(Internal::Oper(Some(password)))
+                if password == "hunter2"
+        ));
+        assert!(matches!(
+            parse("/oper admin hunter2"),
+            Command::Irc(Irc::Oper(name, password), None)
+                if name == "admin" && password == "hunter2"
+        ));
+    }
+
+    #[test]
+    fn parse_workspace() {
+        let config = Config::default();
+
+        let parse = |input| {
+            parse(
+                input,
+                None,
+                None,
+                AutoFormat::default(),
+                true,
+                &isupport::DEFAULT,
+                &Capabilities::default(),
+                &Features::default(),
+                None,
+                &config,
+            )
+        };
+
+        assert!(matches!(
+            parse("/workspace Side projects").unwrap(),
+            Command::Internal(Internal::Workspace(Workspace::Switch(name)))
+                if name == "Side projects"
+        ));
+        assert!(matches!(
+            parse("/workspace save  Work").unwrap(),
+            Command::Internal(Internal::Workspace(Workspace::Save(name)))
+                if name == "Work"
+        ));
+        assert!(matches!(
+            parse("/workspace delete Work").unwrap(),
+            Command::Internal(Internal::Workspace(Workspace::Delete(name)))
+                if name == "Work"
+        ));
+        assert!(matches!(
+            parse("/workspace save"),
+            Err(Error::MissingWorkspaceName)
+        ));
+        assert!(matches!(
+            parse("/workspace delete "),
+            Err(Error::MissingWorkspaceName)
+        ));
+        assert!(parse("/workspace").is_err());
+    }
+
     #[test]
     fn parse_exec_when_disabled() {
         let mut config = Config::default();
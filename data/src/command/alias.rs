@@ -1,5 +1,7 @@
 use std::borrow::Cow;
 
+use chrono::{DateTime, Local};
+
 use super::Error;
 use crate::Config;
 use crate::buffer::Upstream;
@@ -12,11 +14,17 @@ pub struct Alias {
     pub min_args: usize,
 }
 
+/// Values the variables expand to.  Aliases only use `nick`, `channel` and
+/// `server`, the rest are for snippets.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct Context<'a> {
-    nick: Option<Cow<'a, str>>,
-    channel: Option<Cow<'a, str>>,
-    server: Option<Cow<'a, str>>,
+    pub(crate) nick: Option<Cow<'a, str>>,
+    pub(crate) channel: Option<Cow<'a, str>>,
+    pub(crate) server: Option<Cow<'a, str>>,
+    pub(crate) author: Option<Cow<'a, str>>,
+    pub(crate) reply: Option<Cow<'a, str>>,
+    pub(crate) clipboard: Option<Cow<'a, str>>,
+    pub(crate) now: Option<DateTime<Local>>,
 }
 
 impl<'a> Context<'a> {
@@ -35,6 +43,24 @@ impl<'a> Context<'a> {
             nick,
             channel,
             server,
+            ..Self::default()
+        }
+    }
+
+    fn value(&self, variable: Variable) -> Option<Cow<'_, str>> {
+        match variable {
+            Variable::Nick => self.nick.as_deref().map(Cow::Borrowed),
+            Variable::Channel => self.channel.as_deref().map(Cow::Borrowed),
+            Variable::Server => self.server.as_deref().map(Cow::Borrowed),
+            Variable::Date => self
+                .now
+                .map(|now| Cow::Owned(now.format("%Y-%m-%d").to_string())),
+            Variable::Time => self
+                .now
+                .map(|now| Cow::Owned(now.format("%H:%M").to_string())),
+            Variable::Author => self.author.as_deref().map(Cow::Borrowed),
+            Variable::Reply => self.reply.as_deref().map(Cow::Borrowed),
+            Variable::Clipboard => self.clipboard.as_deref().map(Cow::Borrowed),
         }
     }
 }
@@ -152,6 +178,33 @@ fn substitute_args(
     template: &str,
     args: &[&str],
     context: &Context<'_>,
+) -> String {
+    substitute(template, args, context, parse_placeholder)
+}
+
+/// Expands every variable of `template`, such as `$date` or `$clipboard`,
+/// leaving any other `$` as it is.
+pub(crate) fn substitute_variables(
+    template: &str,
+    context: &Context<'_>,
+) -> String {
+    substitute(template, &[], context, |input| {
+        parse_variable(input, &Variable::ALL).map(Placeholder::Variable)
+    })
+}
+
+/// The variables `template` uses, in order.
+pub(crate) fn variables(template: &str) -> impl Iterator<Item = Variable> {
+    template.match_indices('$').filter_map(|(index, _)| {
+        parse_variable(&template[index + 1..], &Variable::ALL)
+    })
+}
+
+fn substitute(
+    template: &str,
+    args: &[&str],
+    context: &Context<'_>,
+    parse: impl Fn(&str) -> Option<Placeholder>,
 ) -> String {
     let mut expanded = String::with_capacity(template.len());
     let mut rest = template;
@@ -160,7 +213,7 @@ fn substitute_args(
         expanded.push_str(&rest[..index]);
         rest = &rest[index + 1..];
 
-        let Some(placeholder) = parse_placeholder(rest) else {
+        let Some(placeholder) = parse(rest) else {
             expanded.push('$');
             continue;
         };
@@ -197,18 +250,41 @@ impl Placeholder {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Variable {
+pub(crate) enum Variable {
     Nick,
     Channel,
     Server,
+    Date,
+    Time,
+    Author,
+    Reply,
+    Clipboard,
 }
 
 impl Variable {
+    const ALIAS: [Self; 3] = [Self::Nick, Self::Channel, Self::Server];
+
+    const ALL: [Self; 8] = [
+        Self::Nick,
+        Self::Channel,
+        Self::Server,
+        Self::Date,
+        Self::Time,
+        Self::Author,
+        Self::Reply,
+        Self::Clipboard,
+    ];
+
     fn as_str(self) -> &'static str {
         match self {
             Self::Nick => "nick",
             Self::Channel => "channel",
             Self::Server => "server",
+            Self::Date => "date",
+            Self::Time => "time",
+            Self::Author => "author",
+            Self::Reply => "reply",
+            Self::Clipboard => "clipboard",
         }
     }
 }
@@ -229,13 +305,17 @@ fn parse_placeholder(input: &str) -> Option<Placeholder> {
         });
     }
 
-    for variable in [Variable::Nick, Variable::Channel, Variable::Server] {
-        if input.starts_with(variable.as_str()) {
-            return Some(Placeholder::Variable(variable));
-        }
-    }
+    parse_variable(input, &Variable::ALIAS).map(Placeholder::Variable)
+}
 
-    None
+/// The variable `input` starts with, when followed by a word boundary (e.g.
+/// `$nick` but not `$nickname`).
+fn parse_variable(input: &str, variables: &[Variable]) -> Option<Variable> {
+    variables.iter().copied().find(|variable| {
+        input.strip_prefix(variable.as_str()).is_some_and(|rest| {
+            !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_')
+        })
+    })
 }
 
 fn push_placeholder(
@@ -257,12 +337,8 @@ fn push_placeholder(
             }
         }
         Placeholder::Variable(variable) => {
-            if let Some(value) = match variable {
-                Variable::Nick => context.nick.as_deref(),
-                Variable::Channel => context.channel.as_deref(),
-                Variable::Server => context.server.as_deref(),
-            } {
-                expanded.push_str(value);
+            if let Some(value) = context.value(*variable) {
+                expanded.push_str(&value);
             }
         }
     }
@@ -408,6 +484,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_placeholder_named_needs_word_boundary() {
+        assert_eq!(parse_placeholder("nickname"), None);
+        assert_eq!(parse_placeholder("server_name"), None);
+        assert_eq!(
+            parse_placeholder("nick's"),
+            Some(Placeholder::Variable(Variable::Nick))
+        );
+        // Variables of snippets are left as they are in aliases
+        assert_eq!(parse_placeholder("date"), None);
+    }
+
     #[test]
     fn parse_placeholder_invalid() {
         assert_eq!(parse_placeholder("0"), None);
@@ -497,6 +585,7 @@ mod tests {
             nick: Some(Cow::Borrowed("casperstorm")),
             channel: Some(Cow::Borrowed("#halloy")),
             server: Some(Cow::Borrowed("libera")),
+            ..Context::default()
         };
 
         assert_eq!(
@@ -515,6 +604,7 @@ mod tests {
             nick: Some(Cow::Borrowed("casperstorm")),
             channel: Some(Cow::Borrowed("#halloy")),
             server: Some(Cow::Borrowed("libera")),
+            ..Context::default()
         };
 
         assert_eq!(
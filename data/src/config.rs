@@ -4,6 +4,7 @@ use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
 use std::path::{Path, PathBuf};
 use std::{str, string};
 
+use chrono::NaiveTime;
 use iced_core::font;
 use indexmap::IndexMap;
 use rand::prelude::*;
@@ -35,13 +36,16 @@ pub use self::window::Window;
 use crate::appearance::theme::Styles;
 use crate::appearance::{self, Appearance};
 use crate::audio::{self};
+use crate::config::accessibility::Accessibility;
 use crate::config::metadata::Metadata;
 use crate::serde::{
-    deserialize_f32_positive_float_maybe, deserialize_u8_positive_integer_maybe,
+    deserialize_f32_positive_float_maybe, deserialize_time_of_day,
+    deserialize_u8_positive_integer_maybe,
 };
 use crate::server::{ConfigMap as ServerMap, ServerName};
 use crate::{Theme, environment};
 
+pub mod accessibility;
 pub mod actions;
 pub mod buffer;
 pub mod context_menu;
@@ -94,6 +98,7 @@ pub struct Config {
     pub runtime: Runtime,
     pub check_for_update_on_launch: bool,
     pub metadata: Metadata,
+    pub accessibility: Accessibility,
 }
 
 impl Default for Config {
@@ -124,6 +129,7 @@ impl Default for Config {
             runtime: Runtime::default(),
             check_for_update_on_launch: true,
             metadata: Metadata::default(),
+            accessibility: Accessibility::default(),
         }
     }
 }
@@ -384,6 +390,14 @@ impl Config {
         pub enum ThemeKeys {
             Static(ThemeValue),
             Dynamic { light: ThemeValue, dark: ThemeValue },
+            Scheduled(Vec<ScheduledTheme>),
+        }
+
+        #[derive(Deserialize, Debug)]
+        pub struct ScheduledTheme {
+            #[serde(deserialize_with = "deserialize_time_of_day")]
+            at: NaiveTime,
+            theme: String,
         }
 
         #[derive(Deserialize, Debug, Clone)]
@@ -409,7 +423,7 @@ impl Config {
         }
 
         impl ThemeKeys {
-            pub fn keys(&self) -> (String, Option<String>) {
+            fn keys(&self) -> ThemeNames {
                 use rand::prelude::*;
                 use rand_chacha::ChaCha8Rng;
 
@@ -420,11 +434,11 @@ impl Config {
                         let themes = theme_value.to_vec();
 
                         if themes.is_empty() {
-                            (String::default(), None)
+                            ThemeNames::Static(String::default())
                         } else {
                             let selected =
                                 themes.choose(&mut rng).unwrap_or(&themes[0]);
-                            (selected.clone(), None)
+                            ThemeNames::Static(selected.clone())
                         }
                     }
                     ThemeKeys::Dynamic { light, dark } => {
@@ -440,19 +454,26 @@ impl Config {
                                 .clone()
                         };
 
-                        let dark_selected = if dark_themes.is_empty() {
-                            None
+                        if dark_themes.is_empty() {
+                            ThemeNames::Static(light_selected)
                         } else {
-                            Some(
-                                dark_themes
+                            ThemeNames::Dynamic {
+                                light: light_selected,
+                                dark: dark_themes
                                     .choose(&mut rng)
                                     .unwrap_or(&dark_themes[0])
                                     .clone(),
-                            )
-                        };
-
-                        (light_selected, dark_selected)
+                            }
+                        }
                     }
+                    ThemeKeys::Scheduled(schedule) => ThemeNames::Scheduled(
+                        schedule
+                            .iter()
+                            .map(|scheduled| {
+                                (scheduled.at, scheduled.theme.clone())
+                            })
+                            .collect(),
+                    ),
                 }
             }
         }
@@ -485,6 +506,7 @@ impl Config {
             pub runtime: Runtime,
             pub check_for_update_on_launch: bool,
             pub metadata: Metadata,
+            pub accessibility: Accessibility,
         }
 
         impl Default for Configuration {
@@ -515,6 +537,7 @@ impl Config {
                     runtime: Runtime::default(),
                     check_for_update_on_launch: true,
                     metadata: Metadata::default(),
+                    accessibility: Accessibility::default(),
                 }
             }
         }
@@ -539,7 +562,7 @@ impl Config {
             scale_factor,
             buffer,
             sidebar,
-            keyboard,
+            mut keyboard,
             notifications,
             file_transfer,
             filehost,
@@ -556,11 +579,15 @@ impl Config {
             runtime,
             check_for_update_on_launch,
             metadata,
+            accessibility,
         } = serde_ignored::deserialize(config, |ignored| {
             log::warn!("[config.toml] Ignoring unknown setting: {ignored}");
         })
         .map_err(|e| Error::Parse(e.to_string()))?;
 
+        keyboard.resolve(&buffer.text_input.key_bindings);
+        keyboard.validate()?;
+
         let servers = ServerMap::new(
             servers,
             sidebar.order_channels_by,
@@ -598,11 +625,12 @@ impl Config {
             runtime,
             check_for_update_on_launch,
             metadata,
+            accessibility,
         })
     }
 
     async fn load_appearance(
-        theme_keys: (String, Option<String>),
+        theme_names: ThemeNames,
     ) -> Result<Appearance, Error> {
         use tokio::fs;
 
@@ -629,8 +657,6 @@ impl Config {
         };
 
         let mut all = vec![];
-        let mut first_theme = Theme::default();
-        let mut second_theme = theme_keys.1.clone().map(|_| Theme::default());
         let mut has_halloy_theme = false;
 
         let mut stream =
@@ -648,14 +674,6 @@ impl Config {
             if let Some(file_name) = file_name.strip_suffix(".toml")
                 && let Some(theme) = read_entry(entry).await
             {
-                if file_name == theme_keys.0 {
-                    first_theme = theme.clone();
-                }
-
-                if Some(file_name) == theme_keys.1.as_deref() {
-                    second_theme = Some(theme.clone());
-                }
-
                 if file_name.to_lowercase() == DEFAULT_THEME_NAME {
                     has_halloy_theme = true;
                 }
@@ -668,10 +686,28 @@ impl Config {
             all.push(Theme::default());
         }
 
-        let selected = if let Some(second_theme) = second_theme {
-            appearance::Selected::dynamic(first_theme, second_theme)
-        } else {
-            appearance::Selected::specific(first_theme)
+        let theme = |name: &str| {
+            all.iter()
+                .find(|theme| theme.name == name)
+                .cloned()
+                .unwrap_or_default()
+        };
+
+        let selected = match &theme_names {
+            ThemeNames::Static(name) => {
+                appearance::Selected::specific(theme(name))
+            }
+            ThemeNames::Dynamic { light, dark } => {
+                appearance::Selected::dynamic(theme(light), theme(dark))
+            }
+            ThemeNames::Scheduled(schedule) => {
+                appearance::Selected::scheduled_by(
+                    schedule
+                        .iter()
+                        .map(|(start, name)| (*start, theme(name)))
+                        .collect(),
+                )
+            }
         };
 
         Ok(Appearance { selected, all })
@@ -787,6 +823,13 @@ pub fn random_nickname_with_seed<R: Rng>(rng: &mut R) -> String {
     rand_nick
 }
 
+/// Names of the themes selected by the `theme` setting.
+enum ThemeNames {
+    Static(String),
+    Dynamic { light: String, dark: String },
+    Scheduled(Vec<(NaiveTime, String)>),
+}
+
 #[derive(Debug, Error, Clone)]
 pub enum Error {
     #[error("config could not be read: {0}")]
@@ -815,8 +858,16 @@ pub enum Error {
         "Exactly one of sasl.plain.password, sasl.plain.password_file or sasl.plain.password_command must be set."
     )]
     DuplicateSaslPassword,
+    #[error(
+        "Only one of oper.password, oper.password_file and oper.password_command can be set."
+    )]
+    DuplicateOperPassword,
     #[error("Config does not exist")]
     ConfigMissing,
+    #[error(
+        "Key bind {chord} in keyboard.commands can't be pressed, since {bound} is already bound."
+    )]
+    BlockedChord { chord: String, bound: String },
 }
 
 impl From<std::io::Error> for Error {
@@ -0,0 +1,8 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Accessibility {
+    /// Announce incoming messages in open buffers to screen readers
+    pub screen_reader: bool,
+}
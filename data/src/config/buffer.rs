@@ -53,6 +53,8 @@ pub struct Buffer {
     pub redaction: Redaction,
     pub close: Close,
     pub reply: Reply,
+    #[serde(deserialize_with = "deserialize_snippets")]
+    pub snippets: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -343,6 +345,30 @@ impl Default for Commands {
     }
 }
 
+fn deserialize_snippets<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<String, String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let snippets = HashMap::<String, String>::deserialize(deserializer)?;
+
+    snippets
+        .into_iter()
+        .map(|(name, template)| {
+            let name = name.trim().trim_start_matches(':').to_lowercase();
+
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return Err(serde::de::Error::custom(format!(
+                    "snippet name '{name}' must be a single word"
+                )));
+            }
+
+            Ok((name, template))
+        })
+        .collect()
+}
+
 fn deserialize_aliases<'de, D>(
     deserializer: D,
 ) -> Result<HashMap<String, String>, D::Error>
@@ -398,21 +424,33 @@ where
     )
 }
 
-#[derive(Debug, Clone, Copy)]
-pub enum Away {
-    Dimmed(Dimmed),
-    None,
+#[derive(Debug, Clone, Default)]
+pub struct Away {
+    pub appearance: AwayAppearance,
+    /// Away message to set when connecting.  `*` marks only this connection
+    /// as away on bouncers which support multiple connections.
+    pub on_connect: Option<String>,
 }
 
 impl Away {
-    pub fn is_away(&self, is_user_away: bool) -> Option<Away> {
-        is_user_away.then_some(*self)
+    pub fn is_away(&self, is_user_away: bool) -> Option<AwayAppearance> {
+        is_user_away.then_some(self.appearance)
+    }
+
+    pub fn is_connection_only(message: &str) -> bool {
+        message == "*"
     }
 }
 
-impl Default for Away {
+#[derive(Debug, Clone, Copy)]
+pub enum AwayAppearance {
+    Dimmed(Dimmed),
+    None,
+}
+
+impl Default for AwayAppearance {
     fn default() -> Self {
-        Away::Dimmed(Dimmed::default())
+        AwayAppearance::Dimmed(Dimmed::default())
     }
 }
 
@@ -425,30 +463,60 @@ impl<'de> Deserialize<'de> for Away {
         #[serde(untagged)]
         enum AppearanceRepr {
             String(String),
-            Struct(DimmedStruct),
+            Struct(AwayStruct),
         }
 
         #[derive(Deserialize)]
-        struct DimmedStruct {
-            dimmed: Option<f32>,
+        struct AwayStruct {
+            #[serde(default)]
+            dimmed: Option<DimmedRepr>,
+            #[serde(default)]
+            on_connect: Option<String>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum DimmedRepr {
+            Enabled(bool),
+            Alpha(f32),
         }
 
         let repr = AppearanceRepr::deserialize(deserializer)?;
         match repr {
             AppearanceRepr::String(s) => match s.as_str() {
-                "dimmed" => Ok(Away::Dimmed(Dimmed {
-                    enabled: true,
-                    alpha: None,
-                })),
-                "solid" | "none" => Ok(Away::None),
+                "dimmed" => Ok(Away {
+                    appearance: AwayAppearance::Dimmed(Dimmed {
+                        enabled: true,
+                        alpha: None,
+                    }),
+                    on_connect: None,
+                }),
+                "solid" | "none" => Ok(Away {
+                    appearance: AwayAppearance::None,
+                    on_connect: None,
+                }),
                 _ => Err(serde::de::Error::custom(format!(
                     "unknown appearance: {s}",
                 ))),
             },
-            AppearanceRepr::Struct(s) => Ok(Away::Dimmed(Dimmed {
-                enabled: true,
-                alpha: s.dimmed,
-            })),
+            AppearanceRepr::Struct(s) => Ok(Away {
+                appearance: match s.dimmed {
+                    Some(DimmedRepr::Enabled(false)) => AwayAppearance::None,
+                    Some(DimmedRepr::Enabled(true)) | None => {
+                        AwayAppearance::Dimmed(Dimmed {
+                            enabled: true,
+                            alpha: None,
+                        })
+                    }
+                    Some(DimmedRepr::Alpha(alpha)) => {
+                        AwayAppearance::Dimmed(Dimmed {
+                            enabled: true,
+                            alpha: Some(alpha),
+                        })
+                    }
+                },
+                on_connect: s.on_connect,
+            }),
         }
     }
 }
@@ -461,6 +529,8 @@ pub struct ServerMessages {
     pub join: ServerMessage,
     pub part: ServerMessage,
     pub quit: ServerMessage,
+    pub netsplit: ServerMessage,
+    pub netjoin: ServerMessage,
     pub change_host: ServerMessage,
     pub change_mode: ServerMessage,
     pub change_nick: ServerMessage,
@@ -469,6 +539,12 @@ pub struct ServerMessages {
     pub standard_reply_fail: ServerMessage,
     pub standard_reply_warn: ServerMessage,
     pub standard_reply_note: ServerMessage,
+    pub server_notice_connect: ServerMessage,
+    pub server_notice_exit: ServerMessage,
+    pub server_notice_kill: ServerMessage,
+    pub server_notice_flood: ServerMessage,
+    pub server_notice_oper: ServerMessage,
+    pub server_notice_other: ServerMessage,
     pub wallops: ServerMessage,
     pub kick: ServerMessage,
     pub change_topic: ServerMessage,
@@ -489,6 +565,8 @@ impl Default for ServerMessages {
             join: ServerMessage::default(),
             part: ServerMessage::default(),
             quit: ServerMessage::default(),
+            netsplit: ServerMessage::default(),
+            netjoin: ServerMessage::default(),
             change_host: ServerMessage::default(),
             change_mode: ServerMessage::default(),
             change_nick: ServerMessage::default(),
@@ -497,6 +575,12 @@ impl Default for ServerMessages {
             standard_reply_fail: ServerMessage::default(),
             standard_reply_warn: ServerMessage::default(),
             standard_reply_note: ServerMessage::default(),
+            server_notice_connect: ServerMessage::default(),
+            server_notice_exit: ServerMessage::default(),
+            server_notice_kill: ServerMessage::default(),
+            server_notice_flood: ServerMessage::default(),
+            server_notice_oper: ServerMessage::default(),
+            server_notice_other: ServerMessage::default(),
             wallops: ServerMessage::default(),
             kick: ServerMessage::default(),
             change_topic: ServerMessage::default(),
@@ -515,6 +599,8 @@ impl ServerMessages {
             source::server::Kind::Join => &self.join,
             source::server::Kind::Part => &self.part,
             source::server::Kind::Quit => &self.quit,
+            source::server::Kind::Netsplit => &self.netsplit,
+            source::server::Kind::Netjoin => &self.netjoin,
             source::server::Kind::ChangeHost => &self.change_host,
             source::server::Kind::ChangeMode => &self.change_mode,
             source::server::Kind::ChangeNick => &self.change_nick,
@@ -529,6 +615,24 @@ impl ServerMessages {
             source::server::Kind::StandardReply(
                 source::server::StandardReply::Note,
             ) => &self.standard_reply_note,
+            source::server::Kind::ServerNotice(
+                source::server::ServerNotice::Connect,
+            ) => &self.server_notice_connect,
+            source::server::Kind::ServerNotice(
+                source::server::ServerNotice::Exit,
+            ) => &self.server_notice_exit,
+            source::server::Kind::ServerNotice(
+                source::server::ServerNotice::Kill,
+            ) => &self.server_notice_kill,
+            source::server::Kind::ServerNotice(
+                source::server::ServerNotice::Flood,
+            ) => &self.server_notice_flood,
+            source::server::Kind::ServerNotice(
+                source::server::ServerNotice::Oper,
+            ) => &self.server_notice_oper,
+            source::server::Kind::ServerNotice(
+                source::server::ServerNotice::Other,
+            ) => &self.server_notice_other,
             source::server::Kind::WAllOps => &self.wallops,
             source::server::Kind::Kick => &self.kick,
             source::server::Kind::ChangeTopic => &self.change_topic,
@@ -689,6 +793,9 @@ impl Condensation {
             | source::server::Kind::MonitoredOnline
             | source::server::Kind::MonitoredOffline
             | source::server::Kind::StandardReply(_)
+            | source::server::Kind::ServerNotice(_)
+            | source::server::Kind::Netsplit
+            | source::server::Kind::Netjoin
             | source::server::Kind::WAllOps
             | source::server::Kind::ChangeTopic
             | source::server::Kind::RequestTopic
@@ -1,7 +1,12 @@
+use std::path::PathBuf;
+
 use serde::Deserialize;
 
 use crate::config::buffer::AccessLevelFormat;
-use crate::serde::deserialize_usize_positive_integer;
+use crate::serde::{
+    deserialize_path_buf_with_path_transformations_maybe,
+    deserialize_usize_positive_integer,
+};
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
@@ -16,6 +21,8 @@ pub struct TextInput {
     pub max_lines: usize,
     pub send_line_delay: u64,
     pub persist: bool,
+    pub history: History,
+    pub spell_check: SpellCheck,
 }
 
 impl Default for TextInput {
@@ -30,6 +37,80 @@ impl Default for TextInput {
             max_lines: 5,
             send_line_delay: 100,
             persist: true,
+            history: History::default(),
+            spell_check: SpellCheck::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct History {
+    pub persist: bool,
+    /// Sent lines kept per buffer
+    pub size: usize,
+    /// Commands whose lines are never kept, as they may carry secrets
+    pub exclude: Vec<String>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            persist: true,
+            size: 100,
+            exclude: [
+                "/msg NickServ",
+                "/msg ChanServ",
+                "/nickserv",
+                "/chanserv",
+                "/ns",
+                "/cs",
+                "/oper",
+                "/pass",
+            ]
+            .map(String::from)
+            .to_vec(),
+        }
+    }
+}
+
+impl History {
+    /// Whether `text` starts with one of the excluded commands
+    pub fn is_excluded(&self, text: &str) -> bool {
+        let text = text.trim_start();
+
+        self.exclude.iter().any(|command| {
+            text.get(..command.len()).is_some_and(|start| {
+                start.eq_ignore_ascii_case(command)
+                    && text[command.len()..]
+                        .chars()
+                        .next()
+                        .is_none_or(char::is_whitespace)
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct SpellCheck {
+    pub enabled: bool,
+    /// Dictionaries to check words against, by the name of their
+    /// `.aff` and `.dic` files
+    pub languages: Vec<String>,
+    /// Directory searched for dictionaries before the system ones
+    #[serde(
+        deserialize_with = "deserialize_path_buf_with_path_transformations_maybe"
+    )]
+    pub path: Option<PathBuf>,
+}
+
+impl Default for SpellCheck {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            languages: vec!["en_US".to_string()],
+            path: None,
         }
     }
 }
@@ -39,6 +120,7 @@ impl Default for TextInput {
 pub enum KeyBindings {
     Default,
     Emacs,
+    Vi,
 }
 
 impl Default for KeyBindings {
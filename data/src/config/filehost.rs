@@ -11,6 +11,8 @@ pub struct Filehost {
     pub paste: bool,
     /// Handle drag-and-drop. Defaults to `true`.
     pub file_drop: bool,
+    /// How images are prepared before they are uploaded.
+    pub images: Images,
 }
 
 impl Default for Filehost {
@@ -20,6 +22,7 @@ impl Default for Filehost {
             button: true,
             paste: true,
             file_drop: false,
+            images: Images::default(),
         }
     }
 }
@@ -37,3 +40,50 @@ impl Filehost {
         self.enabled && self.file_drop
     }
 }
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Images {
+    /// Remove EXIF and other metadata, such as the location a photo was
+    /// taken at, without re-encoding the image. Defaults to `true`.
+    pub strip_metadata: bool,
+    /// Downscale images wider or taller than this many pixels.
+    pub max_dimension: Option<u32>,
+    /// Re-encode PNGs larger than `large_png_size` in this format.
+    pub convert_large_png: Option<ImageFormat>,
+    /// Size in KiB above which a PNG is re-encoded. Defaults to `1024`.
+    pub large_png_size: u64,
+    /// Quality of re-encoded JPEGs, from 1 to 100. Defaults to `90`.
+    pub jpeg_quality: u8,
+    /// Show the prepared images and their size before uploading. Defaults
+    /// to `false`.
+    pub confirm: bool,
+}
+
+impl Default for Images {
+    fn default() -> Self {
+        Self {
+            strip_metadata: true,
+            max_dimension: None,
+            convert_large_png: None,
+            large_png_size: 1024,
+            jpeg_quality: 90,
+            confirm: false,
+        }
+    }
+}
+
+impl Images {
+    pub fn enabled(&self) -> bool {
+        self.strip_metadata
+            || self.max_dimension.is_some()
+            || self.convert_large_png.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    Webp,
+    Jpeg,
+}
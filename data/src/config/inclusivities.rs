@@ -196,19 +196,12 @@ pub fn is_user_channel_server_included(
                         server: &Server|
      -> bool {
         inclusivities.is_some_and(|inclusivities| {
-            inclusivities.is_user_inclusive(user, casemapping)
-                || channel.is_some_and(|channel| {
-                    inclusivities.is_channel_inclusive(channel, casemapping)
-                })
-                || inclusivities.is_server_inclusive(server)
-                || inclusivities.criteria.iter().any(|criterion| {
-                    criterion.is_user_channel_server_inclusive(
-                        Some(user),
-                        channel,
-                        Some(server),
-                        casemapping,
-                    )
-                })
+            inclusivities.is_user_channel_server_inclusive(
+                Some(user),
+                channel,
+                server,
+                casemapping,
+            )
         })
     };
 
@@ -389,6 +382,28 @@ impl Inclusivities {
             })
     }
 
+    pub fn is_user_channel_server_inclusive(
+        &self,
+        user: Option<NickRef>,
+        channel: Option<&Channel>,
+        server: &Server,
+        casemapping: isupport::CaseMap,
+    ) -> bool {
+        user.is_some_and(|user| self.is_user_inclusive(user, casemapping))
+            || channel.is_some_and(|channel| {
+                self.is_channel_inclusive(channel, casemapping)
+            })
+            || self.is_server_inclusive(server)
+            || self.criteria.iter().any(|criterion| {
+                criterion.is_user_channel_server_inclusive(
+                    user,
+                    channel,
+                    Some(server),
+                    casemapping,
+                )
+            })
+    }
+
     pub fn is_server_inclusive(&self, server: &Server) -> bool {
         self.servers
             .as_ref()
@@ -1,6 +1,10 @@
 use serde::Deserialize;
 
-use crate::shortcut::{KeyBind, KeyBinds, Shortcut, shortcut};
+use super::buffer::text_input::KeyBindings;
+use crate::buffer::Upstream;
+use crate::shortcut::{
+    self, Command, KeyBind, KeyBinds, KeySequence, Shortcut, chord, shortcut,
+};
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
@@ -22,6 +26,7 @@ pub struct Keyboard {
     pub toggle_sidebar: KeyBinds,
     pub toggle_fullscreen: KeyBinds,
     pub command_bar: KeyBinds,
+    pub buffer_switcher: KeyBinds,
     pub reload_configuration: KeyBinds,
     pub file_transfers: KeyBinds,
     pub logs: KeyBinds,
@@ -29,15 +34,53 @@ pub struct Keyboard {
     // Keep highlight as alias for backwards compatibility
     #[serde(alias = "highlight")]
     pub highlights: KeyBinds,
+    /// Unset until the config is loaded, see [`Keyboard::resolve`].
+    #[serde(default)]
+    pub find: Option<KeyBinds>,
+    pub search_input_history: KeyBinds,
     pub scroll_up_page: KeyBinds,
     pub scroll_down_page: KeyBinds,
     pub scroll_to_top: KeyBinds,
     pub scroll_to_bottom: KeyBinds,
     pub cycle_next_unread_buffer: KeyBinds,
     pub cycle_previous_unread_buffer: KeyBinds,
+    pub cycle_next_workspace: KeyBinds,
+    pub cycle_previous_workspace: KeyBinds,
     pub mark_as_read: KeyBinds,
     pub quit_application: KeyBinds,
     pub open_config_file: KeyBinds,
+    /// Key that starts the chords written with `leader` in `commands`.
+    pub leader: KeyBind,
+    pub commands: Vec<CommandBind>,
+}
+
+/// Runs an input line, such as `/away lunch` or an alias, in the focused
+/// buffer.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommandBind {
+    pub keys: KeySequence,
+    pub command: String,
+    /// Buffers the bind applies to; all of them when empty.
+    #[serde(default)]
+    pub buffers: Vec<BufferKind>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BufferKind {
+    Server,
+    Channel,
+    Query,
+}
+
+impl From<&Upstream> for BufferKind {
+    fn from(buffer: &Upstream) -> Self {
+        match buffer {
+            Upstream::Server(_) => BufferKind::Server,
+            Upstream::Channel(..) => BufferKind::Channel,
+            Upstream::Query(..) => BufferKind::Query,
+        }
+    }
 }
 
 impl Default for Keyboard {
@@ -60,11 +103,14 @@ impl Default for Keyboard {
             toggle_topic: KeyBind::toggle_topic().into(),
             toggle_fullscreen: KeyBind::toggle_fullscreen().into(),
             command_bar: KeyBind::command_bar().into(),
+            buffer_switcher: KeyBind::buffer_switcher().into(),
             reload_configuration: KeyBind::reload_configuration().into(),
             file_transfers: KeyBind::file_transfers().into(),
             logs: KeyBind::logs().into(),
             theme_editor: KeyBind::theme_editor().into(),
             highlights: KeyBind::highlights().into(),
+            find: Some(KeyBind::find().into()),
+            search_input_history: KeyBind::search_input_history().into(),
             scroll_up_page: KeyBind::scroll_up_page().into(),
             scroll_down_page: KeyBind::scroll_down_page().into(),
             scroll_to_top: KeyBind::scroll_to_top().into(),
@@ -73,25 +119,83 @@ impl Default for Keyboard {
                 .into(),
             cycle_previous_unread_buffer:
                 KeyBind::cycle_previous_unread_buffer().into(),
+            cycle_next_workspace: KeyBind::cycle_next_workspace().into(),
+            cycle_previous_workspace: KeyBind::cycle_previous_workspace()
+                .into(),
             mark_as_read: KeyBind::mark_as_read().into(),
             quit_application: KeyBind::quit_application().into(),
             open_config_file: KeyBind::open_config_file().into(),
+            leader: KeyBind::leader(),
+            commands: vec![],
         }
     }
 }
 
 impl Keyboard {
-    pub fn shortcuts(&self) -> Vec<Shortcut> {
-        use crate::shortcut::Command::*;
+    /// Binds `find` to its default when it isn't set, unless the text input
+    /// uses emacs key bindings, which move the cursor with ctrl+f.
+    pub fn resolve(&mut self, key_bindings: &KeyBindings) {
+        if self.find.is_none() {
+            // Command is ctrl outside of macOS
+            let conflicts = !cfg!(target_os = "macos")
+                && matches!(key_bindings, KeyBindings::Emacs);
+
+            self.find = Some(if conflicts {
+                KeyBinds::default()
+            } else {
+                KeyBind::find().into()
+            });
+        }
+    }
 
-        let mut shortcuts = vec![];
+    /// Fails on a chord of `commands` that can't be pressed, since its first
+    /// keys are bound on their own in some buffer.
+    pub fn validate(&self) -> Result<(), super::Error> {
+        [
+            None,
+            Some(BufferKind::Server),
+            Some(BufferKind::Channel),
+            Some(BufferKind::Query),
+        ]
+        .into_iter()
+        .try_for_each(|buffer| {
+            match shortcut::blocked_chord(&self.shortcuts(buffer)) {
+                Some((chord, bound)) => Err(super::Error::BlockedChord {
+                    chord: shortcut::describe(chord),
+                    bound: shortcut::describe(bound),
+                }),
+                None => Ok(()),
+            }
+        })
+    }
+
+    /// Shortcuts available while a buffer of kind `buffer` is focused.  Command
+    /// binds come first so they take precedence over the built-in actions.
+    pub fn shortcuts(&self, buffer: Option<BufferKind>) -> Vec<Shortcut> {
+        use Command::*;
 
-        let mut push = |key_binds: &KeyBinds, command| {
+        let mut shortcuts = self
+            .commands
+            .iter()
+            .filter(|bind| {
+                buffer.is_some_and(|buffer| {
+                    bind.buffers.is_empty() || bind.buffers.contains(&buffer)
+                })
+            })
+            .filter_map(|bind| {
+                Some(chord(
+                    bind.keys.resolve(&self.leader)?,
+                    Run(bind.command.clone()),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        let mut push = |key_binds: &KeyBinds, command: Command| {
             shortcuts.extend(
                 key_binds
                     .iter()
                     .cloned()
-                    .map(|key_bind| shortcut(key_bind, command)),
+                    .map(|key_bind| shortcut(key_bind, command.clone())),
             );
         };
 
@@ -112,6 +216,7 @@ impl Keyboard {
         push(&self.toggle_sidebar, ToggleSidebar);
         push(&self.toggle_fullscreen, ToggleFullscreen);
         push(&self.command_bar, CommandBar);
+        push(&self.buffer_switcher, BufferSwitcher);
         push(&self.reload_configuration, ReloadConfiguration);
         push(&self.file_transfers, FileTransfers);
         push(&self.logs, Logs);
@@ -121,11 +226,17 @@ impl Keyboard {
         push(&self.scroll_to_top, ScrollToTop);
         push(&self.scroll_to_bottom, ScrollToBottom);
         push(&self.highlights, Highlights);
+        if let Some(find) = &self.find {
+            push(find, Find);
+        }
+        push(&self.search_input_history, SearchInputHistory);
         push(&self.cycle_next_unread_buffer, CycleNextUnreadBuffer);
         push(
             &self.cycle_previous_unread_buffer,
             CyclePreviousUnreadBuffer,
         );
+        push(&self.cycle_next_workspace, CycleNextWorkspace);
+        push(&self.cycle_previous_workspace, CyclePreviousWorkspace);
         push(&self.mark_as_read, MarkAsRead);
         push(&self.quit_application, QuitApplication);
         push(&self.open_config_file, OpenConfigFile);
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use chrono::NaiveTime;
 use serde::Deserialize;
 
 use crate::audio::Sound;
@@ -7,9 +8,10 @@ use crate::config::inclusivities::{
     Inclusivities, is_user_channel_server_included,
 };
 use crate::isupport;
+use crate::serde::deserialize_time_of_day;
 use crate::server::Server;
 use crate::target::Channel;
-use crate::user::User;
+use crate::user::{NickRef, User};
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
@@ -70,9 +72,145 @@ pub struct Notifications {
     #[serde(rename = "channel")]
     pub channels: HashMap<String, Notification>,
     pub reaction: Notification,
+    pub overrides: Vec<Override>,
+    pub quiet_hours: Option<QuietHours>,
+}
+
+/// Replaces the highlight, direct message and reaction notifications for
+/// the users, channels and servers matched by `include`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Override {
+    pub include: Inclusivities,
+    #[serde(default)]
+    pub highlight: Option<Notification>,
+    #[serde(default)]
+    pub direct_message: Option<Notification>,
+    #[serde(default)]
+    pub reaction: Option<Notification>,
+}
+
+/// Time of day when notifications are silenced, except for the users,
+/// channels and servers matched by `include`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuietHours {
+    #[serde(deserialize_with = "deserialize_time_of_day")]
+    pub start: NaiveTime,
+    #[serde(deserialize_with = "deserialize_time_of_day")]
+    pub end: NaiveTime,
+    #[serde(default)]
+    pub include: Option<Inclusivities>,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            // Spans midnight
+            time >= self.start || time < self.end
+        }
+    }
+
+    pub fn is_quiet(
+        &self,
+        time: NaiveTime,
+        user: Option<NickRef>,
+        channel: Option<&Channel>,
+        server: &Server,
+        casemapping: isupport::CaseMap,
+    ) -> bool {
+        self.contains(time)
+            && !self.include.as_ref().is_some_and(|include| {
+                include.is_user_channel_server_inclusive(
+                    user,
+                    channel,
+                    server,
+                    casemapping,
+                )
+            })
+    }
 }
 
 impl Notifications {
+    pub fn highlight(
+        &self,
+        user: NickRef,
+        channel: Option<&Channel>,
+        server: &Server,
+        casemapping: isupport::CaseMap,
+    ) -> &Notification {
+        self.overridden(
+            &self.highlight,
+            user,
+            channel,
+            server,
+            casemapping,
+            |notification_override| notification_override.highlight.as_ref(),
+        )
+    }
+
+    pub fn direct_message(
+        &self,
+        user: NickRef,
+        server: &Server,
+        casemapping: isupport::CaseMap,
+    ) -> &Notification {
+        self.overridden(
+            &self.direct_message,
+            user,
+            None,
+            server,
+            casemapping,
+            |notification_override| {
+                notification_override.direct_message.as_ref()
+            },
+        )
+    }
+
+    pub fn reaction(
+        &self,
+        user: NickRef,
+        channel: Option<&Channel>,
+        server: &Server,
+        casemapping: isupport::CaseMap,
+    ) -> &Notification {
+        self.overridden(
+            &self.reaction,
+            user,
+            channel,
+            server,
+            casemapping,
+            |notification_override| notification_override.reaction.as_ref(),
+        )
+    }
+
+    /// The first override matching the user, channel and server, or
+    /// `default` when none does.
+    fn overridden<'a>(
+        &'a self,
+        default: &'a Notification,
+        user: NickRef,
+        channel: Option<&Channel>,
+        server: &Server,
+        casemapping: isupport::CaseMap,
+        notification: impl Fn(&'a Override) -> Option<&'a Notification>,
+    ) -> &'a Notification {
+        self.overrides
+            .iter()
+            .filter(|notification_override| {
+                notification_override
+                    .include
+                    .is_user_channel_server_inclusive(
+                        Some(user),
+                        channel,
+                        server,
+                        casemapping,
+                    )
+            })
+            .find_map(notification)
+            .unwrap_or(default)
+    }
+
     pub fn load_sounds<'a>(
         &self,
         highlight_matches_sounds: impl Iterator<Item = &'a str>,
@@ -121,6 +259,21 @@ impl Notifications {
         if let Some(sound_name) = self.reaction.sound.as_deref() {
             load_and_insert(sound_name);
         }
+        for notification in
+            self.overrides.iter().flat_map(|notification_override| {
+                [
+                    &notification_override.highlight,
+                    &notification_override.direct_message,
+                    &notification_override.reaction,
+                ]
+                .into_iter()
+                .flatten()
+            })
+        {
+            if let Some(sound_name) = notification.sound.as_deref() {
+                load_and_insert(sound_name);
+            }
+        }
         for notification in self.channels.values() {
             if let Some(sound_name) = notification.sound.as_deref() {
                 load_and_insert(sound_name);
@@ -133,3 +286,29 @@ impl Notifications {
         sounds
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quiet_hours() {
+        let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+        let quiet_hours = |start, end| QuietHours {
+            start: time(start),
+            end: time(end),
+            include: None,
+        };
+
+        let night = quiet_hours(22, 7);
+        assert!(night.contains(time(23)));
+        assert!(night.contains(time(3)));
+        assert!(!night.contains(time(7)));
+        assert!(!night.contains(time(12)));
+
+        let lunch = quiet_hours(12, 13);
+        assert!(lunch.contains(time(12)));
+        assert!(!lunch.contains(time(13)));
+        assert!(!lunch.contains(time(23)));
+    }
+}
@@ -9,6 +9,8 @@ pub struct Pane {
     pub split_axis: SplitAxis,
     pub scrollbar: Scrollbar,
     pub restore_on_launch: bool,
+    /// Workspace opened on launch in place of the last panes.
+    pub workspace_on_launch: Option<String>,
     pub gap: Gap,
 }
 
@@ -18,6 +20,7 @@ impl Default for Pane {
             split_axis: SplitAxis::default(),
             scrollbar: Scrollbar::default(),
             restore_on_launch: true,
+            workspace_on_launch: None,
             gap: Gap::default(),
         }
     }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use fancy_regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Deserializer};
 
@@ -18,6 +20,7 @@ pub struct Preview {
     pub request: Request,
     pub card: Card,
     pub image: Image,
+    pub providers: Providers,
 }
 
 impl Default for Preview {
@@ -29,6 +32,7 @@ impl Default for Preview {
             request: Request::default(),
             card: Card::default(),
             image: Image::default(),
+            providers: Providers::default(),
         }
     }
 }
@@ -50,8 +54,12 @@ impl Preview {
 
     pub fn hide_url_when(&self, preview: &crate::Preview) -> HideUrlCondition {
         match preview {
-            crate::Preview::Card(_) => self.card.hide_url,
             crate::Preview::Image(_) => self.image.hide_url,
+            crate::Preview::Card(_)
+            | crate::Preview::Video(_)
+            | crate::Preview::Code(_)
+            | crate::Preview::Snippet(_)
+            | crate::Preview::Embed(_) => self.card.hide_url,
         }
     }
 }
@@ -304,6 +312,7 @@ pub struct Image {
     pub max_width: f32,
     /// Maximum height of the image in pixels
     pub max_height: f32,
+    pub animation: Animation,
 }
 
 impl Default for Image {
@@ -316,6 +325,7 @@ impl Default for Image {
             round_corners: true,
             max_width: 550.0,
             max_height: 350.0,
+            animation: Animation::default(),
         }
     }
 }
@@ -328,6 +338,43 @@ pub enum ImageAction {
     Preview,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Animation {
+    pub playback: Playback,
+    /// Animations with more frames are shown as still images
+    pub max_frames: usize,
+    /// Maximum size of an animation's decoded frames in megabytes
+    ///
+    /// Larger animations are shown as still images
+    pub max_memory: u64,
+}
+
+impl Animation {
+    pub fn max_memory_bytes(&self) -> u64 {
+        self.max_memory.saturating_mul(1_000_000)
+    }
+}
+
+impl Default for Animation {
+    fn default() -> Self {
+        Self {
+            playback: Playback::default(),
+            max_frames: 500,
+            max_memory: 64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Playback {
+    #[default]
+    Autoplay,
+    OnHover,
+    Never,
+}
+
 impl Image {
     pub fn visible(
         &self,
@@ -362,6 +409,46 @@ impl Image {
     }
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Providers {
+    /// Fall back to oEmbed endpoints advertised by pages that don't
+    /// provide enough OpenGraph data for a card
+    pub oembed: bool,
+    /// Show a poster and duration for pages with `og:video`
+    pub video: bool,
+    /// Query code hosting APIs for repositories, issues and pull requests
+    pub code: bool,
+    /// Show the first lines of pastebins and plain text links
+    pub snippet: bool,
+    /// Maximum number of lines shown in a snippet
+    pub snippet_max_lines: usize,
+    /// Additional (self-hosted) code hosting sites, keyed by host
+    pub forges: HashMap<String, Forge>,
+}
+
+impl Default for Providers {
+    fn default() -> Self {
+        Self {
+            oembed: false,
+            video: false,
+            code: false,
+            snippet: false,
+            snippet_max_lines: 8,
+            forges: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Forge {
+    Github,
+    Gitlab,
+    #[serde(alias = "forgejo")]
+    Gitea,
+}
+
 pub fn deserialize_trim_interval<'de, D>(
     deserializer: D,
 ) -> Result<u64, D::Error>
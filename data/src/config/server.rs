@@ -10,6 +10,9 @@ use tokio::process::Command;
 
 use self::filehost::Filehost;
 use self::icon::Icon;
+use self::oper::Oper;
+use self::webpush::WebPush;
+use self::znc::Znc;
 use crate::config::inclusivities::{
     Inclusivities, is_target_channel_included, is_target_query_included,
 };
@@ -24,7 +27,10 @@ use crate::{config, isupport, metadata, target};
 pub mod filehost;
 pub mod filters;
 pub mod icon;
+pub mod oper;
 pub mod reroute;
+pub mod webpush;
+pub mod znc;
 
 pub use self::filters::{FancyRegex, Filters, Ignore};
 pub use self::reroute::{Reroute, RerouteRule, RerouteTarget};
@@ -145,6 +151,9 @@ pub struct Server {
     pub filehost: Filehost,
     pub metadata: HashMap<metadata::Key, String>,
     pub icon: Icon,
+    pub znc: Znc,
+    pub webpush: WebPush,
+    pub oper: Oper,
 }
 
 impl Server {
@@ -278,6 +287,9 @@ impl Default for Server {
             filehost: Filehost::default(),
             metadata: HashMap::default(),
             icon: Icon::default(),
+            znc: Znc::default(),
+            webpush: WebPush::default(),
+            oper: Oper::default(),
         }
     }
 }
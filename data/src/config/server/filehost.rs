@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Deserializer};
 
 use super::Sasl;
@@ -10,6 +12,9 @@ pub struct Filehost {
     /// Override the filehost URL advertised by the server via ISUPPORT
     pub override_url: Option<String>,
     pub credentials: Credentials,
+    /// Where files are uploaded to. Defaults to the `soju.im/filehost`
+    /// advertised by the server (or `override_url`).
+    pub backend: Backend,
 }
 
 impl Default for Filehost {
@@ -18,10 +23,89 @@ impl Default for Filehost {
             enabled: true,
             override_url: None,
             credentials: Credentials::default(),
+            backend: Backend::default(),
         }
     }
 }
 
+#[derive(PartialEq, Eq, Debug, Default, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum Backend {
+    #[default]
+    Soju,
+    Multipart(Multipart),
+    S3(S3),
+    Command(Command),
+}
+
+impl Backend {
+    /// The location uploads are sent to, shown before uploading to it for
+    /// the first time.  `None` for the server's filehost.
+    pub fn location(&self) -> Option<&str> {
+        match self {
+            Backend::Soju => None,
+            Backend::Multipart(multipart) => Some(&multipart.url),
+            Backend::S3(s3) => Some(&s3.endpoint),
+            Backend::Command(command) => Some(&command.command),
+        }
+    }
+}
+
+/// Generic `multipart/form-data` POST, as used by 0x0.st and similar hosts.
+#[derive(PartialEq, Eq, Debug, Clone, Deserialize)]
+pub struct Multipart {
+    pub url: String,
+    /// Name of the form field holding the file.
+    #[serde(default = "default_multipart_field")]
+    pub field: String,
+    /// Additional form fields sent along with the file.
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// JSON pointer to the URL in a JSON response.  The whole response body
+    /// is used as the URL when not set.
+    #[serde(default)]
+    pub url_pointer: Option<String>,
+}
+
+fn default_multipart_field() -> String {
+    String::from("file")
+}
+
+/// S3-compatible object storage, uploaded to with a presigned PUT.
+#[derive(PartialEq, Eq, Debug, Clone, Deserialize)]
+pub struct S3 {
+    pub endpoint: String,
+    pub bucket: String,
+    #[serde(default = "default_s3_region")]
+    pub region: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Prefix prepended to the key of uploaded objects.
+    #[serde(default)]
+    pub prefix: String,
+    /// Address the bucket as `<endpoint>/<bucket>` rather than
+    /// `<bucket>.<endpoint>`.
+    #[serde(default)]
+    pub path_style: bool,
+    /// Base URL uploaded objects are publicly reachable at.  Defaults to the
+    /// object URL.
+    #[serde(default)]
+    pub public_url: Option<String>,
+}
+
+fn default_s3_region() -> String {
+    String::from("us-east-1")
+}
+
+/// A local command that uploads the file in `HALLOY_UPLOAD_FILE` and prints
+/// its URL to stdout.
+#[derive(PartialEq, Eq, Debug, Clone, Deserialize)]
+pub struct Command {
+    pub command: String,
+}
+
 #[derive(PartialEq, Eq, Debug, Default, Clone)]
 pub enum Credentials {
     #[default]
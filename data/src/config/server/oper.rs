@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use tokio::fs;
+
+use super::read_from_command;
+use crate::config;
+use crate::serde::deserialize_path_buf_with_path_transformations_maybe;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Oper {
+    /// Operator name (falls back to nickname if not provided)
+    pub name: Option<String>,
+    /// Operator password
+    pub password: Option<String>,
+    /// Operator password file
+    #[serde(
+        deserialize_with = "deserialize_path_buf_with_path_transformations_maybe"
+    )]
+    pub password_file: Option<PathBuf>,
+    /// Truncate read from password file to first newline
+    pub password_file_first_line_only: bool,
+    /// Operator password command
+    pub password_command: Option<String>,
+    /// Send OPER automatically once connected. Defaults to `false`.
+    pub on_connect: bool,
+    /// Server notice mask to set once opered up (e.g. `+cFkns`)
+    pub snomask: Option<String>,
+}
+
+impl Default for Oper {
+    fn default() -> Self {
+        Self {
+            name: None,
+            password: None,
+            password_file: None,
+            password_file_first_line_only: true,
+            password_command: None,
+            on_connect: false,
+            snomask: None,
+        }
+    }
+}
+
+impl Oper {
+    pub fn check_permissions(&self, server: &str) {
+        if let Some(pass_file) = &self.password_file {
+            config::check_sensitive_file_permissions(
+                server,
+                pass_file,
+                "oper password file",
+            );
+        }
+    }
+
+    pub async fn set_password(&mut self) -> Result<(), config::Error> {
+        match (&self.password, &self.password_file, &self.password_command) {
+            (_, None, None) => {}
+            (None, Some(pass_file), None) => {
+                let mut pass = fs::read_to_string(pass_file).await?;
+
+                if self.password_file_first_line_only {
+                    pass = pass
+                        .lines()
+                        .next()
+                        .map(String::from)
+                        .unwrap_or_default();
+                }
+
+                self.password = Some(pass);
+            }
+            (None, None, Some(pass_command)) => {
+                self.password = Some(read_from_command(pass_command).await?);
+            }
+            _ => return Err(config::Error::DuplicateOperPassword),
+        }
+
+        Ok(())
+    }
+}
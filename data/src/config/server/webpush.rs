@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct WebPush {
+    /// Request the `soju.im/webpush` capability.
+    pub enabled: bool,
+    /// Base URL of the push endpoint to register with the bouncer.  The
+    /// server name is appended as the final path segment.
+    pub endpoint: Option<String>,
+}
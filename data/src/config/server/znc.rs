@@ -0,0 +1,32 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Znc {
+    /// Request messages buffered since the last connection from the
+    /// `playback` module. Defaults to `true`.
+    pub playback: bool,
+    /// Client identifier to register with the `clientbuffer` module.
+    pub clientbuffer: Option<String>,
+    /// Prefix used by ZNC for module nicknames (`StatusPrefix`).
+    pub status_prefix: String,
+    /// Commands sent to `*status` once ZNC has been detected.
+    pub status_commands: Vec<String>,
+}
+
+impl Default for Znc {
+    fn default() -> Self {
+        Self {
+            playback: true,
+            clientbuffer: None,
+            status_prefix: "*".to_string(),
+            status_commands: Vec::default(),
+        }
+    }
+}
+
+impl Znc {
+    pub fn module_nick(&self, module: &str) -> String {
+        format!("{}{module}", self.status_prefix)
+    }
+}
@@ -4,10 +4,13 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+pub mod sidebar;
+pub mod workspace;
+
 use crate::buffer::{self, Buffer};
 use crate::pane::Pane;
 use crate::serde::fail_as_none;
-use crate::{compression, environment};
+use crate::{compression, environment, notification, outbox, schedule};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dashboard {
@@ -18,6 +21,17 @@ pub struct Dashboard {
     pub buffer_settings: BufferSettings,
     #[serde(default, deserialize_with = "fail_as_none")]
     pub focus_buffer: Option<Buffer>,
+    #[serde(default)]
+    pub sidebar: sidebar::Layout,
+    /// Name of the workspace the panes were last switched to.
+    #[serde(default)]
+    pub workspace: Option<String>,
+    #[serde(default)]
+    pub mutes: notification::Mutes,
+    #[serde(default)]
+    pub scheduled: schedule::Scheduled,
+    #[serde(default)]
+    pub outbox: outbox::Outbox,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -57,13 +71,13 @@ pub enum BufferFocusedAction {
 
 impl Dashboard {
     pub fn exists() -> Result<bool, Error> {
-        let path = path()?;
+        let path = path("dashboard.json.gz")?;
 
         Ok(std::fs::exists(path)?)
     }
 
     pub fn load() -> Result<Self, Error> {
-        let path = path()?;
+        let path = path("dashboard.json.gz")?;
 
         let bytes = std::fs::read(path)?;
 
@@ -71,7 +85,7 @@ impl Dashboard {
     }
 
     pub async fn save(self) -> Result<(), Error> {
-        let path = path()?;
+        let path = path("dashboard.json.gz")?;
 
         let bytes = compression::compress(&self)?;
 
@@ -81,14 +95,14 @@ impl Dashboard {
     }
 }
 
-fn path() -> Result<PathBuf, Error> {
+fn path(file_name: &str) -> Result<PathBuf, Error> {
     let parent = environment::data_dir();
 
     if !parent.exists() {
         std::fs::create_dir_all(&parent)?;
     }
 
-    Ok(parent.join("dashboard.json.gz"))
+    Ok(parent.join(file_name))
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -97,4 +111,8 @@ pub enum Error {
     Compression(#[from] compression::Error),
     #[error(transparent)]
     Io(#[from] io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("unsupported version {0}")]
+    UnsupportedVersion(u64),
 }
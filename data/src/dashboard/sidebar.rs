@@ -0,0 +1,306 @@
+use serde::{Deserialize, Serialize};
+
+use crate::buffer::Upstream;
+
+/// How the user has arranged the sidebar: buffers pinned to the top, named
+/// groups that may span servers, and a custom order for the buffers that stay
+/// listed under their server.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Layout {
+    pub pinned: Vec<Upstream>,
+    pub groups: Vec<Group>,
+    order: Vec<Upstream>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Group {
+    pub name: String,
+    pub buffers: Vec<Upstream>,
+}
+
+/// Where a dragged buffer was dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    Buffer(Upstream),
+    Group(usize),
+}
+
+impl Layout {
+    pub fn is_pinned(&self, buffer: &Upstream) -> bool {
+        self.pinned.contains(buffer)
+    }
+
+    pub fn group_of(&self, buffer: &Upstream) -> Option<usize> {
+        self.groups
+            .iter()
+            .position(|group| group.buffers.contains(buffer))
+    }
+
+    /// Whether the buffer is listed outside of its server.
+    pub fn is_placed(&self, buffer: &Upstream) -> bool {
+        self.is_pinned(buffer) || self.group_of(buffer).is_some()
+    }
+
+    pub fn pin(&mut self, buffer: Upstream) {
+        self.release(&buffer);
+        self.pinned.push(buffer);
+    }
+
+    pub fn add_to_group(&mut self, buffer: Upstream, index: usize) {
+        if index >= self.groups.len() {
+            return;
+        }
+
+        self.release(&buffer);
+        self.groups[index].buffers.push(buffer);
+    }
+
+    /// Creates a group holding `buffer` and returns its index.
+    pub fn new_group(&mut self, name: String, buffer: Upstream) -> usize {
+        self.release(&buffer);
+        self.groups.push(Group {
+            name,
+            buffers: vec![buffer],
+        });
+
+        self.groups.len() - 1
+    }
+
+    pub fn rename_group(&mut self, index: usize, name: String) {
+        if let Some(group) = self.groups.get_mut(index) {
+            group.name = name;
+        }
+    }
+
+    /// Removes the group, returning its buffers to their servers.
+    pub fn remove_group(&mut self, index: usize) {
+        if index < self.groups.len() {
+            self.groups.remove(index);
+        }
+    }
+
+    /// Returns the buffer to its server by unpinning it or removing it from
+    /// its group.
+    pub fn release(&mut self, buffer: &Upstream) {
+        self.pinned.retain(|pinned| pinned != buffer);
+
+        for group in &mut self.groups {
+            group.buffers.retain(|grouped| grouped != buffer);
+        }
+    }
+
+    /// Orders the channels and queries listed under a server, leaving out the
+    /// ones that are pinned or grouped.  Buffers without a custom position
+    /// keep their default order after the ones that have one.
+    pub fn arrange(&self, buffers: Vec<Upstream>) -> Vec<Upstream> {
+        let mut buffers = buffers
+            .into_iter()
+            .filter(|buffer| !self.is_placed(buffer))
+            .collect::<Vec<_>>();
+
+        buffers.sort_by_key(|buffer| {
+            self.order
+                .iter()
+                .position(|ordered| ordered == buffer)
+                .unwrap_or(usize::MAX)
+        });
+
+        buffers
+    }
+
+    /// Moves `buffer` onto `target`.  Dropping onto a pinned or grouped buffer
+    /// moves it into that section, dropping onto a buffer listed under its own
+    /// server returns it there.  `listed` holds the channels and queries of
+    /// the target's server, in their default order.
+    pub fn move_to(
+        &mut self,
+        buffer: Upstream,
+        target: Target,
+        listed: Vec<Upstream>,
+    ) {
+        if matches!(buffer, Upstream::Server(_)) {
+            return;
+        }
+
+        let target = match target {
+            Target::Group(index) => {
+                self.add_to_group(buffer, index);
+                return;
+            }
+            Target::Buffer(target) => target,
+        };
+
+        if buffer == target {
+            return;
+        }
+
+        if self.is_pinned(&target) {
+            self.release_keeping(&buffer, Section::Pinned);
+            move_within(&mut self.pinned, buffer, &target);
+        } else if let Some(index) = self.group_of(&target) {
+            self.release_keeping(&buffer, Section::Group(index));
+            move_within(&mut self.groups[index].buffers, buffer, &target);
+        } else if buffer.server() == target.server() {
+            let server = target.server().clone();
+            let mut arranged = self.arrange(listed);
+
+            self.release(&buffer);
+
+            match target {
+                // Dropping onto the server puts the buffer first.
+                Upstream::Server(_) => {
+                    arranged.retain(|arranged| *arranged != buffer);
+                    arranged.insert(0, buffer);
+                }
+                _ => move_within(&mut arranged, buffer, &target),
+            }
+
+            self.order.retain(|ordered| *ordered.server() != server);
+            self.order.extend(arranged);
+        }
+    }
+
+    fn release_keeping(&mut self, buffer: &Upstream, keep: Section) {
+        if keep != Section::Pinned {
+            self.pinned.retain(|pinned| pinned != buffer);
+        }
+
+        for (index, group) in self.groups.iter_mut().enumerate() {
+            if keep != Section::Group(index) {
+                group.buffers.retain(|grouped| grouped != buffer);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Pinned,
+    Group(usize),
+}
+
+/// Moves `buffer` into the slot of `target`, so moving down places it after
+/// the target and moving up places it before.
+fn move_within(list: &mut Vec<Upstream>, buffer: Upstream, target: &Upstream) {
+    let Some(to) = list.iter().position(|item| item == target) else {
+        return;
+    };
+
+    if let Some(from) = list.iter().position(|item| *item == buffer) {
+        list.remove(from);
+    }
+
+    list.insert(to.min(list.len()), buffer);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::ServerName;
+    use crate::{Server, isupport, target};
+
+    fn server(name: &str) -> Server {
+        Server::from(ServerName::from(name))
+    }
+
+    fn channel(server_name: &str, name: &str) -> Upstream {
+        Upstream::Channel(
+            server(server_name),
+            target::Channel::from_str(
+                name,
+                isupport::DEFAULT_CHANTYPES,
+                isupport::CaseMap::default(),
+            ),
+        )
+    }
+
+    fn listed() -> Vec<Upstream> {
+        vec![
+            channel("libera", "#a"),
+            channel("libera", "#b"),
+            channel("libera", "#c"),
+        ]
+    }
+
+    #[test]
+    fn reorder_server_buffers() {
+        let mut layout = Layout::default();
+
+        layout.move_to(
+            channel("libera", "#a"),
+            Target::Buffer(channel("libera", "#c")),
+            listed(),
+        );
+        assert_eq!(
+            layout.arrange(listed()),
+            vec![
+                channel("libera", "#b"),
+                channel("libera", "#c"),
+                channel("libera", "#a"),
+            ]
+        );
+
+        layout.move_to(
+            channel("libera", "#a"),
+            Target::Buffer(Upstream::Server(server("libera"))),
+            listed(),
+        );
+        assert_eq!(
+            layout.arrange(listed()),
+            vec![
+                channel("libera", "#a"),
+                channel("libera", "#b"),
+                channel("libera", "#c"),
+            ]
+        );
+
+        // Buffers can't be moved under another server.
+        layout.move_to(
+            channel("oftc", "#d"),
+            Target::Buffer(channel("libera", "#b")),
+            listed(),
+        );
+        assert_eq!(layout.arrange(listed()).len(), 3);
+    }
+
+    #[test]
+    fn pin_and_group() {
+        let mut layout = Layout::default();
+
+        layout.pin(channel("libera", "#a"));
+        let work = layout.new_group("Work".into(), channel("oftc", "#d"));
+
+        layout.move_to(channel("libera", "#b"), Target::Group(work), listed());
+        layout.move_to(
+            channel("libera", "#c"),
+            Target::Buffer(channel("libera", "#a")),
+            listed(),
+        );
+
+        assert_eq!(
+            layout.pinned,
+            vec![channel("libera", "#c"), channel("libera", "#a")]
+        );
+        assert_eq!(
+            layout.groups[work].buffers,
+            vec![channel("oftc", "#d"), channel("libera", "#b")]
+        );
+        assert!(layout.arrange(listed()).is_empty());
+
+        // Dropping a pinned buffer back under its server releases it.
+        layout.move_to(
+            channel("libera", "#a"),
+            Target::Buffer(Upstream::Server(server("libera"))),
+            listed(),
+        );
+        assert!(!layout.is_pinned(&channel("libera", "#a")));
+        assert_eq!(layout.arrange(listed()), vec![channel("libera", "#a")]);
+
+        layout.remove_group(work);
+        assert_eq!(
+            layout.arrange(listed()),
+            vec![channel("libera", "#a"), channel("libera", "#b")]
+        );
+    }
+}
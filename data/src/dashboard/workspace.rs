@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Dashboard, Error};
+use crate::buffer::Buffer;
+use crate::compression;
+use crate::pane::Pane;
+use crate::serde::fail_as_none;
+
+/// Schema version written to `workspaces.json.gz`; bump it when the layout
+/// of [`Workspaces`] changes in a way older versions can't read.
+const VERSION: u32 = 1;
+
+/// A named pane layout with the buffers assigned to each pane.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    pub pane: Pane,
+    #[serde(default)]
+    pub popout_panes: Vec<Pane>,
+    #[serde(default, deserialize_with = "fail_as_none")]
+    pub focus_buffer: Option<Buffer>,
+}
+
+impl Workspace {
+    pub fn new(name: String, dashboard: Dashboard) -> Self {
+        Self {
+            name,
+            pane: dashboard.pane,
+            popout_panes: dashboard.popout_panes,
+            focus_buffer: dashboard.focus_buffer,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspaces {
+    version: u32,
+    workspaces: Vec<Workspace>,
+}
+
+impl Default for Workspaces {
+    fn default() -> Self {
+        Self {
+            version: VERSION,
+            workspaces: vec![],
+        }
+    }
+}
+
+impl Workspaces {
+    pub fn get(&self, name: &str) -> Option<&Workspace> {
+        self.workspaces
+            .iter()
+            .find(|workspace| workspace.name == name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.workspaces
+            .iter()
+            .map(|workspace| workspace.name.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.workspaces.is_empty()
+    }
+
+    /// Stores the workspace, replacing any workspace with the same name.
+    pub fn insert(&mut self, workspace: Workspace) {
+        if let Some(existing) = self
+            .workspaces
+            .iter_mut()
+            .find(|existing| existing.name == workspace.name)
+        {
+            *existing = workspace;
+        } else {
+            self.workspaces.push(workspace);
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Workspace> {
+        let index = self
+            .workspaces
+            .iter()
+            .position(|workspace| workspace.name == name)?;
+
+        Some(self.workspaces.remove(index))
+    }
+
+    /// The workspace after (or before, when `forward` is false) `current`,
+    /// wrapping around.  Starts from the first workspace when there is no
+    /// current one.
+    pub fn cycle(&self, current: Option<&str>, forward: bool) -> Option<&str> {
+        let len = self.workspaces.len();
+
+        let index = match current.and_then(|current| {
+            self.workspaces
+                .iter()
+                .position(|workspace| workspace.name == current)
+        }) {
+            Some(index) if forward => (index + 1) % len,
+            Some(index) => (index + len - 1) % len,
+            None => 0,
+        };
+
+        self.workspaces
+            .get(index)
+            .map(|workspace| workspace.name.as_str())
+    }
+
+    /// Stores every workspace of `other`, replacing any workspace with the
+    /// same name.
+    pub fn extend(&mut self, other: Workspaces) {
+        for workspace in other.workspaces {
+            self.insert(workspace);
+        }
+    }
+
+    pub async fn load() -> Result<Self, Error> {
+        let path = super::path("workspaces.json.gz")?;
+
+        if !tokio::fs::try_exists(&path).await? {
+            return Ok(Self::default());
+        }
+
+        let bytes = tokio::fs::read(path).await?;
+
+        // Check the version before decoding the rest, so a file written by a
+        // newer version is reported as such rather than as a decoding error.
+        let value = compression::decompress::<serde_json::Value>(&bytes)?;
+        let version = value
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or_default();
+
+        if version > u64::from(VERSION) {
+            return Err(Error::UnsupportedVersion(version));
+        }
+
+        Ok(serde_json::from_value(value)?)
+    }
+
+    pub async fn save(self) -> Result<(), Error> {
+        let path = super::path("workspaces.json.gz")?;
+
+        let bytes = compression::compress(&self)?;
+
+        tokio::fs::write(path, &bytes).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace(name: &str) -> Workspace {
+        Workspace {
+            name: name.to_string(),
+            pane: Pane::Empty,
+            popout_panes: vec![],
+            focus_buffer: None,
+        }
+    }
+
+    #[test]
+    fn insert_and_cycle() {
+        let mut workspaces = Workspaces::default();
+
+        assert_eq!(workspaces.cycle(None, true), None);
+
+        workspaces.insert(workspace("Work"));
+        workspaces.insert(workspace("Projects"));
+        workspaces.insert(workspace("Work"));
+
+        assert_eq!(
+            workspaces.names().collect::<Vec<_>>(),
+            ["Work", "Projects"]
+        );
+        assert_eq!(workspaces.cycle(None, false), Some("Work"));
+        assert_eq!(workspaces.cycle(Some("Work"), true), Some("Projects"));
+        assert_eq!(workspaces.cycle(Some("Projects"), true), Some("Work"));
+        assert_eq!(workspaces.cycle(Some("Work"), false), Some("Projects"));
+
+        assert!(workspaces.remove("Work").is_some());
+        assert_eq!(workspaces.cycle(Some("Work"), true), Some("Projects"));
+
+        let mut saved = Workspaces::default();
+        saved.insert(workspace("Work"));
+        workspaces.extend(saved);
+
+        assert_eq!(
+            workspaces.names().collect::<Vec<_>>(),
+            ["Projects", "Work"]
+        );
+    }
+}
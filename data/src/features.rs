@@ -8,6 +8,7 @@ pub struct Features {
     pub detach: bool,
     pub mass_message: bool,
     pub list_mode_with_equal: bool,
+    pub znc: bool,
     pub version_request: VersionRequest,
 }
 
@@ -33,6 +34,8 @@ impl Features {
         } else if server_version.starts_with("solanum") {
             self.mass_message = true;
             self.list_mode_with_equal = true;
+        } else if server_version.starts_with("ZNC") {
+            self.znc = true;
         }
     }
 }
@@ -4,14 +4,21 @@ use std::sync::Arc;
 
 use any_ascii::any_ascii;
 use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+use rand::RngExt as _;
 use reqwest::{Client, header};
 use tokio::io::AsyncReadExt as _;
 use tokio_util::io::ReaderStream;
 use url::Url;
 
-use crate::config::server::Sasl;
+use crate::config::server::{Sasl, filehost};
 use crate::config::{self, proxy};
 
+pub mod preprocess;
+
+mod command;
+mod multipart;
+mod s3;
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("unsupported or invalid URI: {0}")]
@@ -22,14 +29,21 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error("HTTP: {0}")]
     Http(#[from] reqwest::Error),
-    #[error("HTTP POST {url} returned {status}: {body}")]
+    #[error("HTTP {method} {url} returned {status}: {body}")]
     HttpStatusError {
+        method: reqwest::Method,
         status: reqwest::StatusCode,
         url: String,
         body: String,
     },
     #[error("server did not return a Location header")]
     NoLocation,
+    #[error("server response did not contain a URL: {0}")]
+    NoUrl(String),
+    #[error("upload command failed: {0}")]
+    Command(String),
+    #[error("image: {0}")]
+    Image(#[from] image::ImageError),
     #[error("client certificate error: {0}")]
     ClientCert(String),
     #[error("client build error: {0}")]
@@ -73,6 +87,56 @@ impl Auth {
     }
 }
 
+/// Where files are uploaded to.
+#[derive(Debug)]
+pub enum Backend {
+    /// A `soju.im/filehost` compatible filehost.
+    Filehost {
+        url: String,
+        auth: Option<Auth>,
+        irc_uses_tls: bool,
+        is_override_url: bool,
+    },
+    Multipart(filehost::Multipart),
+    S3(filehost::S3),
+    Command(filehost::Command),
+}
+
+impl Backend {
+    /// Upload the file at `path`, returning its public URL.
+    pub async fn upload(
+        self,
+        path: &Path,
+        client: Arc<Client>,
+        proxy_config: Option<&proxy::Proxy>,
+    ) -> Result<String, Error> {
+        match self {
+            Backend::Filehost {
+                url,
+                auth,
+                irc_uses_tls,
+                is_override_url,
+            } => {
+                upload(
+                    &url,
+                    path,
+                    auth,
+                    irc_uses_tls,
+                    is_override_url,
+                    client,
+                    proxy_config,
+                )
+                .await
+            }
+            Backend::Multipart(multipart) => {
+                multipart::upload(&multipart, path, &client).await
+            }
+            Backend::S3(s3) => s3::upload(&s3, path, &client).await,
+            Backend::Command(command) => command::upload(&command, path).await,
+        }
+    }
+}
+
 /// Upload the file at `path` to `upload_url`.
 ///
 /// Returns the public URL of the uploaded file as reported by the server's
@@ -115,24 +179,12 @@ pub async fn upload(
         return Err(Error::InsecureTransport);
     }
 
-    let file_name = path.file_name().map_or_else(
-        || String::from("file"),
-        |n| n.to_string_lossy().into_owned(),
-    );
-
-    let mut file = tokio::fs::File::open(path).await?;
-    let file_size = file.metadata().await?.len();
-
-    // read 36 bytes to infer file type
-    let magic_len = (36usize).min(file_size as usize);
-    let mut magic_buffer = vec![0u8; magic_len];
-    file.read_exact(&mut magic_buffer).await?;
-
-    let content_type = infer_mime_type(path, &magic_buffer);
-
-    // merge magic_buffer into remaining file stream
-    let stream = ReaderStream::new(Cursor::new(magic_buffer).chain(file));
-    let body = reqwest::Body::wrap_stream(stream);
+    let File {
+        name: file_name,
+        size: file_size,
+        content_type,
+        body,
+    } = File::open(path).await?;
 
     let (external_client, auth_header) = match &auth {
         Some(Auth::External { cert, key }) => (
@@ -168,7 +220,12 @@ pub async fn upload(
     if status.as_u16() != 201 {
         let url = resp.url().to_string();
         let body = resp.text().await.unwrap_or_default();
-        return Err(Error::HttpStatusError { status, url, body });
+        return Err(Error::HttpStatusError {
+            method: reqwest::Method::POST,
+            status,
+            url,
+            body,
+        });
     }
 
     let location = resp
@@ -187,6 +244,76 @@ pub async fn upload(
     Ok(file_url.to_string())
 }
 
+const TEMPORARY_PREFIX: &str = "halloy-upload-";
+
+/// Path for a file named `file_name` that is only written to be uploaded,
+/// in a directory of its own in the temporary directory.
+pub fn temporary_path(file_name: &str) -> Result<PathBuf, std::io::Error> {
+    let dir = std::env::temp_dir().join(format!(
+        "{TEMPORARY_PREFIX}{:016x}",
+        rand::rng().random::<u64>()
+    ));
+    std::fs::create_dir_all(&dir)?;
+
+    Ok(dir.join(file_name))
+}
+
+/// Removes `path` if it was created with [`temporary_path`], once its upload
+/// is done, has failed or was cancelled. Other files are left alone.
+pub fn remove_temporary(path: &Path) {
+    let Some(dir) = path.parent() else {
+        return;
+    };
+
+    let is_temporary = dir.parent() == Some(std::env::temp_dir().as_path())
+        && dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(TEMPORARY_PREFIX));
+
+    if is_temporary && let Err(error) = std::fs::remove_dir_all(dir) {
+        log::warn!("failed to remove {}: {error}", dir.display());
+    }
+}
+
+/// A file opened for uploading.
+struct File {
+    name: String,
+    size: u64,
+    content_type: &'static str,
+    body: reqwest::Body,
+}
+
+impl File {
+    async fn open(path: &Path) -> Result<Self, Error> {
+        let name = path.file_name().map_or_else(
+            || String::from("file"),
+            |n| n.to_string_lossy().into_owned(),
+        );
+
+        let mut file = tokio::fs::File::open(path).await?;
+        let size = file.metadata().await?.len();
+
+        // read 36 bytes to infer file type
+        let magic_len = (36usize).min(size as usize);
+        let mut magic_buffer = vec![0u8; magic_len];
+        file.read_exact(&mut magic_buffer).await?;
+
+        let content_type = infer_mime_type(path, &magic_buffer);
+
+        // merge magic_buffer into remaining file stream
+        let stream = ReaderStream::new(Cursor::new(magic_buffer).chain(file));
+        let body = reqwest::Body::wrap_stream(stream);
+
+        Ok(Self {
+            name,
+            size,
+            content_type,
+            body,
+        })
+    }
+}
+
 fn is_localhost(url: &Url) -> bool {
     match url.host() {
         Some(url::Host::Domain(d)) => d == "localhost",
@@ -0,0 +1,88 @@
+use std::path::Path;
+
+use tokio::process;
+use url::Url;
+
+use super::Error;
+use crate::config::server::filehost::Command;
+
+/// Upload the file at `path` by running a local command.
+///
+/// The path is passed to the command in the `HALLOY_UPLOAD_FILE` environment
+/// variable, and the last line it prints to stdout is used as the URL.
+pub async fn upload(config: &Command, path: &Path) -> Result<String, Error> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = process::Command::new("cmd");
+        command.arg("/C");
+        command
+    } else {
+        let mut command = process::Command::new("sh");
+        command.arg("-c");
+        command
+    };
+
+    log::debug!("uploading {} with {}", path.display(), config.command);
+
+    let output = command
+        .arg(&config.command)
+        .env("HALLOY_UPLOAD_FILE", path)
+        .kill_on_drop(true)
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        return Err(Error::Command(format!(
+            "{}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let location = stdout
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .ok_or_else(|| Error::NoUrl(String::new()))?;
+
+    let file_url =
+        Url::parse(location).map_err(|e| Error::InvalidUri(e.to_string()))?;
+
+    log::info!("file uploaded successfully: {file_url}");
+
+    Ok(file_url.to_string())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_file;
+
+    #[tokio::test]
+    async fn prints_url() {
+        let (_dir, path) = temp_file("hello.txt", "hello world");
+        let config = Command {
+            command: String::from(
+                r#"echo uploading; echo "https://example.org/$(basename "$HALLOY_UPLOAD_FILE")""#,
+            ),
+        };
+
+        assert_eq!(
+            upload(&config, &path).await.unwrap(),
+            "https://example.org/hello.txt"
+        );
+    }
+
+    #[tokio::test]
+    async fn fails() {
+        let (_dir, path) = temp_file("hello.txt", "hello world");
+        let config = Command {
+            command: String::from("echo nope >&2; exit 1"),
+        };
+
+        assert!(matches!(
+            upload(&config, &path).await,
+            Err(Error::Command(error)) if error.ends_with("nope")
+        ));
+    }
+}
@@ -0,0 +1,168 @@
+use std::path::Path;
+
+use reqwest::Client;
+use reqwest::multipart::{Form, Part};
+use url::Url;
+
+use super::{Error, File};
+use crate::config::server::filehost::Multipart;
+
+/// Upload the file at `path` as a `multipart/form-data` POST.
+///
+/// Returns the URL in the response body, or the one at `url_pointer` of a
+/// JSON response.
+pub async fn upload(
+    config: &Multipart,
+    path: &Path,
+    client: &Client,
+) -> Result<String, Error> {
+    let base = Url::parse(&config.url)
+        .map_err(|e| Error::InvalidUri(e.to_string()))?;
+
+    let File {
+        name,
+        size,
+        content_type,
+        body,
+    } = File::open(path).await?;
+
+    let part = Part::stream_with_length(body, size)
+        .file_name(name.clone())
+        .mime_str(content_type)?;
+
+    let form = config
+        .fields
+        .iter()
+        .fold(Form::new(), |form, (name, value)| {
+            form.text(name.clone(), value.clone())
+        })
+        .part(config.field.clone(), part);
+
+    log::debug!("uploading {name} to {base}");
+
+    let mut req = client.post(base.clone()).multipart(form);
+
+    for (name, value) in &config.headers {
+        req = req.header(name.as_str(), value.as_str());
+    }
+
+    let resp = req.send().await?;
+
+    let status = resp.status();
+    let url = resp.url().to_string();
+    let body = resp.text().await?;
+
+    if !status.is_success() {
+        return Err(Error::HttpStatusError {
+            method: reqwest::Method::POST,
+            status,
+            url,
+            body,
+        });
+    }
+
+    let location = match &config.url_pointer {
+        Some(pointer) => serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|value| {
+                value.pointer(pointer)?.as_str().map(ToString::to_string)
+            })
+            .ok_or_else(|| Error::NoUrl(body.clone()))?,
+        None => body.trim().to_string(),
+    };
+
+    if location.is_empty() {
+        return Err(Error::NoUrl(body));
+    }
+
+    let file_url = base
+        .join(&location)
+        .map_err(|e| Error::InvalidUri(e.to_string()))?;
+
+    log::info!("file uploaded successfully: {file_url}");
+
+    Ok(file_url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::test_support::{StandIn, temp_file};
+
+    fn multipart(url: &Url) -> Multipart {
+        Multipart {
+            url: url.to_string(),
+            field: String::from("file"),
+            fields: BTreeMap::from([(
+                String::from("expires"),
+                String::from("24"),
+            )]),
+            headers: BTreeMap::from([(
+                String::from("X-Token"),
+                String::from("secret"),
+            )]),
+            url_pointer: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn plain_response() {
+        let mut server =
+            StandIn::respond("200 OK", "https://0x0.example/abc.txt\n").await;
+        let (_dir, path) = temp_file("hello.txt", "hello world");
+
+        let file_url = upload(&multipart(&server.url), &path, &Client::new())
+            .await
+            .unwrap();
+
+        assert_eq!(file_url, "https://0x0.example/abc.txt");
+
+        let request = server.request().await;
+        assert!(request.starts_with("POST / HTTP/1.1"));
+        assert!(request.to_lowercase().contains("x-token: secret"));
+        assert!(request.contains("name=\"file\"; filename=\"hello.txt\""));
+        assert!(request.contains("name=\"expires\""));
+        assert!(request.contains("hello world"));
+    }
+
+    #[tokio::test]
+    async fn json_response() {
+        let server = StandIn::respond(
+            "200 OK",
+            r#"{"files":[{"url":"/f/abc.txt"}],"success":true}"#,
+        )
+        .await;
+        let (_dir, path) = temp_file("hello.txt", "hello world");
+
+        let config = Multipart {
+            url_pointer: Some(String::from("/files/0/url")),
+            ..multipart(&server.url)
+        };
+
+        let file_url = upload(&config, &path, &Client::new()).await.unwrap();
+
+        assert_eq!(
+            file_url,
+            server.url.join("/f/abc.txt").unwrap().to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn error_status() {
+        let server =
+            StandIn::respond("413 Payload Too Large", "too large").await;
+        let (_dir, path) = temp_file("hello.txt", "hello world");
+
+        let error = upload(&multipart(&server.url), &path, &Client::new())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            Error::HttpStatusError { status, .. }
+                if status == reqwest::StatusCode::PAYLOAD_TOO_LARGE
+        ));
+    }
+}
@@ -0,0 +1,344 @@
+use std::fmt;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader};
+
+use super::Error;
+use crate::config::filehost::{self, Images};
+
+mod metadata;
+
+/// An image prepared for uploading.
+#[derive(Debug, Clone)]
+pub struct Processed {
+    /// Temporary file holding the prepared image.
+    pub path: PathBuf,
+    pub original: Summary,
+    pub processed: Summary,
+    pub metadata_stripped: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    pub width: u32,
+    pub height: u32,
+    /// File size in bytes.
+    pub size: u64,
+    pub format: Format,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Png,
+    Jpeg,
+    Webp,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Png => write!(f, "PNG"),
+            Format::Jpeg => write!(f, "JPEG"),
+            Format::Webp => write!(f, "WebP"),
+        }
+    }
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Png => "png",
+            Format::Jpeg => "jpg",
+            Format::Webp => "webp",
+        }
+    }
+}
+
+impl From<filehost::ImageFormat> for Format {
+    fn from(format: filehost::ImageFormat) -> Self {
+        match format {
+            filehost::ImageFormat::Webp => Format::Webp,
+            filehost::ImageFormat::Jpeg => Format::Jpeg,
+        }
+    }
+}
+
+/// Prepares the image at `path` for uploading as configured, writing the
+/// result to a temporary file.
+///
+/// Metadata is removed without touching the pixels; the image is only
+/// decoded and re-encoded when it has to be downscaled or converted.
+///
+/// Returns `None` for files that aren't PNG or JPEG images, or when the
+/// configuration leaves the image as it is.  Decoding and encoding is slow,
+/// so this should be run off the UI thread.
+pub fn process(
+    path: &Path,
+    config: &Images,
+) -> Result<Option<Processed>, Error> {
+    if !config.enabled() {
+        return Ok(None);
+    }
+
+    let bytes = std::fs::read(path)?;
+    let reader = ImageReader::new(Cursor::new(&bytes)).with_guessed_format()?;
+
+    // Other formats may be animated, which would be lost when re-encoded.
+    let format = match reader.format() {
+        Some(ImageFormat::Png) => Format::Png,
+        Some(ImageFormat::Jpeg) => Format::Jpeg,
+        _ => return Ok(None),
+    };
+
+    let mut decoder = reader.into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let (width, height) = decoder.dimensions();
+
+    let original = Summary {
+        width,
+        height,
+        size: bytes.len() as u64,
+        format,
+    };
+
+    let max_dimension =
+        config.max_dimension.filter(|max| width.max(height) > *max);
+    let convert_to = config
+        .convert_large_png
+        .filter(|_| {
+            format == Format::Png
+                && original.size > config.large_png_size.saturating_mul(1024)
+        })
+        .map(Format::from);
+
+    let (output, processed, metadata_stripped) =
+        if max_dimension.is_none() && convert_to.is_none() {
+            let Some(output) = config
+                .strip_metadata
+                .then(|| metadata::strip(&bytes, format, orientation))
+                .flatten()
+            else {
+                return Ok(None);
+            };
+
+            let processed = Summary {
+                size: output.len() as u64,
+                ..original
+            };
+
+            (output, processed, true)
+        } else {
+            let mut image = DynamicImage::from_decoder(decoder)?;
+
+            // The orientation is stored in the metadata, so it has to be
+            // applied to the pixels before the metadata is left out.
+            image.apply_orientation(orientation);
+
+            if let Some(max) = max_dimension {
+                image = image.resize(max, max, FilterType::Lanczos3);
+            }
+
+            let format = convert_to.unwrap_or(format);
+            let output = encode(&image, format, config.jpeg_quality)?;
+
+            let processed = Summary {
+                width: image.width(),
+                height: image.height(),
+                size: output.len() as u64,
+                format,
+            };
+
+            // None of the encoders write the metadata of the original image
+            (
+                output,
+                processed,
+                metadata::contains(&bytes, original.format),
+            )
+        };
+
+    let stem = path.file_stem().map_or_else(
+        || String::from("image"),
+        |stem| stem.to_string_lossy().into_owned(),
+    );
+    let processed_path = super::temporary_path(&format!(
+        "{stem}.{}",
+        processed.format.extension()
+    ))?;
+
+    std::fs::write(&processed_path, &output)?;
+
+    Ok(Some(Processed {
+        path: processed_path,
+        original,
+        processed,
+        metadata_stripped,
+    }))
+}
+
+fn encode(
+    image: &DynamicImage,
+    format: Format,
+    jpeg_quality: u8,
+) -> Result<Vec<u8>, Error> {
+    let mut bytes = Cursor::new(vec![]);
+
+    match format {
+        Format::Png => {
+            image.write_with_encoder(PngEncoder::new(&mut bytes))?;
+        }
+        Format::Jpeg => {
+            // JPEG has no alpha channel
+            DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(
+                JpegEncoder::new_with_quality(
+                    &mut bytes,
+                    jpeg_quality.clamp(1, 100),
+                ),
+            )?;
+        }
+        Format::Webp => {
+            let image = if image.color().has_alpha() {
+                DynamicImage::ImageRgba8(image.to_rgba8())
+            } else {
+                DynamicImage::ImageRgb8(image.to_rgb8())
+            };
+
+            image.write_with_encoder(WebPEncoder::new_lossless(&mut bytes))?;
+        }
+    }
+
+    Ok(bytes.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgb, RgbImage};
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::fileupload;
+    use crate::test_support::temp_file;
+
+    fn gradient(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |x, y| {
+            Rgb([(x % 256) as u8, (y % 256) as u8, 128])
+        }))
+    }
+
+    fn write(
+        name: &str,
+        image: &DynamicImage,
+        format: Format,
+    ) -> (TempDir, PathBuf) {
+        temp_file(name, encode(image, format, 90).unwrap())
+    }
+
+    /// Processes `path` and reads back the prepared file, removing it.
+    fn prepare(path: &Path, config: &Images) -> Option<(Processed, Vec<u8>)> {
+        let processed = process(path, config).unwrap()?;
+        let output = std::fs::read(&processed.path).unwrap();
+
+        fileupload::remove_temporary(&processed.path);
+        assert!(!processed.path.parent().unwrap().exists());
+
+        Some((processed, output))
+    }
+
+    #[test]
+    fn strips_exif() {
+        let jpeg = encode(&gradient(64, 48), Format::Jpeg, 90).unwrap();
+
+        // APP1 segment with a (bogus) EXIF payload right after SOI
+        let payload = b"Exif\0\0GPSLatitude 52.3676 GPSLongitude 4.9041";
+        let mut exif = vec![0xFF, 0xE1];
+        exif.extend_from_slice(&(payload.len() as u16 + 2).to_be_bytes());
+        exif.extend_from_slice(payload);
+
+        let mut bytes = jpeg[..2].to_vec();
+        bytes.extend_from_slice(&exif);
+        bytes.extend_from_slice(&jpeg[2..]);
+
+        let (_dir, path) = temp_file("photo.jpg", &bytes);
+
+        let (processed, output) = prepare(&path, &Images::default()).unwrap();
+
+        assert!(processed.metadata_stripped);
+        assert_eq!(processed.processed.format, Format::Jpeg);
+        assert_eq!(
+            (processed.processed.width, processed.processed.height),
+            (64, 48)
+        );
+        // Nothing but the metadata is touched
+        assert_eq!(output, jpeg);
+    }
+
+    #[test]
+    fn leaves_images_without_metadata_alone() {
+        let (_dir, path) = write("photo.jpg", &gradient(64, 48), Format::Jpeg);
+
+        assert!(process(&path, &Images::default()).unwrap().is_none());
+    }
+
+    #[test]
+    fn downscales() {
+        let (_dir, path) = write("wide.png", &gradient(400, 100), Format::Png);
+        let config = Images {
+            max_dimension: Some(200),
+            ..Images::default()
+        };
+
+        let (processed, _) = prepare(&path, &config).unwrap();
+
+        assert_eq!(
+            (processed.original.width, processed.original.height),
+            (400, 100)
+        );
+        assert_eq!(
+            (processed.processed.width, processed.processed.height),
+            (200, 50)
+        );
+        assert_eq!(processed.processed.format, Format::Png);
+        assert!(!processed.metadata_stripped);
+    }
+
+    #[test]
+    fn converts_large_png() {
+        let (_dir, path) =
+            write("screenshot.png", &gradient(64, 64), Format::Png);
+        let config = Images {
+            convert_large_png: Some(filehost::ImageFormat::Jpeg),
+            large_png_size: 0,
+            ..Images::default()
+        };
+
+        let (processed, output) = prepare(&path, &config).unwrap();
+
+        assert_eq!(processed.processed.format, Format::Jpeg);
+        assert_eq!(processed.path.extension().unwrap(), "jpg");
+        assert_eq!(
+            ImageReader::new(Cursor::new(output))
+                .with_guessed_format()
+                .unwrap()
+                .format(),
+            Some(ImageFormat::Jpeg)
+        );
+
+        let small = Images {
+            large_png_size: 1024,
+            strip_metadata: false,
+            ..config
+        };
+        assert!(process(&path, &small).unwrap().is_none());
+    }
+
+    #[test]
+    fn skips_other_files() {
+        let (_dir, path) = temp_file("notes.txt", "not an image");
+
+        assert!(process(&path, &Images::default()).unwrap().is_none());
+    }
+}
@@ -0,0 +1,306 @@
+//! Removes metadata from PNG and JPEG files without decoding the image, so
+//! the pixels are uploaded exactly as they are.
+//!
+//! The orientation is the only metadata that changes how an image looks, so
+//! it is written back in a minimal EXIF block with nothing else in it.
+
+use image::metadata::Orientation;
+
+use super::Format;
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Returns the file without its metadata, or `None` if there is none (or the
+/// file isn't understood, in which case it should be re-encoded instead).
+pub fn strip(
+    bytes: &[u8],
+    format: Format,
+    orientation: Orientation,
+) -> Option<Vec<u8>> {
+    match format {
+        Format::Jpeg => strip_jpeg(bytes, orientation),
+        Format::Png => strip_png(bytes, orientation),
+        Format::Webp => None,
+    }
+}
+
+/// Whether the file carries any metadata [`strip`] would remove.
+pub fn contains(bytes: &[u8], format: Format) -> bool {
+    strip(bytes, format, Orientation::NoTransforms).is_some()
+}
+
+fn strip_jpeg(bytes: &[u8], orientation: Orientation) -> Option<Vec<u8>> {
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+
+    let mut output = Vec::with_capacity(bytes.len());
+    output.extend_from_slice(&bytes[..2]);
+
+    // Where the orientation goes: after the JFIF header if there is one, as
+    // it has to come first.
+    let mut insert_at = output.len();
+    let mut stripped = false;
+    let mut i = 2;
+
+    loop {
+        if *bytes.get(i)? != 0xFF {
+            return None;
+        }
+
+        let marker = *bytes.get(i + 1)?;
+
+        match marker {
+            // Fill byte
+            0xFF => {
+                i += 1;
+                continue;
+            }
+            // EOI; anything after it (e.g. thumbnails) is left out
+            0xD9 => {
+                output.extend_from_slice(&bytes[i..i + 2]);
+                stripped |= bytes.len() > i + 2;
+                break;
+            }
+            // Markers without a length
+            0x01 | 0xD0..=0xD7 => {
+                output.extend_from_slice(&bytes[i..i + 2]);
+                i += 2;
+                continue;
+            }
+            _ => {}
+        }
+
+        let length =
+            u16::from_be_bytes([*bytes.get(i + 2)?, *bytes.get(i + 3)?])
+                as usize;
+        let end = i + 2 + length;
+
+        if length < 2 || end > bytes.len() {
+            return None;
+        }
+
+        if is_jpeg_metadata(marker) {
+            stripped = true;
+        } else {
+            let first = output.len() == 2;
+            output.extend_from_slice(&bytes[i..end]);
+
+            if first && marker == 0xE0 {
+                insert_at = output.len();
+            }
+        }
+
+        i = end;
+
+        // SOS is followed by entropy-coded data, which runs until the next
+        // marker that isn't a stuffed byte or a restart marker
+        if marker == 0xDA {
+            let start = i;
+
+            while *bytes.get(i)? != 0xFF
+                || matches!(bytes.get(i + 1)?, 0x00 | 0xD0..=0xD7 | 0xFF)
+            {
+                i += 1;
+            }
+
+            output.extend_from_slice(&bytes[start..i]);
+        }
+    }
+
+    if !stripped {
+        return None;
+    }
+
+    if let Some(tiff) = orientation_tiff(orientation) {
+        let mut segment = vec![0xFF, 0xE1];
+        segment.extend_from_slice(&(tiff.len() as u16 + 8).to_be_bytes());
+        segment.extend_from_slice(b"Exif\0\0");
+        segment.extend_from_slice(&tiff);
+
+        output.splice(insert_at..insert_at, segment);
+    }
+
+    Some(output)
+}
+
+/// APP1 (EXIF, XMP), APP3 to APP13 (e.g. IPTC) and comments. The JFIF
+/// header (APP0), ICC profiles (APP2) and Adobe's color transform (APP14)
+/// change how the image is decoded, so they are kept.
+fn is_jpeg_metadata(marker: u8) -> bool {
+    matches!(marker, 0xE1 | 0xE3..=0xED | 0xEF | 0xFE)
+}
+
+fn strip_png(bytes: &[u8], orientation: Orientation) -> Option<Vec<u8>> {
+    if !bytes.starts_with(PNG_SIGNATURE) {
+        return None;
+    }
+
+    let mut output = Vec::with_capacity(bytes.len());
+    output.extend_from_slice(PNG_SIGNATURE);
+
+    let mut insert_at = None;
+    let mut stripped = false;
+    let mut i = PNG_SIGNATURE.len();
+
+    loop {
+        let length =
+            u32::from_be_bytes(bytes.get(i..i + 4)?.try_into().ok()?) as usize;
+        let kind = bytes.get(i + 4..i + 8)?;
+        let end = i.checked_add(12)?.checked_add(length)?;
+
+        if end > bytes.len() {
+            return None;
+        }
+
+        if matches!(kind, b"eXIf" | b"tEXt" | b"zTXt" | b"iTXt" | b"tIME") {
+            stripped = true;
+        } else {
+            output.extend_from_slice(&bytes[i..end]);
+        }
+
+        // eXIf has to come before the image data
+        if kind == b"IHDR" {
+            insert_at = Some(output.len());
+        }
+
+        i = end;
+
+        if kind == b"IEND" {
+            stripped |= bytes.len() > i;
+            break;
+        }
+    }
+
+    if !stripped {
+        return None;
+    }
+
+    if let Some(tiff) = orientation_tiff(orientation) {
+        let insert_at = insert_at?;
+
+        let mut chunk = (tiff.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(b"eXIf");
+        chunk.extend_from_slice(&tiff);
+        chunk.extend_from_slice(&crc32fast::hash(&chunk[4..]).to_be_bytes());
+
+        output.splice(insert_at..insert_at, chunk);
+    }
+
+    Some(output)
+}
+
+/// Big-endian TIFF structure with a single IFD entry for the orientation.
+fn orientation_tiff(orientation: Orientation) -> Option<Vec<u8>> {
+    if orientation == Orientation::NoTransforms {
+        return None;
+    }
+
+    let mut tiff = vec![];
+    // Header, with the IFD right after it
+    tiff.extend_from_slice(b"MM\0\x2A");
+    tiff.extend_from_slice(&8u32.to_be_bytes());
+    // One entry: orientation (0x0112), SHORT (3), one value
+    tiff.extend_from_slice(&1u16.to_be_bytes());
+    tiff.extend_from_slice(&0x0112u16.to_be_bytes());
+    tiff.extend_from_slice(&3u16.to_be_bytes());
+    tiff.extend_from_slice(&1u32.to_be_bytes());
+    tiff.extend_from_slice(&u16::from(orientation.to_exif()).to_be_bytes());
+    tiff.extend_from_slice(&[0, 0]);
+    // No next IFD
+    tiff.extend_from_slice(&0u32.to_be_bytes());
+
+    Some(tiff)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use image::{DynamicImage, ImageDecoder, ImageReader, RgbImage};
+
+    use super::*;
+
+    fn encoded(format: image::ImageFormat) -> Vec<u8> {
+        let mut bytes = Cursor::new(vec![]);
+        DynamicImage::ImageRgb8(RgbImage::new(8, 4))
+            .write_to(&mut bytes, format)
+            .unwrap();
+        bytes.into_inner()
+    }
+
+    fn orientation(bytes: &[u8]) -> Orientation {
+        ImageReader::new(Cursor::new(bytes))
+            .with_guessed_format()
+            .unwrap()
+            .into_decoder()
+            .unwrap()
+            .orientation()
+            .unwrap()
+    }
+
+    fn contains_bytes(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+    }
+
+    #[test]
+    fn jpeg_keeps_orientation_only() {
+        let jpeg = encoded(image::ImageFormat::Jpeg);
+
+        // EXIF with the orientation, followed by something that shouldn't
+        // survive, then a comment and a thumbnail after the image
+        let mut tiff = orientation_tiff(Orientation::Rotate90).unwrap();
+        tiff.extend_from_slice(b"GPSLatitude 52.3676");
+        let mut exif = vec![0xFF, 0xE1];
+        exif.extend_from_slice(&(tiff.len() as u16 + 8).to_be_bytes());
+        exif.extend_from_slice(b"Exif\0\0");
+        exif.extend_from_slice(&tiff);
+
+        let mut bytes = jpeg[..2].to_vec();
+        bytes.extend_from_slice(&exif);
+        bytes.extend_from_slice(&[0xFF, 0xFE, 0x00, 0x07]);
+        bytes.extend_from_slice(b"hello");
+        bytes.extend_from_slice(&jpeg[2..]);
+        bytes.extend_from_slice(b"thumbnail");
+
+        assert_eq!(orientation(&bytes), Orientation::Rotate90);
+
+        let output =
+            strip(&bytes, Format::Jpeg, Orientation::Rotate90).unwrap();
+
+        assert_eq!(orientation(&output), Orientation::Rotate90);
+        assert!(!contains_bytes(&output, b"GPSLatitude"));
+        assert!(!contains_bytes(&output, b"hello"));
+        assert!(output.ends_with(&[0xFF, 0xD9]));
+        assert!(contains(&bytes, Format::Jpeg));
+    }
+
+    #[test]
+    fn png_drops_text_chunks() {
+        let png = encoded(image::ImageFormat::Png);
+        let ihdr_end = PNG_SIGNATURE.len() + 12 + 13;
+
+        let data = b"Location\0Amsterdam";
+        let mut text = (data.len() as u32).to_be_bytes().to_vec();
+        text.extend_from_slice(b"tEXt");
+        text.extend_from_slice(data);
+        text.extend_from_slice(&crc32fast::hash(&text[4..]).to_be_bytes());
+
+        let mut bytes = png[..ihdr_end].to_vec();
+        bytes.extend_from_slice(&text);
+        bytes.extend_from_slice(&png[ihdr_end..]);
+
+        assert_eq!(
+            strip(&bytes, Format::Png, Orientation::NoTransforms),
+            Some(png.clone())
+        );
+        assert_eq!(strip(&png, Format::Png, Orientation::NoTransforms), None);
+
+        let rotated =
+            strip(&bytes, Format::Png, Orientation::Rotate180).unwrap();
+        assert_eq!(orientation(&rotated), Orientation::Rotate180);
+        assert!(!contains_bytes(&rotated, b"Amsterdam"));
+    }
+}
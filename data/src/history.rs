@@ -26,6 +26,7 @@ use crate::{
 };
 
 pub mod filter;
+pub mod find;
 pub mod manager;
 pub mod metadata;
 pub mod reroute;
@@ -45,6 +46,7 @@ pub enum Kind {
     Server(Server),
     Channel(Server, target::Channel),
     Query(Server, target::Query),
+    ServerNotices(Server),
     Logs,
     Highlights,
 }
@@ -101,8 +103,12 @@ impl Kind {
         target: &message::Target,
     ) -> Option<Self> {
         match target {
-            message::Target::Server { .. } => {
-                Some(Self::Server(server.clone()))
+            message::Target::Server { source } => {
+                if source.is_server_notice() {
+                    Some(Self::ServerNotices(server.clone()))
+                } else {
+                    Some(Self::Server(server.clone()))
+                }
             }
             message::Target::Channel { channel, .. } => {
                 Some(Self::Channel(server.clone(), channel.clone()))
@@ -126,11 +132,17 @@ impl Kind {
             Buffer::Upstream(buffer::Upstream::Query(server, nick)) => {
                 Some(Kind::Query(server, nick))
             }
+            Buffer::Internal(buffer::Internal::ServerNotices(server)) => {
+                Some(Kind::ServerNotices(server))
+            }
             Buffer::Internal(buffer::Internal::Logs) => Some(Kind::Logs),
             Buffer::Internal(buffer::Internal::Highlights) => {
                 Some(Kind::Highlights)
             }
-            Buffer::Internal(buffer::Internal::FileTransfers) => None,
+            Buffer::Internal(
+                buffer::Internal::FileTransfers
+                | buffer::Internal::ScheduledMessages,
+            ) => None,
             Buffer::Internal(buffer::Internal::ChannelDiscovery(_)) => None,
         }
     }
@@ -142,6 +154,7 @@ impl Kind {
             Kind::Server(server) => Some(server),
             Kind::Channel(server, _) => Some(server),
             Kind::Query(server, _) => Some(server),
+            Kind::ServerNotices(server) => Some(server),
             Kind::Logs => None,
             Kind::Highlights => None,
         }
@@ -152,6 +165,7 @@ impl Kind {
             Kind::Server(_) => None,
             Kind::Channel(_, channel) => Some(Target::Channel(channel.clone())),
             Kind::Query(_, nick) => Some(Target::Query(nick.clone())),
+            Kind::ServerNotices(_) => None,
             Kind::Logs => None,
             Kind::Highlights => None,
         }
@@ -166,6 +180,9 @@ impl fmt::Display for Kind {
                 write!(f, "channel {channel} on {server}")
             }
             Kind::Query(server, nick) => write!(f, "user {nick} on {server}"),
+            Kind::ServerNotices(server) => {
+                write!(f, "server notices on {server}")
+            }
             Kind::Logs => write!(f, "logs"),
             Kind::Highlights => write!(f, "highlights"),
         }
@@ -184,6 +201,9 @@ impl From<Kind> for Buffer {
             Kind::Query(server, nick) => {
                 Buffer::Upstream(buffer::Upstream::Query(server, nick))
             }
+            Kind::ServerNotices(server) => {
+                Buffer::Internal(buffer::Internal::ServerNotices(server))
+            }
             Kind::Logs => Buffer::Internal(buffer::Internal::Logs),
             Kind::Highlights => Buffer::Internal(buffer::Internal::Highlights),
         }
@@ -396,6 +416,7 @@ async fn path(kind: &Kind) -> Result<PathBuf, Error> {
         Kind::Query(server, query) => {
             format!("{server:b}nickname{}", query.as_normalized_str())
         }
+        Kind::ServerNotices(server) => format!("{server:b}server-notices"),
         Kind::Logs => "logs".to_string(),
         Kind::Highlights => "highlights".to_string(),
     };
@@ -661,6 +682,90 @@ impl History {
             .is_some_and(|msg| msg.direction == Direction::Sent || msg.is_echo)
     }
 
+    /// When the latest echo of a sent message was received.
+    fn last_echo(&self) -> Option<DateTime<Utc>> {
+        let is_echo = |message: &&Message| {
+            message.direction == Direction::Received && message.is_echo
+        };
+
+        match self {
+            History::Partial {
+                pending_messages, ..
+            } => pending_messages
+                .iter()
+                .map(|(message, _)| message)
+                .filter(is_echo)
+                .map(|message| message.server_time)
+                .max(),
+            History::Full { messages, .. } => messages
+                .iter()
+                .rev()
+                .find(is_echo)
+                .map(|message| message.server_time),
+        }
+    }
+
+    /// Sent messages since `since` that are still waiting for their echo,
+    /// in the order they were sent.
+    fn unconfirmed(&self, since: DateTime<Utc>) -> Vec<Message> {
+        let is_unconfirmed = |message: &&Message| {
+            message.direction == Direction::Sent
+                && message.command.is_some()
+                && message.server_time >= since
+        };
+
+        match self {
+            History::Partial {
+                pending_messages, ..
+            } => {
+                let mut unconfirmed = pending_messages
+                    .iter()
+                    .map(|(message, _)| message)
+                    .filter(is_unconfirmed)
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                unconfirmed.sort_by_key(|message| message.server_time);
+
+                unconfirmed
+            }
+            History::Full { messages, .. } => {
+                let start_index = messages
+                    .partition_point(|message| message.server_time < since);
+
+                messages[start_index..]
+                    .iter()
+                    .filter(is_unconfirmed)
+                    .cloned()
+                    .collect()
+            }
+        }
+    }
+
+    /// Whether an echo of the sent message has been received since, e.g.
+    /// replayed from chathistory after reconnecting. Labeled echoes replace
+    /// the message they answer, so either way an echo is matched by target
+    /// and content, within the same window as in [`insert_message`].
+    fn is_echoed(&self, sent: &Message) -> bool {
+        let fuzz_seconds = chrono::Duration::seconds(300);
+        let start = sent.server_time - fuzz_seconds;
+        let end = sent.server_time + fuzz_seconds;
+
+        let is_echo = |message: &Message| {
+            message.direction == Direction::Received
+                && message.is_echo
+                && (start..=end).contains(&message.server_time)
+                && has_matching_content(sent, message, true)
+        };
+
+        match self {
+            History::Partial {
+                pending_messages, ..
+            } => pending_messages.iter().any(|(message, _)| is_echo(message)),
+            History::Full { messages, .. } => messages.iter().any(is_echo),
+        }
+    }
+
     fn remove_message(
         &mut self,
         server_time: DateTime<Utc>,
@@ -750,7 +855,9 @@ impl History {
                             .then_some(start_index + slice_index)
                     })
                 {
-                    if messages[index].redaction.is_some() {
+                    if messages[index].redaction.is_some()
+                        || messages[index].target.source().is_netsplit()
+                    {
                         return vec![&mut messages[index]];
                     } else if let Some(first_index) = messages[..=index]
                         .iter()
@@ -1598,6 +1705,9 @@ fn has_matching_content(
                 | message::source::server::Kind::MonitoredOnline
                 | message::source::server::Kind::MonitoredOffline
                 | message::source::server::Kind::StandardReply(_)
+                | message::source::server::Kind::ServerNotice(_)
+                | message::source::server::Kind::Netsplit
+                | message::source::server::Kind::Netjoin
                 | message::source::server::Kind::WAllOps
                 | message::source::server::Kind::Kick
                 | message::source::server::Kind::Away
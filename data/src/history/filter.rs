@@ -285,7 +285,8 @@ impl Filter {
             FilterClass::Server(target_server) => match kind {
                 Kind::Server(server)
                 | Kind::Channel(server, _)
-                | Kind::Query(server, _) => target_server == server,
+                | Kind::Query(server, _)
+                | Kind::ServerNotices(server) => target_server == server,
                 Kind::Highlights | Kind::Logs => false,
             },
         }
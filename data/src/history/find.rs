@@ -0,0 +1,189 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use fancy_regex::{Regex, RegexBuilder};
+
+use crate::Message;
+use crate::message::{Content, Fragment};
+
+/// What part of a message a search looks at.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Scope {
+    #[default]
+    Content,
+    Nick,
+}
+
+/// A case-insensitive search of the messages in a buffer, either for plain
+/// text or a regex.
+#[derive(Debug, Clone)]
+pub struct Query {
+    scope: Scope,
+    regex: Regex,
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum Error {
+    #[error("invalid regex: {0}")]
+    Regex(String),
+}
+
+impl Query {
+    /// Returns `None` when `text` is empty.
+    pub fn new(
+        text: &str,
+        scope: Scope,
+        is_regex: bool,
+    ) -> Result<Option<Self>, Error> {
+        if text.is_empty() {
+            return Ok(None);
+        }
+
+        let pattern = if is_regex {
+            Cow::Borrowed(text)
+        } else {
+            fancy_regex::escape(text)
+        };
+
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(true)
+            .build()
+            .map_err(|error| Error::Regex(error.to_string()))?;
+
+        Ok(Some(Self { scope, regex }))
+    }
+
+    pub fn matches(&self, message: &Message) -> bool {
+        match self.scope {
+            Scope::Content => self.is_match(&message.text()),
+            Scope::Nick => message
+                .target
+                .source()
+                .user()
+                .is_some_and(|user| self.is_match(user.nickname().as_str())),
+        }
+    }
+
+    /// Content with the matches split out into [`Fragment::HighlightMatch`],
+    /// or `None` when there is nothing to highlight.  Matches spanning more
+    /// than one fragment, or inside links and nicknames, are not
+    /// highlighted.
+    pub fn highlight(&self, content: &Content) -> Option<Content> {
+        if self.scope != Scope::Content {
+            return None;
+        }
+
+        match content {
+            Content::Plain(text) => self.split(text).map(Content::Fragments),
+            Content::Fragments(fragments) => {
+                let mut highlighted = false;
+
+                let fragments = fragments
+                    .iter()
+                    .flat_map(|fragment| {
+                        if let Fragment::Text(text) = fragment
+                            && let Some(split) = self.split(text)
+                        {
+                            highlighted = true;
+                            split
+                        } else {
+                            vec![fragment.clone()]
+                        }
+                    })
+                    .collect();
+
+                highlighted.then_some(Content::Fragments(fragments))
+            }
+            Content::Log(_) => None,
+        }
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text).unwrap_or_default()
+    }
+
+    fn ranges(&self, text: &str) -> Vec<Range<usize>> {
+        self.regex
+            .find_iter(text)
+            .filter_map(Result::ok)
+            .map(|found| found.range())
+            .filter(|range| !range.is_empty())
+            .collect()
+    }
+
+    fn split(&self, text: &str) -> Option<Vec<Fragment>> {
+        let ranges = self.ranges(text);
+
+        if ranges.is_empty() {
+            return None;
+        }
+
+        let mut fragments = vec![];
+        let mut end = 0;
+
+        for range in ranges {
+            if range.start > end {
+                fragments.push(Fragment::Text(text[end..range.start].into()));
+            }
+
+            fragments
+                .push(Fragment::HighlightMatch(text[range.clone()].into()));
+
+            end = range.end;
+        }
+
+        if end < text.len() {
+            fragments.push(Fragment::Text(text[end..].into()));
+        }
+
+        Some(fragments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(query: &Query, text: &str) -> Vec<(bool, String)> {
+        query
+            .split(text)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|fragment| match fragment {
+                Fragment::HighlightMatch(text) => (true, text),
+                fragment => (false, fragment.as_str().to_string()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn plain() {
+        let query = Query::new("a.b", Scope::Content, false).unwrap().unwrap();
+
+        assert_eq!(
+            split(&query, "A.B and axb then a.b"),
+            [
+                (true, "A.B".to_string()),
+                (false, " and axb then ".to_string()),
+                (true, "a.b".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn regex() {
+        let query = Query::new("l+", Scope::Content, true).unwrap().unwrap();
+
+        assert_eq!(
+            split(&query, "hello"),
+            [
+                (false, "he".to_string()),
+                (true, "ll".to_string()),
+                (false, "o".to_string()),
+            ]
+        );
+
+        assert!(Query::new("(", Scope::Content, true).is_err());
+        assert!(Query::new("", Scope::Content, true).unwrap().is_none());
+    }
+}
@@ -80,6 +80,7 @@ pub enum Message {
     SentMessageUpdated(history::Kind, history::ReadMarker),
     ResendMessage(history::Kind, message::Message),
     DraftsSaved,
+    InputHistorySaved,
     EchoEvents(Server, Vec<EchoEvent>),
 }
 
@@ -98,6 +99,7 @@ pub struct Manager {
     reroute_rules: RerouteRules,
     data: Data,
     last_draft_changed: Option<tokio::time::Instant>,
+    last_input_history_changed: Option<tokio::time::Instant>,
 }
 
 impl Manager {
@@ -263,7 +265,7 @@ impl Manager {
             Message::ResendMessage(kind, message) => {
                 return Some(Event::ResendMessage(kind, message));
             }
-            Message::DraftsSaved => {}
+            Message::DraftsSaved | Message::InputHistorySaved => {}
             Message::EchoEvents(server, events) => {
                 return Some(Event::EchoEvents(server, events));
             }
@@ -328,9 +330,14 @@ impl Manager {
     pub fn exit(
         &mut self,
         clients: &client::Map,
+        config: &Config,
     ) -> impl Future<Output = Message> + use<> {
         let data = std::mem::take(&mut self.data);
         let drafts = data.input.clone_drafts();
+        let input_history =
+            config.buffer.text_input.history.persist.then(|| {
+                data.input.clone_history(&config.buffer.text_input.history)
+            });
         let seeds: Vec<Option<history::Seed>> =
             data.map.keys().map(|kind| clients.get_seed(kind)).collect();
         let seeded_map = data.map.into_iter().zip(seeds);
@@ -342,6 +349,9 @@ impl Manager {
 
             let results = future::join_all(tasks).await;
             input::save_drafts(drafts).await;
+            if let Some(input_history) = input_history {
+                input::save_history(input_history).await;
+            }
             Message::Exited(results)
         }
     }
@@ -368,6 +378,37 @@ impl Manager {
         )
     }
 
+    pub fn maybe_save_input_history(
+        &mut self,
+        now: tokio::time::Instant,
+        config: &config::buffer::text_input::History,
+    ) -> Option<BoxFuture<'static, Message>> {
+        let last_changed = self.last_input_history_changed?;
+
+        if now.duration_since(last_changed) < DRAFT_SAVE_EVERY {
+            return None;
+        }
+
+        self.last_input_history_changed = None;
+        let history = self.data.input.clone_history(config);
+
+        Some(
+            async move {
+                input::save_history(history).await;
+                Message::InputHistorySaved
+            }
+            .boxed(),
+        )
+    }
+
+    pub fn preload_input_history(
+        &mut self,
+        history: HashMap<buffer::Upstream, Vec<input::Sent>>,
+        config: &config::buffer::text_input::History,
+    ) {
+        self.data.input.load_history_into(history, config);
+    }
+
     pub fn preload_drafts(
         &mut self,
         drafts: HashMap<buffer::Upstream, input::SavedDraft>,
@@ -413,8 +454,20 @@ impl Manager {
         &mut self,
         buffer: &buffer::Upstream,
         text: String,
+        config: &config::buffer::text_input::History,
     ) {
-        self.data.input.record(buffer, text);
+        self.data.input.record(buffer, text, config);
+        // Only set if None, so input history saves on an interval
+        if self.last_input_history_changed.is_none() {
+            self.last_input_history_changed = Some(tokio::time::Instant::now());
+        }
+    }
+
+    pub fn search_input_history(
+        &self,
+        query: &str,
+    ) -> Vec<(&buffer::Upstream, &input::Sent)> {
+        self.data.input.search(query)
     }
 
     pub fn record_draft(&mut self, raw_input: input::RawInput) {
@@ -571,6 +624,64 @@ impl Manager {
         self.data.remove_message(kind, server_time, hash, resend)
     }
 
+    /// When the latest echo of a message sent to the server was received.
+    pub fn last_echo(&self, server: &Server) -> Option<DateTime<Utc>> {
+        self.data
+            .map
+            .iter()
+            .filter(|(kind, _)| {
+                matches!(
+                    kind,
+                    history::Kind::Server(s)
+                        | history::Kind::Channel(s, _)
+                        | history::Kind::Query(s, _) if s == server
+                )
+            })
+            .filter_map(|(_, history)| history.last_echo())
+            .max()
+    }
+
+    /// Sent messages to the server since `since` that are still waiting for
+    /// their echo, for the buffers where delivery is confirmed.
+    pub fn unconfirmed_messages(
+        &self,
+        server: &Server,
+        since: DateTime<Utc>,
+        is_confirmed: impl Fn(&history::Kind) -> bool,
+    ) -> Vec<(history::Kind, crate::Message)> {
+        self.data
+            .map
+            .iter()
+            .filter(|(kind, _)| {
+                matches!(
+                    kind,
+                    history::Kind::Server(s)
+                        | history::Kind::Channel(s, _)
+                        | history::Kind::Query(s, _) if s == server
+                ) && is_confirmed(kind)
+            })
+            .flat_map(|(kind, history)| {
+                history
+                    .unconfirmed(since)
+                    .into_iter()
+                    .map(move |message| (kind.clone(), message))
+            })
+            .collect()
+    }
+
+    /// Whether an echo of the sent message has been received since it was
+    /// sent.
+    pub fn is_echoed(
+        &self,
+        kind: &history::Kind,
+        message: &crate::Message,
+    ) -> bool {
+        self.data
+            .map
+            .get(kind)
+            .is_some_and(|history| history.is_echoed(message))
+    }
+
     pub fn expand_message(
         &mut self,
         kind: history::Kind,
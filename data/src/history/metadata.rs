@@ -217,6 +217,9 @@ async fn path(kind: &Kind) -> Result<PathBuf, Error> {
         Kind::Query(server, query) => {
             format!("{server}nickname{}-metadata", query.as_normalized_str())
         }
+        Kind::ServerNotices(server) => {
+            format!("{server}server-notices-metadata")
+        }
         Kind::Logs => "logs-metadata".to_string(),
         Kind::Highlights => "highlights-metadata".to_string(),
     };
@@ -3,8 +3,11 @@ use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+pub use self::animation::Animation;
 use crate::cache::HexDigest;
 
+pub mod animation;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Format {
     #[serde(with = "serde_image_format")]
@@ -50,6 +53,8 @@ pub struct Image {
     pub url: Url,
     pub digest: HexDigest,
     pub path: PathBuf,
+    #[serde(default)]
+    pub animation: Option<Animation>,
 }
 
 impl Image {
@@ -64,6 +69,7 @@ impl Image {
             url,
             digest,
             path,
+            animation: None,
         }
     }
 }
@@ -0,0 +1,292 @@
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use flate2::Compression;
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use iced_core::image::Handle;
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::codecs::webp::WebPDecoder;
+use image::{AnimationDecoder, Frames, ImageFormat};
+use serde::{Deserialize, Serialize};
+
+use super::Error;
+use crate::cache::HexDigest;
+use crate::config;
+
+/// Like browsers, treat very short frame delays as unset
+const MIN_DELAY_MS: u32 = 11;
+const DEFAULT_DELAY_MS: u32 = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Animation {
+    pub width: u32,
+    pub height: u32,
+    /// Display duration of each frame in milliseconds
+    pub delays: Vec<u32>,
+    /// Zlib compressed RGBA pixels of every frame, back to back
+    pub path: PathBuf,
+    pub digest: HexDigest,
+    /// Decoded frames, loaded along with the preview
+    #[serde(skip)]
+    pub frames: Option<Arc<[Handle]>>,
+}
+
+impl Animation {
+    /// The frame shown `elapsed` into the (looping) animation and how long
+    /// until the next one.
+    pub fn frame_at(&self, elapsed: Duration) -> (usize, Duration) {
+        let total: u64 = self.delays.iter().copied().map(u64::from).sum();
+
+        if total == 0 {
+            return (0, Duration::from_millis(u64::from(DEFAULT_DELAY_MS)));
+        }
+
+        let mut position =
+            u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX) % total;
+
+        for (index, delay) in
+            self.delays.iter().copied().map(u64::from).enumerate()
+        {
+            if position < delay {
+                return (index, Duration::from_millis(delay - position));
+            }
+
+            position -= delay;
+        }
+
+        (0, Duration::from_millis(u64::from(DEFAULT_DELAY_MS)))
+    }
+
+    /// Reads the cached frames from disk (blocking)
+    pub fn load_frames(&self) -> io::Result<Arc<[Handle]>> {
+        let mut pixels = vec![];
+        ZlibDecoder::new(BufReader::new(File::open(&self.path)?))
+            .read_to_end(&mut pixels)?;
+
+        let frame_size = self.width as usize * self.height as usize * 4;
+
+        if frame_size == 0 || pixels.len() != frame_size * self.delays.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "cached frames don't match animation",
+            ));
+        }
+
+        Ok(pixels
+            .chunks_exact(frame_size)
+            .map(|frame| {
+                Handle::from_rgba(self.width, self.height, frame.to_vec())
+            })
+            .collect())
+    }
+}
+
+/// Frames of an animation, decoded but not yet cached
+pub struct Decoded {
+    pub width: u32,
+    pub height: u32,
+    pub delays: Vec<u32>,
+    pixels: Vec<u8>,
+}
+
+impl Decoded {
+    /// Compresses the frames for storing in the cache
+    pub fn compress(&self) -> io::Result<Vec<u8>> {
+        let mut encoder = ZlibEncoder::new(vec![], Compression::fast());
+        encoder.write_all(&self.pixels)?;
+        encoder.finish()
+    }
+}
+
+/// Decodes every frame of an animated GIF, WebP or PNG (APNG) image
+///
+/// Still images and animations over the configured frame or memory budget
+/// are `None`, and are shown as still images.
+pub fn decode(
+    path: &Path,
+    format: ImageFormat,
+    config: &config::preview::Animation,
+) -> Result<Option<Decoded>, Error> {
+    let reader = BufReader::new(File::open(path)?);
+
+    let frames = match format {
+        ImageFormat::Gif => GifDecoder::new(reader)?.into_frames(),
+        ImageFormat::WebP => {
+            let decoder = WebPDecoder::new(reader)?;
+
+            if !decoder.has_animation() {
+                return Ok(None);
+            }
+
+            decoder.into_frames()
+        }
+        ImageFormat::Png => {
+            let decoder = PngDecoder::new(reader)?;
+
+            if !decoder.is_apng()? {
+                return Ok(None);
+            }
+
+            decoder.apng()?.into_frames()
+        }
+        _ => return Ok(None),
+    };
+
+    collect(frames, config.max_frames, config.max_memory_bytes())
+}
+
+fn collect(
+    frames: Frames<'_>,
+    max_frames: usize,
+    max_memory: u64,
+) -> Result<Option<Decoded>, Error> {
+    let mut decoded = Decoded {
+        width: 0,
+        height: 0,
+        delays: vec![],
+        pixels: vec![],
+    };
+
+    for frame in frames {
+        let frame = frame?;
+
+        let (numerator, denominator) = frame.delay().numer_denom_ms();
+        let delay = numerator.checked_div(denominator).unwrap_or_default();
+
+        let buffer = frame.into_buffer();
+
+        if decoded.delays.is_empty() {
+            decoded.width = buffer.width();
+            decoded.height = buffer.height();
+        }
+
+        if decoded.delays.len() >= max_frames
+            || (decoded.pixels.len() + buffer.len()) as u64 > max_memory
+            || (buffer.width(), buffer.height())
+                != (decoded.width, decoded.height)
+        {
+            return Ok(None);
+        }
+
+        decoded.delays.push(if delay < MIN_DELAY_MS {
+            DEFAULT_DELAY_MS
+        } else {
+            delay
+        });
+        decoded.pixels.extend_from_slice(&buffer);
+    }
+
+    Ok((decoded.delays.len() > 1).then_some(decoded))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use image::codecs::gif::GifEncoder;
+    use image::{Delay, Frame, ImageFormat, Rgba, RgbaImage};
+    use tempfile::TempDir;
+
+    use super::{Animation, DEFAULT_DELAY_MS, decode};
+    use crate::cache::HexDigest;
+    use crate::config;
+    use crate::test_support::temp_file;
+
+    fn animation(delays: Vec<u32>) -> Animation {
+        Animation {
+            width: 1,
+            height: 1,
+            delays,
+            path: "frames".into(),
+            digest: HexDigest::new(&[]),
+            frames: None,
+        }
+    }
+
+    fn gif(frames: &[(u8, u32)]) -> (TempDir, PathBuf) {
+        let mut bytes = vec![];
+        {
+            let mut encoder = GifEncoder::new(Cursor::new(&mut bytes));
+            encoder
+                .encode_frames(frames.iter().map(|(shade, delay)| {
+                    Frame::from_parts(
+                        RgbaImage::from_pixel(4, 2, Rgba([*shade, 0, 0, 255])),
+                        0,
+                        0,
+                        Delay::from_numer_denom_ms(*delay, 1),
+                    )
+                }))
+                .unwrap();
+        }
+
+        temp_file("animation.gif", bytes)
+    }
+
+    #[test]
+    fn frame_at_loops() {
+        let animation = animation(vec![100, 50, 200]);
+
+        assert_eq!(
+            animation.frame_at(Duration::ZERO),
+            (0, Duration::from_millis(100))
+        );
+        assert_eq!(
+            animation.frame_at(Duration::from_millis(120)),
+            (1, Duration::from_millis(30))
+        );
+        assert_eq!(
+            animation.frame_at(Duration::from_millis(349)),
+            (2, Duration::from_millis(1))
+        );
+        assert_eq!(
+            animation.frame_at(Duration::from_millis(350 + 100)),
+            (1, Duration::from_millis(50))
+        );
+    }
+
+    #[test]
+    fn decodes_gif_frames() {
+        let (_dir, path) = gif(&[(0, 40), (128, 0), (255, 60)]);
+
+        let decoded = decode(
+            &path,
+            ImageFormat::Gif,
+            &config::preview::Animation::default(),
+        )
+        .unwrap()
+        .expect("animated");
+
+        assert_eq!((decoded.width, decoded.height), (4, 2));
+        assert_eq!(decoded.delays, vec![40, DEFAULT_DELAY_MS, 60]);
+        assert_eq!(decoded.pixels.len(), 3 * 4 * 2 * 4);
+    }
+
+    #[test]
+    fn respects_budgets() {
+        let (_dir, path) = gif(&[(0, 40), (128, 40), (255, 40)]);
+
+        let config = config::preview::Animation {
+            max_frames: 2,
+            ..Default::default()
+        };
+        assert!(decode(&path, ImageFormat::Gif, &config).unwrap().is_none());
+
+        let (_dir, path) = gif(&[(10, 40)]);
+        assert!(
+            decode(
+                &path,
+                ImageFormat::Gif,
+                &config::preview::Animation::default()
+            )
+            .unwrap()
+            .is_none()
+        );
+    }
+}
@@ -1,5 +1,5 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Utc};
 use irc::proto;
@@ -8,9 +8,12 @@ use nom::character::complete::char;
 use nom::combinator::{cut, map, rest, verify};
 use nom::multi::{many_m_n, many0_count, many1_count};
 use nom::{Finish, IResult, Parser};
+use nucleo_matcher::pattern::{AtomKind, CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Matcher, Utf32Str};
+use tokio::io::AsyncWriteExt as _;
 
 use crate::capabilities::{Capabilities, MultilineBatchKind};
-use crate::config::buffer::text_input::AutoFormat;
+use crate::config::buffer::text_input::{self, AutoFormat};
 use crate::features::Features;
 use crate::history::reroute::RerouteRules;
 use crate::message::formatting;
@@ -21,8 +24,6 @@ use crate::{
     isupport, message,
 };
 
-const INPUT_HISTORY_LENGTH: usize = 100;
-
 pub fn parse(
     buffer: buffer::Upstream,
     auto_format: AutoFormat,
@@ -94,6 +95,7 @@ pub fn parse(
                     command::Internal::Reconnect
                         | command::Internal::Connect(_)
                         | command::Internal::Exec(_)
+                        | command::Internal::Schedule(_, _)
                 ) {
                     return Ok(Parsed::Internal(command));
                 } else {
@@ -278,6 +280,20 @@ impl Input {
         }
     }
 
+    /// Whether the input sends a message (rather than some other command)
+    pub fn is_message(&self) -> bool {
+        matches!(
+            &self.content,
+            Content::Text(_)
+                | Content::Command(
+                    command::Irc::Msg(_, _)
+                        | command::Irc::Me(_, _)
+                        | command::Irc::Notice(_, _),
+                    _
+                )
+        )
+    }
+
     pub fn server(&self) -> &Server {
         self.buffer.server()
     }
@@ -377,9 +393,16 @@ pub struct RawInput {
     pub reply: Option<DraftReply>,
 }
 
+/// A line sent from the text input
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Sent {
+    pub text: String,
+    pub at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Storage {
-    sent: HashMap<buffer::Upstream, Vec<String>>,
+    sent: HashMap<buffer::Upstream, Vec<Sent>>,
     draft_messages: HashMap<buffer::Upstream, String>,
     draft_reply: HashMap<buffer::Upstream, DraftReply>,
     cursor_position: HashMap<buffer::Upstream, (usize, usize)>,
@@ -403,12 +426,78 @@ impl Storage {
         }
     }
 
-    pub fn record(&mut self, buffer: &buffer::Upstream, text: String) {
+    pub fn record(
+        &mut self,
+        buffer: &buffer::Upstream,
+        text: String,
+        config: &text_input::History,
+    ) {
         self.draft_messages.remove(buffer);
         self.draft_reply.remove(buffer);
+
+        // Excluded lines are still recalled, they're only left out when the
+        // history is saved
         let history = self.sent.entry(buffer.clone()).or_default();
-        history.insert(0, text);
-        history.truncate(INPUT_HISTORY_LENGTH);
+        history.insert(
+            0,
+            Sent {
+                text,
+                at: Utc::now(),
+            },
+        );
+        history.truncate(config.size);
+    }
+
+    /// Sent lines of every buffer matching `query`, best match first
+    ///
+    /// Lines sent more than once are only listed for their latest use.
+    pub fn search(&self, query: &str) -> Vec<(&buffer::Upstream, &Sent)> {
+        let mut sent = self
+            .sent
+            .iter()
+            .flat_map(|(buffer, history)| {
+                history.iter().map(move |sent| (buffer, sent))
+            })
+            .collect::<Vec<_>>();
+
+        sent.sort_by_key(|(_, sent)| std::cmp::Reverse(sent.at));
+
+        let mut seen = HashSet::new();
+        sent.retain(|(_, sent)| seen.insert(sent.text.as_str()));
+
+        let query = query.trim();
+
+        if query.is_empty() {
+            return sent;
+        }
+
+        let pattern = Pattern::new(
+            query,
+            CaseMatching::Smart,
+            Normalization::Smart,
+            AtomKind::Fuzzy,
+        );
+        let mut matcher = Matcher::new(nucleo_matcher::Config::DEFAULT);
+        let mut buf = vec![];
+
+        let mut matches = sent
+            .into_iter()
+            .filter_map(|(buffer, sent)| {
+                let haystack = Utf32Str::new(&sent.text, &mut buf);
+
+                pattern
+                    .score(haystack, &mut matcher)
+                    .map(|score| (score, buffer, sent))
+            })
+            .collect::<Vec<_>>();
+
+        // Stable, so equal scores stay most recent first
+        matches.sort_by_key(|(score, _, _)| std::cmp::Reverse(*score));
+
+        matches
+            .into_iter()
+            .map(|(_, buffer, sent)| (buffer, sent))
+            .collect()
     }
 
     pub fn store_draft(&mut self, raw_input: RawInput) {
@@ -443,6 +532,41 @@ impl Storage {
             .collect()
     }
 
+    /// The history to save, without the lines matching `exclude`.
+    pub fn clone_history(
+        &self,
+        config: &text_input::History,
+    ) -> HashMap<buffer::Upstream, Vec<Sent>> {
+        self.sent
+            .iter()
+            .map(|(buffer, sent)| {
+                (
+                    buffer.clone(),
+                    sent.iter()
+                        .filter(|sent| !config.is_excluded(&sent.text))
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .filter(|(_, sent)| !sent.is_empty())
+            .collect()
+    }
+
+    pub fn load_history_into(
+        &mut self,
+        history: HashMap<buffer::Upstream, Vec<Sent>>,
+        config: &text_input::History,
+    ) {
+        for (buffer, mut sent) in history {
+            sent.retain(|sent| !config.is_excluded(&sent.text));
+            sent.truncate(config.size);
+
+            if !sent.is_empty() {
+                self.sent.insert(buffer, sent);
+            }
+        }
+    }
+
     pub fn load_drafts_into(
         &mut self,
         drafts: HashMap<buffer::Upstream, SavedDraft>,
@@ -496,10 +620,54 @@ pub fn load_drafts_sync() -> HashMap<buffer::Upstream, SavedDraft> {
         .collect()
 }
 
+fn history_path() -> PathBuf {
+    environment::data_dir().join("input_history.json")
+}
+
+pub async fn save_history(history: HashMap<buffer::Upstream, Vec<Sent>>) {
+    let pairs: Vec<(buffer::Upstream, Vec<Sent>)> =
+        history.into_iter().collect();
+    match serde_json::to_vec(&pairs) {
+        Ok(bytes) => {
+            if let Err(e) = write_private(&history_path(), &bytes).await {
+                log::warn!("failed to save input history: {e}");
+            }
+        }
+        Err(e) => log::warn!("failed to serialize input history: {e}"),
+    }
+}
+
+/// Replaces the file with one only the user can read, as lines may carry
+/// secrets that aren't excluded.
+async fn write_private(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let temp = path.with_extension("json.tmp");
+
+    let mut options = tokio::fs::OpenOptions::new();
+    options.create(true).write(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+
+    let mut file = options.open(&temp).await?;
+    file.write_all(bytes).await?;
+    file.sync_all().await?;
+
+    tokio::fs::rename(&temp, path).await
+}
+
+pub fn load_history_sync() -> HashMap<buffer::Upstream, Vec<Sent>> {
+    let Ok(bytes) = std::fs::read(history_path()) else {
+        return HashMap::new();
+    };
+    serde_json::from_slice::<Vec<(buffer::Upstream, Vec<Sent>)>>(&bytes)
+        .unwrap_or_default()
+        .into_iter()
+        .collect()
+}
+
 /// Cached values for a buffers input
 #[derive(Debug, Clone, Copy)]
 pub struct Cache<'a> {
-    pub history: &'a [String],
+    pub history: &'a [Sent],
     pub draft_message: &'a str,
     pub draft_reply: Option<&'a DraftReply>,
     pub cursor_position: Option<&'a (usize, usize)>,
@@ -567,7 +735,7 @@ pub enum Error {
 mod test {
     use crate::capabilities::Capabilities;
     use crate::config::buffer::text_input::AutoFormat;
-    use crate::input::{CodeFence, Content, Input, Parsed, parse};
+    use crate::input::{CodeFence, Content, Input, Parsed, Storage, parse};
     use crate::user::Nick;
     use crate::{Config, Server, buffer, command, features, isupport, target};
 
@@ -740,4 +908,59 @@ mod test {
             assert_eq!(parsed, expected);
         }
     }
+
+    #[test]
+    fn input_history() {
+        let config = Config::default();
+        let history = &config.buffer.text_input.history;
+
+        let buffer = buffer::Upstream::Server(Server {
+            name: "Libera".into(),
+            network: None,
+        });
+
+        let mut storage = Storage::default();
+
+        for text in [
+            "/msg nickserv identify hunter2",
+            "/join #halloy",
+            "hello world",
+            "/msg NickServ ghost tester hunter2",
+            "/nsfw",
+            "hello world",
+        ] {
+            storage.record(&buffer, text.to_string(), history);
+        }
+
+        let search = |query| {
+            storage
+                .search(query)
+                .into_iter()
+                .map(|(_, sent)| sent.text.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            search(""),
+            vec![
+                "hello world",
+                "/nsfw",
+                "/msg NickServ ghost tester hunter2",
+                "/join #halloy",
+                "/msg nickserv identify hunter2",
+            ]
+        );
+        assert_eq!(search("jh"), vec!["/join #halloy"]);
+        assert_eq!(storage.get(&buffer).history.len(), 6);
+
+        // Excluded lines are recalled but never saved
+        let saved = storage.clone_history(history);
+        assert_eq!(
+            saved[&buffer]
+                .iter()
+                .map(|sent| sent.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["hello world", "/nsfw", "hello world", "/join #halloy"]
+        );
+    }
 }
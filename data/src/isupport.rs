@@ -49,6 +49,7 @@ pub enum Kind {
     USERIP,
     USERLEN,
     UTF8ONLY,
+    VAPID,
     WHOX,
 }
 
@@ -520,6 +521,9 @@ impl FromStr for Operation {
                             parse_required_positive_integer(value)?,
                         ))),
                         "UTF8ONLY" => Ok(Operation::Add(Parameter::UTF8ONLY)),
+                        "VAPID" => Ok(Operation::Add(Parameter::VAPID(
+                            value.to_owned(),
+                        ))),
                         "VLIST" => Ok(Operation::Add(Parameter::VLIST(
                             parse_required_letters(value)?,
                         ))),
@@ -614,6 +618,7 @@ impl FromStr for Operation {
                         "USERIP" => Ok(Operation::Add(Parameter::USERIP)),
                         "USERLEN" => Err("value required"),
                         "UTF8ONLY" => Ok(Operation::Add(Parameter::UTF8ONLY)),
+                        "VAPID" => Err("value required"),
                         "VLIST" => Err("value required"),
                         "WATCH" => Err("value required"),
                         "WHOX" => Ok(Operation::Add(Parameter::WHOX)),
@@ -662,6 +667,7 @@ impl Operation {
                 "USERIP" => Some(Kind::USERIP),
                 "USERLEN" => Some(Kind::USERLEN),
                 "UTF8ONLY" => Some(Kind::UTF8ONLY),
+                "VAPID" => Some(Kind::VAPID),
                 "WHOX" => Some(Kind::WHOX),
                 _ => None,
             },
@@ -739,6 +745,7 @@ pub enum Parameter {
     USERIP,
     USERLEN(u16),
     UTF8ONLY,
+    VAPID(String),
     VLIST(String),
     WATCH(u16),
     WHOX,
@@ -779,6 +786,7 @@ impl Parameter {
             Parameter::USERIP => Some(Kind::USERIP),
             Parameter::USERLEN(_) => Some(Kind::USERLEN),
             Parameter::UTF8ONLY => Some(Kind::UTF8ONLY),
+            Parameter::VAPID(_) => Some(Kind::VAPID),
             Parameter::WHOX => Some(Kind::WHOX),
             Parameter::BOUNCER_NETID(_) => Some(Kind::BOUNCER_NETID),
             _ => None,
@@ -1369,6 +1377,14 @@ pub fn get_filehost(isupport: &HashMap<Kind, Parameter>) -> Option<&str> {
     }
 }
 
+pub fn get_vapid(isupport: &HashMap<Kind, Parameter>) -> Option<&str> {
+    if let Some(Parameter::VAPID(key)) = isupport.get(&Kind::VAPID) {
+        Some(key.as_str())
+    } else {
+        None
+    }
+}
+
 pub fn get_icon_url(isupport: &HashMap<Kind, Parameter>) -> Option<&str> {
     if let Some(Parameter::ICON(url)) = isupport.get(&Kind::ICON) {
         Some(url.as_str())
@@ -50,19 +50,28 @@ pub mod message;
 pub mod metadata;
 pub mod mode;
 pub mod notification;
+pub mod outbox;
 pub mod pane;
 pub mod preview;
+pub mod queue;
 pub mod rate_limit;
 pub mod reaction;
 pub mod redaction;
+pub mod schedule;
 pub mod serde;
 pub mod server;
 pub mod server_icon;
 pub mod shortcut;
+pub mod snippet;
+pub mod spell_check;
 pub mod stream;
 pub mod target;
+#[cfg(test)]
+mod test_support;
 pub mod time;
 pub mod url;
 pub mod user;
 pub mod version;
+pub mod webpush;
 pub mod window;
+pub mod znc;
@@ -18,7 +18,7 @@ pub use self::broadcast::Broadcast;
 pub use self::formatting::{Color, Formatting};
 pub use self::highlight::Highlight;
 pub use self::source::Source;
-pub use self::source::server::{Change, Kind, StandardReply};
+pub use self::source::server::{Change, Kind, ServerNotice, StandardReply};
 use crate::capabilities::LabeledResponseContext;
 use crate::client::Destination;
 use crate::config::buffer::{CondensationFormat, UsernameFormat};
@@ -92,6 +92,7 @@ static EXCLUDED_TRAILING_DELIMITER_CHARS_REGEX: LazyLock<Regex> =
 pub(crate) mod broadcast;
 pub mod formatting;
 pub mod highlight;
+pub mod server_notice;
 pub mod source;
 
 pub fn reroute_private_message_target(
@@ -2634,8 +2635,23 @@ fn target(
                         .map(|user| user.with_bot(message.from_bot()))
                 };
 
+            // Server notices sent to operators (snomasks)
+            let server_notice = || {
+                is_notice
+                    .then(|| server_notice::classify(text))
+                    .flatten()
+                    .map(|kind| {
+                        source::Server::new(
+                            Kind::ServerNotice(kind),
+                            None,
+                            None,
+                        )
+                    })
+            };
+
             if target == "*" {
-                let source = user.map_or(Source::Server(None), source);
+                let source = user
+                    .map_or_else(|| Source::Server(server_notice()), source);
 
                 return Some((Target::Server { source }, None));
             }
@@ -2742,7 +2758,7 @@ fn target(
                     }
                     (target::Target::Query(_), None) => (
                         Target::Server {
-                            source: Source::Server(None),
+                            source: Source::Server(server_notice()),
                         },
                         None,
                     ),
@@ -2963,6 +2979,7 @@ fn target(
         | Command::BOUNCER(_, _)
         | Command::REDACT(_, _, _)
         | Command::METADATA(_, _)
+        | Command::WEBPUSH(_, _)
         | Command::Raw(_) => Some((
             Target::Server {
                 source: Source::Server(None),
@@ -4835,8 +4852,11 @@ pub mod tests {
         assert!(matches!(
             &message.target,
             crate::message::Target::Server {
-                source: crate::message::Source::Server(None),
-            }
+                source: crate::message::Source::Server(Some(source)),
+            } if source.kind()
+                == crate::message::Kind::ServerNotice(
+                    crate::message::ServerNotice::Other
+                )
         ));
         assert_eq!(message.text(), "*** Notice -- [snip]");
     }
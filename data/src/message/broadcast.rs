@@ -2,16 +2,20 @@
 use std::collections::HashSet;
 
 use chrono::{DateTime, Utc};
+use indexmap::IndexMap;
 
+pub use self::netsplit::Split;
 use super::{
-    Content, Direction, Message, Source, Target, kick_text, nickname_text,
-    parse_fragments_with_user, plain, quit_text, source,
+    Content, Direction, Fragment, Message, Source, Target, kick_text,
+    nickname_text, parse_fragments_with_user, plain, quit_text, source,
 };
 use crate::config::buffer::UsernameFormat;
 use crate::time::Posix;
 use crate::user::Nick;
 use crate::{Config, User, isupport, message, target};
 
+pub mod netsplit;
+
 enum Cause {
     Server(Option<source::Server>),
     Status(source::Status),
@@ -188,6 +192,107 @@ pub fn quit(
     )
 }
 
+pub fn netsplit(
+    kind: netsplit::Kind,
+    split: &Split,
+    users: &[(User, Vec<target::Channel>)],
+    queries: impl IntoIterator<Item = target::Query>,
+    config: &Config,
+    sent_time: DateTime<Utc>,
+) -> Vec<Message> {
+    let cause = || {
+        Cause::Server(Some(source::Server::new(
+            match kind {
+                netsplit::Kind::Split => source::server::Kind::Netsplit,
+                netsplit::Kind::Join => source::server::Kind::Netjoin,
+            },
+            None,
+            None,
+        )))
+    };
+
+    let mut channel_users: IndexMap<&target::Channel, Vec<&User>> =
+        IndexMap::new();
+
+    for (user, channels) in users {
+        for channel in channels {
+            channel_users.entry(channel).or_default().push(user);
+        }
+    }
+
+    let channel_messages =
+        channel_users.into_iter().flat_map(|(channel, users)| {
+            expand(
+                [channel.clone()],
+                [],
+                false,
+                cause(),
+                netsplit_text(kind, split, &users, config),
+                sent_time,
+            )
+        });
+
+    // Queries only list the user on the other end of the query
+    let query_messages = queries
+        .into_iter()
+        .filter_map(|query| {
+            users
+                .iter()
+                .find(|(user, _)| {
+                    user.as_normalized_str() == query.as_normalized_str()
+                })
+                .map(|(user, _)| (query, user))
+        })
+        .flat_map(|(query, user)| {
+            expand(
+                [],
+                [query],
+                false,
+                cause(),
+                netsplit_text(kind, split, &[user], config),
+                sent_time,
+            )
+        });
+
+    channel_messages.chain(query_messages).collect()
+}
+
+/// The summary is the first fragment, followed by the affected nicknames
+/// which are only shown once the message is expanded
+fn netsplit_text(
+    kind: netsplit::Kind,
+    split: &Split,
+    users: &[&User],
+    config: &Config,
+) -> Content {
+    let (arrow, label) = match kind {
+        netsplit::Kind::Split => {
+            (&config.display.direction_arrows.left, "Netsplit")
+        }
+        netsplit::Kind::Join => {
+            (&config.display.direction_arrows.right, "Netjoin")
+        }
+    };
+
+    let count = users.len();
+    let noun = if count == 1 { "user" } else { "users" };
+
+    let mut fragments = vec![Fragment::Text(format!(
+        "{arrow} {label} {} <-> {}, {count} {noun}",
+        split.server, split.remote,
+    ))];
+
+    for (index, user) in users.iter().enumerate() {
+        fragments.push(Fragment::Text(
+            if index == 0 { ": " } else { ", " }.to_string(),
+        ));
+        fragments
+            .push(Fragment::User((*user).clone(), user.nickname().to_string()));
+    }
+
+    Content::Fragments(fragments)
+}
+
 pub fn nickname(
     channels: impl IntoIterator<Item = target::Channel>,
     queries: impl IntoIterator<Item = target::Query>,
@@ -298,6 +403,11 @@ pub enum Broadcast {
         user_channels: Vec<target::Channel>,
         casemapping: isupport::CaseMap,
     },
+    Netsplit {
+        kind: netsplit::Kind,
+        split: Split,
+        users: Vec<(User, Vec<target::Channel>)>,
+    },
     Nickname {
         old_nick: Nick,
         new_nick: Nick,
@@ -365,6 +475,9 @@ pub fn into_messages(
                 sent_time,
             )
         }
+        Broadcast::Netsplit { kind, split, users } => {
+            netsplit(kind, &split, &users, queries, config, sent_time)
+        }
         Broadcast::Nickname {
             old_nick,
             new_nick,
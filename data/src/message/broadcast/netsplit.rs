@@ -0,0 +1,364 @@
+//! Group the QUITs and rejoins caused by a netsplit, so that they can be
+//! broadcast as a single line per channel rather than one line per user
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+use crate::user::Nick;
+use crate::{User, target};
+
+// Time without any further QUIT (or JOIN) for a split before it is reported
+const SETTLE_INTERVAL: Duration = Duration::from_secs(2);
+// Time after which users lost in a split are no longer expected to rejoin
+const REJOIN_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+// Fewer QUITs with the same reason are users who happened to quit with a
+// message that looks like a split, which are reported as they are
+const MIN_SPLIT_QUITS: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Split {
+    pub server: String,
+    pub remote: String,
+}
+
+impl Split {
+    /// Recognizes the QUIT reason set by the server for users lost in a
+    /// netsplit, which is the names of the two servers that split
+    /// (e.g. `irc.example.com hub.example.com` or `*.net *.split`)
+    pub fn parse(comment: Option<&str>) -> Option<Self> {
+        let mut servers = comment?.split(' ');
+
+        let server = servers.next()?;
+        let remote = servers.next()?;
+
+        (servers.next().is_none()
+            && server != remote
+            && is_server_name(server)
+            && is_server_name(remote))
+        .then(|| Self {
+            server: server.to_string(),
+            remote: remote.to_string(),
+        })
+    }
+}
+
+fn is_server_name(name: &str) -> bool {
+    name.contains('.')
+        && !name.starts_with('.')
+        && !name.ends_with('.')
+        && !name.contains("..")
+        && name.chars().all(|c| {
+            c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '*')
+        })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Split,
+    Join,
+}
+
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub kind: Kind,
+    pub split: Split,
+    pub users: Vec<(User, Vec<target::Channel>)>,
+    pub sent_time: DateTime<Utc>,
+}
+
+/// QUIT held back in case it was part of a netsplit, which turned out not
+/// to be one.
+#[derive(Debug, Clone)]
+pub struct Quit {
+    pub user: User,
+    pub channels: Vec<target::Channel>,
+    pub comment: String,
+    pub sent_time: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+struct Pending {
+    event: Event,
+    // When each user of a split quit
+    quit_times: Vec<DateTime<Utc>>,
+    updated_at: Instant,
+}
+
+#[derive(Debug, Default)]
+pub struct Tracker {
+    pending: Vec<Pending>,
+    split_users: HashMap<Nick, (Split, Instant)>,
+}
+
+impl Tracker {
+    pub fn quit(
+        &mut self,
+        user: User,
+        channels: Vec<target::Channel>,
+        split: Split,
+        sent_time: DateTime<Utc>,
+    ) {
+        let pending =
+            self.pending(Kind::Split, split, sent_time, Instant::now());
+
+        pending.event.users.push((user, channels));
+        pending.quit_times.push(sent_time);
+    }
+
+    /// Returns `true` if the JOIN is a user rejoining after a netsplit, in
+    /// which case it will be reported as part of the netjoin
+    pub fn join(
+        &mut self,
+        user: User,
+        channel: target::Channel,
+        sent_time: DateTime<Utc>,
+    ) -> bool {
+        let Some(split) = self
+            .split_users
+            .get(&user.nickname().to_owned())
+            .map(|(split, _)| split.clone())
+        else {
+            return false;
+        };
+
+        let netjoin = &mut self
+            .pending(Kind::Join, split, sent_time, Instant::now())
+            .event;
+
+        if let Some((_, channels)) = netjoin
+            .users
+            .iter_mut()
+            .find(|(joined, _)| joined.nickname() == user.nickname())
+        {
+            if !channels.contains(&channel) {
+                channels.push(channel);
+            }
+        } else {
+            netjoin.users.push((user, vec![channel]));
+        }
+
+        true
+    }
+
+    /// Takes the netsplits and netjoins that have settled, i.e. those that
+    /// have not seen any activity for a short while, along with the QUITs
+    /// of splits too small to be netsplits
+    pub fn drain(&mut self, now: Instant) -> (Vec<Event>, Vec<Quit>) {
+        let (settled, pending): (Vec<_>, Vec<_>) =
+            self.pending.drain(..).partition(|pending| {
+                now.saturating_duration_since(pending.updated_at)
+                    >= SETTLE_INTERVAL
+            });
+
+        self.pending = pending;
+
+        let mut events = vec![];
+        let mut quits = vec![];
+
+        for Pending {
+            event, quit_times, ..
+        } in settled
+        {
+            match event.kind {
+                Kind::Split if event.users.len() < MIN_SPLIT_QUITS => {
+                    let comment = format!(
+                        "{} {}",
+                        event.split.server, event.split.remote
+                    );
+
+                    quits.extend(event.users.into_iter().zip(quit_times).map(
+                        |((user, channels), sent_time)| Quit {
+                            user,
+                            channels,
+                            comment: comment.clone(),
+                            sent_time,
+                        },
+                    ));
+                }
+                Kind::Split => {
+                    // Users lost in the split are expected to rejoin
+                    for (user, _) in &event.users {
+                        self.split_users.insert(
+                            user.nickname().to_owned(),
+                            (event.split.clone(), now),
+                        );
+                    }
+
+                    events.push(event);
+                }
+                Kind::Join => {
+                    // Users that have rejoined are no longer part of the split
+                    for (user, _) in &event.users {
+                        self.split_users.remove(&user.nickname().to_owned());
+                    }
+
+                    events.push(event);
+                }
+            }
+        }
+
+        self.split_users.retain(|_, (_, split_at)| {
+            now.saturating_duration_since(*split_at) < REJOIN_TIMEOUT
+        });
+
+        (events, quits)
+    }
+
+    fn pending(
+        &mut self,
+        kind: Kind,
+        split: Split,
+        sent_time: DateTime<Utc>,
+        now: Instant,
+    ) -> &mut Pending {
+        let index = if let Some(index) =
+            self.pending.iter().position(|pending| {
+                pending.event.kind == kind && pending.event.split == split
+            }) {
+            index
+        } else {
+            self.pending.push(Pending {
+                event: Event {
+                    kind,
+                    split,
+                    users: vec![],
+                    sent_time,
+                },
+                quit_times: vec![],
+                updated_at: now,
+            });
+
+            self.pending.len() - 1
+        };
+
+        let pending = &mut self.pending[index];
+        pending.updated_at = now;
+
+        pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::isupport;
+
+    fn user(nick: &str) -> User {
+        User::from(Nick::from_str(nick, isupport::CaseMap::default()))
+    }
+
+    fn channel(name: &str) -> target::Channel {
+        target::Channel::from_str(
+            name,
+            isupport::DEFAULT_CHANTYPES,
+            isupport::CaseMap::default(),
+        )
+    }
+
+    #[test]
+    fn parse_split() {
+        let tests = [
+            (
+                Some("irc.example.com hub.example.com"),
+                Some(("irc.example.com", "hub.example.com")),
+            ),
+            (Some("*.net *.split"), Some(("*.net", "*.split"))),
+            (Some("Quit: leaving"), None),
+            (Some("Ping timeout: 240 seconds"), None),
+            (Some("irc.example.com irc.example.com"), None),
+            (Some("irc.example.com"), None),
+            (Some("a.example.com b.example.com c.example.com"), None),
+            (Some("example. com"), None),
+            (None, None),
+        ];
+
+        for (comment, expected) in tests {
+            assert_eq!(
+                Split::parse(comment),
+                expected.map(|(server, remote)| Split {
+                    server: server.to_string(),
+                    remote: remote.to_string(),
+                }),
+                "{comment:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn groups_split_and_rejoin() {
+        let split = Split {
+            server: "irc.example.com".to_string(),
+            remote: "hub.example.com".to_string(),
+        };
+        let mut tracker = Tracker::default();
+
+        tracker.quit(
+            user("alice"),
+            vec![channel("#halloy")],
+            split.clone(),
+            Utc::now(),
+        );
+        tracker.quit(
+            user("bob"),
+            vec![channel("#halloy"), channel("#rust")],
+            split.clone(),
+            Utc::now(),
+        );
+        tracker.quit(
+            user("dave"),
+            vec![channel("#rust")],
+            split.clone(),
+            Utc::now(),
+        );
+
+        let (events, quits) = tracker.drain(Instant::now());
+        assert!(events.is_empty() && quits.is_empty());
+
+        let (events, quits) = tracker.drain(Instant::now() + SETTLE_INTERVAL);
+        assert!(quits.is_empty());
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, Kind::Split);
+        assert_eq!(events[0].split, split);
+        assert_eq!(events[0].users.len(), 3);
+
+        assert!(!tracker.join(user("carol"), channel("#halloy"), Utc::now()));
+        assert!(tracker.join(user("bob"), channel("#halloy"), Utc::now()));
+        assert!(tracker.join(user("bob"), channel("#rust"), Utc::now()));
+
+        let (events, _) = tracker.drain(Instant::now() + SETTLE_INTERVAL);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, Kind::Join);
+        assert_eq!(events[0].users.len(), 1);
+        assert_eq!(events[0].users[0].1.len(), 2);
+
+        // Once reported, further joins are no longer part of the netjoin
+        assert!(!tracker.join(user("bob"), channel("#halloy"), Utc::now()));
+    }
+
+    #[test]
+    fn reports_lone_quits() {
+        let split = Split {
+            server: "irc.example.com".to_string(),
+            remote: "hub.example.com".to_string(),
+        };
+        let mut tracker = Tracker::default();
+
+        tracker.quit(
+            user("alice"),
+            vec![channel("#halloy")],
+            split.clone(),
+            Utc::now(),
+        );
+        tracker.quit(user("bob"), vec![channel("#rust")], split, Utc::now());
+
+        let (events, quits) = tracker.drain(Instant::now() + SETTLE_INTERVAL);
+        assert!(events.is_empty());
+        assert_eq!(quits.len(), 2);
+        assert_eq!(quits[0].comment, "irc.example.com hub.example.com");
+        assert_eq!(quits[1].channels, vec![channel("#rust")]);
+
+        // Users who weren't lost in a netsplit don't rejoin from one
+        assert!(!tracker.join(user("alice"), channel("#halloy"), Utc::now()));
+    }
+}
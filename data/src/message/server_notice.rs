@@ -0,0 +1,130 @@
+use super::source::server::ServerNotice;
+
+/// Classifies a NOTICE sent by the server as an operator server notice
+/// (snomask), returning `None` for regular server notices such as the
+/// ident/hostname lookups sent during registration.
+pub fn classify(text: &str) -> Option<ServerNotice> {
+    let text = text.trim_start();
+
+    // Solanum, Charybdis, Hybrid, ratbox: `*** Notice -- <notice>`
+    // UnrealIRCd: `*** <notice>` (only with a recognized prefix)
+    // InspIRCd: `*** <SNOMASK>: <notice>`
+    let (notice, strict) = if let Some(notice) = text
+        .strip_prefix("*** Notice -- ")
+        .or_else(|| text.strip_prefix("*** Global -- "))
+    {
+        (notice, false)
+    } else {
+        (text.strip_prefix("*** ")?, true)
+    };
+
+    if let Some((snomask, _)) = notice.split_once(": ")
+        && !snomask.is_empty()
+        && snomask
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c == '-' || c == '_')
+    {
+        return Some(match snomask {
+            "CONNECT" | "REMOTECONNECT" => ServerNotice::Connect,
+            "QUIT" | "REMOTEQUIT" => ServerNotice::Exit,
+            "KILL" | "REMOTEKILL" => ServerNotice::Kill,
+            "FLOOD" | "REMOTEFLOOD" => ServerNotice::Flood,
+            "OPER" | "REMOTEOPER" => ServerNotice::Oper,
+            _ => ServerNotice::Other,
+        });
+    }
+
+    let kind = if contains(notice, &["Client connecting"]) {
+        Some(ServerNotice::Connect)
+    } else if contains(notice, &["Client exiting"]) {
+        Some(ServerNotice::Exit)
+    } else if contains(
+        notice,
+        &["Received KILL message", "KILL message for", "was killed by"],
+    ) {
+        Some(ServerNotice::Kill)
+    } else if contains(notice, &["Possible Flooder", "Flood", "flood"]) {
+        Some(ServerNotice::Flood)
+    } else if contains(
+        notice,
+        &["is now an operator", "is now a network administrator"],
+    ) {
+        Some(ServerNotice::Oper)
+    } else {
+        None
+    };
+
+    if strict {
+        kind
+    } else {
+        kind.or(Some(ServerNotice::Other))
+    }
+}
+
+fn contains(notice: &str, patterns: &[&str]) -> bool {
+    patterns.iter().any(|pattern| notice.contains(pattern))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snomasks() {
+        let tests = [
+            (
+                "*** Notice -- Client connecting: foo (~foo@example.com) \
+                 [192.0.2.1] {users} [Foo]",
+                Some(ServerNotice::Connect),
+            ),
+            (
+                "*** Notice -- Client exiting: foo (~foo@example.com) \
+                 [Quit: bye] [192.0.2.1]",
+                Some(ServerNotice::Exit),
+            ),
+            (
+                "*** Notice -- Received KILL message for foo!~foo@example.com. \
+                 From bar Path: bar (spam)",
+                Some(ServerNotice::Kill),
+            ),
+            (
+                "*** Notice -- Possible Flooder foo[~foo@example.com] on \
+                 irc.example.com target: #chan",
+                Some(ServerNotice::Flood),
+            ),
+            (
+                "*** Notice -- bar (~bar@example.com) is now an operator",
+                Some(ServerNotice::Oper),
+            ),
+            (
+                "*** Notice -- Too many local connections for foo",
+                Some(ServerNotice::Other),
+            ),
+            (
+                "*** CONNECT: Client connecting on port 6697 (class main): \
+                 foo!foo@example.com (192.0.2.1) [Foo]",
+                Some(ServerNotice::Connect),
+            ),
+            (
+                "*** REMOTEQUIT: From irc2.example.com: Client exiting: foo",
+                Some(ServerNotice::Exit),
+            ),
+            (
+                "*** XLINE: Added G-line on *@example.com",
+                Some(ServerNotice::Other),
+            ),
+            (
+                "*** Client connecting: foo (foo@example.com) [192.0.2.1] \
+                 {clients} [secure TLSv1.3]",
+                Some(ServerNotice::Connect),
+            ),
+            ("*** Looking up your hostname...", None),
+            ("*** Found your hostname", None),
+            ("Welcome to the network", None),
+        ];
+
+        for (text, expected) in tests {
+            assert_eq!(classify(text), expected, "{text}");
+        }
+    }
+}
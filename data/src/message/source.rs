@@ -21,6 +21,25 @@ impl Source {
             }
         }
     }
+
+    pub fn is_server_notice(&self) -> bool {
+        matches!(
+            self,
+            Source::Server(Some(server))
+                if matches!(server.kind(), server::Kind::ServerNotice(_))
+        )
+    }
+
+    pub fn is_netsplit(&self) -> bool {
+        matches!(
+            self,
+            Source::Server(Some(server))
+                if matches!(
+                    server.kind(),
+                    server::Kind::Netsplit | server::Kind::Netjoin
+                )
+        )
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -108,9 +127,12 @@ pub mod server {
                 | Kind::MonitoredOnline
                 | Kind::MonitoredOffline
                 | Kind::StandardReply(_)
+                | Kind::ServerNotice(_)
                 | Kind::Away
                 | Kind::Invite
-                | Kind::RequestTopic => false,
+                | Kind::RequestTopic
+                | Kind::Netsplit
+                | Kind::Netjoin => false,
             }
         }
     }
@@ -141,6 +163,8 @@ pub mod server {
         MonitoredOffline,
         #[strum(to_string = "standard-reply-{0}")]
         StandardReply(StandardReply),
+        #[strum(to_string = "server-notice-{0}")]
+        ServerNotice(ServerNotice),
         #[strum(serialize = "wallops")]
         WAllOps,
         Kick,
@@ -148,6 +172,8 @@ pub mod server {
         Away,
         Invite,
         RequestTopic,
+        Netsplit,
+        Netjoin,
     }
 
     impl Kind {
@@ -160,10 +186,13 @@ pub mod server {
                 | Kind::ChangeHost
                 | Kind::ChangeMode
                 | Kind::ChangeNick
-                | Kind::Away => false,
+                | Kind::Away
+                | Kind::Netsplit
+                | Kind::Netjoin => false,
                 Kind::MonitoredOnline
                 | Kind::MonitoredOffline
                 | Kind::StandardReply(_)
+                | Kind::ServerNotice(_)
                 | Kind::WAllOps
                 | Kind::Kick
                 | Kind::ChangeTopic
@@ -191,6 +220,28 @@ pub mod server {
         Note,
     }
 
+    /// Category of a server notice (snomask) delivered to IRC operators.
+    #[derive(
+        Debug,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        Hash,
+        Serialize,
+        Deserialize,
+        strum::Display,
+    )]
+    #[strum(serialize_all = "kebab-case")]
+    pub enum ServerNotice {
+        Connect,
+        Exit,
+        Kill,
+        Flood,
+        Oper,
+        Other,
+    }
+
     #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
     pub enum Change {
         Nick(Nick),
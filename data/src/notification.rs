@@ -1,6 +1,12 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::buffer::Upstream;
 use crate::target::Channel;
-use crate::user::Nick;
-use crate::{User, isupport, reaction};
+use crate::user::{Nick, NickRef};
+use crate::{Server, User, isupport, reaction};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Notification {
@@ -45,3 +51,206 @@ pub enum Notification {
         message: String,
     },
 }
+
+impl Notification {
+    /// User and channel the notification is about, if any.
+    pub fn source(&self) -> (Option<NickRef<'_>>, Option<&Channel>) {
+        match self {
+            Notification::Connected
+            | Notification::Disconnected
+            | Notification::Reconnected
+            | Notification::MonitoredOnline(_)
+            | Notification::MonitoredOffline(_) => (None, None),
+            Notification::DirectMessage { user, .. } => {
+                (Some(user.nickname()), None)
+            }
+            Notification::Highlight { user, channel, .. }
+            | Notification::Channel { user, channel, .. }
+            | Notification::Reply { user, channel, .. } => {
+                (Some(user.nickname()), Some(channel))
+            }
+            Notification::FileTransferRequest { nick, .. } => {
+                (Some(nick.as_nickref()), None)
+            }
+            Notification::Reaction { reaction, .. } => (
+                Some(reaction.inner.sender.as_nickref()),
+                reaction.target.as_channel(),
+            ),
+        }
+    }
+
+    pub fn casemapping(&self) -> isupport::CaseMap {
+        match self {
+            Notification::DirectMessage { casemapping, .. }
+            | Notification::Highlight { casemapping, .. }
+            | Notification::FileTransferRequest { casemapping, .. }
+            | Notification::Channel { casemapping, .. }
+            | Notification::Reaction { casemapping, .. }
+            | Notification::Reply { casemapping, .. } => *casemapping,
+            Notification::Connected
+            | Notification::Disconnected
+            | Notification::Reconnected
+            | Notification::MonitoredOnline(_)
+            | Notification::MonitoredOffline(_) => isupport::CaseMap::default(),
+        }
+    }
+
+    /// Normalized name of the channel or query buffer the notification
+    /// belongs to.
+    fn buffer(&self) -> Option<&str> {
+        match self {
+            Notification::DirectMessage { user, .. } => {
+                Some(user.as_normalized_str())
+            }
+            Notification::Highlight { channel, .. }
+            | Notification::Channel { channel, .. }
+            | Notification::Reply { channel, .. } => {
+                Some(channel.as_normalized_str())
+            }
+            Notification::Reaction { reaction, .. } => {
+                Some(reaction.target.as_normalized_str())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Buffers with their notifications muted, either until a given time or
+/// until unmuted.  Muting a server buffer mutes every buffer of the server.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Mutes(HashMap<String, Option<DateTime<Utc>>>);
+
+impl Mutes {
+    pub fn mute(&mut self, buffer: &Upstream, duration: Option<TimeDelta>) {
+        self.0.insert(
+            key(buffer),
+            duration.map(|duration| Utc::now() + duration),
+        );
+    }
+
+    pub fn unmute(&mut self, buffer: &Upstream) {
+        self.0.remove(&key(buffer));
+    }
+
+    /// `Some(None)` when muted until unmuted.
+    pub fn muted_until(
+        &self,
+        buffer: &Upstream,
+        now: DateTime<Utc>,
+    ) -> Option<Option<DateTime<Utc>>> {
+        self.0
+            .get(&key(buffer))
+            .copied()
+            .filter(|until| until.is_none_or(|until| until > now))
+    }
+
+    pub fn is_muted(&self, buffer: &Upstream, now: DateTime<Utc>) -> bool {
+        self.muted_until(buffer, now).is_some()
+    }
+
+    pub fn is_notification_muted(
+        &self,
+        notification: &Notification,
+        server: &Server,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let is_muted = |key: &str| {
+            self.0
+                .get(key)
+                .is_some_and(|until| until.is_none_or(|until| until > now))
+        };
+
+        is_muted(&server_key(server))
+            || notification
+                .buffer()
+                .is_some_and(|buffer| is_muted(&target_key(server, buffer)))
+    }
+
+    /// Removes expired mutes, returning whether any were removed.
+    pub fn remove_expired(&mut self, now: DateTime<Utc>) -> bool {
+        let len = self.0.len();
+
+        self.0
+            .retain(|_, until| until.is_none_or(|until| until > now));
+
+        self.0.len() != len
+    }
+}
+
+fn key(buffer: &Upstream) -> String {
+    match buffer {
+        Upstream::Server(server) => server_key(server),
+        Upstream::Channel(server, channel) => {
+            target_key(server, channel.as_normalized_str())
+        }
+        Upstream::Query(server, query) => {
+            target_key(server, query.as_normalized_str())
+        }
+    }
+}
+
+fn server_key(server: &Server) -> String {
+    format!("server:{server}")
+}
+
+fn target_key(server: &Server, target: &str) -> String {
+    format!("server:{server}:{target}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::ServerName;
+    use crate::target;
+
+    #[test]
+    fn mutes() {
+        let server = Server::from(ServerName::from("libera"));
+        let channel = |name: &str| {
+            target::Channel::from_str(
+                name,
+                isupport::DEFAULT_CHANTYPES,
+                isupport::CaseMap::default(),
+            )
+        };
+        let highlight = |name: &str| Notification::Channel {
+            user: User::from(Nick::from_str(
+                "casper",
+                isupport::CaseMap::default(),
+            )),
+            channel: channel(name),
+            casemapping: isupport::CaseMap::default(),
+            message: String::new(),
+        };
+
+        let now = Utc::now();
+        let mut mutes = Mutes::default();
+
+        mutes.mute(
+            &Upstream::Channel(server.clone(), channel("#Halloy")),
+            Some(TimeDelta::hours(1)),
+        );
+
+        assert!(mutes.is_notification_muted(
+            &highlight("#halloy"),
+            &server,
+            now
+        ));
+        assert!(!mutes.is_notification_muted(
+            &highlight("#rust"),
+            &server,
+            now
+        ));
+        assert!(!mutes.is_notification_muted(
+            &highlight("#halloy"),
+            &server,
+            now + TimeDelta::hours(2),
+        ));
+
+        mutes.mute(&Upstream::Server(server.clone()), None);
+
+        assert!(mutes.is_notification_muted(&highlight("#rust"), &server, now));
+        assert!(!mutes.remove_expired(now));
+        assert!(mutes.remove_expired(now + TimeDelta::hours(2)));
+    }
+}
@@ -0,0 +1,426 @@
+use std::borrow::Cow;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::buffer::Upstream;
+use crate::input::{self, Parsed};
+pub use crate::queue::Id;
+use crate::queue::{self, Queue};
+use crate::{Config, client};
+
+/// How long a sent message waits for its echo before it's considered failed.
+pub const CONFIRMATION_TIMEOUT: TimeDelta = TimeDelta::seconds(10);
+
+const NOT_CONFIRMED: &str = "not confirmed by the server";
+
+/// Messages waiting for their server to be connected again, in the order they
+/// were sent.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Outbox(Queue<Message>);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Message {
+    pub id: Id,
+    pub buffer: Upstream,
+    pub content: Content,
+    pub queued_at: DateTime<Utc>,
+    /// When an unconfirmed message is sent again, once it first could be
+    #[serde(skip)]
+    pub retry_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub status: Status,
+}
+
+impl queue::Item for Message {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn at(&self) -> DateTime<Utc> {
+        self.queued_at
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Content {
+    /// Line to send as if typed into the input of the buffer
+    Input(String),
+    /// Sent message that was not confirmed by its echo before the connection
+    /// was lost
+    Unconfirmed(Box<crate::Message>),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum Status {
+    /// Waiting to be sent
+    #[default]
+    Queued,
+    /// Unconfirmed message that was sent again, waiting for the echo of the
+    /// message it was sent as
+    Retried {
+        message: Box<crate::Message>,
+        at: DateTime<Utc>,
+    },
+    /// Couldn't be sent, or wasn't confirmed after being sent again, in which
+    /// case it's kept until cancelled
+    Failed(String),
+}
+
+impl Content {
+    pub fn text(&self) -> Cow<'_, str> {
+        match self {
+            Content::Input(text) => Cow::Borrowed(text),
+            Content::Unconfirmed(message) => message.text(),
+        }
+    }
+}
+
+impl Outbox {
+    pub fn queue(
+        &mut self,
+        buffer: Upstream,
+        text: String,
+        now: DateTime<Utc>,
+    ) -> Id {
+        self.insert(buffer, Content::Input(text), now)
+    }
+
+    /// Queues a sent message to be sent again, ahead of anything queued after
+    /// it was first sent, unless it already is. A message that is unconfirmed
+    /// again after being sent again is marked as failed instead.
+    pub fn requeue(&mut self, buffer: Upstream, message: crate::Message) -> Id {
+        if let Some(queued) = self.0.iter_mut().find(|queued| {
+            matches!(
+                &queued.content,
+                Content::Unconfirmed(unconfirmed)
+                    if unconfirmed.hash == message.hash
+            ) || matches!(
+                &queued.status,
+                Status::Retried { message: retried, .. }
+                    if retried.hash == message.hash
+            )
+        }) {
+            if matches!(queued.status, Status::Retried { .. }) {
+                queued.status = Status::Failed(NOT_CONFIRMED.to_string());
+            }
+
+            return queued.id;
+        }
+
+        let queued_at = message.server_time;
+
+        self.insert(buffer, Content::Unconfirmed(Box::new(message)), queued_at)
+    }
+
+    fn insert(
+        &mut self,
+        buffer: Upstream,
+        content: Content,
+        queued_at: DateTime<Utc>,
+    ) -> Id {
+        self.0.push(|id| Message {
+            id,
+            buffer,
+            content,
+            queued_at,
+            retry_at: None,
+            status: Status::Queued,
+        })
+    }
+
+    /// Keeps a message that couldn't be sent, to show why.
+    pub fn fail(&mut self, message: Message, error: String) -> Id {
+        self.0.push(|id| Message {
+            id,
+            status: Status::Failed(error),
+            ..message
+        })
+    }
+
+    /// Keeps an unconfirmed message that was sent again as `resent` until its
+    /// echo is received.
+    pub fn retried(
+        &mut self,
+        message: Message,
+        resent: crate::Message,
+        now: DateTime<Utc>,
+    ) -> Id {
+        self.0.push(|id| Message {
+            id,
+            status: Status::Retried {
+                message: Box::new(resent),
+                at: now,
+            },
+            ..message
+        })
+    }
+
+    /// Removes the messages sent again whose echo was received, and marks
+    /// the ones still unconfirmed after [`CONFIRMATION_TIMEOUT`] as failed.
+    /// Returns whether any message changed.
+    pub fn confirm_retried(
+        &mut self,
+        now: DateTime<Utc>,
+        is_echoed: impl Fn(&Upstream, &crate::Message) -> bool,
+    ) -> bool {
+        let mut changed = false;
+
+        let confirmed = self.0.take(|queued| {
+            let Status::Retried { message, at } = &queued.status else {
+                return false;
+            };
+
+            if is_echoed(&queued.buffer, message) {
+                return true;
+            }
+
+            if now >= *at + CONFIRMATION_TIMEOUT {
+                queued.status = Status::Failed(NOT_CONFIRMED.to_string());
+                changed = true;
+            }
+
+            false
+        });
+
+        changed || !confirmed.is_empty()
+    }
+
+    /// Returns whether the message was still queued.
+    pub fn cancel(&mut self, id: Id) -> bool {
+        self.0.cancel(id)
+    }
+
+    pub fn pending<'a>(
+        &'a self,
+        buffer: &'a Upstream,
+    ) -> impl Iterator<Item = &'a Message> {
+        self.0
+            .iter()
+            .filter(move |message| message.buffer == *buffer)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Removes and returns the queued messages that can be sent, in the order
+    /// they were queued. Once a message of a buffer has to wait, so do the
+    /// ones queued after it for the same buffer.
+    ///
+    /// Unconfirmed messages wait [`CONFIRMATION_TIMEOUT`] more once they can
+    /// be sent, which gives their echo time to be replayed (e.g. from
+    /// chathistory) if the server did receive them.
+    pub fn take_sendable(
+        &mut self,
+        now: DateTime<Utc>,
+        can_send: impl Fn(&Message) -> bool,
+    ) -> Vec<Message> {
+        let mut waiting = vec![];
+
+        self.0.take(|message| {
+            if !matches!(message.status, Status::Queued) {
+                return false;
+            }
+
+            let mut sendable = can_send(message);
+
+            // Unconfirmed messages of a buffer all start waiting together
+            if sendable && matches!(message.content, Content::Unconfirmed(_)) {
+                let retry_at =
+                    *message.retry_at.get_or_insert(now + CONFIRMATION_TIMEOUT);
+
+                sendable = now >= retry_at;
+            }
+
+            if waiting.contains(&message.buffer) {
+                return false;
+            }
+
+            if !sendable {
+                waiting.push(message.buffer.clone());
+            }
+
+            sendable
+        })
+    }
+}
+
+/// Whether the line would send a message once the server of the buffer is
+/// connected again, which is what gets queued while it's disconnected.
+pub fn is_queueable(
+    buffer: &Upstream,
+    text: &str,
+    clients: &client::Map,
+    config: &Config,
+) -> bool {
+    let server = buffer.server();
+
+    matches!(
+        input::parse(
+            buffer.clone(),
+            config.buffer.text_input.auto_format,
+            text,
+            None,
+            clients.nickname(server),
+            None,
+            true,
+            clients.get_isupport_ref(server),
+            clients.get_capabilities_ref(server),
+            clients.get_features_ref(server),
+            clients.get_filehost(server),
+            clients.get_relay_bytes(server),
+            config,
+        ),
+        Ok(Parsed::Input(input)) if input.is_message()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message;
+    use crate::server::{Server, ServerName};
+
+    #[test]
+    fn keeps_messages_in_order() {
+        let libera = Server::from(ServerName::from("libera"));
+        let oftc = Server::from(ServerName::from("oftc"));
+        let now = Utc::now();
+        let mut outbox = Outbox::default();
+
+        outbox.queue(Upstream::Server(libera.clone()), "first".into(), now);
+        let cancelled = outbox.queue(
+            Upstream::Server(libera.clone()),
+            "cancelled".into(),
+            now,
+        );
+        outbox.queue(Upstream::Server(oftc.clone()), "elsewhere".into(), now);
+        outbox.queue(
+            Upstream::Server(libera.clone()),
+            "second".into(),
+            now + TimeDelta::seconds(1),
+        );
+
+        assert!(outbox.cancel(cancelled));
+        assert!(!outbox.cancel(cancelled));
+        assert_eq!(
+            outbox.pending(&Upstream::Server(libera.clone())).count(),
+            2
+        );
+        assert_eq!(outbox.pending(&Upstream::Server(oftc)).count(), 1);
+
+        let texts = |messages: Vec<Message>| {
+            messages
+                .iter()
+                .map(|message| message.content.text().into_owned())
+                .collect::<Vec<_>>()
+        };
+
+        // Later messages of the buffer wait for the ones queued before them
+        assert_eq!(
+            texts(outbox.take_sendable(now, |message| {
+                message.content.text() != "first"
+            })),
+            ["elsewhere"]
+        );
+        assert_eq!(
+            texts(outbox.take_sendable(now, |message| {
+                message.buffer.server() == &libera
+            })),
+            ["first", "second"]
+        );
+        assert!(outbox.is_empty());
+    }
+
+    #[test]
+    fn waits_for_echo_before_retrying() {
+        let buffer = Upstream::Server(Server::from(ServerName::from("libera")));
+        let sent = crate::Message::sent(
+            message::Target::Server {
+                source: message::Source::Server(None),
+            },
+            message::plain("hello".into()),
+            None,
+        );
+        let now = Utc::now();
+        let mut outbox = Outbox::default();
+
+        let id = outbox.requeue(buffer.clone(), sent.clone());
+        assert_eq!(outbox.requeue(buffer.clone(), sent), id);
+        outbox.queue(buffer, "after".into(), now);
+
+        assert!(outbox.take_sendable(now, |_| true).is_empty());
+        assert!(
+            outbox
+                .take_sendable(
+                    now + CONFIRMATION_TIMEOUT - TimeDelta::seconds(1),
+                    |_| true
+                )
+                .is_empty()
+        );
+        assert_eq!(
+            outbox
+                .take_sendable(now + CONFIRMATION_TIMEOUT, |_| true)
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn fails_when_unconfirmed_after_retry() {
+        let buffer = Upstream::Server(Server::from(ServerName::from("libera")));
+        let sent = |text: &str| {
+            crate::Message::sent(
+                message::Target::Server {
+                    source: message::Source::Server(None),
+                },
+                message::plain(text.into()),
+                None,
+            )
+        };
+        let now = Utc::now();
+        let mut outbox = Outbox::default();
+
+        outbox.requeue(buffer.clone(), sent("echoed"));
+        outbox.requeue(buffer.clone(), sent("timed out"));
+        outbox.requeue(buffer.clone(), sent("disconnected"));
+
+        assert!(outbox.take_sendable(now, |_| true).is_empty());
+        let now = now + CONFIRMATION_TIMEOUT;
+        let retried = outbox.take_sendable(now, |_| true);
+        assert_eq!(retried.len(), 3);
+
+        let mut resent = vec![];
+        for message in retried {
+            let again = sent(&message.content.text());
+            resent.push(again.clone());
+            outbox.retried(message, again, now);
+        }
+
+        // Retried messages don't hold the ones queued after them
+        outbox.queue(buffer.clone(), "after".into(), now);
+        assert_eq!(outbox.take_sendable(now, |_| true).len(), 1);
+
+        outbox.requeue(buffer.clone(), resent[2].clone());
+
+        assert!(
+            outbox.confirm_retried(now, |_, message| {
+                message.text() == "echoed"
+            })
+        );
+        assert!(!outbox.confirm_retried(now, |_, _| false));
+        assert!(
+            outbox.confirm_retried(now + CONFIRMATION_TIMEOUT, |_, _| false)
+        );
+        assert!(outbox.take_sendable(now, |_| true).is_empty());
+
+        let failed = outbox
+            .pending(&buffer)
+            .filter(|message| matches!(message.status, Status::Failed(_)))
+            .map(|message| message.content.text().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(failed, ["timed out", "disconnected"]);
+    }
+}
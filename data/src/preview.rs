@@ -8,6 +8,7 @@ use fancy_regex::Regex;
 use iced_wgpu::wgpu;
 use log;
 use reqwest::header::{self, HeaderValue};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use tokio::fs::{self, File};
@@ -17,15 +18,25 @@ use tokio::time;
 use url::Url;
 
 pub use self::card::Card;
+pub use self::code::Code;
+pub use self::embed::Embed;
+use self::provider::Provider;
+pub use self::snippet::Snippet;
+pub use self::video::Video;
 use crate::cache::{self, Asset, CacheState, CachedAsset, FileCache};
-use crate::config::preview::Visibility;
-use crate::image::Image;
+use crate::config::preview::{Playback, Visibility};
+use crate::image::{Animation, Image};
 use crate::message::Source;
 use crate::server::Server;
 use crate::target::{self, TargetRef};
 use crate::{config, image, isupport};
 
 pub mod card;
+pub mod code;
+pub mod embed;
+pub mod provider;
+pub mod snippet;
+pub mod video;
 
 // Prevent us from rate limiting ourselves
 static RATE_LIMIT: OnceLock<Semaphore> = OnceLock::new();
@@ -74,8 +85,12 @@ impl<'a> Previews<'a> {
             State::Loading => true,
             State::Loaded(preview) => {
                 let visibility = match preview {
-                    Preview::Card(_) => self.cards_are_visible,
                     Preview::Image(_) => self.images_are_visible,
+                    Preview::Card(_)
+                    | Preview::Video(_)
+                    | Preview::Code(_)
+                    | Preview::Snippet(_)
+                    | Preview::Embed(_) => self.cards_are_visible,
                 };
 
                 matches!(visibility, Visibility::All | Visibility::BySource)
@@ -104,13 +119,20 @@ pub type Collection = HashMap<Url, State>;
 pub enum Preview {
     Card(Card),
     Image(Image),
+    Video(Video),
+    Code(Code),
+    Snippet(Snippet),
+    Embed(Embed),
 }
 
 impl Preview {
-    pub fn image(&self) -> &Image {
+    pub fn image(&self) -> Option<&Image> {
         match self {
-            Self::Card(card) => &card.image,
-            Self::Image(image) => image,
+            Self::Card(card) => Some(&card.image),
+            Self::Image(image) => Some(image),
+            Self::Video(video) => Some(&video.poster),
+            Self::Embed(embed) => embed.thumbnail.as_ref(),
+            Self::Code(_) | Self::Snippet(_) => None,
         }
     }
 
@@ -125,7 +147,11 @@ impl Preview {
         config: &config::Preview,
     ) -> bool {
         match self {
-            Self::Card(_) => match cards_visibility {
+            Self::Card(_)
+            | Self::Video(_)
+            | Self::Code(_)
+            | Self::Snippet(_)
+            | Self::Embed(_) => match cards_visibility {
                 Visibility::All => true,
                 Visibility::BySource => config.card.visible_for_source(
                     source,
@@ -151,12 +177,17 @@ impl Preview {
 
 impl CachedAsset for Preview {
     fn assets(&self) -> Vec<Asset<'_>> {
-        match self {
-            Preview::Card(c) => {
-                vec![Asset(c.image.path.as_path(), &c.image.digest)]
-            }
-            Preview::Image(i) => vec![Asset(i.path.as_path(), &i.digest)],
+        let Some(image) = self.image() else {
+            return vec![];
+        };
+
+        let mut assets = vec![Asset(image.path.as_path(), &image.digest)];
+
+        if let Some(animation) = &image.animation {
+            assets.push(Asset(animation.path.as_path(), &animation.digest));
         }
+
+        assets
     }
 }
 
@@ -253,9 +284,9 @@ async fn load_inner(
         }
     };
 
-    if let Ok(ref preview) = result {
-        let image = preview.image();
-
+    let result = if let Ok(ref preview) = result
+        && let Some(image) = preview.image()
+    {
         if matches!(image.format, image::Format::Svg) {
             result
         } else if let Ok((image_width, image_height)) =
@@ -287,7 +318,120 @@ async fn load_inner(
         }
     } else {
         result
+    };
+
+    match result {
+        Ok(Preview::Image(image)) if matches!(kind, Kind::Preview) => {
+            Ok(Preview::Image(load_frames(image, preview_config).await))
+        }
+        result => result,
+    }
+}
+
+/// Loads the cached frames of an animated image into memory
+async fn load_frames(mut image: Image, config: &config::Preview) -> Image {
+    if config.image.animation.playback == Playback::Never {
+        return image;
     }
+
+    let Some(animation) = image.animation.take() else {
+        return image;
+    };
+
+    match tokio::task::spawn_blocking(move || {
+        let frames = animation.load_frames();
+        (animation, frames)
+    })
+    .await
+    {
+        Ok((mut animation, Ok(frames))) => {
+            animation.frames = Some(frames);
+            image.animation = Some(animation);
+        }
+        Ok((_, Err(error))) => {
+            log::debug!("Failed to load frames of {}: {error}", image.url);
+        }
+        Err(error) => {
+            log::debug!("Failed to load frames of {}: {error}", image.url);
+        }
+    }
+
+    image
+}
+
+/// Decodes the frames of an animated image and stores them in the cache
+async fn animate(
+    mut image: Image,
+    config: &config::Preview,
+    cache: &FileCache,
+) -> Image {
+    let animation_config = config.image.animation.clone();
+
+    let image::Format::Raster(format) = image.format else {
+        return image;
+    };
+
+    if animation_config.playback == Playback::Never {
+        return image;
+    }
+
+    let path = image.path.clone();
+
+    let decoded = tokio::task::spawn_blocking(move || {
+        let Some(decoded) =
+            image::animation::decode(&path, format, &animation_config)?
+        else {
+            return Ok(None);
+        };
+
+        let compressed = decoded.compress()?;
+
+        Ok::<_, LoadError>(Some((decoded, compressed)))
+    })
+    .await;
+
+    let (decoded, compressed) = match decoded {
+        Ok(Ok(Some(decoded))) => decoded,
+        Ok(Ok(None)) => return image,
+        Ok(Err(error)) => {
+            log::debug!("Failed to decode animation {}: {error}", image.url);
+            return image;
+        }
+        Err(error) => {
+            log::debug!("Failed to decode animation {}: {error}", image.url);
+            return image;
+        }
+    };
+
+    let digest = cache::HexDigest::new(&Sha256::digest(&compressed));
+    let path = cache.blob_path(&digest, "frames");
+
+    let saved = async {
+        if let Some(parent) = path.parent().filter(|p| !p.exists()) {
+            fs::create_dir_all(&parent).await?;
+        }
+
+        fs::write(&path, &compressed).await
+    }
+    .await;
+
+    if let Err(error) = saved {
+        log::debug!("Failed to cache animation {}: {error}", image.url);
+        return image;
+    }
+
+    cache.account_blob(compressed.len() as u64, path.clone());
+
+    image.animation = Some(Animation {
+        width: decoded.width,
+        height: decoded.height,
+        delays: decoded.delays,
+        path,
+        digest,
+        frames: None,
+    });
+
+    image
 }
 
 fn canonical_preview_url(url: &Url) -> Url {
@@ -304,35 +448,106 @@ async fn load_uncached(
 ) -> Result<Preview, LoadError> {
     log::trace!("Loading preview for {url}");
 
+    if let Some(provider) = Provider::for_url(&url, &config.providers) {
+        let name = provider.to_string();
+
+        match provider.load(&url, client.clone(), config, cache).await {
+            Ok(preview) => return Ok(preview),
+            Err(error) => {
+                log::debug!(
+                    "{name} preview provider failed for {url}: {error}"
+                );
+            }
+        }
+    }
+
     match fetch(url.clone(), client.clone(), config, cache).await? {
-        Fetched::Image(image) => Ok(Preview::Image(image)),
-        Fetched::Other(bytes) => {
-            let MetaTagProperties {
-                canonical_url,
-                image_url,
-                title,
-                description,
-            } = parse_meta_tag_properties(&bytes)?;
-
-            let image_url =
-                image_url.ok_or(LoadError::MissingProperty("image"))?;
-
-            let Fetched::Image(image) =
-                fetch(image_url, client, config, cache).await?
-            else {
-                return Err(LoadError::NotImage);
-            };
-
-            Ok(Preview::Card(Card {
-                url: url.clone(),
-                canonical_url: canonical_url
-                    .ok_or(LoadError::MissingProperty("url"))?,
-                image,
-                title: title.ok_or(LoadError::MissingProperty("title"))?,
-                description,
-            }))
+        Fetched::Image(image) => {
+            Ok(Preview::Image(animate(image, config, cache).await))
         }
+        Fetched::Other { body, content_type } => {
+            if config.providers.snippet
+                && content_type.as_deref().is_some_and(is_plain_text)
+            {
+                return Snippet::new(
+                    &url,
+                    &body,
+                    config.providers.snippet_max_lines,
+                )
+                .map(Preview::Snippet)
+                .ok_or(LoadError::NotText);
+            }
+
+            let meta = parse_meta_tag_properties(&body)?;
+
+            match load_open_graph(
+                url.clone(),
+                meta,
+                client.clone(),
+                config,
+                cache,
+            )
+            .await
+            {
+                Err(error) if config.providers.oembed => {
+                    let Some(endpoint) = embed::discover(&body, &url) else {
+                        return Err(error);
+                    };
+
+                    embed::load(url, endpoint, client, config, cache)
+                        .await
+                        .map(Preview::Embed)
+                }
+                result => result,
+            }
+        }
+    }
+}
+
+async fn load_open_graph(
+    url: Url,
+    meta: MetaTagProperties,
+    client: Arc<reqwest::Client>,
+    config: &config::Preview,
+    cache: &FileCache,
+) -> Result<Preview, LoadError> {
+    let MetaTagProperties {
+        canonical_url,
+        image_url,
+        title,
+        description,
+        video_url,
+        video_duration,
+    } = meta;
+
+    let image_url = image_url.ok_or(LoadError::MissingProperty("image"))?;
+
+    let Fetched::Image(image) = fetch(image_url, client, config, cache).await?
+    else {
+        return Err(LoadError::NotImage);
+    };
+
+    let title = title.ok_or(LoadError::MissingProperty("title"))?;
+
+    if config.providers.video && video_url.is_some() {
+        return Ok(Preview::Video(Video {
+            canonical_url: canonical_url.unwrap_or_else(|| url.clone()),
+            url,
+            poster: image,
+            title,
+            description,
+            duration: video_duration,
+        }));
     }
+
+    Ok(Preview::Card(Card {
+        url,
+        canonical_url: canonical_url
+            .ok_or(LoadError::MissingProperty("url"))?,
+        image,
+        title,
+        description,
+    }))
 }
 
 async fn load_avatar_uncached(
@@ -352,7 +567,10 @@ async fn load_avatar_uncached(
 
 enum Fetched {
     Image(Image),
-    Other(Vec<u8>),
+    Other {
+        body: Vec<u8>,
+        content_type: Option<String>,
+    },
 }
 
 async fn fetch(
@@ -383,92 +601,102 @@ async fn fetch(
 
     // First chunk should always be enough bytes to detect raster image
     // MAGIC value (<32 bytes)
-    let fetched = match image::Format::from_magic_bytes(&first_chunk).or(resp
+    let content_type = resp
         .headers()
-        .get("content-type")
+        .get(header::CONTENT_TYPE)
         .and_then(|content_type| content_type.to_str().ok())
-        .and_then(image::Format::from_mime_type))
-    {
-        Some(format) => {
-            if exceeds_image_size(
-                resp.content_length(),
-                config.request.max_image_size,
-            ) {
-                return Err(LoadError::ImageTooLarge);
-            }
+        .map(ToString::to_string);
 
-            // Store image to disk, we don't want to explode memory
-            let temp_path = cache.download_path(&url);
+    let fetched =
+        match image::Format::from_magic_bytes(&first_chunk).or(content_type
+            .as_deref()
+            .and_then(image::Format::from_mime_type))
+        {
+            Some(format) => {
+                if exceeds_image_size(
+                    resp.content_length(),
+                    config.request.max_image_size,
+                ) {
+                    return Err(LoadError::ImageTooLarge);
+                }
 
-            if let Some(parent) = temp_path.parent().filter(|p| !p.exists()) {
-                fs::create_dir_all(&parent).await?;
-            }
+                // Store image to disk, we don't want to explode memory
+                let temp_path = cache.download_path(&url);
 
-            let image_result = async {
-                let mut file = File::create(&temp_path).await?;
-                let mut hasher = Sha256::default();
+                if let Some(parent) = temp_path.parent().filter(|p| !p.exists())
+                {
+                    fs::create_dir_all(&parent).await?;
+                }
 
-                file.write_all(&first_chunk).await?;
-                hasher.update(&first_chunk);
+                let image_result = async {
+                    let mut file = File::create(&temp_path).await?;
+                    let mut hasher = Sha256::default();
 
-                let mut written = first_chunk.len();
+                    file.write_all(&first_chunk).await?;
+                    hasher.update(&first_chunk);
 
-                while let Some(chunk) = resp.chunk().await? {
-                    if written + chunk.len() > config.request.max_image_size {
-                        return Err(LoadError::ImageTooLarge);
+                    let mut written = first_chunk.len();
+
+                    while let Some(chunk) = resp.chunk().await? {
+                        if written + chunk.len() > config.request.max_image_size
+                        {
+                            return Err(LoadError::ImageTooLarge);
+                        }
+
+                        file.write_all(&chunk).await?;
+                        hasher.update(&chunk);
+
+                        written += chunk.len();
                     }
 
-                    file.write_all(&chunk).await?;
-                    hasher.update(&chunk);
+                    let digest = cache::HexDigest::new(&hasher.finalize());
+                    let image_path =
+                        cache.blob_path(&digest, format.extensions_str()[0]);
 
-                    written += chunk.len();
-                }
+                    if let Some(parent) =
+                        image_path.parent().filter(|p| !p.exists())
+                    {
+                        fs::create_dir_all(&parent).await?;
+                    }
 
-                let digest = cache::HexDigest::new(&hasher.finalize());
-                let image_path =
-                    cache.blob_path(&digest, format.extensions_str()[0]);
+                    fs::rename(&temp_path, &image_path).await?;
+                    cache.account_blob(written as u64, image_path.clone());
 
-                if let Some(parent) =
-                    image_path.parent().filter(|p| !p.exists())
-                {
-                    fs::create_dir_all(&parent).await?;
+                    Ok::<Image, LoadError>(Image::new(
+                        format, url, digest, image_path,
+                    ))
                 }
+                .await;
 
-                fs::rename(&temp_path, &image_path).await?;
-                cache.account_blob(written as u64, image_path.clone());
-
-                Ok::<Image, LoadError>(Image::new(
-                    format, url, digest, image_path,
-                ))
-            }
-            .await;
+                if image_result.is_err() {
+                    remove_download_file(&temp_path).await;
+                }
 
-            if image_result.is_err() {
-                remove_download_file(&temp_path).await;
+                Fetched::Image(image_result?)
             }
+            None => {
+                let max_scrape_size = config.request.max_scrape_size;
+
+                let mut buffer = Vec::with_capacity(max_scrape_size);
+                buffer.extend(first_chunk);
+
+                while let Some(mut chunk) = resp.chunk().await? {
+                    if buffer.len() + chunk.len() > max_scrape_size {
+                        buffer.extend(chunk.split_to(
+                            max_scrape_size.saturating_sub(buffer.len()),
+                        ));
+                        break;
+                    } else {
+                        buffer.extend(chunk);
+                    }
+                }
 
-            Fetched::Image(image_result?)
-        }
-        None => {
-            let max_scrape_size = config.request.max_scrape_size;
-
-            let mut buffer = Vec::with_capacity(max_scrape_size);
-            buffer.extend(first_chunk);
-
-            while let Some(mut chunk) = resp.chunk().await? {
-                if buffer.len() + chunk.len() > max_scrape_size {
-                    buffer.extend(chunk.split_to(
-                        max_scrape_size.saturating_sub(buffer.len()),
-                    ));
-                    break;
-                } else {
-                    buffer.extend(chunk);
+                Fetched::Other {
+                    body: buffer,
+                    content_type,
                 }
             }
-
-            Fetched::Other(buffer)
-        }
-    };
+        };
 
     // Artificially wait before releasing this permit for rate limiting
     time::sleep(Duration::from_millis(config.request.delay_ms)).await;
@@ -476,6 +704,27 @@ async fn fetch(
     Ok(fetched)
 }
 
+async fn fetch_json<T: DeserializeOwned>(
+    url: Url,
+    client: Arc<reqwest::Client>,
+    config: &config::Preview,
+    cache: &FileCache,
+) -> Result<T, LoadError> {
+    let Fetched::Other { body, .. } = fetch(url, client, config, cache).await?
+    else {
+        return Err(LoadError::NotJson);
+    };
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+fn is_plain_text(content_type: &str) -> bool {
+    content_type
+        .split(';')
+        .next()
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("text/plain"))
+}
+
 async fn remove_download_file(path: &std::path::Path) {
     let _ = fs::remove_file(path).await;
 }
@@ -497,32 +746,20 @@ struct MetaTagProperties {
     image_url: Option<Url>,
     title: Option<String>,
     description: Option<String>,
+    video_url: Option<Url>,
+    video_duration: Option<u64>,
 }
 
-fn parse_meta_tag_properties(
-    bytes: &[u8],
-) -> Result<MetaTagProperties, LoadError> {
-    let mut meta = MetaTagProperties::default();
-
-    for meta_tag in META_TAG_REGEX
-        .find_iter(&String::from_utf8_lossy(bytes))
+/// Lowercased attribute names and decoded values of an html tag
+fn tag_attributes(tag: &str) -> impl Iterator<Item = (String, String)> {
+    META_ATTR_REGEX
+        .captures_iter(tag)
         .filter_map(Result::ok)
-    {
-        let meta_tag = meta_tag.as_str();
-        let mut property = None;
-        let mut content = None;
-
-        for captures in META_ATTR_REGEX
-            .captures_iter(meta_tag)
-            .filter_map(Result::ok)
-        {
-            let Some((key, value)) = captures
+        .filter_map(|captures| {
+            let (key, value) = captures
                 .get(1)
                 .map(|r| r.as_str())
-                .zip(captures.get(2).map(|r| r.as_str()))
-            else {
-                continue;
-            };
+                .zip(captures.get(2).map(|r| r.as_str()))?;
 
             let key = key.trim().to_ascii_lowercase();
             let value = decode_html_string(
@@ -533,6 +770,24 @@ fn parse_meta_tag_properties(
             .trim()
             .to_string();
 
+            Some((key, value))
+        })
+}
+
+fn parse_meta_tag_properties(
+    bytes: &[u8],
+) -> Result<MetaTagProperties, LoadError> {
+    let mut meta = MetaTagProperties::default();
+
+    for meta_tag in META_TAG_REGEX
+        .find_iter(&String::from_utf8_lossy(bytes))
+        .filter_map(Result::ok)
+    {
+        let meta_tag = meta_tag.as_str();
+        let mut property = None;
+        let mut content = None;
+
+        for (key, value) in tag_attributes(meta_tag) {
             match key.as_str() {
                 "property" => property = Some(value),
                 "name" if property.is_none() => {
@@ -562,6 +817,18 @@ fn parse_meta_tag_properties(
             "og:description" if meta.description.is_none() => {
                 meta.description = Some(content);
             }
+            // Unlike the required properties, a malformed video shouldn't
+            // prevent the page from showing as a card
+            "og:video" | "og:video:url" | "og:video:secure_url"
+                if meta.video_url.is_none() =>
+            {
+                meta.video_url = content.parse().ok();
+            }
+            "og:video:duration" | "video:duration"
+                if meta.video_duration.is_none() =>
+            {
+                meta.video_duration = content.parse().ok();
+            }
             _ => {}
         }
     }
@@ -583,6 +850,12 @@ pub enum LoadError {
     NotHtml,
     #[error("url is not an image")]
     NotImage,
+    #[error("url is not plain text")]
+    NotText,
+    #[error("url is not json")]
+    NotJson,
+    #[error("failed to parse json: {0}")]
+    Json(#[from] serde_json::Error),
     #[error("image exceeds max file size")]
     ImageTooLarge,
     #[error("failed to parse image: {0}")]
@@ -608,9 +881,41 @@ pub enum LoadError {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use url::Url;
+
     use super::{
-        canonical_preview_url, exceeds_image_size, parse_meta_tag_properties,
+        Preview, canonical_preview_url, code, exceeds_image_size,
+        load_uncached, parse_meta_tag_properties,
     };
+    use crate::cache::FileCache;
+    use crate::config;
+    use crate::test_support::{StandIn, temp_dir};
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    async fn load(url: Url, config: config::Preview) -> Preview {
+        let root = temp_dir();
+        let cache = FileCache::new(root.path().to_path_buf(), None, 0);
+
+        load_uncached(url, Arc::new(reqwest::Client::new()), &config, &cache)
+            .await
+            .expect("preview should load")
+    }
+
+    fn config() -> config::Preview {
+        let mut config = config::Preview::default();
+        config.request.delay_ms = 0;
+        config.providers = config::preview::Providers {
+            oembed: true,
+            video: true,
+            code: true,
+            snippet: true,
+            ..config::preview::Providers::default()
+        };
+        config
+    }
 
     #[test]
     fn canonical_preview_url_strips_fragment_but_keeps_query() {
@@ -722,4 +1027,164 @@ mod tests {
             Some("https://example.com/og.png")
         );
     }
+
+    #[tokio::test]
+    async fn loads_video_poster_and_duration() {
+        let server = StandIn::routes(|base| {
+            vec![
+                (
+                    "/watch",
+                    "text/html",
+                    format!(
+                        r#"<meta property="og:title" content="A talk">
+                        <meta property="og:image" content="{base}poster.png">
+                        <meta property="og:video" content="{base}talk.mp4">
+                        <meta property="video:duration" content="3725">"#
+                    )
+                    .into_bytes(),
+                ),
+                ("/poster.png", "image/png", PNG.to_vec()),
+            ]
+        })
+        .await;
+        let base = &server.url;
+
+        let Preview::Video(video) =
+            load(base.join("watch").unwrap(), config()).await
+        else {
+            panic!("expected a video preview");
+        };
+
+        assert_eq!(video.title, "A talk");
+        assert_eq!(video.canonical_url, base.join("watch").unwrap());
+        assert_eq!(video.duration_label().as_deref(), Some("1:02:05"));
+    }
+
+    #[tokio::test]
+    async fn loads_pull_request_from_forge_api() {
+        let server = StandIn::routes(|_| {
+            vec![(
+                "/api/v3/repos/owner/repo/pulls/12",
+                "application/json",
+                br#"{"title": "Fix it", "state": "closed", "merged": true}"#
+                    .to_vec(),
+            )]
+        })
+        .await;
+        let base = &server.url;
+
+        let mut config = config();
+        config.providers.forges.insert(
+            base[url::Position::BeforeHost..url::Position::AfterPort]
+                .to_string(),
+            config::preview::Forge::Github,
+        );
+
+        let Preview::Code(code) =
+            load(base.join("owner/repo/pull/12").unwrap(), config).await
+        else {
+            panic!("expected a code preview");
+        };
+
+        assert_eq!(code.repository, "owner/repo");
+        assert_eq!(code.title, "Fix it");
+        assert!(matches!(
+            code.kind,
+            code::Kind::PullRequest {
+                number: 12,
+                state: code::State::Merged
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_open_graph_when_provider_fails() {
+        let server = StandIn::routes(|base| {
+            vec![
+                (
+                    "/owner/repo",
+                    "text/html",
+                    format!(
+                        r#"<meta property="og:title" content="owner/repo">
+                        <meta property="og:url" content="{base}owner/repo">
+                        <meta property="og:image" content="{base}card.png">"#
+                    )
+                    .into_bytes(),
+                ),
+                ("/card.png", "image/png", PNG.to_vec()),
+            ]
+        })
+        .await;
+        let base = &server.url;
+
+        let mut config = config();
+        config.providers.forges.insert(
+            base.host_str().unwrap().to_string(),
+            config::preview::Forge::Gitea,
+        );
+
+        let preview = load(base.join("owner/repo").unwrap(), config).await;
+
+        assert!(matches!(preview, Preview::Card(_)));
+    }
+
+    #[tokio::test]
+    async fn loads_plain_text_as_snippet() {
+        let server = StandIn::routes(|_| {
+            vec![(
+                "/paste",
+                "text/plain; charset=utf-8",
+                b"fn main() {\n    println!(\"hi\");\n}\n".to_vec(),
+            )]
+        })
+        .await;
+        let base = &server.url;
+
+        let Preview::Snippet(snippet) =
+            load(base.join("paste").unwrap(), config()).await
+        else {
+            panic!("expected a snippet preview");
+        };
+
+        assert_eq!(snippet.text, "fn main() {\n    println!(\"hi\");\n}");
+        assert!(!snippet.truncated);
+    }
+
+    #[tokio::test]
+    async fn discovers_oembed_without_open_graph() {
+        let server = StandIn::routes(|base| {
+            vec![
+                (
+                    "/post",
+                    "text/html",
+                    format!(
+                        r#"<link rel="alternate" type="application/json+oembed" href="{base}oembed?format=json">"#
+                    )
+                    .into_bytes(),
+                ),
+                (
+                    "/oembed",
+                    "application/json",
+                    format!(
+                        r#"{{"type": "rich", "title": "A post", "author_name": "someone", "provider_name": "Example", "thumbnail_url": "{base}thumb.png"}}"#
+                    )
+                    .into_bytes(),
+                ),
+                ("/thumb.png", "image/png", PNG.to_vec()),
+            ]
+        })
+        .await;
+        let base = &server.url;
+
+        let Preview::Embed(embed) =
+            load(base.join("post").unwrap(), config()).await
+        else {
+            panic!("expected an embed preview");
+        };
+
+        assert_eq!(embed.title, "A post");
+        assert_eq!(embed.author_name.as_deref(), Some("someone"));
+        assert_eq!(embed.provider_name.as_deref(), Some("Example"));
+        assert!(embed.thumbnail.is_some());
+    }
 }
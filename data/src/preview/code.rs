@@ -0,0 +1,390 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use url::{Position, Url};
+
+use super::{FileCache, LoadError, fetch_json};
+use crate::config;
+use crate::config::preview::Forge;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Code {
+    pub url: Url,
+    pub host: String,
+    pub repository: String,
+    pub kind: Kind,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    Repository { description: Option<String> },
+    Issue { number: u64, state: State },
+    PullRequest { number: u64, state: State },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum State {
+    Open,
+    Closed,
+    Merged,
+    Draft,
+}
+
+impl State {
+    fn new(state: &str, merged: bool, draft: bool) -> Self {
+        if merged || state == "merged" {
+            State::Merged
+        } else if matches!(state, "closed" | "locked") {
+            State::Closed
+        } else if draft {
+            State::Draft
+        } else {
+            State::Open
+        }
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            State::Open => write!(f, "Open"),
+            State::Closed => write!(f, "Closed"),
+            State::Merged => write!(f, "Merged"),
+            State::Draft => write!(f, "Draft"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Repository,
+    Issue(u64),
+    PullRequest(u64),
+}
+
+/// An API request describing a link to a code hosting site
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    host: String,
+    repository: String,
+    target: Target,
+    api: Url,
+}
+
+impl Request {
+    pub fn for_url(url: &Url, forges: &HashMap<String, Forge>) -> Option<Self> {
+        let host = url.host_str()?;
+        let authority = &url[Position::BeforeHost..Position::AfterPort];
+
+        let forge = forges
+            .get(authority)
+            .or_else(|| forges.get(host))
+            .copied()
+            .or(match host {
+                "github.com" => Some(Forge::Github),
+                "gitlab.com" => Some(Forge::Gitlab),
+                "codeberg.org" => Some(Forge::Gitea),
+                _ => None,
+            })?;
+
+        let segments = url
+            .path_segments()?
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+
+        let (repository, target) = match forge {
+            Forge::Github | Forge::Gitea => {
+                let [owner, name, rest @ ..] = segments.as_slice() else {
+                    return None;
+                };
+
+                let target = match rest {
+                    [] => Target::Repository,
+                    ["issues", number, ..] => {
+                        Target::Issue(number.parse().ok()?)
+                    }
+                    ["pull", number, ..] if forge == Forge::Github => {
+                        Target::PullRequest(number.parse().ok()?)
+                    }
+                    ["pulls", number, ..] if forge == Forge::Gitea => {
+                        Target::PullRequest(number.parse().ok()?)
+                    }
+                    _ => return None,
+                };
+
+                let name = name.strip_suffix(".git").unwrap_or(name);
+
+                (format!("{owner}/{name}"), target)
+            }
+            Forge::Gitlab => {
+                let (project, rest) = match segments
+                    .iter()
+                    .position(|segment| *segment == "-")
+                {
+                    Some(index) => (&segments[..index], &segments[index + 1..]),
+                    None => (segments.as_slice(), &[][..]),
+                };
+
+                if project.len() < 2 {
+                    return None;
+                }
+
+                let target = match rest {
+                    [] => Target::Repository,
+                    ["issues", number, ..] => {
+                        Target::Issue(number.parse().ok()?)
+                    }
+                    ["merge_requests", number, ..] => {
+                        Target::PullRequest(number.parse().ok()?)
+                    }
+                    _ => return None,
+                };
+
+                let project = project.join("/");
+
+                (
+                    project
+                        .strip_suffix(".git")
+                        .unwrap_or(&project)
+                        .to_string(),
+                    target,
+                )
+            }
+        };
+
+        let api = api_url(url, forge, host, authority, &repository, target)?;
+
+        Some(Self {
+            host: host.to_string(),
+            repository,
+            target,
+            api,
+        })
+    }
+}
+
+fn api_url(
+    url: &Url,
+    forge: Forge,
+    host: &str,
+    authority: &str,
+    repository: &str,
+    target: Target,
+) -> Option<Url> {
+    let mut api = if forge == Forge::Github && host == "github.com" {
+        Url::parse("https://api.github.com/").ok()?
+    } else {
+        Url::parse(&format!("{}://{authority}/", url.scheme())).ok()?
+    };
+
+    {
+        let mut segments = api.path_segments_mut().ok()?;
+        segments.pop_if_empty();
+
+        match forge {
+            Forge::Github => {
+                if host != "github.com" {
+                    segments.extend(["api", "v3"]);
+                }
+                segments.push("repos").extend(repository.split('/'));
+            }
+            // GitLab addresses projects by their url-encoded full path
+            Forge::Gitlab => {
+                segments.extend(["api", "v4", "projects", repository]);
+            }
+            Forge::Gitea => {
+                segments
+                    .extend(["api", "v1", "repos"])
+                    .extend(repository.split('/'));
+            }
+        }
+
+        match (forge, target) {
+            (_, Target::Repository) => {}
+            (_, Target::Issue(number)) => {
+                segments.push("issues").push(&number.to_string());
+            }
+            (Forge::Gitlab, Target::PullRequest(number)) => {
+                segments.push("merge_requests").push(&number.to_string());
+            }
+            (Forge::Github | Forge::Gitea, Target::PullRequest(number)) => {
+                segments.push("pulls").push(&number.to_string());
+            }
+        }
+    }
+
+    Some(api)
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryResponse {
+    description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueResponse {
+    title: String,
+    state: String,
+    #[serde(default)]
+    merged: bool,
+    #[serde(default)]
+    draft: bool,
+}
+
+pub(super) async fn load(
+    request: Request,
+    url: Url,
+    client: Arc<reqwest::Client>,
+    config: &config::Preview,
+    cache: &FileCache,
+) -> Result<Code, LoadError> {
+    let Request {
+        host,
+        repository,
+        target,
+        api,
+    } = request;
+
+    let (kind, title) = match target {
+        Target::Repository => {
+            let response: RepositoryResponse =
+                fetch_json(api, client, config, cache).await?;
+
+            (
+                Kind::Repository {
+                    description: response
+                        .description
+                        .filter(|description| !description.is_empty()),
+                },
+                repository.clone(),
+            )
+        }
+        Target::Issue(number) => {
+            let response: IssueResponse =
+                fetch_json(api, client, config, cache).await?;
+
+            (
+                Kind::Issue {
+                    number,
+                    state: State::new(&response.state, false, false),
+                },
+                response.title,
+            )
+        }
+        Target::PullRequest(number) => {
+            let response: IssueResponse =
+                fetch_json(api, client, config, cache).await?;
+
+            (
+                Kind::PullRequest {
+                    number,
+                    state: State::new(
+                        &response.state,
+                        response.merged,
+                        response.draft,
+                    ),
+                },
+                response.title,
+            )
+        }
+    };
+
+    Ok(Code {
+        url,
+        host,
+        repository,
+        kind,
+        title,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use url::Url;
+
+    use super::{Request, State, Target};
+    use crate::config::preview::Forge;
+
+    fn request(url: &str) -> Option<Request> {
+        let forges =
+            HashMap::from([("git.example.com:3000".to_string(), Forge::Gitea)]);
+
+        Request::for_url(&Url::parse(url).unwrap(), &forges)
+    }
+
+    #[test]
+    fn github_links() {
+        let repository = request("https://github.com/squidowl/halloy").unwrap();
+        assert_eq!(repository.repository, "squidowl/halloy");
+        assert_eq!(repository.target, Target::Repository);
+        assert_eq!(
+            repository.api.as_str(),
+            "https://api.github.com/repos/squidowl/halloy"
+        );
+
+        let pull = request("https://github.com/squidowl/halloy/pull/42/files")
+            .unwrap();
+        assert_eq!(pull.target, Target::PullRequest(42));
+        assert_eq!(
+            pull.api.as_str(),
+            "https://api.github.com/repos/squidowl/halloy/pulls/42"
+        );
+
+        assert!(
+            request("https://github.com/squidowl/halloy/tree/main").is_none()
+        );
+        assert!(request("https://github.com/squidowl").is_none());
+    }
+
+    #[test]
+    fn gitlab_links() {
+        let issue =
+            request("https://gitlab.com/group/sub/project/-/issues/7").unwrap();
+        assert_eq!(issue.repository, "group/sub/project");
+        assert_eq!(issue.target, Target::Issue(7));
+        assert_eq!(
+            issue.api.as_str(),
+            "https://gitlab.com/api/v4/projects/group%2Fsub%2Fproject/issues/7"
+        );
+
+        let merge_request =
+            request("https://gitlab.com/group/project/-/merge_requests/3")
+                .unwrap();
+        assert_eq!(merge_request.target, Target::PullRequest(3));
+        assert!(
+            merge_request
+                .api
+                .as_str()
+                .ends_with("/projects/group%2Fproject/merge_requests/3")
+        );
+    }
+
+    #[test]
+    fn configured_forges() {
+        let pull =
+            request("http://git.example.com:3000/owner/repo/pulls/5").unwrap();
+        assert_eq!(pull.host, "git.example.com");
+        assert_eq!(
+            pull.api.as_str(),
+            "http://git.example.com:3000/api/v1/repos/owner/repo/pulls/5"
+        );
+
+        assert!(request("https://example.com/owner/repo").is_none());
+    }
+
+    #[test]
+    fn state_precedence() {
+        assert_eq!(State::new("closed", true, false), State::Merged);
+        assert_eq!(State::new("merged", false, false), State::Merged);
+        assert_eq!(State::new("opened", false, true), State::Draft);
+        assert_eq!(State::new("locked", false, false), State::Closed);
+        assert_eq!(State::new("open", false, false), State::Open);
+    }
+}
@@ -0,0 +1,129 @@
+use std::sync::{Arc, LazyLock};
+
+use fancy_regex::Regex;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use super::{Fetched, FileCache, Image, LoadError, fetch, fetch_json};
+use crate::config;
+
+static LINK_TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?is)<link\b[^>]*?>"#).expect("valid link tag regex")
+});
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Embed {
+    pub url: Url,
+    pub provider_name: Option<String>,
+    pub title: String,
+    pub author_name: Option<String>,
+    pub thumbnail: Option<Image>,
+}
+
+/// Finds the JSON oEmbed endpoint advertised by a page
+///
+/// https://oembed.com/#section4
+pub(super) fn discover(bytes: &[u8], base: &Url) -> Option<Url> {
+    LINK_TAG_REGEX
+        .find_iter(&String::from_utf8_lossy(bytes))
+        .filter_map(Result::ok)
+        .find_map(|link_tag| {
+            let mut rel = None;
+            let mut kind = None;
+            let mut href = None;
+
+            for (key, value) in super::tag_attributes(link_tag.as_str()) {
+                match key.as_str() {
+                    "rel" => rel = Some(value),
+                    "type" => kind = Some(value),
+                    "href" => href = Some(value),
+                    _ => {}
+                }
+            }
+
+            let is_alternate = rel?
+                .split_ascii_whitespace()
+                .any(|rel| rel.eq_ignore_ascii_case("alternate"));
+            let is_json = kind?.eq_ignore_ascii_case("application/json+oembed");
+
+            if !(is_alternate && is_json) {
+                return None;
+            }
+
+            base.join(&href?).ok()
+        })
+}
+
+#[derive(Debug, Deserialize)]
+struct Response {
+    title: Option<String>,
+    author_name: Option<String>,
+    provider_name: Option<String>,
+    thumbnail_url: Option<String>,
+}
+
+pub(super) async fn load(
+    url: Url,
+    endpoint: Url,
+    client: Arc<reqwest::Client>,
+    config: &config::Preview,
+    cache: &FileCache,
+) -> Result<Embed, LoadError> {
+    let response: Response =
+        fetch_json(endpoint.clone(), client.clone(), config, cache).await?;
+
+    let title = response
+        .title
+        .filter(|title| !title.trim().is_empty())
+        .ok_or(LoadError::MissingProperty("title"))?;
+
+    // The thumbnail is optional, so failing to load it is not an error
+    let thumbnail = match response
+        .thumbnail_url
+        .and_then(|thumbnail_url| endpoint.join(&thumbnail_url).ok())
+    {
+        Some(thumbnail_url) => {
+            match fetch(thumbnail_url, client, config, cache).await {
+                Ok(Fetched::Image(image)) => Some(image),
+                Ok(Fetched::Other { .. }) => None,
+                Err(error) => {
+                    log::debug!("Failed to load oEmbed thumbnail: {error}");
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+
+    Ok(Embed {
+        url,
+        provider_name: response.provider_name,
+        title,
+        author_name: response.author_name,
+        thumbnail,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::discover;
+
+    #[test]
+    fn discovers_json_endpoint() {
+        let base = Url::parse("https://example.com/posts/1").unwrap();
+        let html = br#"
+            <link rel="alternate" type="text/xml+oembed" href="/oembed.xml">
+            <link href="/oembed?url=a&amp;format=json" type="application/json+oembed" rel="alternate">
+        "#;
+
+        assert_eq!(
+            discover(html, &base).map(|url| url.to_string()).as_deref(),
+            Some("https://example.com/oembed?url=a&format=json")
+        );
+        assert!(
+            discover(b"<link rel=\"icon\" href=\"/a.png\">", &base).is_none()
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt;
+use std::sync::Arc;
+
+use url::Url;
+
+use super::{
+    Fetched, FileCache, LoadError, Preview, Snippet, code, fetch, snippet,
+};
+use crate::config;
+
+/// Providers which recognize a link by its URL alone and know a better
+/// source for its preview than the page itself
+///
+/// When a provider fails, the preview falls back to the page's OpenGraph
+/// data.
+#[derive(Debug, Clone)]
+pub enum Provider {
+    Code(code::Request),
+    Snippet(Url),
+}
+
+impl Provider {
+    pub fn for_url(
+        url: &Url,
+        config: &config::preview::Providers,
+    ) -> Option<Self> {
+        if config.code
+            && let Some(request) = code::Request::for_url(url, &config.forges)
+        {
+            return Some(Provider::Code(request));
+        }
+
+        if config.snippet
+            && let Some(raw_url) = snippet::raw_url(url)
+        {
+            return Some(Provider::Snippet(raw_url));
+        }
+
+        None
+    }
+
+    pub(super) async fn load(
+        self,
+        url: &Url,
+        client: Arc<reqwest::Client>,
+        config: &config::Preview,
+        cache: &FileCache,
+    ) -> Result<Preview, LoadError> {
+        match self {
+            Provider::Code(request) => {
+                code::load(request, url.clone(), client, config, cache)
+                    .await
+                    .map(Preview::Code)
+            }
+            Provider::Snippet(raw_url) => {
+                let Fetched::Other { body, .. } =
+                    fetch(raw_url, client, config, cache).await?
+                else {
+                    return Err(LoadError::NotText);
+                };
+
+                Snippet::new(url, &body, config.providers.snippet_max_lines)
+                    .map(Preview::Snippet)
+                    .ok_or(LoadError::NotText)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Provider::Code(_) => write!(f, "code"),
+            Provider::Snippet(_) => write!(f, "snippet"),
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Lines longer than this are cut off in the preview
+const MAX_LINE_LENGTH: usize = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snippet {
+    pub url: Url,
+    pub source: String,
+    pub text: String,
+    pub truncated: bool,
+}
+
+impl Snippet {
+    pub fn new(url: &Url, body: &[u8], max_lines: usize) -> Option<Self> {
+        // Binary data served as text
+        if body.contains(&0) {
+            return None;
+        }
+
+        let body = String::from_utf8_lossy(body);
+        let mut lines = body.lines().skip_while(|line| line.trim().is_empty());
+
+        let mut text = lines
+            .by_ref()
+            .take(max_lines)
+            .map(|line| {
+                let line = line.trim_end();
+
+                match line.char_indices().nth(MAX_LINE_LENGTH) {
+                    Some((index, _)) => format!("{}…", &line[..index]),
+                    None => line.to_string(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let truncated = lines.any(|line| !line.trim().is_empty());
+
+        while text.last().is_some_and(String::is_empty) {
+            text.pop();
+        }
+
+        if text.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            url: url.clone(),
+            source: url.host_str().unwrap_or_default().to_string(),
+            text: text.join("\n"),
+            truncated,
+        })
+    }
+}
+
+/// Location of the raw text for links to well known pastebins
+pub fn raw_url(url: &Url) -> Option<Url> {
+    let segments = url
+        .path_segments()?
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+
+    let raw = match (url.host_str()?, segments.as_slice()) {
+        ("pastebin.com", [id]) if *id != "raw" => {
+            format!("https://pastebin.com/raw/{id}")
+        }
+        ("bpa.st", [id]) if *id != "raw" => {
+            format!("https://bpa.st/raw/{id}")
+        }
+        ("paste.debian.net", [id]) => {
+            format!("https://paste.debian.net/plain/{id}")
+        }
+        ("dpaste.org", [id]) => format!("https://dpaste.org/{id}/raw"),
+        ("dpaste.com", [id]) if !id.ends_with(".txt") => {
+            format!("https://dpaste.com/{id}.txt")
+        }
+        ("gist.github.com", [user, id]) => {
+            format!("https://gist.githubusercontent.com/{user}/{id}/raw")
+        }
+        _ => return None,
+    };
+
+    Url::parse(&raw).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::{Snippet, raw_url};
+
+    #[test]
+    fn rewrites_known_pastebins() {
+        let raw = |url: &str| {
+            raw_url(&Url::parse(url).unwrap()).map(|url| url.to_string())
+        };
+
+        assert_eq!(
+            raw("https://pastebin.com/abc123").as_deref(),
+            Some("https://pastebin.com/raw/abc123")
+        );
+        assert_eq!(
+            raw("https://gist.github.com/user/0123abcd").as_deref(),
+            Some("https://gist.githubusercontent.com/user/0123abcd/raw")
+        );
+        assert_eq!(raw("https://pastebin.com/raw/abc123"), None);
+        assert_eq!(raw("https://example.com/abc123"), None);
+    }
+
+    #[test]
+    fn limits_lines() {
+        let url = Url::parse("https://paste.example/x").unwrap();
+
+        let snippet =
+            Snippet::new(&url, b"\n\none\ntwo  \nthree\n\n", 2).unwrap();
+        assert_eq!(snippet.text, "one\ntwo");
+        assert!(snippet.truncated);
+        assert_eq!(snippet.source, "paste.example");
+
+        let snippet = Snippet::new(&url, b"one\n\n\n", 2).unwrap();
+        assert_eq!(snippet.text, "one");
+        assert!(!snippet.truncated);
+
+        assert!(Snippet::new(&url, b"\x89PNG\0\0", 2).is_none());
+        assert!(Snippet::new(&url, b"  \n", 2).is_none());
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use super::Image;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Video {
+    pub url: Url,
+    pub canonical_url: Url,
+    pub poster: Image,
+    pub title: String,
+    pub description: Option<String>,
+    /// Duration in seconds
+    pub duration: Option<u64>,
+}
+
+impl Video {
+    pub fn duration_label(&self) -> Option<String> {
+        let duration = self.duration.filter(|duration| *duration > 0)?;
+
+        let hours = duration / 3600;
+        let minutes = duration % 3600 / 60;
+        let seconds = duration % 60;
+
+        Some(if hours > 0 {
+            format!("{hours}:{minutes:02}:{seconds:02}")
+        } else {
+            format!("{minutes}:{seconds:02}")
+        })
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Items kept in the order of their time, with ids to cancel them by. Items
+/// with the same time stay in the order they were pushed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Queue<T> {
+    messages: Vec<T>,
+    next_id: u64,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+)]
+pub struct Id(u64);
+
+pub trait Item {
+    fn id(&self) -> Id;
+
+    fn at(&self) -> DateTime<Utc>;
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self {
+            messages: vec![],
+            next_id: 0,
+        }
+    }
+}
+
+impl<T: Item> Queue<T> {
+    /// Inserts the item built with its new id after those due no later.
+    pub fn push(&mut self, item: impl FnOnce(Id) -> T) -> Id {
+        let id = Id(self.next_id);
+        self.next_id += 1;
+
+        let item = item(id);
+        let index = self
+            .messages
+            .partition_point(|message| message.at() <= item.at());
+        self.messages.insert(index, item);
+
+        id
+    }
+
+    /// Returns whether the item was still queued.
+    pub fn cancel(&mut self, id: Id) -> bool {
+        let len = self.messages.len();
+
+        self.messages.retain(|message| message.id() != id);
+
+        self.messages.len() != len
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.messages.iter()
+    }
+
+    /// Items in order, to update without changing their time.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.messages.iter_mut()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Removes and returns the items for which `take` is true, in order.
+    /// `take` is called on every item, in order, and may update the ones it
+    /// keeps.
+    pub fn take(&mut self, mut take: impl FnMut(&mut T) -> bool) -> Vec<T> {
+        let mut taken = vec![];
+
+        for mut message in std::mem::take(&mut self.messages) {
+            if take(&mut message) {
+                taken.push(message);
+            } else {
+                self.messages.push(message);
+            }
+        }
+
+        taken
+    }
+}
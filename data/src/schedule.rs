@@ -0,0 +1,261 @@
+use chrono::{DateTime, Local, NaiveTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::buffer::Upstream;
+use crate::input::{self, Input, Parsed};
+pub use crate::queue::Id;
+use crate::queue::{self, Queue};
+use crate::{Config, client, command};
+
+/// Messages waiting to be sent at a later time, in the order they are due.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Scheduled(Queue<Message>);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Message {
+    pub id: Id,
+    pub buffer: Upstream,
+    /// Line to send as if typed into the input of the buffer
+    pub text: String,
+    pub at: DateTime<Utc>,
+    /// Why the message couldn't be sent when it was due, in which case it's
+    /// kept until cancelled
+    #[serde(default)]
+    pub failed: Option<String>,
+}
+
+impl queue::Item for Message {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn at(&self) -> DateTime<Utc> {
+        self.at
+    }
+}
+
+impl Scheduled {
+    pub fn add(
+        &mut self,
+        buffer: Upstream,
+        text: String,
+        at: DateTime<Utc>,
+    ) -> Id {
+        self.0.push(|id| Message {
+            id,
+            buffer,
+            text,
+            at,
+            failed: None,
+        })
+    }
+
+    /// Keeps a message that was due but couldn't be sent, to show why.
+    pub fn fail(&mut self, message: Message, error: String) -> Id {
+        self.0.push(|id| Message {
+            id,
+            failed: Some(error),
+            ..message
+        })
+    }
+
+    /// Returns whether the message was still pending.
+    pub fn cancel(&mut self, id: Id) -> bool {
+        self.0.cancel(id)
+    }
+
+    pub fn list(&self) -> impl Iterator<Item = &Message> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Removes and returns the messages due by `now` whose buffer can be sent
+    /// to.  Overdue messages for other buffers stay pending until then.
+    pub fn take_due(
+        &mut self,
+        now: DateTime<Utc>,
+        can_send: impl Fn(&Upstream) -> bool,
+    ) -> Vec<Message> {
+        self.0.take(|message| {
+            message.failed.is_none()
+                && message.at <= now
+                && can_send(&message.buffer)
+        })
+    }
+}
+
+/// Parses the text of a scheduled message as if it was sent from the input
+/// of its buffer.
+pub fn parse(
+    buffer: &Upstream,
+    text: &str,
+    clients: &client::Map,
+    config: &Config,
+) -> Result<Input, input::Error> {
+    let server = buffer.server();
+
+    let parsed = input::parse(
+        buffer.clone(),
+        config.buffer.text_input.auto_format,
+        text,
+        None,
+        clients.nickname(server),
+        buffer.channel().map(|channel| {
+            clients.get_channels(server).any(|joined| joined == channel)
+        }),
+        clients.get_server_is_connected(server),
+        clients.get_isupport_ref(server),
+        clients.get_capabilities_ref(server),
+        clients.get_features_ref(server),
+        clients.get_filehost(server),
+        clients.get_relay_bytes(server),
+        config,
+    )?;
+
+    match parsed {
+        Parsed::Input(input) => Ok(input),
+        Parsed::Internal(_) | Parsed::CodeFence(_) => {
+            Err(command::Error::NotSchedulable.into())
+        }
+    }
+}
+
+/// Whether a scheduled message can be sent to the buffer, which needs the
+/// server to be connected and channels to be joined.
+pub fn can_send(buffer: &Upstream, clients: &client::Map) -> bool {
+    let server = buffer.server();
+
+    clients.get_server_is_connected(server)
+        && buffer.channel().is_none_or(|channel| {
+            clients.get_channels(server).any(|joined| joined == channel)
+        })
+}
+
+/// Parses when to send a message: a delay such as `10m` or `1h30m`, a time of
+/// day such as `14:30` (the next time it comes around), or a date and time
+/// such as `2026-10-20T14:30`.
+pub fn parse_when(when: &str, now: DateTime<Local>) -> Option<DateTime<Utc>> {
+    if let Some(delay) = parse_delay(when) {
+        return Some((now + delay).to_utc());
+    }
+
+    if let Ok(time) = NaiveTime::parse_from_str(when, "%H:%M") {
+        let today = now.date_naive().and_time(time);
+
+        return [today, today + TimeDelta::days(1)]
+            .into_iter()
+            .filter_map(|at| at.and_local_timezone(Local).earliest())
+            .find(|at| *at > now)
+            .map(|at| at.to_utc());
+    }
+
+    crate::command::validated_timestamp(when)
+}
+
+/// Parses a delay made of a number and unit (`s`, `m`, `h`, `d` or `w`), or
+/// several of them such as `1h30m`.
+pub fn parse_delay(delay: &str) -> Option<TimeDelta> {
+    let mut total = TimeDelta::zero();
+    let mut rest = delay;
+
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let amount = rest[..digits].parse::<i64>().ok()?;
+
+        let mut unit = rest[digits..].chars();
+        let delta = match unit.next()? {
+            's' => TimeDelta::try_seconds(amount),
+            'm' => TimeDelta::try_minutes(amount),
+            'h' => TimeDelta::try_hours(amount),
+            'd' => TimeDelta::try_days(amount),
+            'w' => TimeDelta::try_weeks(amount),
+            _ => None,
+        }?;
+
+        total = total.checked_add(&delta)?;
+        rest = unit.as_str();
+    }
+
+    (total > TimeDelta::zero()).then_some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::server::{Server, ServerName};
+
+    #[test]
+    fn parses_delays() {
+        assert_eq!(parse_delay("10m"), Some(TimeDelta::minutes(10)));
+        assert_eq!(parse_delay("1h30m"), Some(TimeDelta::minutes(90)));
+        assert_eq!(parse_delay("2d"), Some(TimeDelta::days(2)));
+        assert_eq!(parse_delay("0m"), None);
+        assert_eq!(parse_delay("10"), None);
+        assert_eq!(parse_delay("m"), None);
+        assert_eq!(parse_delay("5y"), None);
+        assert_eq!(parse_delay(""), None);
+    }
+
+    #[test]
+    fn parses_time_of_day() {
+        let now = Local.with_ymd_and_hms(2026, 3, 14, 15, 0, 0).unwrap();
+
+        assert_eq!(
+            parse_when("16:30", now),
+            Some(
+                Local
+                    .with_ymd_and_hms(2026, 3, 14, 16, 30, 0)
+                    .unwrap()
+                    .to_utc()
+            )
+        );
+        assert_eq!(
+            parse_when("09:00", now),
+            Some(
+                Local
+                    .with_ymd_and_hms(2026, 3, 15, 9, 0, 0)
+                    .unwrap()
+                    .to_utc()
+            )
+        );
+        assert_eq!(parse_when("soon", now), None);
+    }
+
+    #[test]
+    fn takes_due_messages() {
+        let server = Server::from(ServerName::from("libera"));
+        let now = Utc::now();
+        let mut scheduled = Scheduled::default();
+
+        let later = scheduled.add(
+            Upstream::Server(server.clone()),
+            "later".into(),
+            now + TimeDelta::hours(1),
+        );
+        scheduled.add(
+            Upstream::Server(server.clone()),
+            "due".into(),
+            now - TimeDelta::minutes(1),
+        );
+
+        assert!(scheduled.take_due(now, |_| false).is_empty());
+
+        let due = scheduled.take_due(now, |_| true);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].text, "due");
+
+        scheduled.fail(due[0].clone(), "not connected".into());
+        assert!(scheduled.take_due(now, |_| true).is_empty());
+
+        assert!(scheduled.cancel(later));
+        assert_eq!(scheduled.list().count(), 1);
+    }
+}
@@ -1,7 +1,7 @@
 use std::path::{self, PathBuf};
 
 use chrono::format::StrftimeItems;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveTime, Utc};
 use serde::{Deserialize, Deserializer};
 
 use crate::Config;
@@ -271,6 +271,23 @@ where
     }
 }
 
+/// Deserializes a time of day written as `HH:MM`.
+pub fn deserialize_time_of_day<'de, D>(
+    deserializer: D,
+) -> Result<NaiveTime, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let time = String::deserialize(deserializer)?;
+
+    NaiveTime::parse_from_str(&time, "%H:%M").map_err(|_| {
+        serde::de::Error::invalid_value(
+            serde::de::Unexpected::Str(&time),
+            &"time of day as HH:MM",
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
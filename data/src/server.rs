@@ -230,6 +230,8 @@ impl ConfigMap {
                 credentials.check_permissions(&server);
                 credentials.set_password().await?;
             }
+            config.oper.check_permissions(&server);
+            config.oper.set_password().await?;
 
             config.order_channels_by =
                 config.order_channels_by.or(Some(default_order_channels_by));
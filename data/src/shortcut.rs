@@ -6,18 +6,47 @@ use iced_core::keyboard::{self, key};
 use serde::Deserialize;
 
 pub fn shortcut(key_bind: KeyBind, command: Command) -> Shortcut {
-    Shortcut { key_bind, command }
+    Shortcut {
+        keys: vec![key_bind],
+        command,
+    }
+}
+
+/// A shortcut run by pressing `keys` one after another.
+pub fn chord(keys: Vec<KeyBind>, command: Command) -> Shortcut {
+    Shortcut { keys, command }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Shortcut {
-    key_bind: KeyBind,
+    keys: Vec<KeyBind>,
     command: Command,
 }
 
-impl Shortcut {
-    pub fn execute(&self, key_bind: &KeyBind) -> Option<Command> {
-        (self.key_bind == *key_bind).then_some(self.command)
+/// What the keys pressed so far amount to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Matched {
+    Command(Command),
+    /// The keys start one or more chords.
+    Pending,
+    None,
+}
+
+/// Matches the keys pressed since the last shortcut ran against
+/// `shortcuts`.  A complete match wins over a chord that continues it (see
+/// [`blocked_chord`]), and earlier shortcuts win over later ones bound to the
+/// same keys.
+pub fn matching(shortcuts: &[Shortcut], keys: &[KeyBind]) -> Matched {
+    if let Some(shortcut) =
+        shortcuts.iter().find(|shortcut| shortcut.keys == keys)
+    {
+        Matched::Command(shortcut.command.clone())
+    } else if shortcuts.iter().any(|shortcut| {
+        shortcut.keys.len() > keys.len() && shortcut.keys.starts_with(keys)
+    }) {
+        Matched::Pending
+    } else {
+        Matched::None
     }
 }
 
@@ -75,7 +104,7 @@ impl<'de> Deserialize<'de> for KeyBinds {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Command {
     MoveUp,
     MoveDown,
@@ -94,11 +123,14 @@ pub enum Command {
     ToggleSidebar,
     ToggleFullscreen,
     CommandBar,
+    BufferSwitcher,
     ReloadConfiguration,
     FileTransfers,
     Logs,
     ThemeEditor,
     Highlights,
+    Find,
+    SearchInputHistory,
     QuitApplication,
     ScrollUpPage,
     ScrollDownPage,
@@ -106,8 +138,102 @@ pub enum Command {
     ScrollToBottom,
     CycleNextUnreadBuffer,
     CyclePreviousUnreadBuffer,
+    CycleNextWorkspace,
+    CyclePreviousWorkspace,
     MarkAsRead,
     OpenConfigFile,
+    /// Runs an input line, such as a slash command or alias, in the focused
+    /// buffer.
+    Run(String),
+}
+
+/// The first chord of `shortcuts` that can never run, since another shortcut
+/// is bound to its first keys and runs as soon as they're pressed, along with
+/// the keys of that shortcut.
+pub fn blocked_chord(
+    shortcuts: &[Shortcut],
+) -> Option<(&[KeyBind], &[KeyBind])> {
+    shortcuts.iter().find_map(|chord| {
+        shortcuts
+            .iter()
+            .find(|shortcut| {
+                shortcut.keys.len() < chord.keys.len()
+                    && chord.keys.starts_with(&shortcut.keys)
+            })
+            .map(|shortcut| (chord.keys.as_slice(), shortcut.keys.as_slice()))
+    })
+}
+
+/// Keys pressed one after another, for messages (e.g. `Ctrl K, R`).
+pub fn describe(keys: &[KeyBind]) -> String {
+    keys.iter()
+        .map(|key_bind| key_bind.to_string().trim().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Keys pressed one after another, written space separated (e.g.
+/// `"ctrl+k r"`).  `leader` stands for the key set in `keyboard.leader`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySequence(Vec<SequenceKey>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SequenceKey {
+    Leader,
+    Bind(KeyBind),
+}
+
+impl KeySequence {
+    /// The keys to press, or `None` if the sequence uses an unbound leader.
+    pub fn resolve(&self, leader: &KeyBind) -> Option<Vec<KeyBind>> {
+        self.0
+            .iter()
+            .map(|key| match key {
+                SequenceKey::Leader => matches!(leader, KeyBind::Bind { .. })
+                    .then(|| leader.clone()),
+                SequenceKey::Bind(key_bind) => Some(key_bind.clone()),
+            })
+            .collect()
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let keys = s
+            .split_whitespace()
+            .map(|key| {
+                if key.eq_ignore_ascii_case("leader") {
+                    return Ok(SequenceKey::Leader);
+                }
+
+                match key.parse::<KeyBind>()? {
+                    KeyBind::Unbind => {
+                        Err(ParseError::InvalidKeyCode(key.to_string()))
+                    }
+                    key_bind => Ok(SequenceKey::Bind(key_bind)),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if keys.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        Ok(Self(keys))
+    }
+}
+
+impl<'de> Deserialize<'de> for KeySequence {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
 }
 
 macro_rules! default {
@@ -276,11 +402,14 @@ impl KeyBind {
     #[cfg(not(target_os = "macos"))]
     default!(toggle_fullscreen, F11);
     default!(command_bar, "k", COMMAND);
+    default!(buffer_switcher, "p", COMMAND);
     default!(reload_configuration, "r", COMMAND);
     default!(file_transfers, "j", COMMAND);
     default!(logs, "l", COMMAND);
     default!(theme_editor, "t", COMMAND);
     default!(highlights, "i", COMMAND);
+    default!(find, "f", COMMAND);
+    default!(search_input_history, "r", COMMAND | SHIFT);
     default!(scroll_up_page, PageUp);
     default!(scroll_down_page, PageDown);
     // Don't use HOME / END since text input is always focused
@@ -288,6 +417,9 @@ impl KeyBind {
     default!(scroll_to_bottom, ArrowDown, COMMAND);
     default!(cycle_next_unread_buffer, "`", CTRL);
     default!(cycle_previous_unread_buffer, "`", CTRL | SHIFT);
+    default!(cycle_next_workspace);
+    default!(cycle_previous_workspace);
+    default!(leader);
     // Command + m is minimize in macOS
     default!(mark_as_read, "m", COMMAND | SHIFT);
     #[cfg(target_os = "linux")]
@@ -316,37 +448,37 @@ impl<'de> Deserialize<'de> for KeyBind {
     where
         D: serde::Deserializer<'de>,
     {
-        use serde::de;
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
 
-        let string = String::deserialize(deserializer)?;
+impl FromStr for KeyBind {
+    type Err = ParseError;
 
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         // Check if the string is "none" or "noop"
-        let trimmed = string.trim();
+        let trimmed = s.trim();
         if trimmed.eq_ignore_ascii_case("none")
             || trimmed.eq_ignore_ascii_case("noop")
         {
             return Ok(KeyBind::Unbind);
         }
 
-        let parts = string.trim().split('+').collect::<Vec<_>>();
+        let parts = trimmed.split('+').collect::<Vec<_>>();
 
         let (key_code, modifiers) = match parts.len() {
-            0 => return Err(de::Error::custom("empty keybind")),
-            1 => (
-                parts[0].parse::<KeyCode>().map_err(de::Error::custom)?,
-                Modifiers::default(),
-            ),
+            0 => return Err(ParseError::Empty),
+            1 => (parts[0].parse::<KeyCode>()?, Modifiers::default()),
             _ => {
                 let modifiers = parts[..parts.len() - 1]
                     .iter()
                     .map(|s| s.parse::<Modifiers>())
-                    .collect::<Result<Vec<_>, ParseError>>()
-                    .map_err(de::Error::custom)?
+                    .collect::<Result<Vec<_>, ParseError>>()?
                     .into_iter()
                     .fold(Modifiers::default(), ops::BitOr::bitor);
-                let key_code = parts[parts.len() - 1]
-                    .parse::<KeyCode>()
-                    .map_err(de::Error::custom)?;
+                let key_code = parts[parts.len() - 1].parse::<KeyCode>()?;
                 (key_code, modifiers)
             }
         };
@@ -583,8 +715,76 @@ impl FromStr for Modifiers {
 
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError {
+    #[error("empty keybind")]
+    Empty,
     #[error("invalid keycode: {0}")]
     InvalidKeyCode(String),
     #[error("invalid modifier: {0}")]
     InvalidModifier(String),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(sequence: &str, leader: &KeyBind) -> Vec<KeyBind> {
+        sequence
+            .parse::<KeySequence>()
+            .unwrap()
+            .resolve(leader)
+            .unwrap()
+    }
+
+    #[test]
+    fn chords() {
+        let leader = "ctrl+space".parse::<KeyBind>().unwrap();
+        let away = Command::Run("/away lunch".into());
+
+        let shortcuts = vec![
+            chord(keys("leader a", &leader), away.clone()),
+            chord(keys("ctrl+g r", &leader), Command::Run("/reconnect".into())),
+            shortcut("ctrl+p".parse().unwrap(), Command::BufferSwitcher),
+        ];
+
+        assert_eq!(blocked_chord(&shortcuts), None);
+
+        assert_eq!(
+            matching(&shortcuts, &keys("ctrl+space", &leader)),
+            Matched::Pending
+        );
+        assert_eq!(
+            matching(&shortcuts, &keys("ctrl+space a", &leader)),
+            Matched::Command(away)
+        );
+        assert_eq!(
+            matching(&shortcuts, &keys("ctrl+space b", &leader)),
+            Matched::None
+        );
+        assert_eq!(
+            matching(&shortcuts, &keys("ctrl+P", &leader)),
+            Matched::Command(Command::BufferSwitcher)
+        );
+
+        // A chord starting with a key bound on its own can't be pressed.
+        let mut blocked = shortcuts.clone();
+        blocked.push(shortcut("ctrl+g".parse().unwrap(), Command::CommandBar));
+        assert_eq!(
+            blocked_chord(&blocked),
+            Some((
+                keys("ctrl+g r", &leader).as_slice(),
+                keys("ctrl+g", &leader).as_slice()
+            ))
+        );
+
+        // A leader sequence is left out while the leader is unbound.
+        assert_eq!(
+            "leader a"
+                .parse::<KeySequence>()
+                .unwrap()
+                .resolve(&KeyBind::Unbind),
+            None
+        );
+        assert!("ctrl+k none".parse::<KeySequence>().is_err());
+        assert!(" ".parse::<KeySequence>().is_err());
+    }
+}
@@ -0,0 +1,96 @@
+use std::borrow::Cow;
+
+use chrono::{DateTime, Local};
+
+use crate::Config;
+use crate::command::alias::{self, Variable};
+
+/// Values the variables of a snippet expand to
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    pub nick: Option<String>,
+    pub channel: Option<String>,
+    pub server: Option<String>,
+    /// Author of the message being replied to
+    pub author: Option<String>,
+    /// Text of the message being replied to
+    pub reply: Option<String>,
+    pub clipboard: Option<String>,
+}
+
+/// Names of the snippets starting with `prefix`, in alphabetical order
+pub fn matching<'a>(prefix: &str, config: &'a Config) -> Vec<&'a str> {
+    let prefix = prefix.to_lowercase();
+
+    let mut names = config
+        .buffer
+        .snippets
+        .keys()
+        .filter(|name| name.starts_with(&prefix))
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+
+    names.sort_unstable();
+
+    names
+}
+
+/// Whether expanding the snippet needs the contents of the clipboard
+pub fn uses_clipboard(template: &str) -> bool {
+    alias::variables(template).any(|variable| variable == Variable::Clipboard)
+}
+
+pub fn expand(
+    template: &str,
+    context: &Context,
+    now: DateTime<Local>,
+) -> String {
+    alias::substitute_variables(
+        template,
+        &alias::Context {
+            nick: context.nick.as_deref().map(Cow::Borrowed),
+            channel: context.channel.as_deref().map(Cow::Borrowed),
+            server: context.server.as_deref().map(Cow::Borrowed),
+            author: context.author.as_deref().map(Cow::Borrowed),
+            reply: context.reply.as_deref().map(Cow::Borrowed),
+            clipboard: context.clipboard.as_deref().map(Cow::Borrowed),
+            now: Some(now),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn expands_variables() {
+        let context = Context {
+            nick: Some("tester".into()),
+            channel: Some("#halloy".into()),
+            author: Some("casper".into()),
+            reply: Some("is it out yet?".into()),
+            ..Context::default()
+        };
+        let now = Local.with_ymd_and_hms(2026, 3, 14, 9, 5, 0).unwrap();
+
+        assert_eq!(
+            expand(
+                "$author: re \"$reply\"\nfixed on $date at $time in $channel. \
+                 costs $5 $clipboard $nickname",
+                &context,
+                now
+            ),
+            "casper: re \"is it out yet?\"\nfixed on 2026-03-14 at 09:05 in \
+             #halloy. costs $5  $nickname"
+        );
+    }
+
+    #[test]
+    fn detects_clipboard() {
+        assert!(uses_clipboard("see $clipboard"));
+        assert!(!uses_clipboard("$nick $$ clip"));
+    }
+}
@@ -0,0 +1,473 @@
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{fmt, io};
+
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+use spellbook::Dictionary;
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+use crate::config::buffer::text_input::SpellCheck;
+use crate::environment;
+
+const PERSONAL_WORDS: &str = "dictionary.txt";
+const MAX_SUGGESTIONS: usize = 5;
+
+pub struct Checker {
+    languages: Vec<String>,
+    dictionaries: Vec<Dictionary>,
+    personal: RwLock<HashSet<String>>,
+    /// Bumped whenever a word is added, so text is checked again
+    revision: AtomicUsize,
+}
+
+impl fmt::Debug for Checker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Checker")
+            .field("languages", &self.languages)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Checker {
+    pub fn check(&self, word: &str) -> bool {
+        let word = normalize(word);
+
+        if self.personal.read().is_ok_and(|personal| {
+            personal.contains(word.as_str())
+                || personal.contains(&word.to_lowercase())
+        }) {
+            return true;
+        }
+
+        self.dictionaries
+            .iter()
+            .any(|dictionary| dictionary.check(&word))
+    }
+
+    pub fn suggest(&self, word: &str) -> Vec<String> {
+        let word = normalize(word);
+        let mut suggestions = vec![];
+
+        for dictionary in &self.dictionaries {
+            let mut found = vec![];
+            dictionary.suggest(&word, &mut found);
+
+            for suggestion in found {
+                if !suggestions.contains(&suggestion) {
+                    suggestions.push(suggestion);
+                }
+            }
+        }
+
+        suggestions.truncate(MAX_SUGGESTIONS);
+        suggestions
+    }
+
+    /// Adds a word to the personal word list for this session, see
+    /// [`save_word`] to keep it
+    pub fn add(&self, word: &str) {
+        if let Ok(mut personal) = self.personal.write()
+            && personal.insert(normalize(word))
+        {
+            self.revision.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn revision(&self) -> usize {
+        self.revision.load(Ordering::Relaxed)
+    }
+
+    /// Byte ranges of the misspelled words in `line`
+    pub fn misspellings(
+        &self,
+        line: &str,
+        ignore: impl Fn(&str) -> bool,
+    ) -> Vec<Range<usize>> {
+        words(line, ignore)
+            .into_iter()
+            .filter(|(_, word)| !self.check(word))
+            .map(|(range, _)| range)
+            .collect()
+    }
+}
+
+/// Dictionaries write apostrophes as `'`
+fn normalize(word: &str) -> String {
+    word.replace('’', "'")
+}
+
+/// Tokens of `line` with their byte offsets, without surrounding
+/// punctuation
+///
+/// Urls, channels, commands, email addresses and emoji shortcodes are
+/// skipped entirely.
+pub fn tokens(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    line.split_inclusive(char::is_whitespace)
+        .scan(0, |offset, piece| {
+            let start = *offset;
+            *offset += piece.len();
+
+            Some((start, piece.trim_end_matches(char::is_whitespace)))
+        })
+        .filter(|(_, token)| {
+            !token.is_empty()
+                && !token.starts_with(['#', '&', '/', ':'])
+                && !token.contains("://")
+                && !token.starts_with("www.")
+                && !token.contains('@')
+        })
+        .filter_map(|(start, token)| {
+            // Unlike other symbols, which are common in nicknames
+            let is_edge = |c: char| {
+                matches!(
+                    c,
+                    '.' | ','
+                        | ';'
+                        | ':'
+                        | '!'
+                        | '?'
+                        | '"'
+                        | '\''
+                        | '('
+                        | ')'
+                        | '*'
+                        | '“'
+                        | '”'
+                        | '‘'
+                        | '’'
+                        | '«'
+                        | '»'
+                        | '…'
+                )
+            };
+            let leading = token.len() - token.trim_start_matches(is_edge).len();
+            let core = token.trim_matches(is_edge);
+
+            (!core.is_empty()).then_some((start + leading, core))
+        })
+}
+
+/// Words of `line` worth spell checking, with their byte ranges
+///
+/// Tokens with digits or symbols, like most nicknames, hostnames and code,
+/// are skipped, as are acronyms and tokens for which `ignore` is true.
+pub fn words(
+    line: &str,
+    ignore: impl Fn(&str) -> bool,
+) -> Vec<(Range<usize>, &str)> {
+    let is_word_char = |c: char| c.is_alphabetic() || matches!(c, '\'' | '’');
+
+    let mut words = vec![];
+
+    for (start, token) in tokens(line) {
+        if ignore(token) || !token.chars().all(|c| is_word_char(c) || c == '-')
+        {
+            continue;
+        }
+
+        let mut offset = start;
+
+        for part in token.split('-') {
+            let word = part.trim_matches(['\'', '’']);
+            let word_start = offset + part.find(word).unwrap_or_default();
+
+            offset += part.len() + 1;
+
+            let is_acronym = word.chars().all(char::is_uppercase);
+
+            if word.chars().count() > 1 && !is_acronym {
+                words.push((word_start..word_start + word.len(), word));
+            }
+        }
+    }
+
+    words
+}
+
+/// Loads the configured dictionaries and the personal word list
+///
+/// Nothing is loaded when spell checking is disabled.
+pub async fn load(config: SpellCheck) -> Result<Option<Checker>, Error> {
+    if !config.enabled {
+        return Ok(None);
+    }
+
+    let personal = load_personal_words().await?;
+
+    let dictionaries =
+        tokio::task::spawn_blocking(move || load_dictionaries(&config))
+            .await??;
+
+    let (languages, dictionaries) = dictionaries.into_iter().unzip();
+
+    Ok(Some(Checker {
+        languages,
+        dictionaries,
+        personal: RwLock::new(personal),
+        revision: AtomicUsize::new(0),
+    }))
+}
+
+fn load_dictionaries(
+    config: &SpellCheck,
+) -> Result<Vec<(String, Dictionary)>, Error> {
+    let directories = search_path(config);
+
+    config
+        .languages
+        .iter()
+        .map(|language| {
+            let (aff, dic) = directories
+                .iter()
+                .map(|directory| {
+                    (
+                        directory.join(format!("{language}.aff")),
+                        directory.join(format!("{language}.dic")),
+                    )
+                })
+                .find(|(aff, dic)| aff.is_file() && dic.is_file())
+                .ok_or_else(|| Error::NotFound(language.clone()))?;
+
+            let (affixes, words) = read(&aff, &dic)?;
+
+            let dictionary =
+                Dictionary::new(&affixes, &words).map_err(|error| {
+                    Error::Parse {
+                        language: language.clone(),
+                        error: error.to_string(),
+                    }
+                })?;
+
+            log::debug!("Loaded {language} dictionary from {}", dic.display());
+
+            Ok((language.clone(), dictionary))
+        })
+        .collect()
+}
+
+/// Reads both files in the encoding named by the `SET` line of the affix
+/// file, as many dictionaries still ship in ISO 8859 or KOI8
+fn read(aff_path: &Path, dic_path: &Path) -> Result<(String, String), Error> {
+    let aff = std::fs::read(aff_path)?;
+    let dic = std::fs::read(dic_path)?;
+
+    let encoding = encoding(&aff).ok_or_else(|| Error::Encoding {
+        path: aff_path.to_path_buf(),
+        encoding: set_line(&aff).unwrap_or_default(),
+    })?;
+
+    let aff = decode(&aff, encoding, aff_path)?;
+    let dic = decode(&dic, encoding, dic_path)?;
+
+    // The text is UTF-8 now, whatever the affix file says
+    let aff = aff
+        .lines()
+        .map(|line| {
+            if line.split_whitespace().next() == Some("SET") {
+                "SET UTF-8"
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok((aff, dic))
+}
+
+/// Value of the `SET` line, e.g. `ISO8859-1`
+fn set_line(aff: &[u8]) -> Option<String> {
+    aff.split(|byte| *byte == b'\n').find_map(|line| {
+        let line = String::from_utf8_lossy(line);
+        let mut parts = line.split_whitespace();
+
+        (parts.next() == Some("SET"))
+            .then(|| parts.next().map(str::to_owned))
+            .flatten()
+    })
+}
+
+/// Without a `SET` line, UTF-8 is assumed unless the file isn't valid
+/// UTF-8, in which case it falls back to Hunspell's default, ISO 8859-1.
+fn encoding(aff: &[u8]) -> Option<&'static Encoding> {
+    let Some(set) = set_line(aff) else {
+        return Some(if std::str::from_utf8(aff).is_ok() {
+            UTF_8
+        } else {
+            WINDOWS_1252
+        });
+    };
+
+    let label = set.to_ascii_lowercase();
+    let label = match label.as_str() {
+        "microsoft-cp1251" => "windows-1251",
+        "tis620-2533" => "tis-620",
+        label => label,
+    };
+
+    Encoding::for_label(label.as_bytes())
+}
+
+fn decode(
+    bytes: &[u8],
+    encoding: &'static Encoding,
+    path: &Path,
+) -> Result<String, Error> {
+    let (text, _, malformed) = encoding.decode(bytes);
+
+    if malformed {
+        return Err(Error::Encoding {
+            path: path.to_path_buf(),
+            encoding: encoding.name().to_owned(),
+        });
+    }
+
+    Ok(text.into_owned())
+}
+
+/// Directories searched for `<language>.aff` and `<language>.dic`
+fn search_path(config: &SpellCheck) -> Vec<PathBuf> {
+    let mut directories = config.path.iter().cloned().collect::<Vec<_>>();
+
+    directories.push(environment::config_dir().join("dictionaries"));
+
+    if cfg!(target_os = "macos") {
+        if let Some(home) = dirs_next::home_dir() {
+            directories.push(home.join("Library/Spelling"));
+        }
+        directories.push("/Library/Spelling".into());
+    } else if cfg!(unix) {
+        directories.extend(
+            [
+                "/usr/share/hunspell",
+                "/usr/share/myspell",
+                "/usr/share/myspell/dicts",
+                "/usr/local/share/hunspell",
+            ]
+            .map(PathBuf::from),
+        );
+    }
+
+    directories
+}
+
+fn personal_words_path() -> PathBuf {
+    environment::data_dir().join(PERSONAL_WORDS)
+}
+
+async fn load_personal_words() -> Result<HashSet<String>, Error> {
+    match fs::read_to_string(personal_words_path()).await {
+        Ok(words) => Ok(words
+            .lines()
+            .map(str::trim)
+            .filter(|word| !word.is_empty())
+            .map(normalize)
+            .collect()),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            Ok(HashSet::new())
+        }
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Appends a word to the personal word list in the data directory
+pub async fn save_word(word: String) -> Result<(), Error> {
+    let path = personal_words_path();
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+
+    file.write_all(format!("{}\n", normalize(&word)).as_bytes())
+        .await?;
+
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("no dictionary found for {0}")]
+    NotFound(String),
+    #[error("{} is not valid {encoding}", path.display())]
+    Encoding { path: PathBuf, encoding: String },
+    #[error("failed to parse the {language} dictionary: {error}")]
+    Parse { language: String, error: String },
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Join(#[from] tokio::task::JoinError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encoding, read, words};
+    use crate::test_support::temp_dir;
+
+    fn checked(line: &str) -> Vec<&str> {
+        words(line, |token| token == "alice")
+            .into_iter()
+            .map(|(range, word)| {
+                assert_eq!(&line[range], word);
+                word
+            })
+            .collect()
+    }
+
+    #[test]
+    fn skips_context() {
+        assert_eq!(
+            checked("alice: see #halloy or https://halloy.chat for teh docs"),
+            vec!["see", "or", "for", "teh", "docs"]
+        );
+        assert_eq!(
+            checked("/msg bob_ hi :wave: mail me@example.com"),
+            vec!["hi", "mail"]
+        );
+    }
+
+    #[test]
+    fn splits_words() {
+        assert_eq!(
+            checked("“Don’t” over-engineer the IRC v3 spec!"),
+            vec!["Don’t", "over", "engineer", "the", "spec"]
+        );
+        assert_eq!(checked("  spaced\tout  "), vec!["spaced", "out"]);
+    }
+
+    #[test]
+    fn decodes_set_encoding() {
+        let dir = temp_dir();
+        let aff = dir.path().join("fr.aff");
+        let dic = dir.path().join("fr.dic");
+
+        std::fs::write(&aff, b"SET ISO8859-1\nTRY e\xe9\n").unwrap();
+        std::fs::write(&dic, b"1\ncaf\xe9\n").unwrap();
+
+        let (aff, dic) = read(&aff, &dic).unwrap();
+
+        assert_eq!(aff, "SET UTF-8\nTRY eé");
+        assert_eq!(dic, "1\ncafé\n");
+
+        assert_eq!(
+            encoding(b"SET KOI8-R\n").map(encoding_rs::Encoding::name),
+            Some("KOI8-R")
+        );
+        assert_eq!(
+            encoding(b"SET microsoft-cp1251\n")
+                .map(encoding_rs::Encoding::name),
+            Some("windows-1251")
+        );
+        assert_eq!(encoding(b"TRY abc\n"), Some(encoding_rs::UTF_8));
+        assert!(encoding(b"SET ISCII-DEVANAGARI\n").is_none());
+    }
+}
@@ -0,0 +1,202 @@
+//! Helpers shared by unit tests.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use tempfile::TempDir;
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use url::Url;
+
+/// New directory in the temporary directory, removed when dropped.
+pub fn temp_dir() -> TempDir {
+    tempfile::Builder::new()
+        .prefix("halloy-")
+        .tempdir()
+        .unwrap()
+}
+
+/// Writes `content` to a new file in a new temporary directory. The file is
+/// removed when the directory is dropped.
+pub fn temp_file(name: &str, content: impl AsRef<[u8]>) -> (TempDir, PathBuf) {
+    let dir = temp_dir();
+    let path = dir.path().join(name);
+    std::fs::write(&path, content).unwrap();
+
+    (dir, path)
+}
+
+pub struct Response {
+    pub status: &'static str,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+/// Local HTTP server standing in for a remote one. Records the raw requests
+/// it receives and stops when dropped.
+pub struct StandIn {
+    pub url: Url,
+    requests: mpsc::UnboundedReceiver<String>,
+    task: JoinHandle<()>,
+}
+
+impl StandIn {
+    /// Answers every request with `status` and `body`.
+    pub async fn respond(status: &'static str, body: &'static str) -> Self {
+        Self::serve(|_| {
+            move |_: &str| Response {
+                status,
+                content_type: "text/plain",
+                body: body.as_bytes().to_vec(),
+            }
+        })
+        .await
+    }
+
+    /// Answers requests with the content type and body registered for their
+    /// path (ignoring the query), or 404 when there is none. Routes are built
+    /// from the stand-in's URL so bodies can link back to it.
+    pub async fn routes(
+        routes: impl FnOnce(&Url) -> Vec<(&'static str, &'static str, Vec<u8>)>,
+    ) -> Self {
+        Self::serve(|url| {
+            let routes = routes(url)
+                .into_iter()
+                .map(|(path, content_type, body)| (path, (content_type, body)))
+                .collect::<HashMap<_, _>>();
+
+            move |request: &str| {
+                let path = request
+                    .split_whitespace()
+                    .nth(1)
+                    .and_then(|target| target.split('?').next())
+                    .unwrap_or_default();
+
+                match routes.get(path) {
+                    Some((content_type, body)) => Response {
+                        status: "200 OK",
+                        content_type,
+                        body: body.clone(),
+                    },
+                    None => Response {
+                        status: "404 Not Found",
+                        content_type: "text/html",
+                        body: b"missing".to_vec(),
+                    },
+                }
+            }
+        })
+        .await
+    }
+
+    async fn serve<H>(handler: impl FnOnce(&Url) -> H) -> Self
+    where
+        H: Fn(&str) -> Response + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url =
+            Url::parse(&format!("http://{}/", listener.local_addr().unwrap()))
+                .unwrap();
+
+        let handler = handler(&url);
+        let (sender, requests) = mpsc::unbounded_channel();
+
+        let task = tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = vec![];
+                let mut buffer = [0u8; 4096];
+
+                loop {
+                    let n = stream.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..n]);
+
+                    if n == 0 || is_complete(&request) {
+                        break;
+                    }
+                }
+
+                let request = String::from_utf8_lossy(&request).into_owned();
+                let Response {
+                    status,
+                    content_type,
+                    body,
+                } = handler(&request);
+
+                let head = format!(
+                    "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                stream.write_all(head.as_bytes()).await.unwrap();
+                stream.write_all(&body).await.unwrap();
+                stream.shutdown().await.unwrap();
+
+                let _ = sender.send(request);
+            }
+        });
+
+        Self {
+            url,
+            requests,
+            task,
+        }
+    }
+
+    /// Next raw request the stand-in received.
+    pub async fn request(&mut self) -> String {
+        self.requests.recv().await.unwrap()
+    }
+}
+
+impl Drop for StandIn {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Whether the raw request has been read in full, its body measured in bytes
+/// against its Content-Length.
+fn is_complete(request: &[u8]) -> bool {
+    let Some(end) = request.windows(4).position(|window| window == b"\r\n\r\n")
+    else {
+        return false;
+    };
+
+    let head = String::from_utf8_lossy(&request[..end]);
+    let body = &request[end + 4..];
+
+    let content_length = head.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.eq_ignore_ascii_case("content-length")
+            .then_some(value)?
+            .trim()
+            .parse::<usize>()
+            .ok()
+    });
+
+    match content_length {
+        Some(length) => body.len() >= length,
+        None => {
+            !head.to_ascii_lowercase().contains("transfer-encoding")
+                || body.ends_with(b"0\r\n\r\n")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_complete;
+
+    #[test]
+    fn measures_body_in_bytes() {
+        let head = b"POST / HTTP/1.1\r\nContent-Length: 4\r\n\r\n";
+
+        // Invalid UTF-8 would grow into replacement characters if decoded
+        let request = [&head[..], &[0xff, 0xfe]].concat();
+        assert!(!is_complete(&request));
+
+        let request = [&head[..], &[0xff, 0xfe, 0xfd, 0xfc]].concat();
+        assert!(is_complete(&request));
+    }
+}
@@ -0,0 +1,157 @@
+// https://codeberg.org/emersion/soju/src/branch/master/doc/ext/webpush.md
+use irc::proto::{self, command};
+pub use push::{Error, Keys};
+
+use crate::isupport::{self, CaseMap};
+use crate::target::Channel;
+use crate::{Notification, Server, User, environment};
+
+/// Load (or create) the subscription keys and record the VAPID key
+/// advertised by `server`, along with the endpoint registered with it.
+pub async fn subscribe(
+    server: Server,
+    vapid: Option<String>,
+    endpoint: Option<String>,
+) -> Result<Keys, Error> {
+    let path = push::Store::path(&environment::data_dir());
+
+    // Waits on the lock shared with other servers subscribing
+    tokio::task::spawn_blocking(move || {
+        push::Store::update(
+            &path,
+            &server.to_string(),
+            vapid,
+            endpoint.as_deref(),
+        )
+    })
+    .await
+    .map_err(|error| Error::Io(error.into()))?
+}
+
+/// Endpoint registered for `server`; its name is appended to the configured
+/// base so the daemon can tell subscriptions apart.
+pub fn endpoint(base: &str, server: &Server) -> String {
+    push::endpoint(base, &server.to_string())
+}
+
+pub fn register(endpoint: &str, keys: &Keys) -> Result<proto::Message, Error> {
+    Ok(command!("WEBPUSH", "REGISTER", endpoint, keys.encoded()?))
+}
+
+/// Notification for the message carried by a push payload; bouncers only
+/// push highlights and direct messages.
+pub fn notification(
+    mut payload: Vec<u8>,
+) -> Result<Option<Notification>, proto::parse::Error> {
+    // Payloads carry a single message, without the line ending the parser
+    // expects
+    if !payload.ends_with(b"\n") {
+        payload.extend_from_slice(b"\r\n");
+    }
+
+    let message = proto::parse::message_bytes(payload)?;
+
+    let Some(proto::Source::User(user)) = message.source else {
+        return Ok(None);
+    };
+
+    let (target, text) = match message.command {
+        proto::Command::PRIVMSG(target, text)
+        | proto::Command::NOTICE(target, text) => (target, text),
+        _ => return Ok(None),
+    };
+
+    let message = if let Some(query) = text.strip_prefix('\u{1}') {
+        let query = query.strip_suffix('\u{1}').unwrap_or(query);
+
+        match query.split_once(' ') {
+            Some(("ACTION", action)) => format!("{} {action}", user.nickname),
+            _ if query == "ACTION" => user.nickname.clone(),
+            _ => return Ok(None),
+        }
+    } else {
+        text
+    };
+
+    // The payload doesn't say which casemapping the server uses
+    let casemapping = CaseMap::default();
+    let user = User::from_proto_user(user, casemapping, false);
+
+    Ok(Some(
+        if proto::is_channel(&target, isupport::DEFAULT_CHANTYPES) {
+            Notification::Highlight {
+                user,
+                channel: Channel::from_str(
+                    &target,
+                    isupport::DEFAULT_CHANTYPES,
+                    casemapping,
+                ),
+                casemapping,
+                message,
+                description: "highlighted you".to_string(),
+                sound: None,
+            }
+        } else {
+            Notification::DirectMessage {
+                user,
+                casemapping,
+                message,
+            }
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notifications() {
+        let notification = |line: &str| {
+            notification(line.as_bytes().to_vec()).unwrap().map(
+                |notification| match notification {
+                    Notification::DirectMessage { user, message, .. } => {
+                        (user.nickname().to_string(), None, message)
+                    }
+                    Notification::Highlight {
+                        user,
+                        channel,
+                        message,
+                        ..
+                    } => (
+                        user.nickname().to_string(),
+                        Some(channel.to_string()),
+                        message,
+                    ),
+                    _ => unreachable!(),
+                },
+            )
+        };
+
+        assert_eq!(
+            notification(":casper!c@h PRIVMSG tarkah :hello"),
+            Some(("casper".to_string(), None, "hello".to_string()))
+        );
+        assert_eq!(
+            notification(":casper!c@h PRIVMSG #halloy :tarkah: hello"),
+            Some((
+                "casper".to_string(),
+                Some("#halloy".to_string()),
+                "tarkah: hello".to_string()
+            ))
+        );
+        assert_eq!(
+            notification(":casper!c@h PRIVMSG #halloy :\u{1}ACTION waves\u{1}"),
+            Some((
+                "casper".to_string(),
+                Some("#halloy".to_string()),
+                "casper waves".to_string()
+            ))
+        );
+        assert_eq!(
+            notification(":casper!c@h PRIVMSG tarkah :\u{1}VERSION\u{1}"),
+            None
+        );
+        assert_eq!(notification(":irc.example.com NOTICE * :hello"), None);
+    }
+}
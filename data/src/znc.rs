@@ -0,0 +1,210 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use irc::proto::{self, command};
+use tokio::fs;
+
+use crate::config::server::znc::Znc;
+use crate::{Server, environment};
+
+// https://wiki.znc.in/Playback
+pub const PLAYBACK: &str = "playback";
+// https://github.com/jpnurmi/znc-clientbuffer
+pub const CLIENTBUFFER: &str = "clientbuffer";
+pub const STATUS: &str = "status";
+
+pub fn status(config: &Znc, text: &str) -> proto::Message {
+    command!("PRIVMSG", config.module_nick(STATUS), text)
+}
+
+pub fn version(config: &Znc) -> proto::Message {
+    status(config, "VERSION")
+}
+
+pub fn is_version_reply(text: &str) -> bool {
+    text.starts_with("ZNC ")
+}
+
+/// Request all buffers newer than `since` be played back, or every buffered
+/// message if `since` is unknown.
+pub fn play(config: &Znc, since: Option<DateTime<Utc>>) -> proto::Message {
+    let since =
+        since.map_or(0.0, |since| since.timestamp_millis() as f64 / 1000.0);
+
+    command!(
+        "PRIVMSG",
+        config.module_nick(PLAYBACK),
+        format!("PLAY * {since:.3}")
+    )
+}
+
+pub fn list(config: &Znc) -> proto::Message {
+    command!("PRIVMSG", config.module_nick(PLAYBACK), "LIST")
+}
+
+pub fn add_client(config: &Znc, identifier: &str) -> proto::Message {
+    command!(
+        "PRIVMSG",
+        config.module_nick(CLIENTBUFFER),
+        format!("AddClient {identifier}")
+    )
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ListEntry {
+    Border,
+    Header,
+    Buffer(String),
+}
+
+/// Parse a row of the table sent by `*playback` in response to `LIST`.
+/// Returns `None` for anything that isn't part of the table.
+pub fn parse_list_entry(line: &str) -> Option<ListEntry> {
+    let line = line.trim();
+
+    if line.starts_with('+') {
+        return Some(ListEntry::Border);
+    }
+
+    let buffer = line.strip_prefix('|')?.split('|').next()?.trim();
+
+    if buffer.is_empty() || buffer == "Buffer" {
+        Some(ListEntry::Header)
+    } else {
+        Some(ListEntry::Buffer(buffer.to_string()))
+    }
+}
+
+/// Progress through the table sent by `*playback` in response to `LIST`,
+/// which has borders above and below its header and after its last row.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct List {
+    borders: usize,
+}
+
+impl List {
+    /// Returns whether the entry ends the table.  A reply that isn't part of
+    /// the table (such as when there are no buffers) ends it as well.
+    pub fn is_finished_by(&mut self, entry: Option<&ListEntry>) -> bool {
+        match entry {
+            Some(ListEntry::Border) => {
+                self.borders += 1;
+
+                self.borders >= 3
+            }
+            Some(ListEntry::Header | ListEntry::Buffer(_)) => false,
+            None => true,
+        }
+    }
+}
+
+async fn playback_path(server: &Server) -> Result<PathBuf, Error> {
+    let data_dir = environment::data_dir();
+
+    let playback_dir = data_dir.join("znc");
+
+    if !playback_dir.exists() {
+        fs::create_dir_all(&playback_dir).await?;
+    }
+
+    let hashed_server = seahash::hash(format!("{server}").as_bytes());
+
+    Ok(playback_dir.join(format!("{hashed_server}.json")))
+}
+
+pub async fn load_playback_timestamp(
+    server: Server,
+) -> Result<Option<DateTime<Utc>>, Error> {
+    let path = playback_path(&server).await?;
+
+    if let Ok(bytes) = fs::read(path).await {
+        Ok(serde_json::from_slice(&bytes).unwrap_or_default())
+    } else {
+        Ok(None)
+    }
+}
+
+pub async fn overwrite_playback_timestamp(
+    server: Server,
+    timestamp: DateTime<Utc>,
+) -> Result<(), Error> {
+    let bytes = serde_json::to_vec(&Some(timestamp))?;
+
+    let path = playback_path(&server).await?;
+
+    fs::write(path, &bytes).await?;
+
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_entries() {
+        let tests = [
+            ("+----------+----------+", Some(ListEntry::Border)),
+            ("| Buffer   | Messages |", Some(ListEntry::Header)),
+            (
+                "| #halloy  | 12       |",
+                Some(ListEntry::Buffer("#halloy".to_string())),
+            ),
+            (
+                "| casper   | 3        |",
+                Some(ListEntry::Buffer("casper".to_string())),
+            ),
+            ("No matches", None),
+        ];
+
+        for (line, expected) in tests {
+            assert_eq!(parse_list_entry(line), expected);
+        }
+    }
+
+    #[test]
+    fn list_ends() {
+        let table = [
+            "+----------+----------+",
+            "| Buffer   | Messages |",
+            "+----------+----------+",
+            "| #halloy  | 12       |",
+            "+----------+----------+",
+        ];
+
+        let mut list = List::default();
+        let finished = table
+            .iter()
+            .map(|line| list.is_finished_by(parse_list_entry(line).as_ref()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(finished, [false, false, false, false, true]);
+
+        let mut list = List::default();
+        assert!(list.is_finished_by(
+            parse_list_entry("You have no playback buffers").as_ref()
+        ));
+    }
+
+    #[test]
+    fn play_timestamp() {
+        let config = Znc::default();
+        let since = DateTime::from_timestamp_millis(1_426_788_341_250);
+
+        assert_eq!(
+            play(&config, since).command,
+            proto::Command::PRIVMSG(
+                "*playback".to_string(),
+                "PLAY * 1426788341.250".to_string()
+            )
+        );
+    }
+}
@@ -45,6 +45,10 @@ const guidesItems = [
 ];
 
 const configurationItems = [
+  {
+    text: "Accessibility",
+    link: "/configuration/accessibility",
+  },
   {
     text: "Actions",
     link: "/configuration/actions",
@@ -142,6 +142,10 @@ pub enum Command {
     /// <subcommand> <subcommand> <params>...   (client->server)
     METADATA(String, Vec<String>),
 
+    /* Web push */
+    /// <subcommand> <params>...
+    WEBPUSH(String, Vec<String>),
+
     Numeric(Numeric, Vec<String>),
     Unknown(String, Vec<String>),
     Raw(String),
@@ -280,6 +284,7 @@ impl Command {
             }
             "BOUNCER" if len > 0 => BOUNCER(req!(), remaining!()),
             "METADATA" if len > 0 => METADATA(req!(), remaining!()),
+            "WEBPUSH" if len > 0 => WEBPUSH(req!(), remaining!()),
             _ => Self::Unknown(tag, remaining!()),
         }
     }
@@ -375,6 +380,9 @@ impl Command {
             Command::METADATA(command, params) => {
                 std::iter::once(command).chain(params).collect()
             }
+            Command::WEBPUSH(command, params) => {
+                std::iter::once(command).chain(params).collect()
+            }
             Command::Numeric(_, params) => params,
             Command::Unknown(_, params) => params,
             Command::Raw(_) => vec![],
@@ -443,6 +451,7 @@ impl Command {
             NOTE(_, _, _, _) => "NOTE".into(),
             BOUNCER(..) => "BOUNCER".into(),
             METADATA(..) => "METADATA".into(),
+            WEBPUSH(..) => "WEBPUSH".into(),
             Numeric(numeric, _) => format!("{:03}", *numeric as u16).into(),
             Unknown(tag, _) => tag.clone().into(),
             Raw(_) => "".into(),
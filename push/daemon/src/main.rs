@@ -0,0 +1,251 @@
+//! Receives Web Push messages sent by bouncers supporting the
+//! `soju.im/webpush` extension and shows them as desktop notifications,
+//! so highlights and direct messages arrive while Halloy is closed.
+//!
+//! Usage: `halloy-push [address]` (defaults to `127.0.0.1:7273`)
+use std::env;
+use std::path::Path;
+use std::process::ExitCode;
+
+use data::{Notification, environment, webpush};
+use push::Store;
+use tiny_http::{Method, Request, Response};
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:7273";
+const APPLICATION_ID: &str = "org.squidowl.halloy";
+
+fn main() -> ExitCode {
+    let address = env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_ADDRESS.to_string());
+
+    let server = match tiny_http::Server::http(&address) {
+        Ok(server) => server,
+        Err(error) => {
+            eprintln!("failed to listen on {address}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("listening for push messages on {address}");
+
+    // Where Halloy stores the subscription keys
+    let data_dir = environment::data_dir();
+
+    for mut request in server.incoming_requests() {
+        let result = receive(&mut request, &data_dir).and_then(|received| {
+            if let Some((server, notification)) = received {
+                show(&server, &notification)?;
+            }
+
+            Ok(())
+        });
+
+        respond(request, result);
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Authenticate and decrypt a push message, returning the server it was
+/// sent for along with its notification, if any.
+fn receive(
+    request: &mut Request,
+    data_dir: &Path,
+) -> Result<Option<(String, Notification)>, Error> {
+    if *request.method() != Method::Post {
+        return Err(Error::Method);
+    }
+
+    let server =
+        push::server_from_path(request.url()).ok_or(Error::NotFound)?;
+
+    let store = Store::load(&Store::path(data_dir))?;
+
+    // Anyone can reach the endpoint, so only accept messages authenticated
+    // by the VAPID key of a server Halloy subscribed to, and addressed to
+    // the endpoint registered with it
+    let vapid = store.vapid.get(&server).ok_or(push::Error::Unauthorized)?;
+    let audience = store
+        .audience
+        .get(&server)
+        .ok_or(push::Error::Unauthorized)?;
+    let authorization = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Authorization"))
+        .map(|header| header.value.as_str())
+        .ok_or(push::Error::Unauthorized)?;
+
+    push::verify_vapid(authorization, vapid, audience)?;
+
+    let mut body = vec![];
+    request.as_reader().read_to_end(&mut body)?;
+
+    let payload = push::decrypt(&store.keys, &body)?;
+
+    Ok(webpush::notification(payload)?
+        .map(|notification| (server, notification)))
+}
+
+fn respond(request: Request, result: Result<(), Error>) {
+    // Push services reply 201 Created once a message is accepted
+    let status = match result {
+        Ok(()) => 201,
+        Err(error) => {
+            eprintln!("{} {}: {error}", request.method(), request.url());
+            error.status()
+        }
+    };
+
+    if let Err(error) = request.respond(Response::empty(status)) {
+        eprintln!("failed to respond: {error}");
+    }
+}
+
+fn show(server: &str, notification: &Notification) -> Result<(), Error> {
+    let (summary, message) = match notification {
+        Notification::DirectMessage { user, message, .. } => {
+            (format!("{} ({server})", user.nickname()), message)
+        }
+        Notification::Highlight {
+            user,
+            channel,
+            message,
+            description,
+            ..
+        } => (
+            format!("{} {description} ({channel}, {server})", user.nickname()),
+            message,
+        ),
+        _ => return Ok(()),
+    };
+
+    notify_rust::Notification::new()
+        .summary(&summary)
+        .body(message)
+        .appname("Halloy")
+        .icon(APPLICATION_ID)
+        .show()?;
+
+    Ok(())
+}
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error("method not allowed")]
+    Method,
+    #[error("no server in endpoint path")]
+    NotFound,
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Push(#[from] push::Error),
+    #[error(transparent)]
+    Parse(#[from] irc_proto::parse::Error),
+    #[error(transparent)]
+    Notification(#[from] notify_rust::error::Error),
+}
+
+impl Error {
+    fn status(&self) -> u16 {
+        match self {
+            Error::Method => 405,
+            Error::NotFound => 404,
+            Error::Push(push::Error::Unauthorized) => 401,
+            Error::Push(
+                push::Error::Malformed
+                | push::Error::Decrypt
+                | push::Error::Base64(_),
+            )
+            | Error::Parse(_) => 400,
+            Error::Io(_) | Error::Push(_) | Error::Notification(_) => 500,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use push::stand_in::Bouncer;
+
+    use super::*;
+
+    #[test]
+    fn bouncer() {
+        let data_dir = env::temp_dir()
+            .join(format!("halloy-push-daemon-{}", std::process::id()));
+
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let address = server.server_addr().to_ip().unwrap();
+        let endpoint = push::endpoint(&format!("http://{address}"), "soju");
+
+        let bouncer = Bouncer::default();
+        let keys = Store::update(
+            &Store::path(&data_dir),
+            "soju",
+            Some(bouncer.vapid()),
+            Some(&endpoint),
+        )
+        .unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        let dir = data_dir.clone();
+
+        thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let result = receive(&mut request, &dir).map(|received| {
+                    let _ = sender.send(received);
+                });
+
+                respond(request, result);
+            }
+        });
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let audience = format!("http://{address}");
+        let body = bouncer.encrypt(
+            &keys,
+            b":casper!c@h PRIVMSG #halloy :tarkah: ping",
+            4096,
+        );
+        let push = |claims: String| {
+            bouncer
+                .push(&endpoint, &bouncer.authorization(&claims), &body)
+                .unwrap()
+        };
+
+        assert_eq!(
+            push(format!(r#"{{"aud":"{audience}","exp":{}}}"#, now + 60)),
+            201
+        );
+
+        let Some((server, Notification::Highlight { user, channel, .. })) =
+            receiver.recv().unwrap()
+        else {
+            panic!("expected a highlight");
+        };
+
+        assert_eq!(server, "soju");
+        assert_eq!(user.nickname().to_string(), "casper");
+        assert_eq!(channel.to_string(), "#halloy");
+
+        assert_eq!(
+            push(format!(
+                r#"{{"aud":"https://push.example.com","exp":{}}}"#,
+                now + 60
+            )),
+            401
+        );
+        assert_eq!(push(format!(r#"{{"aud":"{audience}"}}"#)), 401);
+        assert!(receiver.try_recv().is_err());
+
+        std::fs::remove_dir_all(data_dir).unwrap();
+    }
+}
@@ -0,0 +1,526 @@
+// https://codeberg.org/emersion/soju/src/branch/master/doc/ext/webpush.md
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::{Engine, alphabet};
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use percent_encoding::{
+    NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode,
+};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::{aead, hkdf, signature};
+use serde::{Deserialize, Serialize};
+
+// Keys and payloads are exchanged as unpadded base64url, but be lenient
+// about padding when decoding.
+pub(crate) const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new()
+        .with_encode_padding(false)
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+const SALT_LEN: usize = 16;
+const TAG_LEN: usize = 16;
+const RECORD_SIZE_LEN: usize = 4;
+
+#[cfg(any(test, feature = "stand-in"))]
+pub mod stand_in;
+
+/// Keys shared with the bouncer when registering a subscription (RFC 8291).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keys {
+    /// P-256 private key, base64url encoded.
+    secret: String,
+    /// Authentication secret, base64url encoded.
+    auth: String,
+}
+
+impl Keys {
+    pub fn generate() -> Result<Self, Error> {
+        let rng = SystemRandom::new();
+
+        let secret = loop {
+            let mut bytes = [0; 32];
+            rng.fill(&mut bytes).map_err(|_| Error::KeyGeneration)?;
+
+            // Rejects the (vanishingly rare) values outside the curve order
+            if let Ok(secret) = p256::SecretKey::from_slice(&bytes) {
+                break secret;
+            }
+        };
+
+        let mut auth = [0; 16];
+        rng.fill(&mut auth).map_err(|_| Error::KeyGeneration)?;
+
+        Ok(Self {
+            secret: BASE64.encode(secret.to_bytes()),
+            auth: BASE64.encode(auth),
+        })
+    }
+
+    fn secret(&self) -> Result<p256::SecretKey, Error> {
+        p256::SecretKey::from_slice(&BASE64.decode(&self.secret)?)
+            .map_err(|_| Error::InvalidKey)
+    }
+
+    fn auth(&self) -> Result<Vec<u8>, Error> {
+        Ok(BASE64.decode(&self.auth)?)
+    }
+
+    /// Uncompressed P-256 public key.
+    pub fn public_key(&self) -> Result<Vec<u8>, Error> {
+        Ok(self
+            .secret()?
+            .public_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec())
+    }
+
+    /// Keys formatted as expected by `WEBPUSH REGISTER`.
+    pub fn encoded(&self) -> Result<String, Error> {
+        Ok(format!(
+            "p256dh={};auth={}",
+            BASE64.encode(self.public_key()?),
+            self.auth
+        ))
+    }
+}
+
+/// Subscription state shared between Halloy and the push daemon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Store {
+    pub keys: Keys,
+    /// VAPID public key advertised by each server, used to authenticate
+    /// incoming push messages.
+    #[serde(default)]
+    pub vapid: BTreeMap<String, String>,
+    /// Origin of the endpoint registered with each server, which its push
+    /// messages must be addressed to.
+    #[serde(default)]
+    pub audience: BTreeMap<String, String>,
+}
+
+impl Store {
+    pub fn path(data_dir: &Path) -> PathBuf {
+        data_dir.join("webpush.json")
+    }
+
+    /// Stores are replaced atomically, so loading doesn't need to wait for
+    /// an update in progress.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let bytes = fs::read(path)?;
+
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Load (or create) the subscription keys and record the VAPID key
+    /// advertised by `server`, along with the endpoint registered with it.
+    ///
+    /// Updates are serialized through a lock file, since every connected
+    /// server subscribes on its own.  The store holds the private key, so
+    /// it's only readable by the current user.
+    pub fn update(
+        path: &Path,
+        server: &str,
+        vapid: Option<String>,
+        endpoint: Option<&str>,
+    ) -> Result<Keys, Error> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Released when dropped
+        let lock = open_private(&path.with_extension("lock"), false)?;
+        lock.lock()?;
+
+        let mut store = match fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Store {
+                keys: Keys::generate()?,
+                vapid: BTreeMap::default(),
+                audience: BTreeMap::default(),
+            },
+            Err(error) => return Err(error.into()),
+        };
+
+        if let Some(vapid) = vapid {
+            store.vapid.insert(server.to_string(), vapid);
+        } else {
+            store.vapid.remove(server);
+        }
+
+        if let Some(audience) = endpoint.and_then(audience) {
+            store.audience.insert(server.to_string(), audience);
+        } else {
+            store.audience.remove(server);
+        }
+
+        let temp = path.with_extension("json.tmp");
+        let mut file = open_private(&temp, true)?;
+        file.write_all(&serde_json::to_vec(&store)?)?;
+        file.sync_all()?;
+
+        fs::rename(&temp, path)?;
+
+        Ok(store.keys)
+    }
+}
+
+fn open_private(path: &Path, truncate: bool) -> io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.create(true).write(true).truncate(truncate);
+
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    options.open(path)
+}
+
+/// Endpoint registered for `server`; its name is appended to the configured
+/// base so the daemon can tell subscriptions apart.
+pub fn endpoint(base: &str, server: &str) -> String {
+    format!(
+        "{}/{}",
+        base.trim_end_matches('/'),
+        utf8_percent_encode(server, NON_ALPHANUMERIC)
+    )
+}
+
+/// Origin of `endpoint` (`scheme://host[:port]`), which VAPID tokens name as
+/// their audience.
+pub fn audience(endpoint: &str) -> Option<String> {
+    let (scheme, rest) = endpoint.split_once("://")?;
+    let host = rest.split(['/', '?', '#']).next()?;
+
+    (!scheme.is_empty() && !host.is_empty())
+        .then(|| format!("{}://{host}", scheme.to_ascii_lowercase()))
+}
+
+/// Inverse of [`endpoint`].
+pub fn server_from_path(path: &str) -> Option<String> {
+    let path = path.split_once('?').map_or(path, |(path, _)| path);
+    let segment = path.trim_end_matches('/').rsplit('/').next()?;
+
+    let server = percent_decode_str(segment).decode_utf8().ok()?;
+
+    (!server.is_empty()).then(|| server.into_owned())
+}
+
+#[derive(Debug, Deserialize)]
+struct Claims {
+    aud: String,
+    exp: u64,
+}
+
+/// Verify the `Authorization` header of a push message against the VAPID
+/// key advertised by the server and the origin of the endpoint registered
+/// with it (RFC 8292).  Any malformed header or token is unauthorized.
+pub fn verify_vapid(
+    authorization: &str,
+    vapid: &str,
+    audience: &str,
+) -> Result<(), Error> {
+    let params = authorization
+        .strip_prefix("vapid ")
+        .ok_or(Error::Unauthorized)?;
+
+    let mut token = None;
+    let mut key = None;
+
+    for param in params.split(',') {
+        match param.trim().split_once('=') {
+            Some(("t", value)) => token = Some(value),
+            Some(("k", value)) => key = Some(value),
+            _ => {}
+        }
+    }
+
+    let decode =
+        |value: &str| BASE64.decode(value).map_err(|_| Error::Unauthorized);
+
+    let key = decode(key.ok_or(Error::Unauthorized)?)?;
+
+    if key != decode(vapid)? {
+        return Err(Error::Unauthorized);
+    }
+
+    let (signed, signature) = token
+        .ok_or(Error::Unauthorized)?
+        .rsplit_once('.')
+        .ok_or(Error::Unauthorized)?;
+    let (_, claims) = signed.split_once('.').ok_or(Error::Unauthorized)?;
+
+    // Both claims are required, a token without them is rejected like any
+    // other invalid one
+    let claims: Claims = serde_json::from_slice(&decode(claims)?)
+        .map_err(|_| Error::Unauthorized)?;
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+
+    if claims.exp < now || claims.aud != audience {
+        return Err(Error::Unauthorized);
+    }
+
+    signature::UnparsedPublicKey::new(&signature::ECDSA_P256_SHA256_FIXED, key)
+        .verify(signed.as_bytes(), &decode(signature)?)
+        .map_err(|_| Error::Unauthorized)
+}
+
+struct Len(usize);
+
+impl hkdf::KeyType for Len {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
+fn hkdf(
+    salt: &[u8],
+    ikm: &[u8],
+    info: &[u8],
+    len: usize,
+) -> Result<Vec<u8>, Error> {
+    let mut okm = vec![0; len];
+
+    hkdf::Salt::new(hkdf::HKDF_SHA256, salt)
+        .extract(ikm)
+        .expand(&[info], Len(len))
+        .and_then(|expanded| expanded.fill(&mut okm))
+        .map_err(|_| Error::Decrypt)?;
+
+    Ok(okm)
+}
+
+/// Decrypt an `aes128gcm` encoded push message (RFC 8188, RFC 8291).
+pub fn decrypt(keys: &Keys, body: &[u8]) -> Result<Vec<u8>, Error> {
+    let (salt, rest) =
+        body.split_at_checked(SALT_LEN).ok_or(Error::Malformed)?;
+    let (record_size, rest) = rest
+        .split_at_checked(RECORD_SIZE_LEN)
+        .ok_or(Error::Malformed)?;
+    let (&id_len, rest) = rest.split_first().ok_or(Error::Malformed)?;
+    let (sender, ciphertext) = rest
+        .split_at_checked(usize::from(id_len))
+        .ok_or(Error::Malformed)?;
+
+    let record_size = u32::from_be_bytes(
+        record_size.try_into().map_err(|_| Error::Malformed)?,
+    ) as usize;
+
+    if record_size <= TAG_LEN || ciphertext.is_empty() {
+        return Err(Error::Malformed);
+    }
+
+    let sender_key = p256::PublicKey::from_sec1_bytes(sender)
+        .map_err(|_| Error::InvalidKey)?;
+    let shared = p256::ecdh::diffie_hellman(
+        keys.secret()?.to_nonzero_scalar(),
+        sender_key.as_affine(),
+    );
+
+    let key_info =
+        [b"WebPush: info\0".as_slice(), &keys.public_key()?, sender].concat();
+    let ikm = hkdf(&keys.auth()?, shared.raw_secret_bytes(), &key_info, 32)?;
+    let cek = hkdf(salt, &ikm, b"Content-Encoding: aes128gcm\0", 16)?;
+    let base_nonce = hkdf(salt, &ikm, b"Content-Encoding: nonce\0", 12)?;
+
+    let key = aead::LessSafeKey::new(
+        aead::UnboundKey::new(&aead::AES_128_GCM, &cek)
+            .map_err(|_| Error::Decrypt)?,
+    );
+
+    let records = ciphertext.len().div_ceil(record_size);
+    let mut plaintext = vec![];
+
+    for (sequence, record) in ciphertext.chunks(record_size).enumerate() {
+        let mut nonce = [0; aead::NONCE_LEN];
+        nonce.copy_from_slice(&base_nonce);
+
+        for (byte, sequence) in nonce[aead::NONCE_LEN - 8..]
+            .iter_mut()
+            .zip((sequence as u64).to_be_bytes())
+        {
+            *byte ^= sequence;
+        }
+
+        let mut record = record.to_vec();
+        let decrypted = key
+            .open_in_place(
+                aead::Nonce::assume_unique_for_key(nonce),
+                aead::Aad::empty(),
+                &mut record,
+            )
+            .map_err(|_| Error::Decrypt)?;
+
+        // Records end with a delimiter (2 for the last record, 1 otherwise)
+        // followed by zero or more bytes of padding
+        let end = decrypted
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .ok_or(Error::Malformed)?;
+        let delimiter = if sequence + 1 == records { 2 } else { 1 };
+
+        if decrypted[end] != delimiter {
+            return Err(Error::Malformed);
+        }
+
+        plaintext.extend_from_slice(&decrypted[..end]);
+    }
+
+    Ok(plaintext)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+    #[error(transparent)]
+    Base64(#[from] base64::DecodeError),
+    #[error("failed to generate keys")]
+    KeyGeneration,
+    #[error("invalid key")]
+    InvalidKey,
+    #[error("malformed push message")]
+    Malformed,
+    #[error("failed to decrypt push message")]
+    Decrypt,
+    #[error("push message failed VAPID authentication")]
+    Unauthorized,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stand_in::Bouncer;
+
+    #[test]
+    fn decrypt_payload() {
+        let bouncer = Bouncer::default();
+        let keys = Keys::generate().unwrap();
+        let payload =
+            b":casper!casper@halloy.chat PRIVMSG #halloy :tarkah: ping";
+
+        for record_size in [4096, 32] {
+            let body = bouncer.encrypt(&keys, payload, record_size);
+
+            assert_eq!(decrypt(&keys, &body).unwrap(), payload);
+        }
+
+        let other = Keys::generate().unwrap();
+        let body = bouncer.encrypt(&keys, payload, 4096);
+
+        assert!(matches!(decrypt(&other, &body), Err(Error::Decrypt)));
+        assert!(matches!(decrypt(&keys, &body[..20]), Err(Error::Malformed)));
+    }
+
+    #[test]
+    fn vapid() {
+        let bouncer = Bouncer::default();
+        let vapid = bouncer.vapid();
+        let audience = "http://localhost";
+
+        let token = |aud: &str, exp: u64| {
+            bouncer.authorization(&format!(r#"{{"aud":"{aud}","exp":{exp}}}"#))
+        };
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let valid = token(audience, now + 3600);
+        let expired = token(audience, now - 3600);
+        let elsewhere = token("https://push.example.com", now + 3600);
+        let unbounded =
+            bouncer.authorization(&format!(r#"{{"aud":"{audience}"}}"#));
+
+        assert!(verify_vapid(&valid, &vapid, audience).is_ok());
+        assert!(matches!(
+            verify_vapid(&expired, &vapid, audience),
+            Err(Error::Unauthorized)
+        ));
+        assert!(matches!(
+            verify_vapid(&elsewhere, &vapid, audience),
+            Err(Error::Unauthorized)
+        ));
+        assert!(matches!(
+            verify_vapid(&unbounded, &vapid, audience),
+            Err(Error::Unauthorized)
+        ));
+        assert!(
+            verify_vapid(&valid, &BASE64.encode([4; 65]), audience).is_err()
+        );
+        assert!(verify_vapid("WebPush abc", &vapid, audience).is_err());
+    }
+
+    #[test]
+    fn endpoints() {
+        let endpoint =
+            endpoint("https://push.example.com/halloy/", "Libera Chat");
+
+        assert_eq!(endpoint, "https://push.example.com/halloy/Libera%20Chat");
+        assert_eq!(
+            audience(&endpoint).as_deref(),
+            Some("https://push.example.com")
+        );
+        assert_eq!(
+            audience("http://127.0.0.1:8080/halloy").as_deref(),
+            Some("http://127.0.0.1:8080")
+        );
+        assert_eq!(audience("push.example.com"), None);
+        assert_eq!(
+            server_from_path("/halloy/Libera%20Chat").as_deref(),
+            Some("Libera Chat")
+        );
+        assert_eq!(server_from_path("/"), None);
+    }
+
+    #[test]
+    fn store() {
+        let dir = std::env::temp_dir()
+            .join(format!("halloy-push-{}", std::process::id()));
+        let path = Store::path(&dir);
+
+        let keys = Store::update(
+            &path,
+            "libera",
+            Some("vapid".into()),
+            Some("https://push.example.com/halloy/libera"),
+        )
+        .unwrap();
+        let again = Store::update(&path, "oftc", None, None).unwrap();
+        let store = Store::load(&path).unwrap();
+
+        assert_eq!(keys.secret, again.secret);
+        assert_eq!(store.keys.secret, keys.secret);
+        assert_eq!(
+            store.vapid.get("libera").map(String::as_str),
+            Some("vapid")
+        );
+        assert_eq!(
+            store.audience.get("libera").map(String::as_str),
+            Some("https://push.example.com")
+        );
+        assert!(!store.vapid.contains_key("oftc"));
+        assert!(!store.audience.contains_key("oftc"));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,159 @@
+//! Bouncer stand-in sending push messages the way soju does, for tests.
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+
+use base64::Engine;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use ring::aead;
+use ring::rand::SystemRandom;
+use ring::signature::{self, EcdsaKeyPair, KeyPair};
+
+use super::{BASE64, Keys, SALT_LEN, TAG_LEN, hkdf};
+
+pub struct Bouncer {
+    pair: EcdsaKeyPair,
+    rng: SystemRandom,
+}
+
+impl Default for Bouncer {
+    fn default() -> Self {
+        let rng = SystemRandom::new();
+        let algorithm = &signature::ECDSA_P256_SHA256_FIXED_SIGNING;
+        let pkcs8 =
+            EcdsaKeyPair::generate_pkcs8(algorithm, &rng).expect("pkcs8");
+        let pair = EcdsaKeyPair::from_pkcs8(algorithm, pkcs8.as_ref(), &rng)
+            .expect("key pair");
+
+        Self { pair, rng }
+    }
+}
+
+impl Bouncer {
+    /// VAPID public key the bouncer advertises.
+    pub fn vapid(&self) -> String {
+        BASE64.encode(self.pair.public_key().as_ref())
+    }
+
+    /// `Authorization` header of a push message, with a token carrying
+    /// `claims` (JSON) signed by the VAPID key.
+    pub fn authorization(&self, claims: &str) -> String {
+        let signed = format!(
+            "{}.{}",
+            BASE64.encode(r#"{"typ":"JWT","alg":"ES256"}"#),
+            BASE64.encode(claims)
+        );
+        let signature =
+            self.pair.sign(&self.rng, signed.as_bytes()).expect("sign");
+
+        format!(
+            "vapid t={signed}.{}, k={}",
+            BASE64.encode(signature.as_ref()),
+            self.vapid()
+        )
+    }
+
+    /// Encrypts `plaintext` for the subscription `keys` (RFC 8291), in
+    /// records of `record_size` bytes, with a fixed sender key and salt.
+    pub fn encrypt(
+        &self,
+        keys: &Keys,
+        plaintext: &[u8],
+        record_size: usize,
+    ) -> Vec<u8> {
+        let sender = p256::SecretKey::from_slice(&[0x11; 32]).expect("key");
+        let sender_public = sender
+            .public_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec();
+        let receiver_public = keys.public_key().expect("public key");
+        let receiver =
+            p256::PublicKey::from_sec1_bytes(&receiver_public).expect("key");
+        let shared = p256::ecdh::diffie_hellman(
+            sender.to_nonzero_scalar(),
+            receiver.as_affine(),
+        );
+        let salt = [0x22; SALT_LEN];
+
+        let key_info = [
+            b"WebPush: info\0".as_slice(),
+            &receiver_public,
+            &sender_public,
+        ]
+        .concat();
+        let ikm = hkdf(
+            &keys.auth().expect("auth"),
+            shared.raw_secret_bytes(),
+            &key_info,
+            32,
+        )
+        .expect("ikm");
+        let cek = hkdf(&salt, &ikm, b"Content-Encoding: aes128gcm\0", 16)
+            .expect("cek");
+        let base_nonce =
+            hkdf(&salt, &ikm, b"Content-Encoding: nonce\0", 12).expect("nonce");
+        let key = aead::LessSafeKey::new(
+            aead::UnboundKey::new(&aead::AES_128_GCM, &cek).expect("cek"),
+        );
+
+        let mut body = salt.to_vec();
+        body.extend((record_size as u32).to_be_bytes());
+        body.push(sender_public.len() as u8);
+        body.extend(&sender_public);
+
+        let chunks = plaintext
+            .chunks(record_size - TAG_LEN - 1)
+            .collect::<Vec<_>>();
+
+        for (sequence, chunk) in chunks.iter().enumerate() {
+            let mut nonce = [0; aead::NONCE_LEN];
+            nonce.copy_from_slice(&base_nonce);
+            nonce[aead::NONCE_LEN - 1] ^= sequence as u8;
+
+            let mut record = chunk.to_vec();
+            record.push(if sequence + 1 == chunks.len() { 2 } else { 1 });
+
+            key.seal_in_place_append_tag(
+                aead::Nonce::assume_unique_for_key(nonce),
+                aead::Aad::empty(),
+                &mut record,
+            )
+            .expect("seal");
+
+            body.extend(record);
+        }
+
+        body
+    }
+
+    /// Sends a push message to an `http://` endpoint, returning the status
+    /// of the response.
+    pub fn push(
+        &self,
+        endpoint: &str,
+        authorization: &str,
+        body: &[u8],
+    ) -> io::Result<u16> {
+        let rest = endpoint
+            .strip_prefix("http://")
+            .ok_or(io::ErrorKind::InvalidInput)?;
+        let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+
+        let mut stream = TcpStream::connect(host)?;
+        write!(
+            stream,
+            "POST /{path} HTTP/1.1\r\nHost: {host}\r\nAuthorization: {authorization}\r\nContent-Encoding: aes128gcm\r\nContent-Length: {}\r\nTTL: 0\r\nConnection: close\r\n\r\n",
+            body.len()
+        )?;
+        stream.write_all(body)?;
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+
+        response
+            .split_whitespace()
+            .nth(1)
+            .and_then(|status| status.parse().ok())
+            .ok_or_else(|| io::ErrorKind::InvalidData.into())
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::Local;
 use data::appearance;
 use futures::StreamExt;
 use futures::stream::BoxStream;
@@ -40,6 +41,10 @@ impl Mode {
                 // This is because Gnome never specifies `Light` and only sends `Unspecified`.
                 Self::Unspecified => light.clone(),
             },
+            appearance::Selected::Scheduled(_) => selected
+                .scheduled(Local::now().time())
+                .cloned()
+                .unwrap_or_default(),
         }
     }
 }
@@ -59,12 +64,119 @@ impl subscription::Recipe for Appearance {
         self: Box<Self>,
         _input: subscription::EventStream,
     ) -> BoxStream<'static, Mode> {
-        Preferences::stream(Interest::ColorScheme)
-            .map(|preference| Mode::from(preference.color_scheme))
-            .boxed()
+        modes()
     }
 }
 
+/// The system appearance, followed by every change to it (through the
+/// `org.freedesktop.portal.Settings` portal on Linux).
+fn modes() -> BoxStream<'static, Mode> {
+    Preferences::stream(Interest::ColorScheme)
+        .map(|preference| Mode::from(preference.color_scheme))
+        .boxed()
+}
+
 pub fn subscription() -> Subscription<Mode> {
     subscription::from_recipe(Appearance)
 }
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::io::{BufRead, BufReader};
+    use std::process::{Command, Stdio};
+    use std::time::Duration;
+
+    use zbus::zvariant::{OwnedValue, Value};
+
+    use super::*;
+
+    const APPEARANCE: &str = "org.freedesktop.appearance";
+    const COLOR_SCHEME: &str = "color-scheme";
+    const PATH: &str = "/org/freedesktop/portal/desktop";
+
+    /// Stand-in for the settings interface of xdg-desktop-portal.
+    struct Settings {
+        color_scheme: u32,
+    }
+
+    #[zbus::interface(name = "org.freedesktop.portal.Settings")]
+    impl Settings {
+        fn read(
+            &self,
+            namespace: &str,
+            key: &str,
+        ) -> zbus::fdo::Result<OwnedValue> {
+            if (namespace, key) == (APPEARANCE, COLOR_SCHEME) {
+                Ok(OwnedValue::from(self.color_scheme))
+            } else {
+                Err(zbus::fdo::Error::UnknownProperty(key.to_string()))
+            }
+        }
+    }
+
+    #[test]
+    fn follows_portal_color_scheme() {
+        // Private session bus, so the test doesn't depend on (or change)
+        // the desktop it runs in
+        let Ok(mut bus) = Command::new("dbus-daemon")
+            .args(["--session", "--nofork", "--print-address"])
+            .stdout(Stdio::piped())
+            .spawn()
+        else {
+            eprintln!("skipping, dbus-daemon is not available");
+            return;
+        };
+
+        let mut address = String::new();
+        BufReader::new(bus.stdout.take().unwrap())
+            .read_line(&mut address)
+            .unwrap();
+
+        // SAFETY: no other test reads or writes the environment
+        unsafe {
+            std::env::set_var("DBUS_SESSION_BUS_ADDRESS", address.trim());
+        }
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        runtime.block_on(async {
+            let portal = zbus::connection::Builder::address(address.trim())
+                .unwrap()
+                .name("org.freedesktop.portal.Desktop")
+                .unwrap()
+                .serve_at(PATH, Settings { color_scheme: 1 })
+                .unwrap()
+                .build()
+                .await
+                .unwrap();
+
+            let mut modes = modes();
+            let mut next = async || {
+                tokio::time::timeout(Duration::from_secs(5), modes.next())
+                    .await
+                    .unwrap()
+            };
+
+            assert_eq!(next().await, Some(Mode::Dark));
+
+            portal
+                .emit_signal(
+                    None::<()>,
+                    PATH,
+                    "org.freedesktop.portal.Settings",
+                    "SettingChanged",
+                    &(APPEARANCE, COLOR_SCHEME, Value::from(2u32)),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(next().await, Some(Mode::Light));
+        });
+
+        bus.kill().unwrap();
+        bus.wait().unwrap();
+    }
+}
@@ -1,6 +1,7 @@
 pub use data::appearance::theme::{
-    Buffer, Button, Buttons, Formatting, General, ServerMessages, Styles, Text,
-    color_to_hex, hex_to_color,
+    Buffer, Button, Buttons, Formatting, General, MINIMUM_CONTRAST,
+    ServerMessages, Styles, Text, color_to_hex, contrast_ratio, fix_contrast,
+    hex_to_color,
 };
 use data::config;
 use iced::widget::text::LineHeight;
@@ -58,6 +59,17 @@ impl Theme {
         }
     }
 
+    /// Replaces the selected theme, keeping any preview.
+    pub fn select(&mut self, theme: data::Theme) {
+        match self {
+            Theme::Selected(selected) | Theme::Preview { selected, .. } => {
+                if selected.name != theme.name {
+                    *selected = theme;
+                }
+            }
+        }
+    }
+
     pub fn styles(&self) -> &Styles {
         match self {
             Theme::Selected(selected) => &selected.styles,
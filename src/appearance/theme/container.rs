@@ -47,6 +47,17 @@ pub fn buffer(theme: &Theme, selected: bool) -> Style {
     }
 }
 
+pub fn sidebar_drop_target(theme: &Theme) -> Style {
+    Style {
+        border: Border {
+            radius: 4.0.into(),
+            width: 1.0,
+            color: theme.styles().buffer.border_selected,
+        },
+        ..Default::default()
+    }
+}
+
 pub fn buffer_title_bar(theme: &Theme) -> Style {
     let styles = theme.styles().buffer;
 
@@ -38,6 +38,8 @@ pub fn server(
             Kind::Join => styles.join.font_style,
             Kind::Part => styles.part.font_style,
             Kind::Quit => styles.quit.font_style,
+            Kind::Netsplit => styles.quit.font_style,
+            Kind::Netjoin => styles.join.font_style,
             Kind::JoinTopic => styles.join_topic.font_style,
             Kind::RequestTopic => styles.request_topic.font_style,
             Kind::ChangeHost => styles.change_host.font_style,
@@ -56,7 +58,7 @@ pub fn server(
             Kind::StandardReply(StandardReply::Note) => {
                 styles.standard_reply_note.font_style
             }
-            Kind::WAllOps => styles.wallops.font_style,
+            Kind::WAllOps | Kind::ServerNotice(_) => styles.wallops.font_style,
             Kind::Kick => styles.kick.font_style,
             Kind::ChangeTopic => styles.change_topic.font_style,
             Kind::Away => styles.away.font_style,
@@ -97,6 +97,8 @@ pub fn server(
             Kind::Join => styles.join.color,
             Kind::Part => styles.part.color,
             Kind::Quit => styles.quit.color,
+            Kind::Netsplit => styles.quit.color,
+            Kind::Netjoin => styles.join.color,
             Kind::JoinTopic => styles.join_topic.color,
             Kind::RequestTopic => styles.request_topic.color,
             Kind::ChangeHost => styles.change_host.color,
@@ -117,7 +119,7 @@ pub fn server(
             Kind::StandardReply(StandardReply::Note) => {
                 styles.standard_reply_note.color
             }
-            Kind::WAllOps => styles.wallops.color,
+            Kind::WAllOps | Kind::ServerNotice(_) => styles.wallops.color,
             Kind::Kick => styles.kick.color,
             Kind::Away => styles.away.color,
             Kind::Invite => styles.invite.color,
@@ -170,7 +172,7 @@ fn nickname_style(
     theme: &Theme,
     kind: &data::buffer::Color,
     user: &User,
-    is_away: Option<buffer::Away>,
+    is_away: Option<buffer::AwayAppearance>,
     is_offline: bool,
 ) -> Style {
     let color =
@@ -1,14 +1,17 @@
 use std::borrow::Cow;
 use std::fmt;
+use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 pub use data::buffer::{Internal, Settings, Upstream};
 use data::config::buffer::text_input::Autocomplete;
 use data::dashboard::BufferAction;
+use data::spell_check::Checker;
 use data::target::{self, Target};
 use data::user::Nick;
 use data::{
     Config, Image, buffer, file_transfer, history, input, message, preview,
+    schedule,
 };
 use iced::{Size, Task};
 
@@ -18,7 +21,9 @@ pub use self::file_transfers::FileTransfers;
 pub use self::highlights::Highlights;
 pub use self::logs::Logs;
 pub use self::query::Query;
+pub use self::scheduled_messages::ScheduledMessages;
 pub use self::server::Server;
+pub use self::server_notices::ServerNotices;
 use crate::Theme;
 use crate::screen::dashboard::sidebar;
 use crate::widget::Element;
@@ -33,9 +38,12 @@ pub mod highlights;
 mod input_view;
 pub mod logs;
 mod message_view;
+mod outbox;
 pub mod query;
+pub mod scheduled_messages;
 mod scroll_view;
 pub mod server;
+pub mod server_notices;
 pub mod typing;
 
 #[derive(Clone, Debug)]
@@ -45,9 +53,11 @@ pub enum Buffer {
     Server(Server),
     Query(Query),
     FileTransfers(FileTransfers),
+    ScheduledMessages(ScheduledMessages),
     Logs(Logs),
     Highlights(Highlights),
     ChannelDiscovery(ChannelDiscovery),
+    ServerNotices(ServerNotices),
 }
 
 #[derive(Debug, Clone)]
@@ -56,9 +66,11 @@ pub enum Message {
     Server(server::Message),
     Query(query::Message),
     FileTransfers(file_transfers::Message),
+    ScheduledMessages(scheduled_messages::Message),
     Logs(logs::Message),
     Highlights(highlights::Message),
     ChannelList(channel_discovery::Message),
+    ServerNotices(server_notices::Message),
 }
 
 pub enum Event {
@@ -66,6 +78,11 @@ pub enum Event {
     OpenBuffers(data::Server, Vec<(Target, BufferAction)>),
     OpenInternalBuffer(buffer::Internal),
     OpenServer(String),
+    Workspace(data::command::Workspace),
+    Schedule(Upstream, String, DateTime<Utc>),
+    CancelScheduled(schedule::Id),
+    Queue(Upstream, Vec<String>),
+    CancelQueued(data::outbox::Id),
     Reconnect(data::Server),
     LeaveBuffers(Vec<Target>, Option<String>),
     SelectedServer(data::Server),
@@ -76,7 +93,7 @@ pub enum Event {
     HidePreview(history::Kind, message::Hash, url::Url),
     MarkAsRead(history::Kind),
     OpenUrl(String),
-    ImagePreview(Image),
+    ImagePreview(Image, Vec<url::Url>),
     ExpandMessage(DateTime<Utc>, message::Hash),
     ContractMessage(DateTime<Utc>, message::Hash),
     InputSent {
@@ -121,6 +138,9 @@ impl Buffer {
                 buffer::Internal::FileTransfers => {
                     Self::FileTransfers(FileTransfers::new())
                 }
+                buffer::Internal::ScheduledMessages => {
+                    Self::ScheduledMessages(ScheduledMessages::new())
+                }
                 buffer::Internal::Logs => {
                     Self::Logs(Logs::new(pane_size, config))
                 }
@@ -130,6 +150,9 @@ impl Buffer {
                 buffer::Internal::ChannelDiscovery(server) => {
                     Self::ChannelDiscovery(ChannelDiscovery::new(server))
                 }
+                buffer::Internal::ServerNotices(server) => Self::ServerNotices(
+                    ServerNotices::new(server, pane_size, config),
+                ),
             },
         }
     }
@@ -144,9 +167,11 @@ impl Buffer {
             Buffer::Query(state) => Some(&state.buffer),
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
-            | Buffer::ChannelDiscovery(_) => None,
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::ServerNotices(_) => None,
         }
     }
 
@@ -157,11 +182,17 @@ impl Buffer {
             | Buffer::Server(_)
             | Buffer::Query(_) => None,
             Buffer::FileTransfers(_) => Some(buffer::Internal::FileTransfers),
+            Buffer::ScheduledMessages(_) => {
+                Some(buffer::Internal::ScheduledMessages)
+            }
             Buffer::Logs(_) => Some(buffer::Internal::Logs),
             Buffer::Highlights(_) => Some(buffer::Internal::Highlights),
             Buffer::ChannelDiscovery(state) => {
                 Some(buffer::Internal::ChannelDiscovery(state.server.clone()))
             }
+            Buffer::ServerNotices(state) => {
+                Some(buffer::Internal::ServerNotices(state.server.clone()))
+            }
         }
     }
 
@@ -180,6 +211,9 @@ impl Buffer {
             Buffer::FileTransfers(_) => {
                 Some(data::Buffer::Internal(buffer::Internal::FileTransfers))
             }
+            Buffer::ScheduledMessages(_) => Some(data::Buffer::Internal(
+                buffer::Internal::ScheduledMessages,
+            )),
             Buffer::Logs(_) => {
                 Some(data::Buffer::Internal(buffer::Internal::Logs))
             }
@@ -189,6 +223,9 @@ impl Buffer {
             Buffer::ChannelDiscovery(state) => Some(data::Buffer::Internal(
                 buffer::Internal::ChannelDiscovery(state.server.clone()),
             )),
+            Buffer::ServerNotices(state) => Some(data::Buffer::Internal(
+                buffer::Internal::ServerNotices(state.server.clone()),
+            )),
         }
     }
 
@@ -199,9 +236,11 @@ impl Buffer {
             Buffer::Server(state) => Some(state.server.clone()),
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
-            | Buffer::ChannelDiscovery(_) => None,
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::ServerNotices(_) => None,
         }
     }
 
@@ -214,9 +253,11 @@ impl Buffer {
             Buffer::Empty
             | Buffer::Server(_)
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
-            | Buffer::ChannelDiscovery(_) => None,
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::ServerNotices(_) => None,
         }
     }
 
@@ -244,9 +285,11 @@ impl Buffer {
             Buffer::Empty
             | Buffer::Server(_)
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
-            | Buffer::ChannelDiscovery(_) => None,
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::ServerNotices(_) => None,
         }
     }
 
@@ -258,6 +301,7 @@ impl Buffer {
         file_transfers: &mut file_transfer::Manager,
         main_window: &Window,
         config: &Config,
+        spell_checker: Option<&Arc<Checker>>,
     ) -> (Task<Message>, Option<Event>) {
         match (self, message) {
             (Buffer::Channel(state), Message::Channel(message)) => {
@@ -267,6 +311,7 @@ impl Buffer {
                     history,
                     main_window,
                     config,
+                    spell_checker,
                 );
 
                 let event = event.map(|event| match event {
@@ -282,6 +327,16 @@ impl Buffer {
                     channel::Event::OpenServer(server) => {
                         Event::OpenServer(server)
                     }
+                    channel::Event::Workspace(workspace) => {
+                        Event::Workspace(workspace)
+                    }
+                    channel::Event::Schedule(buffer, text, at) => {
+                        Event::Schedule(buffer, text, at)
+                    }
+                    channel::Event::Queue(buffer, lines) => {
+                        Event::Queue(buffer, lines)
+                    }
+                    channel::Event::CancelQueued(id) => Event::CancelQueued(id),
                     channel::Event::Reconnect(server) => {
                         Event::Reconnect(server)
                     }
@@ -298,8 +353,8 @@ impl Buffer {
                     }
                     channel::Event::MarkAsRead(kind) => Event::MarkAsRead(kind),
                     channel::Event::OpenUrl(url) => Event::OpenUrl(url),
-                    channel::Event::ImagePreview(image) => {
-                        Event::ImagePreview(image)
+                    channel::Event::ImagePreview(image, urls) => {
+                        Event::ImagePreview(image, urls)
                     }
                     channel::Event::ExpandMessage(server_time, hash) => {
                         Event::ExpandMessage(server_time, hash)
@@ -341,6 +396,7 @@ impl Buffer {
                     history,
                     main_window,
                     config,
+                    spell_checker,
                 );
 
                 let event = event.map(|event| match event {
@@ -353,6 +409,16 @@ impl Buffer {
                     server::Event::OpenServer(server) => {
                         Event::OpenServer(server)
                     }
+                    server::Event::Workspace(workspace) => {
+                        Event::Workspace(workspace)
+                    }
+                    server::Event::Schedule(buffer, text, at) => {
+                        Event::Schedule(buffer, text, at)
+                    }
+                    server::Event::Queue(buffer, lines) => {
+                        Event::Queue(buffer, lines)
+                    }
+                    server::Event::CancelQueued(id) => Event::CancelQueued(id),
                     server::Event::Reconnect(server) => {
                         Event::Reconnect(server)
                     }
@@ -365,8 +431,8 @@ impl Buffer {
                     server::Event::History(task) => Event::History(task),
                     server::Event::MarkAsRead(kind) => Event::MarkAsRead(kind),
                     server::Event::OpenUrl(url) => Event::OpenUrl(url),
-                    server::Event::ImagePreview(image) => {
-                        Event::ImagePreview(image)
+                    server::Event::ImagePreview(image, urls) => {
+                        Event::ImagePreview(image, urls)
                     }
                     server::Event::ExpandMessage(server_time, hash) => {
                         Event::ExpandMessage(server_time, hash)
@@ -405,6 +471,7 @@ impl Buffer {
                     history,
                     main_window,
                     config,
+                    spell_checker,
                 );
 
                 let event = event.map(|event| match event {
@@ -420,6 +487,16 @@ impl Buffer {
                     query::Event::OpenServer(server) => {
                         Event::OpenServer(server)
                     }
+                    query::Event::Workspace(workspace) => {
+                        Event::Workspace(workspace)
+                    }
+                    query::Event::Schedule(buffer, text, at) => {
+                        Event::Schedule(buffer, text, at)
+                    }
+                    query::Event::Queue(buffer, lines) => {
+                        Event::Queue(buffer, lines)
+                    }
+                    query::Event::CancelQueued(id) => Event::CancelQueued(id),
                     query::Event::Reconnect(server) => Event::Reconnect(server),
                     query::Event::LeaveBuffers(targets, reason) => {
                         Event::LeaveBuffers(targets, reason)
@@ -434,8 +511,8 @@ impl Buffer {
                     }
                     query::Event::MarkAsRead(kind) => Event::MarkAsRead(kind),
                     query::Event::OpenUrl(url) => Event::OpenUrl(url),
-                    query::Event::ImagePreview(image) => {
-                        Event::ImagePreview(image)
+                    query::Event::ImagePreview(image, urls) => {
+                        Event::ImagePreview(image, urls)
                     }
                     query::Event::ExpandMessage(server_time, hash) => {
                         Event::ExpandMessage(server_time, hash)
@@ -472,6 +549,18 @@ impl Buffer {
 
                 (command.map(Message::FileTransfers), None)
             }
+            (
+                Buffer::ScheduledMessages(state),
+                Message::ScheduledMessages(message),
+            ) => {
+                let event = state.update(message).map(|event| match event {
+                    scheduled_messages::Event::Cancel(id) => {
+                        Event::CancelScheduled(id)
+                    }
+                });
+
+                (Task::none(), event)
+            }
             (
                 Buffer::ChannelDiscovery(state),
                 Message::ChannelList(message),
@@ -518,8 +607,8 @@ impl Buffer {
                         Event::MarkAsRead(history::Kind::Logs)
                     }
                     logs::Event::OpenUrl(url) => Event::OpenUrl(url),
-                    logs::Event::ImagePreview(image) => {
-                        Event::ImagePreview(image)
+                    logs::Event::ImagePreview(image, urls) => {
+                        Event::ImagePreview(image, urls)
                     }
                     logs::Event::ExpandMessage(server_time, hash) => {
                         Event::ExpandMessage(server_time, hash)
@@ -557,8 +646,8 @@ impl Buffer {
                         Event::MarkAsRead(history::Kind::Highlights)
                     }
                     highlights::Event::OpenUrl(url) => Event::OpenUrl(url),
-                    highlights::Event::ImagePreview(image) => {
-                        Event::ImagePreview(image)
+                    highlights::Event::ImagePreview(image, urls) => {
+                        Event::ImagePreview(image, urls)
                     }
                     highlights::Event::ExpandMessage(server_time, hash) => {
                         Event::ExpandMessage(server_time, hash)
@@ -570,6 +659,32 @@ impl Buffer {
 
                 (command.map(Message::Highlights), event)
             }
+            (Buffer::ServerNotices(state), Message::ServerNotices(message)) => {
+                let (command, event) =
+                    state.update(message, history, clients, config);
+
+                let event = event.map(|event| match event {
+                    server_notices::Event::ContextMenu(event) => {
+                        Event::ContextMenu(event)
+                    }
+                    server_notices::Event::MarkAsRead => Event::MarkAsRead(
+                        history::Kind::ServerNotices(state.server.clone()),
+                    ),
+                    server_notices::Event::OpenUrl(url) => Event::OpenUrl(url),
+                    server_notices::Event::ImagePreview(image, urls) => {
+                        Event::ImagePreview(image, urls)
+                    }
+                    server_notices::Event::ExpandMessage(server_time, hash) => {
+                        Event::ExpandMessage(server_time, hash)
+                    }
+                    server_notices::Event::ContractMessage(
+                        server_time,
+                        hash,
+                    ) => Event::ContractMessage(server_time, hash),
+                });
+
+                (command.map(Message::ServerNotices), event)
+            }
             _ => (Task::none(), None),
         }
     }
@@ -579,6 +694,8 @@ impl Buffer {
         typing_animation: Option<&'a typing::Animation>,
         clients: &'a data::client::Map,
         file_transfers: &'a file_transfer::Manager,
+        scheduled: &'a schedule::Scheduled,
+        outbox: &'a data::outbox::Outbox,
         history: &'a history::Manager,
         previews: &'a preview::Collection,
         settings: Option<&'a buffer::Settings>,
@@ -586,6 +703,7 @@ impl Buffer {
         theme: &'a Theme,
         is_focused: bool,
         sidebar: &'a sidebar::Sidebar,
+        spell_checker: Option<&'a Arc<Checker>>,
     ) -> Element<'a, Message> {
         match self {
             Buffer::Empty => empty::view(config, sidebar),
@@ -594,15 +712,25 @@ impl Buffer {
                 typing_animation,
                 clients,
                 history,
+                outbox,
                 previews,
                 settings,
                 config,
                 theme,
                 is_focused,
+                spell_checker,
             )
             .map(Message::Channel),
             Buffer::Server(state) => server::view(
-                state, clients, history, previews, config, theme, is_focused,
+                state,
+                clients,
+                history,
+                outbox,
+                previews,
+                config,
+                theme,
+                is_focused,
+                spell_checker,
             )
             .map(Message::Server),
             Buffer::Query(state) => query::view(
@@ -610,16 +738,22 @@ impl Buffer {
                 typing_animation,
                 clients,
                 history,
+                outbox,
                 previews,
                 config,
                 theme,
                 is_focused,
+                spell_checker,
             )
             .map(Message::Query),
             Buffer::FileTransfers(state) => {
                 file_transfers::view(state, file_transfers, theme)
                     .map(Message::FileTransfers)
             }
+            Buffer::ScheduledMessages(state) => {
+                scheduled_messages::view(state, scheduled, clients, theme)
+                    .map(Message::ScheduledMessages)
+            }
             Buffer::Logs(state) => {
                 logs::view(state, history, config, theme).map(Message::Logs)
             }
@@ -631,6 +765,10 @@ impl Buffer {
                 channel_discovery::view(state, clients, config, theme)
                     .map(Message::ChannelList)
             }
+            Buffer::ServerNotices(state) => {
+                server_notices::view(state, clients, history, config, theme)
+                    .map(Message::ServerNotices)
+            }
         }
     }
 
@@ -641,9 +779,11 @@ impl Buffer {
             Buffer::Empty
             | Buffer::Server(_)
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
-            | Buffer::ChannelDiscovery(_) => false,
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::ServerNotices(_) => false,
         }
     }
 
@@ -676,8 +816,10 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::Logs(_)
-            | Buffer::Highlights(_) => Task::none(),
+            | Buffer::Highlights(_)
+            | Buffer::ServerNotices(_) => Task::none(),
             Buffer::Channel(channel) => channel.focus().map(Message::Channel),
             Buffer::Server(server) => server.focus().map(Message::Server),
             Buffer::Query(query) => query.focus().map(Message::Query),
@@ -691,9 +833,11 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
-            | Buffer::ChannelDiscovery(_) => {}
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::ServerNotices(_) => {}
             Buffer::Channel(channel) => channel.reset(),
             Buffer::Server(server) => server.reset(),
             Buffer::Query(query) => query.reset(),
@@ -709,9 +853,11 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
-            | Buffer::ChannelDiscovery(_) => (),
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::ServerNotices(_) => (),
             Buffer::Server(state) => state.input_view.insert_user(
                 nick,
                 state.buffer.clone(),
@@ -733,6 +879,36 @@ impl Buffer {
         }
     }
 
+    pub fn search_input_history(
+        &mut self,
+        history: &history::Manager,
+    ) -> Task<Message> {
+        match self {
+            Buffer::Empty
+            | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
+            | Buffer::Logs(_)
+            | Buffer::Highlights(_)
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::ServerNotices(_) => Task::none(),
+            Buffer::Server(state) => {
+                state.input_view.search_history(history).map(|message| {
+                    Message::Server(server::Message::InputView(message))
+                })
+            }
+            Buffer::Channel(state) => {
+                state.input_view.search_history(history).map(|message| {
+                    Message::Channel(channel::Message::InputView(message))
+                })
+            }
+            Buffer::Query(state) => {
+                state.input_view.search_history(history).map(|message| {
+                    Message::Query(query::Message::InputView(message))
+                })
+            }
+        }
+    }
+
     pub fn process_input_completion_and_notice(
         &mut self,
         clients: &data::client::Map,
@@ -742,9 +918,11 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
-            | Buffer::ChannelDiscovery(_) => (),
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::ServerNotices(_) => (),
             Buffer::Server(state) => {
                 state.input_view.process_completion_and_notice(
                     &state.buffer,
@@ -776,6 +954,7 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::ChannelDiscovery(_) => Task::none(),
             Buffer::Channel(channel) => {
                 channel.scroll_view.scroll_up_page().map(|message| {
@@ -804,6 +983,13 @@ impl Buffer {
                     ))
                 })
             }
+            Buffer::ServerNotices(state) => {
+                state.scroll_view.scroll_up_page().map(|message| {
+                    Message::ServerNotices(server_notices::Message::ScrollView(
+                        message,
+                    ))
+                })
+            }
         }
     }
 
@@ -811,6 +997,7 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::ChannelDiscovery(_) => Task::none(),
             Buffer::Channel(channel) => {
                 channel.scroll_view.scroll_down_page().map(|message| {
@@ -839,6 +1026,13 @@ impl Buffer {
                     ))
                 })
             }
+            Buffer::ServerNotices(state) => {
+                state.scroll_view.scroll_down_page().map(|message| {
+                    Message::ServerNotices(server_notices::Message::ScrollView(
+                        message,
+                    ))
+                })
+            }
         }
     }
 
@@ -846,6 +1040,7 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::ChannelDiscovery(_) => Task::none(),
             Buffer::Channel(channel) => {
                 channel.scroll_view.scroll_to_start(config).map(|message| {
@@ -875,6 +1070,13 @@ impl Buffer {
                         message,
                     ))
                 }),
+            Buffer::ServerNotices(state) => {
+                state.scroll_view.scroll_to_start(config).map(|message| {
+                    Message::ServerNotices(server_notices::Message::ScrollView(
+                        message,
+                    ))
+                })
+            }
         }
     }
 
@@ -882,6 +1084,7 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::ChannelDiscovery(_) => Task::none(),
             Buffer::Channel(channel) => {
                 channel.scroll_view.scroll_to_end(config).map(|message| {
@@ -910,6 +1113,13 @@ impl Buffer {
                     ))
                 })
             }
+            Buffer::ServerNotices(state) => {
+                state.scroll_view.scroll_to_end(config).map(|message| {
+                    Message::ServerNotices(server_notices::Message::ScrollView(
+                        message,
+                    ))
+                })
+            }
         }
     }
 
@@ -922,6 +1132,7 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::ChannelDiscovery(_) => Task::none(),
             Buffer::Channel(state) => state
                 .scroll_view
@@ -980,6 +1191,85 @@ impl Buffer {
                         message,
                     ))
                 }),
+            Buffer::ServerNotices(state) => state
+                .scroll_view
+                .scroll_to_message(
+                    message,
+                    scroll_view::Kind::ServerNotices(&state.server),
+                    history,
+                    config,
+                )
+                .map(|message| {
+                    Message::ServerNotices(server_notices::Message::ScrollView(
+                        message,
+                    ))
+                }),
+        }
+    }
+
+    /// Opens the find bar of buffers showing messages.
+    pub fn find(
+        &mut self,
+        history: &history::Manager,
+        config: &Config,
+    ) -> Task<Message> {
+        match self {
+            Buffer::Empty
+            | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
+            | Buffer::ChannelDiscovery(_) => Task::none(),
+            Buffer::Channel(state) => state
+                .scroll_view
+                .find(
+                    scroll_view::Kind::Channel(&state.server, &state.target),
+                    history,
+                    config,
+                )
+                .map(|message| {
+                    Message::Channel(channel::Message::ScrollView(message))
+                }),
+            Buffer::Server(state) => state
+                .scroll_view
+                .find(scroll_view::Kind::Server(&state.server), history, config)
+                .map(|message| {
+                    Message::Server(server::Message::ScrollView(message))
+                }),
+            Buffer::Query(state) => state
+                .scroll_view
+                .find(
+                    scroll_view::Kind::Query(&state.server, &state.target),
+                    history,
+                    config,
+                )
+                .map(|message| {
+                    Message::Query(query::Message::ScrollView(message))
+                }),
+            Buffer::Logs(state) => state
+                .scroll_view
+                .find(scroll_view::Kind::Logs, history, config)
+                .map(|message| {
+                    Message::Logs(logs::Message::ScrollView(message))
+                }),
+            Buffer::Highlights(state) => state
+                .scroll_view
+                .find(scroll_view::Kind::Highlights, history, config)
+                .map(|message| {
+                    Message::Highlights(highlights::Message::ScrollView(
+                        message,
+                    ))
+                }),
+            Buffer::ServerNotices(state) => state
+                .scroll_view
+                .find(
+                    scroll_view::Kind::ServerNotices(&state.server),
+                    history,
+                    config,
+                )
+                .map(|message| {
+                    Message::ServerNotices(server_notices::Message::ScrollView(
+                        message,
+                    ))
+                }),
         }
     }
 
@@ -991,6 +1281,7 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::ChannelDiscovery(_) => Task::none(),
             Buffer::Channel(state) => state
                 .scroll_view
@@ -1040,6 +1331,18 @@ impl Buffer {
                         message,
                     ))
                 }),
+            Buffer::ServerNotices(state) => state
+                .scroll_view
+                .scroll_to_backlog(
+                    scroll_view::Kind::ServerNotices(&state.server),
+                    history,
+                    config,
+                )
+                .map(|message| {
+                    Message::ServerNotices(server_notices::Message::ScrollView(
+                        message,
+                    ))
+                }),
         }
     }
 
@@ -1047,6 +1350,7 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::ChannelDiscovery(_) => false,
             Buffer::Channel(state) => state.scroll_view.has_pending_scroll_to(),
             Buffer::Server(state) => state.scroll_view.has_pending_scroll_to(),
@@ -1055,6 +1359,9 @@ impl Buffer {
             Buffer::Highlights(state) => {
                 state.scroll_view.has_pending_scroll_to()
             }
+            Buffer::ServerNotices(state) => {
+                state.scroll_view.has_pending_scroll_to()
+            }
         }
     }
 
@@ -1066,6 +1373,7 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::ChannelDiscovery(_) => Task::none(),
             Buffer::Channel(state) => state
                 .scroll_view
@@ -1119,6 +1427,18 @@ impl Buffer {
                         message,
                     ))
                 }),
+            Buffer::ServerNotices(state) => state
+                .scroll_view
+                .prepare_for_pending_scroll_to(
+                    scroll_view::Kind::ServerNotices(&state.server),
+                    history,
+                    config,
+                )
+                .map(|message| {
+                    Message::ServerNotices(server_notices::Message::ScrollView(
+                        message,
+                    ))
+                }),
         }
     }
 
@@ -1126,6 +1446,7 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::ChannelDiscovery(_) => None,
             Buffer::Channel(channel) => {
                 Some(channel.scroll_view.is_scrolled_to_bottom())
@@ -1140,6 +1461,9 @@ impl Buffer {
             Buffer::Highlights(highlights) => {
                 Some(highlights.scroll_view.is_scrolled_to_bottom())
             }
+            Buffer::ServerNotices(state) => {
+                Some(state.scroll_view.is_scrolled_to_bottom())
+            }
         }
     }
 
@@ -1147,9 +1471,11 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
-            | Buffer::ChannelDiscovery(_) => false,
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::ServerNotices(_) => false,
             Buffer::Server(state) => state.input_view.close_picker(),
             Buffer::Channel(state) => state.input_view.close_picker(),
             Buffer::Query(state) => state.input_view.close_picker(),
@@ -1164,9 +1490,11 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
-            | Buffer::ChannelDiscovery(_) => false,
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::ServerNotices(_) => false,
             Buffer::Server(state) => state.input_view.clear_draft_reply(
                 &state.buffer,
                 history,
@@ -1189,6 +1517,7 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::ChannelDiscovery(_) => (),
             Buffer::Channel(channel) => {
                 channel.scroll_view.update_pane_size(pane_size, config);
@@ -1205,6 +1534,9 @@ impl Buffer {
             Buffer::Highlights(highlights) => {
                 highlights.scroll_view.update_pane_size(pane_size, config);
             }
+            Buffer::ServerNotices(state) => {
+                state.scroll_view.update_pane_size(pane_size, config);
+            }
         }
     }
 
@@ -1212,9 +1544,11 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
-            | Buffer::ChannelDiscovery(_) => None,
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::ServerNotices(_) => None,
             Buffer::Server(state) => state.input_view.draft_reply(),
             Buffer::Channel(state) => state.input_view.draft_reply(),
             Buffer::Query(state) => state.input_view.draft_reply(),
@@ -1225,9 +1559,11 @@ impl Buffer {
         match self {
             Buffer::Empty
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
-            | Buffer::ChannelDiscovery(_) => (),
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::ServerNotices(_) => (),
             Buffer::Server(state) => {
                 state.input_view.set_reply_preview(reply_preview);
             }
@@ -1249,9 +1585,11 @@ impl fmt::Display for Buffer {
             Buffer::Server(Server { server, .. }) => write!(f, "{server}"),
             Buffer::Query(Query { target, .. }) => write!(f, "{target}"),
             Buffer::FileTransfers(_) => write!(f, "File Transfers"),
+            Buffer::ScheduledMessages(_) => write!(f, "Scheduled Messages"),
             Buffer::Logs(_) => write!(f, "Logs"),
             Buffer::Highlights(_) => write!(f, "Highlights"),
             Buffer::ChannelDiscovery(_) => write!(f, "Channel Discovery"),
+            Buffer::ServerNotices(_) => write!(f, "Server Notices"),
         }
     }
 }
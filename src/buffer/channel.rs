@@ -1,8 +1,11 @@
+use std::sync::Arc;
+
 use chrono::{DateTime, Utc};
 use data::dashboard::BufferAction;
 use data::history::filter::FilterChain;
 use data::preview::{self, Previews};
 use data::server::Server;
+use data::spell_check::Checker;
 use data::target::{self, Target};
 use data::user::{ChannelUsers, Nick};
 use data::{Config, Image, Preview, User, buffer, client, history, message};
@@ -10,7 +13,7 @@ use iced::widget::{column, container, row, stack};
 use iced::{Length, Size, Task, padding};
 
 use super::message_view::{ChannelQueryLayout, TargetInfo};
-use super::{context_menu, input_view, scroll_view, typing};
+use super::{context_menu, input_view, outbox, scroll_view, typing};
 use crate::Theme;
 use crate::widget::Element;
 use crate::window::Window;
@@ -21,10 +24,12 @@ mod topic;
 pub enum Message {
     ScrollView(scroll_view::Message),
     InputView(input_view::Message),
+    Outbox(outbox::Message),
     ContextMenu(context_menu::Message),
     Topic(topic::Message),
     FilehostUploadDone { id: u32, url: Option<String> },
     FilesDropped(Vec<std::path::PathBuf>),
+    Run(String),
 }
 
 pub enum Event {
@@ -32,6 +37,10 @@ pub enum Event {
     OpenBuffers(Server, Vec<(Target, BufferAction)>),
     OpenInternalBuffer(buffer::Internal),
     OpenServer(String),
+    Workspace(data::command::Workspace),
+    Schedule(buffer::Upstream, String, DateTime<Utc>),
+    Queue(buffer::Upstream, Vec<String>),
+    CancelQueued(data::outbox::Id),
     Reconnect(Server),
     LeaveBuffers(Vec<Target>, Option<String>),
     History(Task<history::manager::Message>),
@@ -40,7 +49,7 @@ pub enum Event {
     HidePreview(history::Kind, message::Hash, url::Url),
     MarkAsRead(history::Kind),
     OpenUrl(String),
-    ImagePreview(Image),
+    ImagePreview(Image, Vec<url::Url>),
     ExpandMessage(DateTime<Utc>, message::Hash),
     ContractMessage(DateTime<Utc>, message::Hash),
     GoToMessage(Server, target::Channel, message::Hash),
@@ -62,11 +71,13 @@ pub fn view<'a>(
     typing_animation: Option<&'a typing::Animation>,
     clients: &'a data::client::Map,
     history: &'a history::Manager,
+    outbox: &'a data::outbox::Outbox,
     previews: &'a preview::Collection,
     settings: Option<&'a buffer::Settings>,
     config: &'a Config,
     theme: &'a Theme,
     is_focused: bool,
+    spell_checker: Option<&'a Arc<Checker>>,
 ) -> Element<'a, Message> {
     let server = &state.server;
     let connected = matches!(clients.status(server), client::Status::Connected);
@@ -207,15 +218,20 @@ pub fn view<'a>(
             &state.input_view,
             our_user,
             users,
+            None,
             &state.server,
             registry,
             config,
             theme,
             filehost_url,
+            spell_checker,
         )
         .map(Message::InputView)
     });
 
+    let pending = outbox::view(outbox.pending(&state.buffer), config, theme)
+        .map(|pending| pending.map(Message::Outbox));
+
     let content = column![topic, messages];
 
     let nicklist_enabled = settings
@@ -243,12 +259,13 @@ pub fn view<'a>(
 
             column![
                 stack![content, typing_overlay].height(Length::Fill),
+                pending,
                 text_input
             ]
             .height(Length::Fill)
             .into()
         } else {
-            column![column![content].height(Length::Fill), text_input]
+            column![column![content].height(Length::Fill), pending, text_input]
                 .height(Length::Fill)
                 .into()
         };
@@ -301,6 +318,7 @@ impl Channel {
         history: &mut history::Manager,
         main_window: &Window,
         config: &Config,
+        spell_checker: Option<&Arc<Checker>>,
     ) -> (Task<Message>, Option<Event>) {
         match message {
             Message::ScrollView(message) => {
@@ -333,6 +351,7 @@ impl Channel {
                         history,
                         main_window,
                         config,
+                        spell_checker,
                     );
 
                     return (
@@ -381,8 +400,8 @@ impl Channel {
                     scroll_view::Event::OpenUrl(url) => {
                         Some(Event::OpenUrl(url))
                     }
-                    scroll_view::Event::ImagePreview(image) => {
-                        Some(Event::ImagePreview(image))
+                    scroll_view::Event::ImagePreview(image, urls) => {
+                        Some(Event::ImagePreview(image, urls))
                     }
                     scroll_view::Event::ExpandMessage(server_time, hash) => {
                         Some(Event::ExpandMessage(server_time, hash))
@@ -402,6 +421,7 @@ impl Channel {
                     history,
                     main_window,
                     config,
+                    spell_checker,
                 );
                 let command = command.map(Message::InputView);
 
@@ -442,9 +462,40 @@ impl Channel {
                     Some(input_view::Event::OpenServer(server)) => {
                         (command, Some(Event::OpenServer(server)))
                     }
+                    Some(input_view::Event::Workspace(workspace)) => {
+                        (command, Some(Event::Workspace(workspace)))
+                    }
+                    Some(input_view::Event::Schedule { buffer, text, at }) => {
+                        (command, Some(Event::Schedule(buffer, text, at)))
+                    }
+                    Some(input_view::Event::Queue { buffer, lines }) => {
+                        (command, Some(Event::Queue(buffer, lines)))
+                    }
                     Some(input_view::Event::Reconnect(server)) => {
                         (command, Some(Event::Reconnect(server)))
                     }
+                    Some(input_view::Event::Navigate(cursor)) => {
+                        let redaction =
+                            redaction(clients, &self.server, &self.target);
+                        let command = Task::batch(vec![
+                            command,
+                            self.scroll_view
+                                .update_cursor(
+                                    cursor,
+                                    scroll_view::Kind::Channel(
+                                        &self.server,
+                                        &self.target,
+                                    ),
+                                    history,
+                                    clients.nickname(&self.server),
+                                    redaction,
+                                    config,
+                                )
+                                .map(Message::ScrollView),
+                        ]);
+
+                        (command, None)
+                    }
                     Some(input_view::Event::FilehostUpload {
                         server,
                         target,
@@ -476,9 +527,21 @@ impl Channel {
                     history,
                     main_window,
                     config,
+                    spell_checker,
                 );
                 (task.map(Message::InputView), None)
             }
+            Message::Outbox(outbox::Message::Cancel(id)) => {
+                (Task::none(), Some(Event::CancelQueued(id)))
+            }
+            Message::Run(line) => self.update(
+                Message::InputView(input_view::Message::Run(line)),
+                clients,
+                history,
+                main_window,
+                config,
+                spell_checker,
+            ),
             Message::FilesDropped(paths) => {
                 let (task, event) = self.input_view.update(
                     input_view::Message::FilesSelected(paths),
@@ -487,6 +550,7 @@ impl Channel {
                     history,
                     main_window,
                     config,
+                    spell_checker,
                 );
                 (
                     task.map(Message::InputView),
@@ -571,6 +635,34 @@ impl Channel {
     }
 }
 
+/// Messages the message cursor may redact: any message for channel operators,
+/// otherwise only our own.
+fn redaction(
+    clients: &data::client::Map,
+    server: &Server,
+    channel: &target::Channel,
+) -> scroll_view::Redaction {
+    if !clients.get_server_can_redact(server) {
+        return scroll_view::Redaction::None;
+    }
+
+    let is_operator = clients
+        .nickname(server)
+        .map(|our_nick| User::from(Nick::from(our_nick)))
+        .and_then(|user| {
+            clients.resolve_user_attributes(server, channel, &user)
+        })
+        .is_some_and(|user| {
+            user.highest_access_level() >= data::user::AccessLevel::Oper
+        });
+
+    if is_operator {
+        scroll_view::Redaction::Any
+    } else {
+        scroll_view::Redaction::Own
+    }
+}
+
 fn topic<'a>(
     state: &'a Channel,
     clients: &'a data::client::Map,
@@ -1382,9 +1382,9 @@ pub fn user_avatar<'a>(
     previews: &'a preview::Collection,
 ) -> Option<UserAvatar<'a>> {
     avatar_url(user, registry).map(|url| match previews.get(&url) {
-        Some(preview::State::Loaded(preview)) => {
-            UserAvatar::Loaded(preview.image())
-        }
+        Some(preview::State::Loaded(preview)) => preview
+            .image()
+            .map_or(UserAvatar::Pending, UserAvatar::Loaded),
         _ => UserAvatar::Pending,
     })
 }
@@ -28,7 +28,7 @@ pub enum Event {
     History(Task<history::manager::Message>),
     OpenUrl(String),
     MarkAsRead,
-    ImagePreview(Image),
+    ImagePreview(Image, Vec<url::Url>),
     ExpandMessage(DateTime<Utc>, message::Hash),
     ContractMessage(DateTime<Utc>, message::Hash),
 }
@@ -369,8 +369,8 @@ impl Highlights {
                     scroll_view::Event::OpenUrl(url) => {
                         Some(Event::OpenUrl(url))
                     }
-                    scroll_view::Event::ImagePreview(image) => {
-                        Some(Event::ImagePreview(image))
+                    scroll_view::Event::ImagePreview(image, urls) => {
+                        Some(Event::ImagePreview(image, urls))
                     }
                     scroll_view::Event::ExpandMessage(server_time, hash) => {
                         Some(Event::ExpandMessage(server_time, hash))
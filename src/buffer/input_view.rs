@@ -1,9 +1,10 @@
 use std::borrow::Cow;
 use std::collections::VecDeque;
 use std::convert;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, Utc};
 use data::buffer::{self, Upstream};
 use data::capabilities::{MultilineBatchKind, multiline_concat_lines};
 use data::config::buffer::text_input::{AutoFormat, Autocomplete, KeyBindings};
@@ -13,9 +14,13 @@ use data::history::{self, ReadMarker};
 use data::input::{self, CodeFence, RawInput};
 use data::rate_limit::TokenPriority;
 use data::server::Server;
+use data::spell_check::Checker;
 use data::target::Target;
 use data::user::{ChannelUsers, Nick};
-use data::{Config, User, client, command, message, metadata, shortcut};
+use data::{
+    Config, User, client, command, message, metadata, outbox, schedule,
+    shortcut, snippet,
+};
 use iced::advanced::widget::Tree;
 use iced::advanced::{Clipboard, Layout, Shell, mouse};
 use iced::widget::text::{Shaping, Wrapping};
@@ -30,6 +35,7 @@ use unicode_segmentation::UnicodeSegmentation;
 
 use self::completion::Completion;
 use self::exec::run as execute_shell_command;
+use super::scroll_view;
 use crate::widget::key_press::is_numpad;
 use crate::widget::user_display::UserDisplay;
 use crate::widget::{
@@ -41,6 +47,9 @@ use crate::{Theme, font, theme, window};
 
 mod completion;
 mod exec;
+mod history_search;
+mod spell_check;
+mod vi;
 
 const TYPING_REFRESH_INTERVAL: Duration = Duration::from_secs(4);
 
@@ -55,6 +64,17 @@ pub enum Event {
     },
     OpenInternalBuffer(buffer::Internal),
     OpenServer(String),
+    Workspace(command::Workspace),
+    Schedule {
+        buffer: Upstream,
+        text: String,
+        at: DateTime<Utc>,
+    },
+    Queue {
+        buffer: Upstream,
+        lines: Vec<String>,
+    },
+    Navigate(scroll_view::Cursor),
     LeaveBuffers {
         targets: Vec<Target>,
         reason: Option<String>,
@@ -82,6 +102,7 @@ pub enum Message {
     },
     SysInfoReceived(iced::system::Information),
     Send,
+    Run(String),
     DeleteWordForward(bool),
     DeleteWordBackward(bool),
     DeleteToEnd(bool),
@@ -91,6 +112,7 @@ pub enum Message {
     Up(bool),
     Down(bool),
     Escape,
+    Vi(vi::Key),
     SendCommand {
         buffer: Upstream,
         command: command::Irc,
@@ -120,10 +142,17 @@ pub enum Message {
         to_nick: Nick,
     },
     ClearDraftReply,
+    OpenContextMenu(iced::Point),
+    ReplaceMisspelled(usize),
+    AddToDictionary,
+    WordSaved(Result<(), String>),
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum Actions {
+    Suggestion(usize),
+    AddToDictionary,
+    HorizontalRule,
     Cut,
     Copy,
     CopyAll,
@@ -346,11 +375,13 @@ pub fn view<'a>(
     state: &'a State,
     our_user: Option<&User>,
     channel_users: Option<&'a ChannelUsers>,
+    target_nick: Option<&str>,
     server: &'a Server,
     registry: &'a dyn metadata::Registry,
     config: &'a Config,
     theme: &'a Theme,
     filehost_url: Option<&'a str>,
+    spell_checker: Option<&'a Arc<Checker>>,
 ) -> Element<'a, Message> {
     const INPUT_ROW_SPACING: u32 = 4;
 
@@ -365,9 +396,31 @@ pub fn view<'a>(
 
     let key_bindings = config.buffer.text_input.key_bindings.clone();
 
+    let spelling = config
+        .buffer
+        .text_input
+        .spell_check
+        .enabled
+        .then_some(spell_checker)
+        .flatten()
+        .map(|checker| {
+            spell_check::Settings::new(
+                checker.clone(),
+                &state.input_content.text(),
+                our_user.map(User::nickname),
+                target_nick,
+                channel_users,
+            )
+        });
+    let is_spell_checking = spelling.is_some();
+
     let text_input = text_editor(&state.input_content)
         .id(state.input_id.clone())
-        .placeholder("Send message...")
+        .placeholder(if state.history_search.is_some() {
+            "Search input history..."
+        } else {
+            "Send message..."
+        })
         .padding([2, 4])
         .wrapping(Wrapping::WordOrGlyph)
         .height(Length::Shrink)
@@ -382,6 +435,15 @@ pub fn view<'a>(
                 return None;
             }
 
+            // Outside of insert mode vi keys navigate the messages
+            if matches!(key_bindings, KeyBindings::Vi)
+                && !state.vi.is_insert()
+                && let Some(key) =
+                    vi::Key::from_key_press(&key_press.key, key_press.modifiers)
+            {
+                return Some(text_editor::Binding::Custom(Message::Vi(key)));
+            }
+
             // Try emacs bindings first if enabled
             if matches!(key_bindings, KeyBindings::Emacs)
                 && let Some(binding) = emacs_key_binding(key_press.clone())
@@ -473,6 +535,16 @@ pub fn view<'a>(
             }
         });
 
+    let text_input: Element<'a, Message> = match spelling {
+        Some(settings) => text_input
+            .highlight_with::<spell_check::Highlighter>(
+                settings,
+                spell_check::format,
+            )
+            .into(),
+        None => text_input.into(),
+    };
+
     let text_input = decorate(text_input).update(
         move |_state: &mut State,
               inner: &mut Element<'a, Message>,
@@ -490,6 +562,19 @@ pub fn view<'a>(
                 return;
             };
 
+            // Suggestions are for the word under the cursor, so move the
+            // cursor there before the context menu opens
+            if is_spell_checking
+                && let event::Event::Mouse(mouse::Event::ButtonPressed(
+                    mouse::Button::Right,
+                )) = event
+                && let Some(position) = cursor.position_in(layout.bounds())
+            {
+                shell.publish(Message::OpenContextMenu(
+                    position - iced::Vector::new(4.0, 2.0),
+                ));
+            }
+
             inner.as_widget_mut().update(
                 tree, event, layout, cursor, renderer, clipboard, shell,
                 viewport,
@@ -503,7 +588,19 @@ pub fn view<'a>(
         context_menu::ToggleBehavior::KeepOpen,
         Some(mouse::Interaction::Text),
         text_input,
-        Actions::list(),
+        state
+            .misspelled
+            .as_ref()
+            .map(|misspelled| {
+                (0..misspelled.suggestions.len())
+                    .map(Actions::Suggestion)
+                    .chain([Actions::AddToDictionary, Actions::HorizontalRule])
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .chain(Actions::list())
+            .collect(),
         move |menu, length| {
             let context_button =
                 |title: Text<'a>,
@@ -533,6 +630,32 @@ pub fn view<'a>(
                 };
 
             match menu {
+                Actions::Suggestion(index) => context_button(
+                    text(
+                        state
+                            .misspelled
+                            .as_ref()
+                            .and_then(|misspelled| {
+                                misspelled.suggestions.get(index)
+                            })
+                            .cloned()
+                            .unwrap_or_default(),
+                    )
+                    .shaping(Shaping::Advanced),
+                    None,
+                    Some(Message::ReplaceMisspelled(index)),
+                ),
+                Actions::AddToDictionary => context_button(
+                    text("Add to Dictionary"),
+                    None,
+                    Some(Message::AddToDictionary),
+                ),
+                Actions::HorizontalRule => match length {
+                    Length::Fill => {
+                        container(rule::horizontal(1)).padding([0, 6]).into()
+                    }
+                    _ => Space::new().width(length).height(1).into(),
+                },
                 Actions::Cut => context_button(
                     text("Cut"),
                     Some(shortcut::cut()),
@@ -705,12 +828,31 @@ pub fn view<'a>(
     let styled_input =
         container(input_row).style(theme::container::buffer_text_input);
 
+    let maybe_vi_status = state.vi.status().map(|status| {
+        container(
+            text(status)
+                .style(theme::text::secondary)
+                .font_maybe(theme::font_style::secondary(theme).map(font::get))
+                .size(
+                    config.font.size.map_or(theme::TEXT_SIZE, f32::from) * 0.85,
+                ),
+        )
+        .padding([2, 8])
+    });
+
+    let maybe_history_search = state
+        .history_search
+        .as_ref()
+        .map(|search| history_search::view(search, config, theme));
+
     let input_column = column![
         if let Some(bar) = maybe_reply_bar {
             bar
         } else {
             row![].into()
         },
+        maybe_history_search,
+        maybe_vi_status,
         styled_input
     ]
     .spacing(0);
@@ -847,6 +989,9 @@ pub struct State {
     upload_abort_handles: Vec<futures::future::AbortHandle>,
     draft_reply: Option<input::DraftReply>,
     reply_preview: Option<message::ReplyPreview>,
+    vi: vi::State,
+    misspelled: Option<spell_check::Misspelled>,
+    history_search: Option<history_search::Search>,
 }
 
 impl Default for State {
@@ -866,6 +1011,9 @@ impl Default for State {
             upload_abort_handles: Vec::new(),
             draft_reply: None,
             reply_preview: None,
+            vi: vi::State::default(),
+            misspelled: None,
+            history_search: None,
         }
     }
 }
@@ -915,33 +1063,22 @@ impl State {
         history: &mut history::Manager,
         main_window: &Window,
         config: &Config,
+        spell_checker: Option<&Arc<Checker>>,
     ) -> (Task<Message>, Option<Event>) {
         let current_target = buffer.target();
 
+        if self.history_search.is_some()
+            && let Some(result) = self.update_history_search(
+                &message, buffer, clients, history, config,
+            )
+        {
+            return result;
+        }
+
         match message {
             Message::ExecFinished { buffer, result } => match result {
                 Ok(output) => {
-                    let parsed = input::parse(
-                        buffer.clone(),
-                        AutoFormat::Disabled,
-                        output.as_str(),
-                        None,
-                        clients.nickname(buffer.server()),
-                        buffer.channel().map(|target| {
-                            clients
-                                .get_channels(buffer.server())
-                                .any(|channel| target == channel)
-                        }),
-                        clients.get_server_is_connected(buffer.server()),
-                        clients.get_isupport_ref(buffer.server()),
-                        clients.get_capabilities_ref(buffer.server()),
-                        clients.get_features_ref(buffer.server()),
-                        clients.get_filehost(buffer.server()),
-                        clients.get_relay_bytes(buffer.server()),
-                        config,
-                    );
-
-                    match parsed {
+                    match parse_line(&buffer, &output, clients, config) {
                         Ok(input::Parsed::Internal(
                             command::Internal::Exec(_),
                         )) => {
@@ -1052,6 +1189,19 @@ impl State {
                     (Task::none(), None)
                 }
             }
+            Message::Run(line) => {
+                self.notice = None;
+
+                match parse_line(buffer, &line, clients, config) {
+                    Ok(parsed) => self.send_input_line(
+                        parsed, buffer, clients, history, config,
+                    ),
+                    Err(error) => {
+                        self.notice = Some(Notice::Error(error.to_string()));
+                        (Task::none(), None)
+                    }
+                }
+            }
             Message::Send => {
                 let cursor_position = self.input_content.cursor().position;
 
@@ -1074,8 +1224,13 @@ impl State {
                         chantypes,
                         config,
                     );
+                    let snippet =
+                        self.expand_snippet(&entry, buffer, clients, config);
+
+                    let (task, event) =
+                        self.on_completion(buffer, history, actions, true);
 
-                    self.on_completion(buffer, history, actions, true)
+                    (Task::batch([task, snippet]), event)
                 // IRCv3 draft/multiline forbids messages consisting
                 // entirely of blank lines, so we will take that as an
                 // IRC norm and require the same
@@ -1086,6 +1241,31 @@ impl State {
 
                     self.parse_lines(buffer, clients, config);
 
+                    // Messages sent while disconnected are queued, to be
+                    // sent once the server is connected again
+                    if let Some(lines) =
+                        self.queueable_lines(buffer, clients, config)
+                    {
+                        self.completion.reset();
+
+                        history.record_input_history(
+                            buffer,
+                            self.input_content.text().clone(),
+                            &config.buffer.text_input.history,
+                        );
+                        self.input_content = text_editor::Content::new();
+                        self.parsed = Vec::new();
+                        self.reset_typing();
+
+                        return (
+                            Task::none(),
+                            Some(Event::Queue {
+                                buffer: buffer.clone(),
+                                lines,
+                            }),
+                        );
+                    }
+
                     if let Some(Err(error)) =
                         self.parsed.get(cursor_position.line)
                     {
@@ -1142,6 +1322,7 @@ impl State {
                     history.record_input_history(
                         buffer,
                         self.input_content.text().clone(),
+                        &config.buffer.text_input.history,
                     );
                     self.input_content = text_editor::Content::new();
                     self.reset_typing();
@@ -1177,9 +1358,12 @@ impl State {
                         chantypes,
                         config,
                     );
+                    let snippet =
+                        self.expand_snippet(&entry, buffer, clients, config);
 
-                    let result =
+                    let (task, event) =
                         self.on_completion(buffer, history, actions, true);
+                    let result = (Task::batch([task, snippet]), event);
 
                     // If there is only one tab candidate process the completion immediately.
                     if self
@@ -1210,9 +1394,12 @@ impl State {
                         chantypes,
                         config,
                     );
+                    let snippet =
+                        self.expand_snippet(&entry, buffer, clients, config);
 
-                    let result =
+                    let (task, event) =
                         self.on_completion(buffer, history, actions, true);
+                    let result = (Task::batch([task, snippet]), event);
                     self.process_completion_and_notice(
                         buffer, clients, history, config,
                     );
@@ -1262,6 +1449,7 @@ impl State {
                         .history
                         .get(self.selected_history.unwrap())
                         .unwrap()
+                        .text
                         .clone();
 
                     self.on_history_navigation(
@@ -1301,7 +1489,7 @@ impl State {
                         cache.draft_message.to_string()
                     } else {
                         *index -= 1;
-                        cache.history.get(*index).unwrap().clone()
+                        cache.history.get(*index).unwrap().text.clone()
                     };
 
                     self.on_history_navigation(
@@ -1317,7 +1505,19 @@ impl State {
             }
             // Capture escape so that closing context menu or commands/emojis picker
             // does not defocus input
-            Message::Escape => (Task::none(), None),
+            Message::Escape => {
+                if matches!(
+                    config.buffer.text_input.key_bindings,
+                    KeyBindings::Vi
+                ) {
+                    self.vi.enter_normal();
+                }
+
+                (Task::none(), None)
+            }
+            Message::Vi(key) => {
+                (Task::none(), self.vi.key(key).map(Event::Navigate))
+            }
             Message::SendCommand { buffer, command } => {
                 let input = data::Input::from_command(buffer.clone(), command)
                     .encoded();
@@ -1399,6 +1599,98 @@ impl State {
 
                 Self::close_context_menu(main_window.id, vec![])
             }
+            Message::OpenContextMenu(position) => {
+                if self.input_content.selection().is_none() {
+                    self.input_content
+                        .perform(text_editor::Action::Click(position));
+                }
+
+                let channel_users = buffer.channel().and_then(|channel| {
+                    clients.get_channel_users(buffer.server(), channel)
+                });
+                let target_nick = match buffer {
+                    Upstream::Query(_, query) => Some(query.as_str()),
+                    Upstream::Server(_) | Upstream::Channel(..) => None,
+                };
+
+                self.misspelled = spell_checker
+                    .filter(|_| config.buffer.text_input.spell_check.enabled)
+                    .and_then(|checker| {
+                        spell_check::Settings::new(
+                            checker.clone(),
+                            &self.input_content.text(),
+                            clients.nickname(buffer.server()),
+                            target_nick,
+                            channel_users,
+                        )
+                        .misspelled_at_cursor(&self.input_content)
+                    });
+
+                (Task::none(), None)
+            }
+            Message::ReplaceMisspelled(index) => {
+                let mut tasks = vec![];
+
+                if let Some(misspelled) = self.misspelled.take()
+                    && let Some(replacement) =
+                        misspelled.suggestions.get(index).cloned()
+                    // The input may have changed while the menu was open
+                    && self.input_content.line(misspelled.line).is_some_and(
+                        |line| {
+                            line.text.get(misspelled.range.clone())
+                                == Some(misspelled.word.as_str())
+                        },
+                    )
+                {
+                    self.input_content.move_to(text_editor::Cursor {
+                        position: text_editor::Position {
+                            line: misspelled.line,
+                            column: misspelled.range.end,
+                        },
+                        selection: Some(text_editor::Position {
+                            line: misspelled.line,
+                            column: misspelled.range.start,
+                        }),
+                    });
+
+                    tasks.push(Task::done(Message::Action(
+                        text_editor::Action::Edit(text_editor::Edit::Paste(
+                            std::sync::Arc::new(replacement),
+                        )),
+                    )));
+                }
+
+                Self::close_context_menu(main_window.id, tasks)
+            }
+            Message::AddToDictionary => {
+                let mut tasks = vec![];
+
+                if let Some(misspelled) = self.misspelled.take()
+                    && let Some(checker) = spell_checker
+                {
+                    checker.add(&misspelled.word);
+
+                    tasks.push(Task::perform(
+                        data::spell_check::save_word(misspelled.word),
+                        |result| {
+                            Message::WordSaved(
+                                result.map_err(|error| error.to_string()),
+                            )
+                        },
+                    ));
+                }
+
+                Self::close_context_menu(main_window.id, tasks)
+            }
+            Message::WordSaved(result) => {
+                if let Err(error) = result {
+                    self.notice = Some(Notice::Error(format!(
+                        "failed to save word to dictionary: {error}"
+                    )));
+                }
+
+                (Task::none(), None)
+            }
             Message::CloseContextMenu(_, _) => (Task::none(), None),
             Message::UploadFile => (
                 Task::perform(
@@ -1698,6 +1990,8 @@ impl State {
 
                 match &action {
                     text_editor::Action::Edit(_) => {
+                        self.misspelled = None;
+
                         self.parse_lines_and_maybe_send_typing_status(
                             buffer, clients, config,
                         );
@@ -1785,6 +2079,37 @@ impl State {
         }
     }
 
+    /// The lines of the input to queue in the outbox, if every one of them is
+    /// a message that can't be sent only because the server is disconnected.
+    fn queueable_lines(
+        &self,
+        buffer: &buffer::Upstream,
+        clients: &client::Map,
+        config: &Config,
+    ) -> Option<Vec<String>> {
+        let is_disconnected = !self.parsed.is_empty()
+            && self.parsed.iter().all(|parsed| {
+                matches!(
+                    parsed,
+                    Err(input::Error::Command(command::Error::Disconnected))
+                )
+            });
+
+        if !is_disconnected {
+            return None;
+        }
+
+        let text = self.input_content.text();
+
+        input_lines(&text)
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                outbox::is_queueable(buffer, line, clients, config)
+                    .then(|| line.to_string())
+            })
+            .collect()
+    }
+
     // TODO: Create a parse_line variant that updates only a single line's
     // parsed update (and any following lines whose parsed value might change)
     fn parse_lines(
@@ -2421,6 +2746,32 @@ impl State {
                     command::Internal::Delay(_) => {
                         return (Task::none(), None);
                     }
+                    command::Internal::Schedule(at, text) => {
+                        // Whether the server is connected and the channel
+                        // joined is checked again when it's sent
+                        return match schedule::parse(
+                            buffer, &text, clients, config,
+                        ) {
+                            Ok(_)
+                            | Err(input::Error::Command(
+                                command::Error::Disconnected
+                                | command::Error::NotInChannel,
+                            )) => (
+                                Task::none(),
+                                Some(Event::Schedule {
+                                    buffer: buffer.clone(),
+                                    text,
+                                    at,
+                                }),
+                            ),
+                            Err(error) => {
+                                self.notice =
+                                    Some(Notice::Error(error.to_string()));
+
+                                (Task::none(), None)
+                            }
+                        };
+                    }
                     command::Internal::ClearBuffer => {
                         let kind =
                             history::Kind::from_input_buffer(buffer.clone());
@@ -2449,6 +2800,30 @@ impl State {
                             Some(Event::Reconnect(buffer.server().clone())),
                         );
                     }
+                    command::Internal::Oper(password) => {
+                        if !clients.oper(buffer.server(), password.as_deref()) {
+                            self.notice = Some(Notice::Error(String::from(
+                                "no oper password configured for this server",
+                            )));
+
+                            return (Task::none(), None);
+                        }
+
+                        return (
+                            Task::none(),
+                            Some(Event::OpenInternalBuffer(
+                                buffer::Internal::ServerNotices(
+                                    buffer.server().clone(),
+                                ),
+                            )),
+                        );
+                    }
+                    command::Internal::Workspace(workspace) => {
+                        return (
+                            Task::none(),
+                            Some(Event::Workspace(workspace)),
+                        );
+                    }
                     command::Internal::Upload(_)
                         if !config.filehost.enabled =>
                     {
@@ -2745,6 +3120,59 @@ impl State {
         (Task::none(), None)
     }
 
+    /// Pastes the expansion of a snippet picked from the completions, once
+    /// the clipboard is read if the snippet needs it
+    fn expand_snippet(
+        &self,
+        entry: &completion::Entry,
+        buffer: &buffer::Upstream,
+        clients: &client::Map,
+        config: &Config,
+    ) -> Task<Message> {
+        let completion::Entry::Snippet(name) = entry else {
+            return Task::none();
+        };
+        let Some(template) = config.buffer.snippets.get(name).cloned() else {
+            return Task::none();
+        };
+
+        let context = snippet::Context {
+            nick: clients
+                .nickname(buffer.server())
+                .map(|nick| nick.as_str().to_string()),
+            channel: buffer
+                .channel()
+                .map(|channel| channel.as_str().to_string()),
+            server: Some(buffer.server().to_string()),
+            author: self.draft_reply.as_ref().map(|reply| reply.nick.clone()),
+            reply: self
+                .reply_preview
+                .as_ref()
+                .map(message::ReplyPreview::preview_text),
+            clipboard: None,
+        };
+
+        let paste =
+            move |context: &snippet::Context| {
+                Message::Action(text_editor::Action::Edit(
+                    text_editor::Edit::Paste(std::sync::Arc::new(
+                        snippet::expand(&template, context, Local::now()),
+                    )),
+                ))
+            };
+
+        if snippet::uses_clipboard(&template) {
+            clipboard::read().map(move |clipboard| {
+                paste(&snippet::Context {
+                    clipboard,
+                    ..context.clone()
+                })
+            })
+        } else {
+            Task::done(paste(&context))
+        }
+    }
+
     fn on_history_navigation(
         &mut self,
         buffer: &buffer::Upstream,
@@ -2777,6 +3205,78 @@ impl State {
         (Task::none(), None)
     }
 
+    /// Starts searching the input history, or moves on to the next match
+    /// when already searching.
+    pub fn search_history(
+        &mut self,
+        history: &history::Manager,
+    ) -> Task<Message> {
+        if let Some(search) = &mut self.history_search {
+            search.next();
+        } else {
+            self.history_search = Some(history_search::Search::new(
+                self.input_content.text(),
+                history,
+            ));
+            self.input_content = text_editor::Content::new();
+            self.completion.reset();
+            self.notice = None;
+            self.vi.enter_insert();
+        }
+
+        self.focus()
+    }
+
+    /// Handles the messages which behave differently while searching the
+    /// input history.
+    fn update_history_search(
+        &mut self,
+        message: &Message,
+        buffer: &buffer::Upstream,
+        clients: &mut client::Map,
+        history: &mut history::Manager,
+        config: &Config,
+    ) -> Option<(Task<Message>, Option<Event>)> {
+        match message {
+            Message::Send | Message::Tab(_) => {
+                let text = self.history_search.take()?.accept();
+                self.selected_history = None;
+
+                Some(self.on_history_navigation(
+                    buffer, clients, history, config, &text, false,
+                ))
+            }
+            Message::Escape => {
+                let text = self.history_search.take()?.cancel();
+
+                Some(self.on_history_navigation(
+                    buffer, clients, history, config, &text, false,
+                ))
+            }
+            Message::Up(false) => {
+                self.history_search.as_mut()?.next();
+
+                Some((Task::none(), None))
+            }
+            Message::Down(false) => {
+                self.history_search.as_mut()?.previous();
+
+                Some((Task::none(), None))
+            }
+            Message::Action(action) => {
+                self.input_content.perform(action.clone());
+
+                if matches!(action, text_editor::Action::Edit(_)) {
+                    let query = self.input_content.text();
+                    self.history_search.as_mut()?.update(&query, history);
+                }
+
+                Some((Task::none(), None))
+            }
+            _ => None,
+        }
+    }
+
     pub fn focus(&self) -> Task<Message> {
         let input_id = self.input_id.clone();
 
@@ -2793,6 +3293,11 @@ impl State {
         self.notice = None;
         self.completion = Completion::default();
         self.selected_history = None;
+
+        if let Some(search) = self.history_search.take() {
+            self.input_content =
+                text_editor::Content::with_text(&search.cancel());
+        }
     }
 
     pub fn insert_user(
@@ -3056,6 +3561,35 @@ fn input_lines(text: &str) -> impl Iterator<Item = &str> {
     text.split('\n')
 }
 
+/// Parses a line that doesn't come from the text input, such as the output
+/// of `/exec` or the command of a key bind.
+fn parse_line(
+    buffer: &Upstream,
+    line: &str,
+    clients: &client::Map,
+    config: &Config,
+) -> Result<input::Parsed, input::Error> {
+    input::parse(
+        buffer.clone(),
+        AutoFormat::Disabled,
+        line,
+        None,
+        clients.nickname(buffer.server()),
+        buffer.channel().map(|target| {
+            clients
+                .get_channels(buffer.server())
+                .any(|channel| target == channel)
+        }),
+        clients.get_server_is_connected(buffer.server()),
+        clients.get_isupport_ref(buffer.server()),
+        clients.get_capabilities_ref(buffer.server()),
+        clients.get_features_ref(buffer.server()),
+        clients.get_filehost(buffer.server()),
+        clients.get_relay_bytes(buffer.server()),
+        config,
+    )
+}
+
 fn show_while_typing(error: &input::Error) -> bool {
     match error {
         input::Error::ExceedsByteLimit { .. }
@@ -3071,6 +3605,8 @@ fn show_while_typing(error: &input::Error) -> bool {
             | command::Error::InvalidChathistoryTimestamp
             | command::Error::ChathistoryLimitTooLarge { .. }
             | command::Error::ExecDisabled
+            | command::Error::ScheduleTimeInPast
+            | command::Error::NotSchedulable
             | command::Error::CommandNotAvailable { .. }
             | command::Error::CommandNotEnabled { .. },
         ) => true,
@@ -3090,7 +3626,10 @@ fn show_while_typing(error: &input::Error) -> bool {
             | command::Error::NoModeString
             | command::Error::Connected
             | command::Error::Disconnected
-            | command::Error::NotInChannel,
+            | command::Error::NotInChannel
+            | command::Error::InvalidScheduleTime
+            | command::Error::InvalidScheduleDelay
+            | command::Error::MissingWorkspaceName,
         ) => false,
     }
 }
@@ -3122,8 +3661,11 @@ fn try_clipboard_upload() -> Option<Vec<std::path::PathBuf>> {
             img.bytes.into_owned(),
         )?;
 
-        let path = std::env::temp_dir()
-            .join(format!("halloy-paste-{}.png", uuid::Uuid::new_v4()));
+        let path = data::fileupload::temporary_path(&format!(
+            "halloy-paste-{}.png",
+            uuid::Uuid::new_v4()
+        ))
+        .ok()?;
 
         rgba.save(&path).ok()?;
 
@@ -30,6 +30,10 @@ use crate::{emoji, font};
 const MAX_SHOWN_COMMAND_ENTRIES: usize = 5;
 const MAX_SHOWN_EMOJI_ENTRIES: usize = 8;
 const MAX_SHOWN_PATH_ENTRIES: usize = 8;
+const MAX_SHOWN_SNIPPET_ENTRIES: usize = 8;
+/// Characters of a snippet name to type before the picker shows, unless the
+/// name is shorter
+const CHARACTERS_TO_TRIGGER_SNIPPET_PICKER: usize = 2;
 const MAX_SHOWN_WORD_ENTRIES: usize = 8;
 
 #[derive(Debug, Clone, Default)]
@@ -38,6 +42,7 @@ pub struct Completion {
     words: Words,
     emojis: Emojis,
     paths: Paths,
+    snippets: Snippets,
 }
 
 impl Completion {
@@ -86,6 +91,7 @@ impl Completion {
                 self.words = Words::default();
                 self.emojis = Emojis::default();
                 self.paths = Paths::default();
+                self.snippets = Snippets::default();
 
                 return;
             }
@@ -98,10 +104,28 @@ impl Completion {
             self.words = Words::default();
             self.emojis = Emojis::default();
             self.paths = Paths::default();
+            self.snippets = Snippets::default();
 
             return;
         }
 
+        // Snippets share the `:` trigger with emojis and take precedence
+        self.snippets = Snippets::default();
+
+        if let Some(name) = get_word(input, cursor_position)
+            .and_then(|word| word.strip_prefix(':'))
+        {
+            self.snippets.process(name, config);
+
+            if matches!(self.snippets, Snippets::Selecting { .. }) {
+                self.words = Words::default();
+                self.emojis = Emojis::default();
+                self.paths = Paths::default();
+
+                return;
+            }
+        }
+
         if let Some(shortcode) = (config.buffer.emojis.show_picker
             || config.buffer.emojis.auto_replace)
             .then(|| {
@@ -148,6 +172,7 @@ impl Completion {
         self.commands
             .select()
             .map(Entry::Command)
+            .or(self.snippets.select().map(Entry::Snippet))
             .or(self.emojis.select(config).map(Entry::Emoji))
             .or(self.words.select().map(|next| Entry::Word {
                 next,
@@ -163,6 +188,7 @@ impl Completion {
         self.commands
             .select_at(index)
             .map(Entry::Command)
+            .or(self.snippets.select_at(index).map(Entry::Snippet))
             .or(self.emojis.select_at(index, config).map(Entry::Emoji))
             .or(self.paths.select_at(index).map(Entry::Path))
             .or(self.words.select_at(index).map(|next| Entry::Word {
@@ -186,6 +212,7 @@ impl Completion {
     pub fn tab(&mut self, reverse: bool, config: &Config) -> Option<Entry> {
         self.commands
             .tab(reverse)
+            .or_else(|| self.snippets.tab(reverse))
             .or_else(|| self.emojis.tab(reverse, config))
             .or_else(|| self.paths.tab(reverse))
             .or_else(|| self.words.tab(reverse))
@@ -194,6 +221,7 @@ impl Completion {
     pub fn tab_candidate_count(&self) -> Option<usize> {
         self.commands
             .tab_candidate_count()
+            .or_else(|| self.snippets.tab_candidate_count())
             .or_else(|| self.emojis.tab_candidate_count())
             .or_else(|| self.paths.tab_candidate_count())
     }
@@ -208,6 +236,10 @@ impl Completion {
             return true;
         }
 
+        if self.snippets.cycle(reverse) {
+            return true;
+        }
+
         if self.emojis.cycle(reverse) {
             return true;
         }
@@ -241,16 +273,24 @@ impl Completion {
         let emojis_view = self.emojis.view(config, on_select_command);
         let paths_view = self.paths.view(on_select_command);
         let words_view = self.words.view(on_select_command);
+        let snippets_view = self.snippets.view(config, on_select_command);
 
         if command_view.is_some()
             || emojis_view.is_some()
             || paths_view.is_some()
             || words_view.is_some()
+            || snippets_view.is_some()
         {
             Some(
-                column![emojis_view, paths_view, words_view, command_view]
-                    .spacing(4)
-                    .into(),
+                column![
+                    snippets_view,
+                    emojis_view,
+                    paths_view,
+                    words_view,
+                    command_view
+                ]
+                .spacing(4)
+                .into(),
             )
         } else {
             None
@@ -261,6 +301,10 @@ impl Completion {
         if matches!(self.commands, Commands::Selecting { .. }) {
             self.commands = Commands::Idle;
 
+            return true;
+        } else if matches!(self.snippets, Snippets::Selecting { .. }) {
+            self.snippets = Snippets::Idle;
+
             return true;
         } else if matches!(self.emojis, Emojis::Selecting { .. }) {
             self.emojis = Emojis::Idle;
@@ -279,9 +323,14 @@ impl Completion {
 #[derive(Debug, Clone)]
 pub enum Entry {
     Command(String),
-    Word { next: String, append_suffix: bool },
+    Word {
+        next: String,
+        append_suffix: bool,
+    },
     Path(String),
     Emoji(String),
+    /// Name of the snippet, which is expanded in place of the word
+    Snippet(String),
 }
 
 impl Entry {
@@ -329,6 +378,11 @@ impl Entry {
             Entry::Emoji(emoji) => {
                 replace_word_with_text(input, cursor_position, emoji, None)
             }
+            // The word is removed here and the expanded snippet pasted once
+            // its variables are known
+            Entry::Snippet(_) => {
+                replace_word_with_text(input, cursor_position, "", None)
+            }
             Entry::Path(path) => {
                 vec![
                     text_editor::Action::SelectAll,
@@ -1249,8 +1303,33 @@ fn connected_command_list<'a>(
                 subcommands: None,
             }
         },
+        // OPER
+        {
+            Command {
+                title: "OPER".into(),
+                args: vec![
+                    Argument {
+                        text: "name".into(),
+                        kind: ArgumentKind::Optional { skipped: false },
+                        tooltip: Some(String::from(
+                            "may be skipped to use the server's oper config\
+                             \na single argument is used as the password",
+                        )),
+                    },
+                    Argument {
+                        text: "password".into(),
+                        kind: ArgumentKind::Optional { skipped: false },
+                        tooltip: None,
+                    },
+                ],
+                subcommands: None,
+            }
+        },
         // EXEC
         exec_command(),
+        workspace_command(),
+        schedule_command(),
+        later_command(),
         // CLEAR
         {
             Command {
@@ -1492,6 +1571,9 @@ fn disconnected_command_list(server: &Server) -> Vec<Command> {
     vec![
         // EXEC
         exec_command(),
+        workspace_command(),
+        schedule_command(),
+        later_command(),
         // CONNECT
         {
             Command {
@@ -1533,6 +1615,68 @@ fn exec_command() -> Command {
     }
 }
 
+fn workspace_command() -> Command {
+    Command {
+        title: "WORKSPACE".into(),
+        args: vec![Argument {
+            text: "name".into(),
+            kind: ArgumentKind::Required,
+            tooltip: Some(
+                "switches to the workspace, or saves the current layout as it with \"save <name>\" and deletes it with \"delete <name>\""
+                    .to_string(),
+            ),
+        }],
+        subcommands: None,
+    }
+}
+
+fn schedule_command() -> Command {
+    Command {
+        title: "SCHEDULE".into(),
+        args: vec![
+            Argument {
+                text: "when".into(),
+                kind: ArgumentKind::Required,
+                tooltip: Some(
+                    "a delay such as 10m or 1h30m, a time such as 14:30, or a date and time such as 2026-10-20T14:30"
+                        .to_string(),
+                ),
+            },
+            Argument {
+                text: "message".into(),
+                kind: ArgumentKind::Required,
+                tooltip: Some(
+                    "sent to the current buffer, and may be a command"
+                        .to_string(),
+                ),
+            },
+        ],
+        subcommands: None,
+    }
+}
+
+fn later_command() -> Command {
+    Command {
+        title: "LATER".into(),
+        args: vec![
+            Argument {
+                text: "delay".into(),
+                kind: ArgumentKind::Required,
+                tooltip: Some("such as 10m or 1h30m".to_string()),
+            },
+            Argument {
+                text: "message".into(),
+                kind: ArgumentKind::Required,
+                tooltip: Some(
+                    "sent to the current buffer, and may be a command"
+                        .to_string(),
+                ),
+            },
+        ],
+        subcommands: None,
+    }
+}
+
 fn commands_from_aliases(aliases: &[command::Alias]) -> Vec<Command> {
     aliases
         .iter()
@@ -2316,14 +2460,19 @@ impl Words {
 }
 
 fn away_command(max_len: Option<u16>) -> Command {
-    let tooltip = max_len.map(|max_len| format!("maximum length: {max_len}"));
+    let mut tooltip =
+        String::from("\"*\" marks only this connection as away (bouncers)");
+
+    if let Some(max_len) = max_len {
+        tooltip.push_str(format!("\nmaximum length: {max_len}").as_str());
+    }
 
     Command {
         title: "AWAY".into(),
         args: vec![Argument {
             text: "reason".into(),
             kind: ArgumentKind::Optional { skipped: false },
-            tooltip,
+            tooltip: Some(tooltip),
         }],
         subcommands: None,
     }
@@ -3528,6 +3677,172 @@ impl Emojis {
     }
 }
 
+#[derive(Debug, Clone, Default)]
+enum Snippets {
+    #[default]
+    Idle,
+    Selecting {
+        highlighted: Option<usize>,
+        filtered: Vec<String>,
+    },
+}
+
+impl Snippets {
+    fn process(&mut self, name: &str, config: &Config) {
+        let filtered = data::snippet::matching(name, config);
+
+        let is_triggered = name.chars().count()
+            >= CHARACTERS_TO_TRIGGER_SNIPPET_PICKER
+            || filtered
+                .iter()
+                .any(|snippet| snippet.eq_ignore_ascii_case(name));
+
+        *self = if is_triggered && !filtered.is_empty() {
+            Self::Selecting {
+                highlighted: None,
+                filtered: filtered.into_iter().map(String::from).collect(),
+            }
+        } else {
+            Self::Idle
+        };
+    }
+
+    fn select(&mut self) -> Option<String> {
+        let index = if let Self::Selecting { highlighted, .. } = self {
+            highlighted.unwrap_or(0)
+        } else {
+            return None;
+        };
+
+        self.select_at(index)
+    }
+
+    fn select_at(&mut self, index: usize) -> Option<String> {
+        if let Self::Selecting { filtered, .. } = self
+            && index < filtered.len()
+        {
+            let name = filtered.swap_remove(index);
+            *self = Self::Idle;
+            return Some(name);
+        }
+
+        None
+    }
+
+    /// Moves through the snippets, only expanding when there is just one
+    fn tab(&mut self, reverse: bool) -> Option<Entry> {
+        if let Self::Selecting {
+            highlighted,
+            filtered,
+        } = self
+        {
+            if let [name] = filtered.as_slice() {
+                let name = name.clone();
+                *self = Self::Idle;
+                return Some(Entry::Snippet(name));
+            }
+
+            selecting_tab(highlighted, filtered, reverse);
+        }
+
+        None
+    }
+
+    fn cycle(&mut self, reverse: bool) -> bool {
+        if let Self::Selecting {
+            highlighted,
+            filtered,
+        } = self
+        {
+            selecting_tab(highlighted, filtered, reverse);
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn tab_candidate_count(&self) -> Option<usize> {
+        match self {
+            Self::Selecting { filtered, .. } => Some(filtered.len()),
+            Self::Idle => None,
+        }
+    }
+
+    fn view<'a, Message: Clone + 'a>(
+        &self,
+        config: &Config,
+        on_select_command: impl Fn(usize) -> Message + Copy + 'a,
+    ) -> Option<Element<'a, Message>> {
+        let Self::Selecting {
+            highlighted,
+            filtered,
+        } = self
+        else {
+            return None;
+        };
+
+        let skip = {
+            let index = highlighted.unwrap_or(0);
+            let to = index.max(MAX_SHOWN_SNIPPET_ENTRIES - 1);
+            to.saturating_sub(MAX_SHOWN_SNIPPET_ENTRIES - 1)
+        };
+
+        let entries = filtered
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(MAX_SHOWN_SNIPPET_ENTRIES)
+            .map(|(index, name)| {
+                // Only the first line of the template, as a hint
+                let template = config
+                    .buffer
+                    .snippets
+                    .get(name)
+                    .and_then(|template| template.lines().next())
+                    .unwrap_or_default()
+                    .to_string();
+
+                (index, name.clone(), template)
+            })
+            .collect::<Vec<_>>();
+
+        let content = |width| {
+            column(entries.iter().map(|(index, name, template)| {
+                let selected = Some(*index) == *highlighted;
+                let content = row![
+                    text(format!(":{name}")),
+                    text(template.clone())
+                        .style(theme::text::secondary)
+                        .shaping(Shaping::Advanced),
+                ]
+                .spacing(8);
+
+                Element::from(
+                    button(content)
+                        .width(width)
+                        .padding(6)
+                        .style(move |theme, status| {
+                            theme::button::picker(theme, status, selected)
+                        })
+                        .on_press(on_select_command(*index)),
+                )
+            }))
+        };
+
+        (!entries.is_empty()).then(|| {
+            let first_pass = content(Length::Shrink);
+            let second_pass = content(Length::Fill);
+
+            container(double_pass(first_pass, second_pass))
+                .padding(4)
+                .style(theme::container::tooltip)
+                .width(Length::Shrink)
+                .into()
+        })
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 enum Paths {
     #[default]
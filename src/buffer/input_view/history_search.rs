@@ -0,0 +1,124 @@
+use data::{Config, buffer, history};
+use iced::Length;
+use iced::widget::text::Wrapping;
+use iced::widget::{Space, container, row};
+
+use crate::widget::{Element, text};
+use crate::{Theme, font, theme};
+
+/// Reverse incremental search through the input history of every buffer.
+/// While searching, the text input holds the query.
+#[derive(Debug, Clone)]
+pub struct Search {
+    /// Input from before the search, restored when it's cancelled.
+    draft: String,
+    matches: Vec<Match>,
+    index: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Match {
+    text: String,
+    buffer: String,
+}
+
+impl Search {
+    pub fn new(draft: String, history: &history::Manager) -> Self {
+        let mut search = Self {
+            draft,
+            matches: vec![],
+            index: 0,
+        };
+
+        search.update("", history);
+
+        search
+    }
+
+    /// Finds the matches of a changed query, starting over from the best one.
+    pub fn update(&mut self, query: &str, history: &history::Manager) {
+        self.index = 0;
+        self.matches = history
+            .search_input_history(query)
+            .into_iter()
+            .map(|(buffer, sent)| Match {
+                text: sent.text.clone(),
+                buffer: match buffer {
+                    buffer::Upstream::Server(server) => server.to_string(),
+                    buffer::Upstream::Channel(_, channel) => {
+                        channel.as_str().to_string()
+                    }
+                    buffer::Upstream::Query(_, query) => {
+                        query.as_str().to_string()
+                    }
+                },
+            })
+            .collect();
+    }
+
+    pub fn next(&mut self) {
+        self.index = (self.index + 1).min(self.matches.len().saturating_sub(1));
+    }
+
+    pub fn previous(&mut self) {
+        self.index = self.index.saturating_sub(1);
+    }
+
+    /// The selected match, or the draft if nothing matched.
+    pub fn accept(self) -> String {
+        match self.matches.into_iter().nth(self.index) {
+            Some(selected) => selected.text,
+            None => self.draft,
+        }
+    }
+
+    pub fn cancel(self) -> String {
+        self.draft
+    }
+}
+
+pub fn view<'a, Message: 'a>(
+    search: &'a Search,
+    config: &'a Config,
+    theme: &'a Theme,
+) -> Element<'a, Message> {
+    let font_size = config.font.size.map_or(theme::TEXT_SIZE, f32::from) * 0.85;
+
+    let secondary = |content: String| {
+        text(content)
+            .size(font_size)
+            .style(theme::text::secondary)
+            .font_maybe(theme::font_style::secondary(theme).map(font::get))
+    };
+
+    let selected: Element<'a, Message> =
+        if let Some(selected) = search.matches.get(search.index) {
+            row![
+                text(selected.text.as_str())
+                    .size(font_size)
+                    .wrapping(Wrapping::None)
+                    .style(theme::text::primary),
+                Space::new().width(Length::Fill),
+                secondary(format!(
+                    "{} · {}/{}",
+                    selected.buffer,
+                    search.index + 1,
+                    search.matches.len()
+                )),
+            ]
+            .spacing(8)
+            .into()
+        } else {
+            text("No matches")
+                .size(font_size)
+                .style(theme::text::error)
+                .font_maybe(theme::font_style::error(theme).map(font::get))
+                .into()
+        };
+
+    container(
+        row![secondary("History search:".to_string()), selected].spacing(6),
+    )
+    .padding([2, 8])
+    .into()
+}
@@ -0,0 +1,154 @@
+use std::collections::BTreeSet;
+use std::ops::Range;
+use std::sync::Arc;
+
+use data::isupport::CaseMap;
+use data::spell_check::{self, Checker};
+use data::user::{ChannelUsers, Nick, NickRef};
+use iced::advanced::text::highlighter::{self, Format};
+use iced::widget::text_editor;
+
+use crate::Theme;
+
+/// What the text input is checked with, along with the words of the input
+/// which are nicknames in the buffer
+#[derive(Debug, Clone)]
+pub struct Settings {
+    checker: Arc<Checker>,
+    revision: usize,
+    nicknames: BTreeSet<String>,
+}
+
+impl PartialEq for Settings {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.checker, &other.checker)
+            && self.revision == other.revision
+            && self.nicknames == other.nicknames
+    }
+}
+
+impl Settings {
+    pub fn new(
+        checker: Arc<Checker>,
+        input: &str,
+        our_nick: Option<NickRef<'_>>,
+        target_nick: Option<&str>,
+        channel_users: Option<&ChannelUsers>,
+    ) -> Self {
+        let nicknames = input
+            .lines()
+            .flat_map(spell_check::tokens)
+            .filter(|(_, token)| {
+                let nick =
+                    Nick::from_string(token.to_string(), CaseMap::default());
+                let nick = nick.as_nickref();
+
+                our_nick.is_some_and(|our_nick| our_nick == nick)
+                    || target_nick.is_some_and(|target_nick| {
+                        CaseMap::default().normalize(target_nick)
+                            == nick.as_normalized_str()
+                    })
+                    || channel_users.is_some_and(|channel_users| {
+                        channel_users.get_by_nick(nick).is_some()
+                    })
+            })
+            .map(|(_, token)| token.to_string())
+            .collect();
+
+        Self {
+            revision: checker.revision(),
+            checker,
+            nicknames,
+        }
+    }
+
+    /// The misspelled word at the cursor, if any
+    pub fn misspelled_at_cursor(
+        &self,
+        content: &text_editor::Content,
+    ) -> Option<Misspelled> {
+        let position = content.cursor().position;
+        let line = content.line(position.line)?.text;
+
+        let (range, word) =
+            spell_check::words(&line, |token| self.nicknames.contains(token))
+                .into_iter()
+                .find(|(range, word)| {
+                    (range.start..=range.end).contains(&position.column)
+                        && !self.checker.check(word)
+                })?;
+
+        Some(Misspelled {
+            line: position.line,
+            range,
+            word: word.to_string(),
+            suggestions: self.checker.suggest(word),
+        })
+    }
+}
+
+/// A misspelled word of the text input and what it could be instead
+#[derive(Debug, Clone)]
+pub struct Misspelled {
+    pub line: usize,
+    pub range: Range<usize>,
+    pub word: String,
+    pub suggestions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Misspelling;
+
+/// Highlights misspelled words of the text input
+///
+/// The text editor can only color highlighted text, so misspellings are
+/// shown in the error color rather than underlined.
+pub struct Highlighter {
+    settings: Settings,
+    current_line: usize,
+}
+
+impl highlighter::Highlighter for Highlighter {
+    type Settings = Settings;
+    type Highlight = Misspelling;
+    type Iterator<'a> = std::vec::IntoIter<(Range<usize>, Misspelling)>;
+
+    fn new(settings: &Self::Settings) -> Self {
+        Self {
+            settings: settings.clone(),
+            current_line: 0,
+        }
+    }
+
+    fn update(&mut self, new_settings: &Self::Settings) {
+        self.settings = new_settings.clone();
+        self.current_line = 0;
+    }
+
+    fn change_line(&mut self, line: usize) {
+        self.current_line = line;
+    }
+
+    fn highlight_line(&mut self, line: &str) -> Self::Iterator<'_> {
+        self.current_line += 1;
+
+        self.settings
+            .checker
+            .misspellings(line, |token| self.settings.nicknames.contains(token))
+            .into_iter()
+            .map(|range| (range, Misspelling))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn current_line(&self) -> usize {
+        self.current_line
+    }
+}
+
+pub fn format(_misspelling: &Misspelling, theme: &Theme) -> Format<iced::Font> {
+    Format {
+        color: Some(theme.styles().text.error.color),
+        font: None,
+    }
+}
@@ -0,0 +1,227 @@
+use iced::keyboard::{self, key};
+
+use crate::buffer::scroll_view::Cursor;
+
+/// Mode of the vi key bindings.  In normal mode keys move a cursor over the
+/// messages of the buffer instead of editing the input.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+enum Mode {
+    #[default]
+    Insert,
+    Normal {
+        /// First key of `gg` or `dd`, waiting for the second one.
+        pending: Option<char>,
+    },
+    Search(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct State {
+    mode: Mode,
+    last_search: Option<String>,
+}
+
+/// A key pressed outside of insert mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Key {
+    Character(String),
+    Up,
+    Down,
+    Enter,
+    Backspace,
+    Escape,
+    Other,
+}
+
+impl Key {
+    /// Keys held with Ctrl, Alt or Command are left to the input and the
+    /// keyboard shortcuts.
+    pub fn from_key_press(
+        key: &keyboard::Key,
+        modifiers: keyboard::Modifiers,
+    ) -> Option<Self> {
+        if modifiers.command() || modifiers.control() || modifiers.alt() {
+            return None;
+        }
+
+        Some(match key {
+            keyboard::Key::Character(c) => Key::Character(c.to_string()),
+            keyboard::Key::Named(key::Named::ArrowUp) => Key::Up,
+            keyboard::Key::Named(key::Named::ArrowDown) => Key::Down,
+            keyboard::Key::Named(key::Named::Enter) => Key::Enter,
+            keyboard::Key::Named(key::Named::Backspace) => Key::Backspace,
+            keyboard::Key::Named(key::Named::Escape) => Key::Escape,
+            keyboard::Key::Named(key::Named::Space) => {
+                Key::Character(" ".to_string())
+            }
+            _ => Key::Other,
+        })
+    }
+}
+
+impl State {
+    pub fn is_insert(&self) -> bool {
+        matches!(self.mode, Mode::Insert)
+    }
+
+    pub fn enter_insert(&mut self) {
+        self.mode = Mode::Insert;
+    }
+
+    pub fn enter_normal(&mut self) {
+        self.mode = Mode::Normal { pending: None };
+    }
+
+    /// Text shown in place of the mode indicator of vi.
+    pub fn status(&self) -> Option<String> {
+        match &self.mode {
+            Mode::Insert => None,
+            Mode::Normal { .. } => Some("-- NORMAL --".to_string()),
+            Mode::Search(text) => Some(format!("/{text}")),
+        }
+    }
+
+    /// Handles a key pressed in normal or search mode, returning how the
+    /// message cursor should change.
+    pub fn key(&mut self, key: Key) -> Option<Cursor> {
+        match &mut self.mode {
+            Mode::Insert => None,
+            Mode::Search(text) => match key {
+                Key::Character(c) => {
+                    text.push_str(&c);
+                    None
+                }
+                Key::Backspace => {
+                    if text.pop().is_none() {
+                        self.enter_normal();
+                    }
+                    None
+                }
+                Key::Enter => {
+                    let text = std::mem::take(text);
+                    self.enter_normal();
+
+                    if text.is_empty() {
+                        return None;
+                    }
+
+                    self.last_search = Some(text.clone());
+
+                    Some(Cursor::Search { text, older: true })
+                }
+                Key::Escape => {
+                    self.enter_normal();
+                    None
+                }
+                Key::Up | Key::Down | Key::Other => None,
+            },
+            Mode::Normal { pending } => {
+                let was_pending = pending.take();
+
+                match key {
+                    Key::Character(c) => match c.as_str() {
+                        "j" => Some(Cursor::Newer),
+                        "k" => Some(Cursor::Older),
+                        "g" if was_pending == Some('g') => Some(Cursor::Oldest),
+                        "d" if was_pending == Some('d') => Some(Cursor::Redact),
+                        "g" | "d" => {
+                            *pending = c.chars().next();
+                            None
+                        }
+                        "G" => Some(Cursor::Newest),
+                        "/" => {
+                            self.mode = Mode::Search(String::new());
+                            None
+                        }
+                        "n" | "N" => self.last_search.clone().map(|text| {
+                            Cursor::Search {
+                                text,
+                                older: c == "n",
+                            }
+                        }),
+                        "r" => Some(Cursor::Reply),
+                        "y" => Some(Cursor::Copy),
+                        "e" => Some(Cursor::React),
+                        "i" | "a" => {
+                            self.mode = Mode::Insert;
+                            Some(Cursor::Clear)
+                        }
+                        _ => None,
+                    },
+                    Key::Up => Some(Cursor::Older),
+                    Key::Down => Some(Cursor::Newer),
+                    Key::Escape => Some(Cursor::Clear),
+                    Key::Enter | Key::Backspace | Key::Other => None,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(state: &mut State, keys: &str) -> Vec<Cursor> {
+        keys.chars()
+            .filter_map(|c| state.key(Key::Character(c.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn normal_mode() {
+        let mut state = State::default();
+
+        assert!(press(&mut state, "jk").is_empty());
+
+        state.enter_normal();
+        assert_eq!(state.status().as_deref(), Some("-- NORMAL --"));
+
+        assert_eq!(
+            press(&mut state, "jkggGgjrdjdd"),
+            [
+                Cursor::Newer,
+                Cursor::Older,
+                Cursor::Oldest,
+                Cursor::Newest,
+                Cursor::Newer,
+                Cursor::Reply,
+                Cursor::Newer,
+                Cursor::Redact,
+            ]
+        );
+
+        assert_eq!(press(&mut state, "i"), [Cursor::Clear]);
+        assert!(state.is_insert());
+    }
+
+    #[test]
+    fn search() {
+        let mut state = State::default();
+        state.enter_normal();
+
+        assert!(press(&mut state, "n/lunch").is_empty());
+        assert_eq!(state.status().as_deref(), Some("/lunch"));
+
+        assert_eq!(
+            state.key(Key::Enter),
+            Some(Cursor::Search {
+                text: "lunch".to_string(),
+                older: true,
+            })
+        );
+        assert_eq!(
+            press(&mut state, "N"),
+            [Cursor::Search {
+                text: "lunch".to_string(),
+                older: false,
+            }]
+        );
+
+        // Erasing past the start of the search leaves it.
+        press(&mut state, "/a");
+        state.key(Key::Backspace);
+        state.key(Key::Backspace);
+        assert_eq!(state.status().as_deref(), Some("-- NORMAL --"));
+    }
+}
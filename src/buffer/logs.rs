@@ -17,7 +17,7 @@ pub enum Event {
     History(Task<history::manager::Message>),
     MarkAsRead,
     OpenUrl(String),
-    ImagePreview(Image),
+    ImagePreview(Image, Vec<url::Url>),
     ExpandMessage(DateTime<Utc>, message::Hash),
     ContractMessage(DateTime<Utc>, message::Hash),
 }
@@ -158,8 +158,8 @@ impl Logs {
                     scroll_view::Event::OpenUrl(url) => {
                         Some(Event::OpenUrl(url))
                     }
-                    scroll_view::Event::ImagePreview(image) => {
-                        Some(Event::ImagePreview(image))
+                    scroll_view::Event::ImagePreview(image, urls) => {
+                        Some(Event::ImagePreview(image, urls))
                     }
                     scroll_view::Event::ExpandMessage(server_time, hash) => {
                         Some(Event::ExpandMessage(server_time, hash))
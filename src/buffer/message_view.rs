@@ -1,6 +1,6 @@
 use std::collections::{BTreeMap, HashMap, HashSet};
 
-use chrono::{DateTime, TimeDelta, Utc};
+use chrono::{DateTime, Utc};
 use data::buffer::RightAlignmentWidths;
 use data::config::buffer::nickname::ShownStatus;
 use data::config::buffer::{CondensationIcon, Dimmed};
@@ -10,7 +10,7 @@ use data::preview::{self, Previews};
 use data::redaction::Redaction;
 use data::server::Server;
 use data::user::{ChannelUsers, NickRef};
-use data::{Config, Preview, User, history, message, metadata, target};
+use data::{Config, Preview, User, history, message, metadata, outbox, target};
 use iced::widget::text::LineHeight;
 use iced::widget::{
     Space, button, center, column, container, mouse_area, right, row, space,
@@ -316,7 +316,7 @@ impl<'a> ChannelQueryLayout<'a> {
             && message.command.is_some()
             && matches!(message.direction, message::Direction::Sent)
             && Utc::now().signed_duration_since(message.server_time)
-                > TimeDelta::seconds(10)
+                > outbox::CONFIRMATION_TIMEOUT
     }
 
     fn not_sent_row(
@@ -426,7 +426,11 @@ impl<'a> ChannelQueryLayout<'a> {
         let inner = preview_content(preview, self.config, self.theme);
 
         let content = match preview {
-            data::Preview::Card(..) => keyed(
+            data::Preview::Card(..)
+            | data::Preview::Video(..)
+            | data::Preview::Code(..)
+            | data::Preview::Snippet(..)
+            | data::Preview::Embed(..) => keyed(
                 keyed::Key::Preview(message.hash, index),
                 button(inner).style(theme::button::preview_card).on_press(
                     Message::Link(message::Link::Url(url.to_string())),
@@ -773,13 +777,34 @@ impl<'a> ChannelQueryLayout<'a> {
             theme::font_style::server(message_theme, server)
         };
 
-        let link = message.expanded.then_some(message::Link::ContractMessage(
-            message.server_time,
-            message.hash,
-        ));
+        // Netsplits only show their summary until expanded
+        let is_netsplit = message.target.source().is_netsplit();
+
+        let link = if message.expanded {
+            Some(message::Link::ContractMessage(
+                message.server_time,
+                message.hash,
+            ))
+        } else if is_netsplit {
+            Some(message::Link::ExpandMessage(
+                message.server_time,
+                message.hash,
+            ))
+        } else {
+            None
+        };
+
+        let hidden_fragments = match &message.content {
+            message::Content::Fragments(fragments)
+                if is_netsplit && !message.expanded =>
+            {
+                (1..fragments.len()).collect()
+            }
+            _ => hidden_fragments.to_vec(),
+        };
 
         let marker_style = move |message_theme: &Theme| {
-            if message.expanded || message.condensed.is_some() {
+            if message.expanded || message.condensed.is_some() || is_netsplit {
                 theme::selectable_text::condensed_marker(message_theme)
             } else {
                 message_style(message_theme)
@@ -789,7 +814,7 @@ impl<'a> ChannelQueryLayout<'a> {
         let marker = message_marker(
             self.condensation_marker(
                 message.expanded,
-                message.condensed.is_some(),
+                message.condensed.is_some() || is_netsplit,
             ),
             right_alignment_middle_width,
             self.config,
@@ -799,7 +824,7 @@ impl<'a> ChannelQueryLayout<'a> {
 
         let message_content = message_content::with_context(
             &message.content,
-            hidden_fragments,
+            &hidden_fragments,
             formatter.server,
             formatter.registry,
             formatter.chantypes,
@@ -1902,7 +1927,11 @@ impl<'a> ChannelQueryLayout<'a> {
             for (_, preview) in &loaded {
                 let el = preview_content(preview, self.config, self.theme);
                 let el: Element<_> = match preview {
-                    data::Preview::Card(..) => button(el)
+                    data::Preview::Card(..)
+                    | data::Preview::Video(..)
+                    | data::Preview::Code(..)
+                    | data::Preview::Snippet(..)
+                    | data::Preview::Embed(..) => button(el)
                         .style(|theme, _| {
                             theme::button::preview_card(
                                 theme,
@@ -1965,7 +1994,7 @@ impl<'a> ChannelQueryLayout<'a> {
     }
 }
 
-fn selected_reactions(
+pub fn selected_reactions(
     message: &data::Message,
     our_nick: Option<NickRef<'_>>,
 ) -> Vec<String> {
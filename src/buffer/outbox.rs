@@ -0,0 +1,79 @@
+use data::{Config, outbox};
+use iced::widget::text::Wrapping;
+use iced::widget::{button, center, column, container, row};
+use iced::{Alignment, Length, padding};
+
+use crate::widget::{Element, Text, text};
+use crate::{Theme, font, icon, theme};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Cancel(outbox::Id),
+}
+
+/// Messages of the buffer waiting for the server to be connected again, or
+/// that failed to be sent, dimmed and listed above the text input.
+pub fn view<'a>(
+    pending: impl Iterator<Item = &'a outbox::Message>,
+    config: &'a Config,
+    theme: &'a Theme,
+) -> Option<Element<'a, Message>> {
+    let font_size = config.font.size.map_or(theme::TEXT_SIZE, f32::from);
+
+    let rows = pending
+        .map(|message| {
+            let status = match (&message.status, &message.content) {
+                (outbox::Status::Failed(error), _) => {
+                    text(format!("failed: {error}"))
+                        .style(theme::text::error)
+                        .font_maybe(
+                            theme::font_style::error(theme).map(font::get),
+                        )
+                }
+                (outbox::Status::Retried { .. }, _) => {
+                    secondary(theme, "sent again")
+                }
+                (outbox::Status::Queued, outbox::Content::Input(_)) => {
+                    secondary(theme, "pending")
+                }
+                (outbox::Status::Queued, outbox::Content::Unconfirmed(_)) => {
+                    secondary(theme, "retrying")
+                }
+            };
+
+            let cancel = button(center(icon::cancel()))
+                .on_press(Message::Cancel(message.id))
+                .padding(5)
+                .width(22)
+                .height(22)
+                .style(|theme, status| {
+                    theme::button::secondary(theme, status, false)
+                });
+
+            row![
+                text(message.content.text().into_owned())
+                    .wrapping(Wrapping::None)
+                    .width(Length::Fill)
+                    .style(theme::text::secondary),
+                status.size(font_size * 0.85),
+                cancel,
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center)
+            .into()
+        })
+        .collect::<Vec<_>>();
+
+    (!rows.is_empty()).then(|| {
+        container(column(rows).spacing(2))
+            .padding(padding::left(8).right(8).bottom(4))
+            .width(Length::Fill)
+            .into()
+    })
+}
+
+fn secondary<'a>(theme: &Theme, content: &'a str) -> Text<'a> {
+    text(content)
+        .style(theme::text::secondary)
+        .font_maybe(theme::font_style::secondary(theme).map(font::get))
+}
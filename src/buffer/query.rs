@@ -1,7 +1,10 @@
+use std::sync::Arc;
+
 use chrono::{DateTime, Utc};
 use data::dashboard::BufferAction;
 use data::history::filter::FilterChain;
 use data::preview::{self, Previews};
+use data::spell_check::Checker;
 use data::target::{self, Target};
 use data::user::Nick;
 use data::{
@@ -11,7 +14,7 @@ use iced::widget::{column, container, stack};
 use iced::{Length, Size, Task, padding};
 
 use super::message_view::{ChannelQueryLayout, TargetInfo};
-use super::{context_menu, input_view, scroll_view, typing};
+use super::{context_menu, input_view, outbox, scroll_view, typing};
 use crate::Theme;
 use crate::widget::Element;
 use crate::window::Window;
@@ -20,8 +23,10 @@ use crate::window::Window;
 pub enum Message {
     ScrollView(scroll_view::Message),
     InputView(input_view::Message),
+    Outbox(outbox::Message),
     FilehostUploadDone { id: u32, url: Option<String> },
     FilesDropped(Vec<std::path::PathBuf>),
+    Run(String),
 }
 
 pub enum Event {
@@ -29,6 +34,10 @@ pub enum Event {
     OpenBuffers(Server, Vec<(Target, BufferAction)>),
     OpenInternalBuffer(buffer::Internal),
     OpenServer(String),
+    Workspace(data::command::Workspace),
+    Schedule(buffer::Upstream, String, DateTime<Utc>),
+    Queue(buffer::Upstream, Vec<String>),
+    CancelQueued(data::outbox::Id),
     Reconnect(Server),
     LeaveBuffers(Vec<Target>, Option<String>),
     History(Task<history::manager::Message>),
@@ -37,7 +46,7 @@ pub enum Event {
     HidePreview(history::Kind, message::Hash, url::Url),
     MarkAsRead(history::Kind),
     OpenUrl(String),
-    ImagePreview(Image),
+    ImagePreview(Image, Vec<url::Url>),
     ExpandMessage(DateTime<Utc>, message::Hash),
     ContractMessage(DateTime<Utc>, message::Hash),
     InputSent {
@@ -58,10 +67,12 @@ pub fn view<'a>(
     typing_animation: Option<&'a typing::Animation>,
     clients: &'a data::client::Map,
     history: &'a history::Manager,
+    outbox: &'a data::outbox::Outbox,
     previews: &'a preview::Collection,
     config: &'a Config,
     theme: &'a Theme,
     is_focused: bool,
+    spell_checker: Option<&'a Arc<Checker>>,
 ) -> Element<'a, Message> {
     let server = &state.server;
     let connected = matches!(clients.status(server), client::Status::Connected);
@@ -152,15 +163,20 @@ pub fn view<'a>(
             &state.input_view,
             our_user.as_ref(),
             None,
+            Some(query.as_str()),
             &state.server,
             registry,
             config,
             theme,
             filehost_url,
+            spell_checker,
         )
         .map(Message::InputView)
     });
 
+    let pending = outbox::view(outbox.pending(&state.buffer), config, theme)
+        .map(|pending| pending.map(Message::Outbox));
+
     let content = column![messages];
 
     let body: Element<'a, Message> =
@@ -174,12 +190,13 @@ pub fn view<'a>(
 
             column![
                 stack![content, typing_overlay].height(Length::Fill),
+                pending,
                 text_input
             ]
             .height(Length::Fill)
             .into()
         } else {
-            column![column![content].height(Length::Fill), text_input]
+            column![column![content].height(Length::Fill), pending, text_input]
                 .height(Length::Fill)
                 .into()
         };
@@ -232,6 +249,7 @@ impl Query {
         history: &mut history::Manager,
         main_window: &Window,
         config: &Config,
+        spell_checker: Option<&Arc<Checker>>,
     ) -> (Task<Message>, Option<Event>) {
         match message {
             Message::ScrollView(message) => {
@@ -264,6 +282,7 @@ impl Query {
                         history,
                         main_window,
                         config,
+                        spell_checker,
                     );
 
                     return (
@@ -310,8 +329,8 @@ impl Query {
                     scroll_view::Event::OpenUrl(url) => {
                         Some(Event::OpenUrl(url))
                     }
-                    scroll_view::Event::ImagePreview(image) => {
-                        Some(Event::ImagePreview(image))
+                    scroll_view::Event::ImagePreview(image, urls) => {
+                        Some(Event::ImagePreview(image, urls))
                     }
                     scroll_view::Event::ExpandMessage(server_time, hash) => {
                         Some(Event::ExpandMessage(server_time, hash))
@@ -331,6 +350,7 @@ impl Query {
                     history,
                     main_window,
                     config,
+                    spell_checker,
                 );
                 let command = command.map(Message::InputView);
 
@@ -371,9 +391,44 @@ impl Query {
                     Some(input_view::Event::OpenServer(server)) => {
                         (command, Some(Event::OpenServer(server)))
                     }
+                    Some(input_view::Event::Workspace(workspace)) => {
+                        (command, Some(Event::Workspace(workspace)))
+                    }
+                    Some(input_view::Event::Schedule { buffer, text, at }) => {
+                        (command, Some(Event::Schedule(buffer, text, at)))
+                    }
+                    Some(input_view::Event::Queue { buffer, lines }) => {
+                        (command, Some(Event::Queue(buffer, lines)))
+                    }
                     Some(input_view::Event::Reconnect(server)) => {
                         (command, Some(Event::Reconnect(server)))
                     }
+                    Some(input_view::Event::Navigate(cursor)) => {
+                        let command = Task::batch(vec![
+                            command,
+                            self.scroll_view
+                                .update_cursor(
+                                    cursor,
+                                    scroll_view::Kind::Query(
+                                        &self.server,
+                                        &self.target,
+                                    ),
+                                    history,
+                                    clients.nickname(&self.server),
+                                    if clients
+                                        .get_server_can_redact(&self.server)
+                                    {
+                                        scroll_view::Redaction::Own
+                                    } else {
+                                        scroll_view::Redaction::None
+                                    },
+                                    config,
+                                )
+                                .map(Message::ScrollView),
+                        ]);
+
+                        (command, None)
+                    }
                     Some(input_view::Event::FilehostUpload {
                         server,
                         target,
@@ -401,9 +456,21 @@ impl Query {
                     history,
                     main_window,
                     config,
+                    spell_checker,
                 );
                 (task.map(Message::InputView), None)
             }
+            Message::Outbox(outbox::Message::Cancel(id)) => {
+                (Task::none(), Some(Event::CancelQueued(id)))
+            }
+            Message::Run(line) => self.update(
+                Message::InputView(input_view::Message::Run(line)),
+                clients,
+                history,
+                main_window,
+                config,
+                spell_checker,
+            ),
             Message::FilesDropped(paths) => {
                 let (task, event) = self.input_view.update(
                     input_view::Message::FilesSelected(paths),
@@ -412,6 +479,7 @@ impl Query {
                     history,
                     main_window,
                     config,
+                    spell_checker,
                 );
                 (
                     task.map(Message::InputView),
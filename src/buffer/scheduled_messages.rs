@@ -0,0 +1,122 @@
+use chrono::{Local, Utc};
+use data::buffer::Upstream;
+use data::{client, schedule};
+use iced::widget::{
+    Scrollable, button, center, column, container, row, scrollable, text,
+};
+use iced::{Length, alignment, padding};
+
+use crate::widget::Element;
+use crate::{Theme, font, icon, theme};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Cancel(schedule::Id),
+}
+
+pub enum Event {
+    Cancel(schedule::Id),
+}
+
+pub fn view<'a>(
+    _state: &ScheduledMessages,
+    scheduled: &'a schedule::Scheduled,
+    clients: &'a client::Map,
+    theme: &'a Theme,
+) -> Element<'a, Message> {
+    if scheduled.is_empty() {
+        return center(container(
+            text("No scheduled messages")
+                .style(theme::text::secondary)
+                .font_maybe(theme::font_style::secondary(theme).map(font::get)),
+        ))
+        .into();
+    }
+
+    let now = Utc::now();
+
+    let column = column(scheduled.list().enumerate().map(|(idx, message)| {
+        let secondary = |content: String| {
+            text(content)
+                .style(theme::text::secondary)
+                .font_maybe(theme::font_style::secondary(theme).map(font::get))
+        };
+
+        let target = match &message.buffer {
+            Upstream::Server(server) => server.to_string(),
+            Upstream::Channel(server, channel) => {
+                format!("{channel} on {server}")
+            }
+            Upstream::Query(server, query) => format!("{query} on {server}"),
+        };
+
+        let at = message.at.with_timezone(&Local).format("%Y-%m-%d %H:%M");
+
+        let status = if let Some(error) = &message.failed {
+            text(format!("Failed to send to {target} at {at}: {error}"))
+                .style(theme::text::error)
+                .font_maybe(theme::font_style::error(theme).map(font::get))
+        } else if message.at > now {
+            secondary(format!("To {target} at {at}"))
+        } else if clients.get_server_is_connected(message.buffer.server()) {
+            secondary(format!("To {target}, waiting to join since {at}"))
+        } else {
+            secondary(format!("To {target}, waiting to connect since {at}"))
+        };
+
+        let content = column![text(message.text.as_str()), status]
+            // Add 1 padding to make container odd sized
+            // for proper icon centering
+            .padding(padding::top(1))
+            .width(Length::Fill)
+            .spacing(0);
+
+        let cancel = button(center(icon::cancel()))
+            .on_press(Message::Cancel(message.id))
+            .padding(5)
+            .width(22)
+            .height(22)
+            .style(|theme, status| {
+                theme::button::secondary(theme, status, false)
+            });
+
+        container(
+            row![content, cancel]
+                .spacing(6)
+                .align_y(iced::Alignment::Center),
+        )
+        .padding(padding::top(6).bottom(6).right(4).left(8))
+        .width(Length::Fill)
+        .align_y(alignment::Vertical::Center)
+        .style(move |theme| theme::container::table(theme, idx))
+        .into()
+    }))
+    .spacing(1)
+    .padding([0, 2]);
+
+    container(
+        Scrollable::new(column)
+            .direction(scrollable::Direction::Vertical(
+                scrollable::Scrollbar::new().width(1).scroller_width(1),
+            ))
+            .style(theme::scrollable::hidden),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .into()
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ScheduledMessages;
+
+impl ScheduledMessages {
+    pub fn new() -> Self {
+        ScheduledMessages
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::Cancel(id) => Some(Event::Cancel(id)),
+        }
+    }
+}
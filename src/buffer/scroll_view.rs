@@ -14,6 +14,7 @@ use data::rate_limit::TokenPriority;
 use data::reaction::Reaction;
 use data::server::Server;
 use data::target::{self, Target};
+use data::user::NickRef;
 use data::{Config, Image, Preview, client, history, metadata, reaction};
 use iced::widget::{
     self, Scrollable, button, column, container, row, rule, scrollable, space,
@@ -23,12 +24,15 @@ use iced::{Length, Size, Task, padding};
 use tokio::time;
 
 use self::correct_viewport::correct_viewport;
+use self::find::Find;
 use self::keyed::keyed;
-use super::context_menu;
+use super::{context_menu, message_view};
 use crate::widget::user_display::UserDisplay;
 use crate::widget::{Element, notify_visibility, on_resize};
 use crate::{Theme, buffer, font, theme};
 
+mod find;
+
 const SCROLL_TO_TIMEOUT: Duration = Duration::from_millis(200);
 /// Pages of off-screen messages to keep rendered above and below the viewport
 const BUFFER_PAGES: usize = 3;
@@ -36,6 +40,7 @@ const BUFFER_PAGES: usize = 3;
 const HIGHLIGHT_HOLD_MS: u64 = 2000;
 const HIGHLIGHT_ALPHA_START: f32 = 1.0;
 const HOVER_HIGHLIGHT_ALPHA: f32 = 0.4;
+const CURSOR_HIGHLIGHT_ALPHA: f32 = 0.6;
 const HIGHLIGHT_ALPHA_TICK_MS: u64 = 20;
 const HIGHLIGHT_ALPHA_STEP: f32 =
     HIGHLIGHT_ALPHA_START / (400.0 / HIGHLIGHT_ALPHA_TICK_MS as f32);
@@ -77,6 +82,7 @@ pub enum Message {
         msgid: message::Id,
         text: Cow<'static, str>,
     },
+    Find(find::Message),
 }
 
 impl From<context_menu::Message> for Message {
@@ -95,16 +101,66 @@ pub enum Event {
     HidePreview(history::Kind, message::Hash, url::Url),
     MarkAsRead,
     OpenUrl(String),
-    ImagePreview(Image),
+    ImagePreview(Image, Vec<url::Url>),
     ExpandMessage(DateTime<Utc>, message::Hash),
     ContractMessage(DateTime<Utc>, message::Hash),
 }
 
+/// Changes to the message cursor of the vi key bindings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cursor {
+    Older,
+    Newer,
+    Oldest,
+    Newest,
+    /// Moves to the nearest message containing `text`, wrapping around.
+    Search {
+        text: String,
+        older: bool,
+    },
+    Reply,
+    Copy,
+    React,
+    Redact,
+    Clear,
+}
+
+/// Which messages the message cursor may redact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Redaction {
+    /// The server doesn't support redaction
+    None,
+    Own,
+    /// Operators of the channel may redact any message
+    Any,
+}
+
+impl Redaction {
+    fn allows(
+        self,
+        message: &data::Message,
+        our_nick: Option<NickRef<'_>>,
+    ) -> bool {
+        if message.redaction.is_some() {
+            return false;
+        }
+
+        match self {
+            Redaction::None => false,
+            Redaction::Own => message.user().is_some_and(|user| {
+                our_nick.is_some_and(|nick| user.nickname() == nick)
+            }),
+            Redaction::Any => message.user().is_some(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Kind<'a> {
     Server(&'a Server),
     Channel(&'a Server, &'a target::Channel),
     Query(&'a Server, &'a target::Query),
+    ServerNotices(&'a Server),
     Logs,
     Highlights,
 }
@@ -114,7 +170,8 @@ impl Kind<'_> {
         match self {
             Kind::Server(server)
             | Kind::Channel(server, _)
-            | Kind::Query(server, _) => Some(server),
+            | Kind::Query(server, _)
+            | Kind::ServerNotices(server) => Some(server),
             Kind::Logs | Kind::Highlights => None,
         }
     }
@@ -130,6 +187,9 @@ impl From<Kind<'_>> for history::Kind {
             Kind::Query(server, nick) => {
                 history::Kind::Query(server.clone(), nick.clone())
             }
+            Kind::ServerNotices(server) => {
+                history::Kind::ServerNotices(server.clone())
+            }
             Kind::Logs => history::Kind::Logs,
             Kind::Highlights => history::Kind::Highlights,
         }
@@ -486,6 +546,13 @@ pub fn view<'a>(
 
                 *prev_message = Some(message);
 
+                // Show the matches of the find bar
+                let message = state
+                    .find
+                    .as_ref()
+                    .and_then(|find| find.highlighted(message.hash))
+                    .unwrap_or(message);
+
                 Some(
                     formatter
                         .format(
@@ -520,6 +587,19 @@ pub fn view<'a>(
                             theme::container::highlighted_message(theme, alpha)
                         })
                         .into()
+                } else if state.cursor == Some(message.hash)
+                    || state.find.as_ref().and_then(Find::current)
+                        == Some(message.hash)
+                {
+                    container(element)
+                        .width(Length::Fill)
+                        .style(move |theme| {
+                            theme::container::highlighted_message(
+                                theme,
+                                CURSOR_HIGHLIGHT_ALPHA,
+                            )
+                        })
+                        .into()
                 } else if state.hover_highlighted_message == Some(message.hash)
                 {
                     container(element)
@@ -759,7 +839,7 @@ pub fn view<'a>(
         Message::ContentResized,
     );
 
-    correct_viewport(
+    let scrollable = correct_viewport(
         Scrollable::new(container(content).width(Length::Fill).padding([0, 8]))
             .direction(scrollable::Direction::Vertical(
                 scrollable::Scrollbar::default()
@@ -778,7 +858,13 @@ pub fn view<'a>(
             .id(state.scrollable.clone()),
         state.scrollable.clone(),
         matches!(state.status, Status::Unlocked),
-    )
+    );
+
+    if let Some(find) = &state.find {
+        column![find.view(config, theme).map(Message::Find), scrollable].into()
+    } else {
+        scrollable
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -800,6 +886,8 @@ pub struct State {
     pending_preview_exits: HashSet<message::Hash>,
     reply_preview_urls: HashMap<message::Hash, Vec<url::Url>>,
     hovered_preview: Option<(message::Hash, usize)>,
+    cursor: Option<message::Hash>,
+    find: Option<Find>,
 }
 
 impl State {
@@ -825,6 +913,8 @@ impl State {
             pending_preview_exits: HashSet::new(),
             reply_preview_urls: HashMap::new(),
             hovered_preview: None,
+            cursor: None,
+            find: None,
         }
     }
 
@@ -1074,7 +1164,15 @@ impl State {
             }) => {
                 self.is_scrolling_to = false;
 
-                let fade_task = if let keyed::Key::Message(hash) = key {
+                let is_cursor = matches!(
+                    key,
+                    keyed::Key::Message(hash) if self.cursor == Some(hash)
+                );
+
+                // The cursor is highlighted already
+                let fade_task = if let keyed::Key::Message(hash) = key
+                    && !is_cursor
+                {
                     self.highlight_generation += 1;
                     let generation = self.highlight_generation;
                     self.highlighted_message =
@@ -1100,7 +1198,15 @@ impl State {
                     return (fade_task, None);
                 }
 
-                let offset = content_y.max(0.0).min(max_offset);
+                // Keep the cursor at the bottom when it moves down past the
+                // viewport
+                let offset = if is_cursor && content_y > viewport_top {
+                    content_y + hit_bounds.height - scrollable.viewport.height
+                } else {
+                    content_y
+                }
+                .max(0.0)
+                .min(max_offset);
 
                 if (offset - max_offset).abs() <= f32::EPSILON {
                     self.status = Status::Bottom;
@@ -1155,6 +1261,63 @@ impl State {
                     Some(Event::ContractMessage(server_time, hash)),
                 );
             }
+            Message::Find(message) => {
+                let Some(find) = &mut self.find else {
+                    return (Task::none(), None);
+                };
+
+                match message {
+                    find::Message::Older => {
+                        let task = self.find_older(kind, history, config);
+
+                        let supports_chathistory =
+                            kind.server().is_some_and(|server| {
+                                clients.get_server_supports_chathistory(server)
+                            });
+
+                        // Fetch older history when out of loaded matches
+                        if let Some(find) = &mut self.find
+                            && find.awaiting_older
+                        {
+                            if supports_chathistory {
+                                return (
+                                    task,
+                                    Some(Event::RequestOlderChatHistory),
+                                );
+                            }
+
+                            find.awaiting_older = false;
+                        }
+
+                        return (task, None);
+                    }
+                    find::Message::Newer => {
+                        return (
+                            find.step(false).map_or_else(Task::none, |hash| {
+                                self.scroll_to_message(
+                                    hash, kind, history, config,
+                                )
+                            }),
+                            None,
+                        );
+                    }
+                    find::Message::Close => {
+                        self.find = None;
+                    }
+                    message => {
+                        if find.update(&message) {
+                            // Jump to the newest match while typing
+                            let task = self.find_older(kind, history, config);
+
+                            if let Some(find) = &mut self.find {
+                                find.awaiting_older = false;
+                            }
+
+                            return (task, None);
+                        }
+                    }
+                }
+            }
             Message::RequestOlderChatHistory => {
                 if let Some(server) = kind.server() {
                     self.status = Status::Unlocked;
@@ -1230,9 +1393,16 @@ impl State {
             }
             Message::ContentResized(size) => {
                 self.content_size = size;
+
+                // Older history may have loaded the match asked for
+                if self.find.as_ref().is_some_and(|find| find.awaiting_older) {
+                    return (self.find_older(kind, history, config), None);
+                }
             }
             Message::ImagePreview(image) => {
-                return (Task::none(), Some(Event::ImagePreview(image)));
+                let urls = self.preview_urls(kind, history, config);
+
+                return (Task::none(), Some(Event::ImagePreview(image, urls)));
             }
             Message::PendingScrollTo => {
                 if let Some(key) = &self.pending_scroll_to {
@@ -1539,6 +1709,221 @@ impl State {
             .map(Message::HeightsCollected)
     }
 
+    /// Opens the find bar, or focuses it when already open.
+    pub fn find(
+        &mut self,
+        kind: Kind,
+        history: &history::Manager,
+        config: &Config,
+    ) -> Task<Message> {
+        if self.find.is_none() {
+            self.find = Some(Find::default());
+            self.refresh_find(kind, history, config);
+        }
+
+        self.find.as_ref().map_or_else(Task::none, Find::focus)
+    }
+
+    fn refresh_find(
+        &mut self,
+        kind: Kind,
+        history: &history::Manager,
+        config: &Config,
+    ) {
+        let Some(find) = &mut self.find else {
+            return;
+        };
+
+        if let Some(history::View {
+            old_messages,
+            new_messages,
+            ..
+        }) = history.get_messages(&kind.into(), None, config)
+        {
+            find.refresh(old_messages.into_iter().chain(new_messages));
+        }
+    }
+
+    /// Scrolls to the next older match of the find bar.  When none is
+    /// loaded the find bar waits for older history instead.
+    fn find_older(
+        &mut self,
+        kind: Kind,
+        history: &history::Manager,
+        config: &Config,
+    ) -> Task<Message> {
+        self.refresh_find(kind, history, config);
+
+        let Some(find) = &mut self.find else {
+            return Task::none();
+        };
+
+        if let Some(hash) = find.step(true) {
+            find.awaiting_older = false;
+
+            self.scroll_to_message(hash, kind, history, config)
+        } else {
+            find.awaiting_older = true;
+
+            Task::none()
+        }
+    }
+
+    /// Moves the message cursor or acts on the message under it.  Only the
+    /// messages loaded in the buffer are searched.
+    pub fn update_cursor(
+        &mut self,
+        cursor: Cursor,
+        kind: Kind,
+        history: &history::Manager,
+        our_nick: Option<NickRef<'_>>,
+        redaction: Redaction,
+        config: &Config,
+    ) -> Task<Message> {
+        let Some(history::View {
+            old_messages,
+            new_messages,
+            ..
+        }) = history.get_messages(&kind.into(), Some(self.limit), config)
+        else {
+            return Task::none();
+        };
+
+        let messages = old_messages
+            .into_iter()
+            .chain(new_messages)
+            .collect::<Vec<_>>();
+
+        let current = self
+            .cursor
+            .and_then(|hash| messages.iter().position(|m| m.hash == hash));
+
+        let target = match cursor {
+            Cursor::Older => current
+                .map_or(messages.len().checked_sub(1), |index| {
+                    Some(index.saturating_sub(1))
+                }),
+            Cursor::Newer => current
+                .map_or(messages.len().checked_sub(1), |index| {
+                    Some((index + 1).min(messages.len() - 1))
+                }),
+            Cursor::Oldest => {
+                self.cursor = history
+                    .get_messages(&kind.into(), None, config)
+                    .and_then(|view| {
+                        view.old_messages
+                            .into_iter()
+                            .chain(view.new_messages)
+                            .next()
+                            .map(|message| message.hash)
+                    });
+
+                return self.scroll_to_start(config);
+            }
+            Cursor::Newest => {
+                self.cursor = messages.last().map(|message| message.hash);
+
+                return self.scroll_to_end(config);
+            }
+            Cursor::Search { text, older } => {
+                let text = text.to_lowercase();
+                let start = current.unwrap_or(messages.len());
+                let len = messages.len();
+
+                (1..=len)
+                    .map(|step| {
+                        if older {
+                            (start + len * 2 - step) % len
+                        } else {
+                            (start + step) % len
+                        }
+                    })
+                    .find(|index| {
+                        messages[*index].text().to_lowercase().contains(&text)
+                    })
+            }
+            Cursor::Reply | Cursor::Copy | Cursor::React | Cursor::Redact => {
+                let Some(message) = current.map(|index| messages[index]) else {
+                    return Task::none();
+                };
+
+                let msgid = message.id.clone();
+
+                let message = match cursor {
+                    Cursor::Reply => msgid
+                        .zip(message.target.source().user())
+                        .map(|(msgid, user)| context_menu::Message::Reply {
+                            msgid,
+                            server_time: message.server_time,
+                            to_nick: user.nickname().to_owned(),
+                        }),
+                    Cursor::Copy => Some(context_menu::Message::CopyText(
+                        message.text().into_owned(),
+                    )),
+                    Cursor::React => msgid.map(|msgid| {
+                        context_menu::Message::OpenReactionModal(
+                            msgid,
+                            message_view::selected_reactions(message, our_nick),
+                        )
+                    }),
+                    // Opens the redaction modal to confirm
+                    _ => msgid
+                        .filter(|_| redaction.allows(message, our_nick))
+                        .map(context_menu::Message::Redact),
+                };
+
+                return message.map_or_else(Task::none, |message| {
+                    Task::done(Message::ContextMenu(message))
+                });
+            }
+            Cursor::Clear => {
+                self.cursor = None;
+                return Task::none();
+            }
+        };
+
+        let Some(message) = target.and_then(|index| messages.get(index)) else {
+            return Task::none();
+        };
+
+        self.cursor = Some(message.hash);
+        self.pending_scroll_to = Some(keyed::Key::Message(message.hash));
+
+        Task::done(Message::PendingScrollTo)
+    }
+
+    /// Urls with a preview in the loaded messages, in the order they appear
+    fn preview_urls(
+        &self,
+        kind: Kind,
+        history: &history::Manager,
+        config: &Config,
+    ) -> Vec<url::Url> {
+        let Some(history::View {
+            old_messages,
+            new_messages,
+            ..
+        }) = history.get_messages(&kind.into(), Some(self.limit), config)
+        else {
+            return vec![];
+        };
+
+        old_messages
+            .iter()
+            .chain(&new_messages)
+            .filter_map(|message| {
+                let urls = self.visible_url_messages.get(&message.hash)?;
+
+                Some(
+                    urls.iter()
+                        .filter(|url| !message.hidden_urls.contains(*url)),
+                )
+            })
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
     pub fn visible_urls(&self) -> impl Iterator<Item = &url::Url> {
         self.visible_url_messages
             .values()
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use data::history::find::{self, Query, Scope};
+use data::{Config, message};
+use iced::widget::{
+    button, center, container, operation, row, text, text_input,
+};
+use iced::{Alignment, Length, Task, widget};
+
+use crate::widget::Element;
+use crate::{Theme, font, icon, theme};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Input(String),
+    ToggleScope,
+    ToggleRegex,
+    Older,
+    Newer,
+    Close,
+}
+
+/// Find bar of a buffer, searching the messages loaded for its
+/// `history::Kind`.
+#[derive(Debug, Clone)]
+pub struct Find {
+    input_id: widget::Id,
+    text: String,
+    scope: Scope,
+    is_regex: bool,
+    query: Result<Option<Query>, find::Error>,
+    /// Hashes of the matching messages, oldest first.
+    matches: Vec<message::Hash>,
+    current: Option<message::Hash>,
+    /// Copies of the matching messages with the matches highlighted.
+    highlighted: HashMap<message::Hash, data::Message>,
+    /// Older history has been requested since there was no older match.
+    pub awaiting_older: bool,
+}
+
+impl Default for Find {
+    fn default() -> Self {
+        Self {
+            input_id: widget::Id::unique(),
+            text: String::new(),
+            scope: Scope::default(),
+            is_regex: false,
+            query: Ok(None),
+            matches: vec![],
+            current: None,
+            highlighted: HashMap::new(),
+            awaiting_older: false,
+        }
+    }
+}
+
+impl Find {
+    pub fn focus<T: Send + 'static>(&self) -> Task<T> {
+        operation::focus(self.input_id.clone())
+    }
+
+    /// Applies a change to the search, returning whether the query changed.
+    pub fn update(&mut self, message: &Message) -> bool {
+        match message {
+            Message::Input(text) => self.text.clone_from(text),
+            Message::ToggleScope => {
+                self.scope = match self.scope {
+                    Scope::Content => Scope::Nick,
+                    Scope::Nick => Scope::Content,
+                };
+            }
+            Message::ToggleRegex => self.is_regex = !self.is_regex,
+            Message::Older | Message::Newer | Message::Close => return false,
+        }
+
+        self.query = Query::new(&self.text, self.scope, self.is_regex);
+        self.current = None;
+        self.awaiting_older = false;
+
+        true
+    }
+
+    /// Finds the matches among `messages`, which are ordered oldest first.
+    pub fn refresh<'a>(
+        &mut self,
+        messages: impl IntoIterator<Item = &'a data::Message>,
+    ) {
+        self.matches.clear();
+        self.highlighted.clear();
+
+        let Ok(Some(query)) = &self.query else {
+            return;
+        };
+
+        for message in messages {
+            if query.matches(message) {
+                self.matches.push(message.hash);
+
+                if let Some(content) = query.highlight(&message.content) {
+                    self.highlighted.insert(
+                        message.hash,
+                        data::Message {
+                            content,
+                            ..message.clone()
+                        },
+                    );
+                }
+            }
+        }
+
+        if self
+            .current
+            .is_some_and(|current| !self.matches.contains(&current))
+        {
+            self.current = None;
+        }
+    }
+
+    /// Moves to the next older or newer match.  Returns `None` when there
+    /// is no such match among the loaded messages.
+    pub fn step(&mut self, older: bool) -> Option<message::Hash> {
+        let index = match self.current.and_then(|current| {
+            self.matches.iter().position(|hash| *hash == current)
+        }) {
+            Some(index) if older => index.checked_sub(1)?,
+            Some(index) => index + 1,
+            None => self.matches.len().checked_sub(1)?,
+        };
+
+        let hash = *self.matches.get(index)?;
+
+        self.current = Some(hash);
+
+        Some(hash)
+    }
+
+    pub fn current(&self) -> Option<message::Hash> {
+        self.current
+    }
+
+    pub fn highlighted(&self, hash: message::Hash) -> Option<&data::Message> {
+        self.highlighted.get(&hash)
+    }
+
+    pub fn view<'a>(
+        &'a self,
+        config: &'a Config,
+        theme: &'a Theme,
+    ) -> Element<'a, Message> {
+        let font_size =
+            config.font.size.map_or(theme::TEXT_SIZE, f32::from) - 1.0;
+
+        let input = text_input("Find...", &self.text)
+            .id(self.input_id.clone())
+            .on_input(Message::Input)
+            .on_submit(Message::Older)
+            .size(font_size)
+            .padding([2, 4])
+            .style(if self.query.is_err() {
+                theme::text_input::error
+            } else {
+                theme::text_input::primary
+            });
+
+        let status = match &self.query {
+            Err(error) => error.to_string(),
+            Ok(None) => String::new(),
+            Ok(Some(_)) => {
+                let total = self.matches.len();
+
+                match self.current.and_then(|current| {
+                    self.matches.iter().position(|hash| *hash == current)
+                }) {
+                    // Number matches from the newest
+                    Some(index) => format!("{} of {total}", total - index),
+                    None if self.awaiting_older => "...".to_string(),
+                    None => format!("{total} found"),
+                }
+            }
+        };
+
+        let toggle = |label: &'a str, selected: bool, message: Message| {
+            button(text(label).size(font_size))
+                .padding([2, 6])
+                .style(move |theme, status| {
+                    theme::button::secondary(theme, status, selected)
+                })
+                .on_press(message)
+        };
+
+        let step = |label: &'a str, message: Message| {
+            button(text(label).size(font_size))
+                .padding([2, 6])
+                .style(|theme, status| {
+                    theme::button::secondary(theme, status, false)
+                })
+                .on_press_maybe(
+                    matches!(self.query, Ok(Some(_))).then_some(message),
+                )
+        };
+
+        container(
+            row![
+                input,
+                text(status)
+                    .size(font_size)
+                    .style(if self.query.is_err() {
+                        theme::text::error
+                    } else {
+                        theme::text::secondary
+                    })
+                    .font_maybe(
+                        theme::font_style::secondary(theme).map(font::get)
+                    ),
+                toggle("Nick", self.scope == Scope::Nick, Message::ToggleScope),
+                toggle(".*", self.is_regex, Message::ToggleRegex),
+                step("↑", Message::Older),
+                step("↓", Message::Newer),
+                button(center(icon::cancel().size(font_size)))
+                    .padding(2)
+                    .width(22)
+                    .height(22)
+                    .style(|theme, status| {
+                        theme::button::secondary(theme, status, false)
+                    })
+                    .on_press(Message::Close),
+            ]
+            .spacing(4)
+            .align_y(Alignment::Center),
+        )
+        .padding([4, 8])
+        .width(Length::Fill)
+        .style(theme::container::buffer_title_bar)
+        .into()
+    }
+}
@@ -1,6 +1,9 @@
+use std::sync::Arc;
+
 use chrono::{DateTime, Utc};
 use data::buffer::RightAlignmentWidths;
 use data::dashboard::BufferAction;
+use data::spell_check::Checker;
 use data::target::Target;
 use data::user::Nick;
 use data::{
@@ -10,7 +13,7 @@ use iced::advanced::text;
 use iced::widget::{Space, column, container, row, space};
 use iced::{Color, Length, Size, Task, padding};
 
-use super::{context_menu, input_view, scroll_view};
+use super::{context_menu, input_view, outbox, scroll_view};
 use crate::widget::user_display::UserDisplay;
 use crate::widget::{Element, message_content, selectable_text};
 use crate::window::Window;
@@ -42,8 +45,10 @@ fn row_with_timestamp_and_nick<'a>(
 pub enum Message {
     ScrollView(scroll_view::Message),
     InputView(input_view::Message),
+    Outbox(outbox::Message),
     FilehostUploadDone { id: u32, url: Option<String> },
     FilesDropped(Vec<std::path::PathBuf>),
+    Run(String),
 }
 
 pub enum Event {
@@ -51,12 +56,16 @@ pub enum Event {
     OpenBuffers(data::server::Server, Vec<(Target, BufferAction)>),
     OpenInternalBuffer(buffer::Internal),
     OpenServer(String),
+    Workspace(data::command::Workspace),
+    Schedule(buffer::Upstream, String, DateTime<Utc>),
+    Queue(buffer::Upstream, Vec<String>),
+    CancelQueued(data::outbox::Id),
     Reconnect(data::server::Server),
     LeaveBuffers(Vec<Target>, Option<String>),
     History(Task<history::manager::Message>),
     MarkAsRead(history::Kind),
     OpenUrl(String),
-    ImagePreview(Image),
+    ImagePreview(Image, Vec<url::Url>),
     ExpandMessage(DateTime<Utc>, message::Hash),
     ContractMessage(DateTime<Utc>, message::Hash),
     InputSent {
@@ -76,10 +85,12 @@ pub fn view<'a>(
     state: &'a Server,
     clients: &'a data::client::Map,
     history: &'a history::Manager,
+    outbox: &'a data::outbox::Outbox,
     previews: &'a preview::Collection,
     config: &'a Config,
     theme: &'a Theme,
     is_focused: bool,
+    spell_checker: Option<&'a Arc<Checker>>,
 ) -> Element<'a, Message> {
     let chantypes = clients.get_server_chantypes_or_default(&state.server);
     let prefix = clients.get_server_prefix_or_default(&state.server);
@@ -313,18 +324,24 @@ pub fn view<'a>(
                 &state.input_view,
                 our_user.as_ref(),
                 None,
+                None,
                 &state.server,
                 registry,
                 config,
                 theme,
                 filehost_url,
+                spell_checker,
             )
             .map(Message::InputView)
         ]
         .width(Length::Fill)
     });
 
-    let scrollable = column![messages, text_input,].height(Length::Fill);
+    let pending = outbox::view(outbox.pending(&state.buffer), config, theme)
+        .map(|pending| pending.map(Message::Outbox));
+
+    let scrollable =
+        column![messages, pending, text_input,].height(Length::Fill);
 
     container(scrollable)
         .width(Length::Fill)
@@ -371,6 +388,7 @@ impl Server {
         history: &mut history::Manager,
         main_window: &Window,
         config: &Config,
+        spell_checker: Option<&Arc<Checker>>,
     ) -> (Task<Message>, Option<Event>) {
         match message {
             Message::ScrollView(message) => {
@@ -409,8 +427,8 @@ impl Server {
                     scroll_view::Event::OpenUrl(url) => {
                         Some(Event::OpenUrl(url))
                     }
-                    scroll_view::Event::ImagePreview(image) => {
-                        Some(Event::ImagePreview(image))
+                    scroll_view::Event::ImagePreview(image, urls) => {
+                        Some(Event::ImagePreview(image, urls))
                     }
                     scroll_view::Event::ExpandMessage(server_time, hash) => {
                         Some(Event::ExpandMessage(server_time, hash))
@@ -430,6 +448,7 @@ impl Server {
                     history,
                     main_window,
                     config,
+                    spell_checker,
                 );
                 let command = command.map(Message::InputView);
 
@@ -466,9 +485,35 @@ impl Server {
                     Some(input_view::Event::OpenServer(server)) => {
                         (command, Some(Event::OpenServer(server)))
                     }
+                    Some(input_view::Event::Workspace(workspace)) => {
+                        (command, Some(Event::Workspace(workspace)))
+                    }
+                    Some(input_view::Event::Schedule { buffer, text, at }) => {
+                        (command, Some(Event::Schedule(buffer, text, at)))
+                    }
+                    Some(input_view::Event::Queue { buffer, lines }) => {
+                        (command, Some(Event::Queue(buffer, lines)))
+                    }
                     Some(input_view::Event::Reconnect(server)) => {
                         (command, Some(Event::Reconnect(server)))
                     }
+                    Some(input_view::Event::Navigate(cursor)) => {
+                        let command = Task::batch(vec![
+                            command,
+                            self.scroll_view
+                                .update_cursor(
+                                    cursor,
+                                    scroll_view::Kind::Server(&self.server),
+                                    history,
+                                    clients.nickname(&self.server),
+                                    scroll_view::Redaction::None,
+                                    config,
+                                )
+                                .map(Message::ScrollView),
+                        ]);
+
+                        (command, None)
+                    }
                     Some(input_view::Event::FilehostUpload {
                         server,
                         target,
@@ -496,9 +541,21 @@ impl Server {
                     history,
                     main_window,
                     config,
+                    spell_checker,
                 );
                 (task.map(Message::InputView), None)
             }
+            Message::Outbox(outbox::Message::Cancel(id)) => {
+                (Task::none(), Some(Event::CancelQueued(id)))
+            }
+            Message::Run(line) => self.update(
+                Message::InputView(input_view::Message::Run(line)),
+                clients,
+                history,
+                main_window,
+                config,
+                spell_checker,
+            ),
             Message::FilesDropped(paths) => {
                 let (task, event) = self.input_view.update(
                     input_view::Message::FilesSelected(paths),
@@ -507,6 +564,7 @@ impl Server {
                     history,
                     main_window,
                     config,
+                    spell_checker,
                 );
                 (
                     task.map(Message::InputView),
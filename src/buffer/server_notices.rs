@@ -0,0 +1,233 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use data::message::ServerNotice;
+use data::{Config, Image, Preview, client, history, message};
+use iced::widget::{button, column, container, row, text};
+use iced::{Color, Length, Size, Task, padding};
+
+use super::{context_menu, scroll_view};
+use crate::widget::{Element, message_content, selectable_text};
+use crate::{Theme, font, theme};
+
+const KINDS: [ServerNotice; 6] = [
+    ServerNotice::Connect,
+    ServerNotice::Exit,
+    ServerNotice::Kill,
+    ServerNotice::Flood,
+    ServerNotice::Oper,
+    ServerNotice::Other,
+];
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ScrollView(scroll_view::Message),
+    Toggle(ServerNotice),
+}
+
+pub enum Event {
+    ContextMenu(context_menu::Event),
+    MarkAsRead,
+    OpenUrl(String),
+    ImagePreview(Image, Vec<url::Url>),
+    ExpandMessage(DateTime<Utc>, message::Hash),
+    ContractMessage(DateTime<Utc>, message::Hash),
+}
+
+pub fn view<'a>(
+    state: &'a ServerNotices,
+    clients: &'a client::Map,
+    history: &'a history::Manager,
+    config: &'a Config,
+    theme: &'a Theme,
+) -> Element<'a, Message> {
+    let chantypes = clients.get_server_chantypes_or_default(&state.server);
+    let casemapping = clients.get_server_casemapping_or_default(&state.server);
+    let registry = clients.get_registry(&state.server);
+
+    let filters = row(KINDS.into_iter().map(|kind| {
+        let shown = !state.hidden.contains(&kind);
+
+        button(text(label(kind)))
+            .style(move |theme, status| {
+                theme::button::secondary(theme, status, shown)
+            })
+            .on_press(Message::Toggle(kind))
+            .into()
+    }))
+    .spacing(4);
+
+    let messages = container(
+        scroll_view::view(
+            &state.scroll_view,
+            scroll_view::Kind::ServerNotices(&state.server),
+            history,
+            None,
+            Option::<fn(&Preview, &message::Source) -> bool>::None,
+            None,
+            0.0,
+            config,
+            theme,
+            move |message: &'a data::Message, _, _, _| {
+                let message::Source::Server(Some(server)) =
+                    message.target.source()
+                else {
+                    return None;
+                };
+
+                let message::Kind::ServerNotice(kind) = server.kind() else {
+                    return None;
+                };
+
+                if state.hidden.contains(&kind) {
+                    return None;
+                }
+
+                let timestamp = config
+                    .buffer
+                    .format_timestamp(&message.server_time)
+                    .map(|timestamp| {
+                        context_menu::timestamp(
+                            selectable_text(timestamp)
+                                .font_maybe(
+                                    theme::font_style::timestamp(theme)
+                                        .map(font::get),
+                                )
+                                .style(theme::selectable_text::timestamp),
+                            &message.server_time,
+                            config,
+                            theme,
+                        )
+                        .map(scroll_view::Message::ContextMenu)
+                    });
+
+                let content = message_content(
+                    &message.content,
+                    &[],
+                    &state.server,
+                    registry,
+                    chantypes,
+                    casemapping,
+                    theme,
+                    scroll_view::Message::Link,
+                    None,
+                    move |theme| {
+                        theme::selectable_text::server(theme, Some(server))
+                    },
+                    move |theme| theme::font_style::server(theme, Some(server)),
+                    Option::<fn(Color) -> Color>::None,
+                    None,
+                    config,
+                );
+
+                Some(context_menu::message(
+                    container(row![timestamp, selectable_text(" "), content])
+                        .into(),
+                    message,
+                    vec![],
+                    false,
+                    false,
+                    false,
+                    config,
+                    theme,
+                ))
+            },
+            registry,
+        )
+        .map(Message::ScrollView),
+    )
+    .height(Length::Fill);
+
+    container(column![filters, messages].spacing(8))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .padding(padding::all(8).top(4))
+        .into()
+}
+
+fn label(kind: ServerNotice) -> &'static str {
+    match kind {
+        ServerNotice::Connect => "Connects",
+        ServerNotice::Exit => "Exits",
+        ServerNotice::Kill => "Kills",
+        ServerNotice::Flood => "Floods",
+        ServerNotice::Oper => "Opers",
+        ServerNotice::Other => "Other",
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerNotices {
+    pub server: data::server::Server,
+    pub scroll_view: scroll_view::State,
+    pub hidden: HashSet<ServerNotice>,
+}
+
+impl ServerNotices {
+    pub fn new(
+        server: data::server::Server,
+        pane_size: Size,
+        config: &Config,
+    ) -> Self {
+        Self {
+            server,
+            scroll_view: scroll_view::State::new(pane_size, config),
+            hidden: HashSet::new(),
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        message: Message,
+        history: &mut history::Manager,
+        clients: &mut client::Map,
+        config: &Config,
+    ) -> (Task<Message>, Option<Event>) {
+        match message {
+            Message::ScrollView(message) => {
+                let (command, event) = self.scroll_view.update(
+                    message,
+                    false,
+                    scroll_view::Kind::ServerNotices(&self.server),
+                    None,
+                    history,
+                    clients,
+                    config,
+                );
+
+                let event = event.and_then(|event| match event {
+                    scroll_view::Event::ContextMenu(event) => {
+                        Some(Event::ContextMenu(event))
+                    }
+                    scroll_view::Event::OpenBuffer(_, _, _) => None,
+                    scroll_view::Event::GoToMessage(_, _, _) => None,
+                    scroll_view::Event::RequestOlderChatHistory => None,
+                    scroll_view::Event::PreviewChanged => None,
+                    scroll_view::Event::HidePreview(..) => None,
+                    scroll_view::Event::MarkAsRead => Some(Event::MarkAsRead),
+                    scroll_view::Event::OpenUrl(url) => {
+                        Some(Event::OpenUrl(url))
+                    }
+                    scroll_view::Event::ImagePreview(image, urls) => {
+                        Some(Event::ImagePreview(image, urls))
+                    }
+                    scroll_view::Event::ExpandMessage(server_time, hash) => {
+                        Some(Event::ExpandMessage(server_time, hash))
+                    }
+                    scroll_view::Event::ContractMessage(server_time, hash) => {
+                        Some(Event::ContractMessage(server_time, hash))
+                    }
+                });
+
+                (command.map(Message::ScrollView), event)
+            }
+            Message::Toggle(kind) => {
+                if !self.hidden.remove(&kind) {
+                    self.hidden.insert(kind);
+                }
+
+                (Task::none(), None)
+            }
+        }
+    }
+}
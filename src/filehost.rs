@@ -1,7 +1,10 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use data::config::{Proxy, proxy};
+use data::config::server::filehost;
+use data::config::{self, Proxy, proxy};
+use data::fileupload::preprocess;
 use data::target::Target;
 use data::{client, fileupload};
 use iced::Task;
@@ -27,6 +30,7 @@ pub enum Message {
         error: String,
     },
     KnownSaved(Result<(), data::known_filehosts::Error>),
+    Prepared(u64, Vec<Option<preprocess::Processed>>),
 }
 
 pub struct PendingUpload {
@@ -49,12 +53,19 @@ pub enum Event {
         has_credentials: bool,
         window: window::Id,
     },
+    ConfirmImages {
+        images: Vec<preprocess::Processed>,
+        window: window::Id,
+    },
 }
 
 pub struct Manager {
     pub file_being_hovered: bool,
     known: data::KnownFilehosts,
     pending: Vec<PendingUpload>,
+    next_batch: u64,
+    preparing: HashMap<u64, PendingUpload>,
+    confirming: Vec<PendingUpload>,
 }
 
 impl Manager {
@@ -63,7 +74,124 @@ impl Manager {
             file_being_hovered: false,
             known: data::KnownFilehosts::load(),
             pending: vec![],
+            next_batch: 0,
+            preparing: HashMap::new(),
+            confirming: vec![],
+        }
+    }
+
+    /// Called when buffer wants to upload files.
+    /// Prepares images as configured before uploading them
+    pub fn prepare(
+        &mut self,
+        pending: PendingUpload,
+        config: &config::filehost::Images,
+    ) -> Task<Message> {
+        self.next_batch += 1;
+        let batch = self.next_batch;
+
+        let file_paths = pending.file_paths.clone();
+        let config = config.clone();
+
+        self.preparing.insert(batch, pending);
+
+        Task::perform(
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    file_paths
+                        .iter()
+                        .map(|path| {
+                            preprocess::process(path, &config).unwrap_or_else(
+                                |error| {
+                                    log::warn!(
+                                        "failed to prepare {} for upload: {error}",
+                                        path.display()
+                                    );
+                                    None
+                                },
+                            )
+                        })
+                        .collect()
+                })
+                .await
+                .unwrap_or_default()
+            },
+            move |processed| Message::Prepared(batch, processed),
+        )
+    }
+
+    /// Handle prepared images, asking for confirmation before uploading
+    /// them if configured
+    pub fn prepared(
+        &mut self,
+        batch: u64,
+        processed: Vec<Option<preprocess::Processed>>,
+        confirm: bool,
+        clients: &client::Map,
+        default_http_client: Option<Arc<reqwest::Client>>,
+        default_proxy_config: &Option<Proxy>,
+    ) -> (Task<Message>, Option<Event>) {
+        let Some(mut pending) = self.preparing.remove(&batch) else {
+            for processed in processed.into_iter().flatten() {
+                fileupload::remove_temporary(&processed.path);
+            }
+
+            return (Task::none(), None);
+        };
+
+        let mut images = vec![];
+
+        for (path, processed) in pending.file_paths.iter_mut().zip(processed) {
+            if let Some(processed) = processed {
+                fileupload::remove_temporary(path);
+                path.clone_from(&processed.path);
+                images.push(processed);
+            }
+        }
+
+        if confirm && !images.is_empty() {
+            let window = pending.window;
+            self.confirming.push(pending);
+
+            (Task::none(), Some(Event::ConfirmImages { images, window }))
+        } else {
+            self.upload(
+                pending,
+                clients,
+                default_http_client,
+                default_proxy_config,
+            )
+        }
+    }
+
+    /// Handle user confirming the prepared images
+    pub fn proceed_with_images(
+        &mut self,
+        clients: &client::Map,
+        default_http_client: Option<Arc<reqwest::Client>>,
+        default_proxy_config: &Option<Proxy>,
+    ) -> (Task<Message>, Option<Event>) {
+        let mut tasks = vec![];
+        let mut event = None;
+
+        for pending in std::mem::take(&mut self.confirming) {
+            let (task, upload_event) = self.upload(
+                pending,
+                clients,
+                default_http_client.clone(),
+                default_proxy_config,
+            );
+
+            tasks.push(task);
+            event = upload_event.or(event);
         }
+
+        (Task::batch(tasks), event)
+    }
+
+    /// Handle user cancelling the prepared images
+    pub fn cancel_images(&mut self) -> Task<Message> {
+        cancelled(std::mem::take(&mut self.confirming))
     }
 
     /// Called when buffer wants to upload files.
@@ -98,6 +226,9 @@ impl Manager {
                     "[{}] File upload disabled: Unable to build HTTP client",
                     pending.server
                 );
+                for path in &pending.file_paths {
+                    fileupload::remove_temporary(path);
+                }
                 return (Task::none(), None);
             };
 
@@ -156,6 +287,9 @@ impl Manager {
                 let Some(http_client) = http_client else {
                     // Detailed HTTP client build error should already be in the logs
                     log::warn!("[{}] File upload disabled: Unable to build HTTP client", p.server);
+                    for path in &p.file_paths {
+                        fileupload::remove_temporary(path);
+                    }
                     return Task::none();
                 };
 
@@ -174,28 +308,35 @@ impl Manager {
 
     /// Handle user cancel
     pub fn cancel(&mut self) -> Task<Message> {
-        let pending = std::mem::take(&mut self.pending);
-        if pending.is_empty() {
-            return Task::none();
-        }
+        cancelled(std::mem::take(&mut self.pending))
+    }
+}
 
-        let tasks: Vec<_> = pending
-            .into_iter()
-            .flat_map(|p| {
-                p.upload_ids.into_iter().map(move |id| {
-                    Task::done(Message::UploadDone {
-                        window: p.window,
-                        pane_id: p.pane_id,
-                        target: p.target.clone(),
-                        id,
-                        url: None,
-                    })
+fn cancelled(pending: Vec<PendingUpload>) -> Task<Message> {
+    if pending.is_empty() {
+        return Task::none();
+    }
+
+    let tasks: Vec<_> = pending
+        .into_iter()
+        .flat_map(|p| {
+            for path in &p.file_paths {
+                fileupload::remove_temporary(path);
+            }
+
+            p.upload_ids.into_iter().map(move |id| {
+                Task::done(Message::UploadDone {
+                    window: p.window,
+                    pane_id: p.pane_id,
+                    target: p.target.clone(),
+                    id,
+                    url: None,
                 })
             })
-            .collect();
+        })
+        .collect();
 
-        Task::batch(tasks)
-    }
+    Task::batch(tasks)
 }
 
 fn start_tasks(
@@ -223,8 +364,21 @@ fn start_tasks(
         .zip(upload_ids)
         .zip(abort_registrations)
         .map(|((file_path, id), registration)| {
-            let upload_url = upload_url.clone();
-            let auth = clients.get_filehost_auth(&server);
+            let backend = match clients.get_filehost_backend(&server) {
+                filehost::Backend::Soju => fileupload::Backend::Filehost {
+                    url: upload_url.clone(),
+                    auth: clients.get_filehost_auth(&server),
+                    irc_uses_tls,
+                    is_override_url,
+                },
+                filehost::Backend::Multipart(multipart) => {
+                    fileupload::Backend::Multipart(multipart)
+                }
+                filehost::Backend::S3(s3) => fileupload::Backend::S3(s3),
+                filehost::Backend::Command(command) => {
+                    fileupload::Backend::Command(command)
+                }
+            };
             let http_client = http_client.clone();
             let server = server.clone();
             let target = target.clone();
@@ -232,16 +386,18 @@ fn start_tasks(
 
             Task::perform(
                 async move {
-                    let fut = fileupload::upload(
-                        &upload_url,
+                    let fut = backend.upload(
                         &file_path,
-                        auth,
-                        irc_uses_tls,
-                        is_override_url,
                         http_client,
                         proxy_config.as_ref(),
                     );
-                    futures::future::Abortable::new(fut, registration).await
+                    let result =
+                        futures::future::Abortable::new(fut, registration)
+                            .await;
+
+                    fileupload::remove_temporary(&file_path);
+
+                    result
                 },
                 move |result| match result {
                     Ok(Ok(url)) => Message::UploadDone {
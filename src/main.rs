@@ -31,6 +31,7 @@ use appearance::{Theme, theme};
 use data::capabilities::LabeledResponseContext;
 use data::client::{self, Destination};
 use data::config::buffer::OnMessage;
+use data::config::buffer::text_input::SpellCheck;
 use data::config::{self, Config, Runtime, runtime};
 use data::history::filter::FilterChain;
 use data::history::manager::{EchoEvent, ReactionToEcho, ReplyToEcho};
@@ -59,12 +60,16 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut args = env::args();
     args.next();
 
-    let version = args.next().is_some_and(|s| s == "--version" || s == "-V");
+    let command = args.next();
 
-    if version {
-        println!("halloy {}", environment::formatted_version());
+    match command.as_deref() {
+        Some("--version" | "-V") => {
+            println!("halloy {}", environment::formatted_version());
 
-        return Ok(());
+            return Ok(());
+        }
+        Some("import-theme") => return import_theme(args),
+        _ => (),
     }
 
     // Prepare crypto provider before any TLS config is built.
@@ -154,6 +159,35 @@ pub fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// `halloy import-theme <path> [name]`
+fn import_theme(
+    mut args: impl Iterator<Item = String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(path) = args.next() else {
+        eprintln!("usage: halloy import-theme <path> [name]");
+
+        // Usage errors exit with 2, as with other command line tools
+        std::process::exit(2);
+    };
+    let name = args.next();
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let imported = rt.block_on(
+        data::appearance::theme::import::import_file(path.into(), name),
+    )?;
+
+    println!(
+        "imported theme \"{}\" to {}",
+        imported.name,
+        imported.path.display()
+    );
+
+    Ok(())
+}
+
 fn settings(config_load: &Result<Config, config::Error>) -> iced::Settings {
     let default_text_size = config_load
         .as_ref()
@@ -200,6 +234,10 @@ fn configure_runtime(runtime: Runtime) -> Task<Message> {
     .map(Message::RuntimeConfigured)
 }
 
+fn load_spell_check(config: SpellCheck) -> Task<Message> {
+    Task::perform(data::spell_check::load(config), Message::SpellCheckLoaded)
+}
+
 fn handle_irc_error(e: anyhow::Error) {
     log::error!("{e:#}");
 }
@@ -209,6 +247,8 @@ struct Halloy {
     screen: Screen,
     current_mode: appearance::Mode,
     theme: Theme,
+    /// Start of the scheduled theme in effect, if themes are scheduled
+    theme_slot: Option<chrono::NaiveTime>,
     config: Config,
     clients: data::client::Map,
     servers: server::Map,
@@ -218,6 +258,7 @@ struct Halloy {
     focused_window: Option<window::Id>,
     pending_logs: Vec<data::log::Record>,
     notifications: Notifications,
+    spell_checker: Option<Arc<data::spell_check::Checker>>,
 }
 
 impl Halloy {
@@ -229,7 +270,11 @@ impl Halloy {
         let main_window = Window::new(main_window);
         let load_dashboard = |config: &Config| match data::Dashboard::load() {
             Ok(dashboard) => {
-                if config.pane.restore_on_launch {
+                // The workspace on launch replaces the restored panes once
+                // workspaces are loaded
+                if config.pane.restore_on_launch
+                    || config.pane.workspace_on_launch.is_some()
+                {
                     screen::Dashboard::restore(dashboard, config, &main_window)
                 } else {
                     screen::Dashboard::empty(&main_window, config)
@@ -287,6 +332,10 @@ impl Halloy {
                 screen,
                 current_mode,
                 theme: current_mode.theme(&config.appearance.selected).into(),
+                theme_slot: config
+                    .appearance
+                    .selected
+                    .scheduled_slot(chrono::Local::now().time()),
                 clients: data::client::Map::default(),
                 servers,
                 controllers: stream::Map::default(),
@@ -296,6 +345,7 @@ impl Halloy {
                 focused_window: None,
                 pending_logs: vec![],
                 notifications,
+                spell_checker: None,
             },
             command,
         )
@@ -333,6 +383,9 @@ pub enum Message {
     ConfigReloaded(Result<Config, config::Error>),
     RuntimeConfigured(Result<(), iced::backend::Error>),
     SystemInformation(iced::system::Information),
+    SpellCheckLoaded(
+        Result<Option<data::spell_check::Checker>, data::spell_check::Error>,
+    ),
 }
 
 impl Halloy {
@@ -413,6 +466,9 @@ impl Halloy {
             open_task,
             command,
             Task::stream(log_stream).map(Message::Logging),
+            load_spell_check(
+                halloy.config.buffer.text_input.spell_check.clone(),
+            ),
         ];
 
         if check_for_update_on_launch {
@@ -483,6 +539,17 @@ impl Halloy {
             Message::ConfigReloaded(config) => {
                 self.config_file_reloaded(config)
             }
+            Message::SpellCheckLoaded(result) => {
+                self.spell_checker = match result {
+                    Ok(checker) => checker.map(Arc::new),
+                    Err(error) => {
+                        log::warn!("failed to load spell checker: {error}");
+                        None
+                    }
+                };
+
+                Task::none()
+            }
             Message::RuntimeConfigured(result) => {
                 if let Err(error) = result {
                     log::error!("failed to configure runtime: {error}");
@@ -561,6 +628,7 @@ impl Halloy {
                     &self.version,
                     &self.config,
                     &self.main_window,
+                    self.spell_checker.as_ref(),
                 );
 
                 // Retrack after dashboard state changes
@@ -655,14 +723,15 @@ impl Halloy {
                             Task::none()
                         }
                     }
-                    Some(dashboard::Event::ImagePreview(image)) => {
+                    Some(dashboard::Event::ImagePreview(images, index)) => {
                         let Some((id, _, _)) = dashboard.get_focused() else {
                             return Task::none();
                         };
 
                         self.modal = Some(Modal::ImagePreview {
-                            image,
-                            timer: None,
+                            lightbox: modal::image_preview::Lightbox::new(
+                                images, index,
+                            ),
                             window: id,
                         });
                         Task::none()
@@ -682,6 +751,14 @@ impl Halloy {
                         });
                         Task::none()
                     }
+                    Some(dashboard::Event::ConfirmImageUpload {
+                        images,
+                        window,
+                    }) => {
+                        self.modal =
+                            Some(Modal::ConfirmImageUpload { images, window });
+                        Task::none()
+                    }
                     Some(dashboard::Event::EchoEvents(server, events)) => {
                         let casemapping = self
                             .clients
@@ -779,12 +856,29 @@ impl Halloy {
                     error,
                     sent_time,
                 } => {
-                    self.clients.disconnected(server.clone());
-
                     let Screen::Dashboard(dashboard) = &mut self.screen else {
+                        self.clients.disconnected(server);
+
                         return Task::none();
                     };
 
+                    // Record the last message seen before the client is
+                    // dropped, so ZNC playback can resume from it
+                    let znc_playback = dashboard
+                        .overwrite_znc_playback_timestamp(
+                            &self.clients,
+                            &server,
+                        )
+                        .map(|task| task.map(Message::Dashboard));
+
+                    dashboard.requeue_unconfirmed_messages(
+                        &server,
+                        &self.clients,
+                        &self.config,
+                    );
+
+                    self.clients.disconnected(server.clone());
+
                     dashboard.process_server_inputs_completion_and_notice(
                         &server,
                         &self.clients,
@@ -792,13 +886,14 @@ impl Halloy {
                     );
 
                     if is_initial {
-                        Task::none()
+                        znc_playback.unwrap_or_else(Task::none)
                     } else {
                         let request_attention = if !self.main_window.focused {
                             self.notifications.notify(
                                 &self.config.notifications,
                                 &Notification::Disconnected,
                                 &server,
+                                dashboard.notification_mutes(),
                                 dashboard
                                     .find_window_with_server(&server)
                                     .unwrap_or(self.main_window.id),
@@ -826,6 +921,10 @@ impl Halloy {
                             tasks.push(request_attention);
                         }
 
+                        if let Some(znc_playback) = znc_playback {
+                            tasks.push(znc_playback);
+                        }
+
                         Task::batch(tasks)
                     }
                 }
@@ -875,6 +974,7 @@ impl Halloy {
                             &self.config.notifications,
                             &notification,
                             &server,
+                            dashboard.notification_mutes(),
                             dashboard
                                 .find_window_with_server(&server)
                                 .unwrap_or(self.main_window.id),
@@ -1020,14 +1120,62 @@ impl Halloy {
                 Task::none()
             }
             Message::Tick(now) => {
+                // Follow the time of day when themes are scheduled. Only
+                // switching when the next theme is due keeps a theme selected
+                // by hand until then.
+                let selected = &self.config.appearance.selected;
+                let time = chrono::Local::now().time();
+                let slot = selected.scheduled_slot(time);
+
+                if slot != self.theme_slot {
+                    self.theme_slot = slot;
+
+                    if let Some(theme) = selected.scheduled(time) {
+                        self.theme.select(theme.clone());
+                    }
+                }
+
                 if let Err(e) = self.clients.tick(now) {
                     handle_irc_error(e);
                 }
 
                 if let Screen::Dashboard(dashboard) = &mut self.screen {
-                    dashboard
-                        .tick(now, &self.clients, &self.config)
-                        .map(Message::Dashboard)
+                    let mut commands = vec![];
+
+                    for (server, broadcast) in self.clients.netsplits(now) {
+                        handle_broadcast(
+                            &server,
+                            broadcast,
+                            dashboard,
+                            &mut commands,
+                            &self.clients,
+                            &self.config,
+                        );
+                    }
+
+                    commands.push(
+                        dashboard
+                            .tick(now, &self.clients, &self.config)
+                            .map(Message::Dashboard),
+                    );
+                    commands.push(
+                        dashboard
+                            .send_outbox_messages(
+                                &mut self.clients,
+                                &self.config,
+                            )
+                            .map(Message::Dashboard),
+                    );
+                    commands.push(
+                        dashboard
+                            .send_scheduled_messages(
+                                &mut self.clients,
+                                &self.config,
+                            )
+                            .map(Message::Dashboard),
+                    );
+
+                    Task::batch(commands)
                 } else {
                     Task::none()
                 }
@@ -1051,20 +1199,25 @@ impl Halloy {
                 if let Some(event) = event {
                     match event {
                         modal::Event::CloseModal => {
-                            let cancel_upload = matches!(
-                                self.modal,
-                                Some(Modal::ConfirmFileUpload { .. })
-                            );
+                            let cancel_upload = match self.modal {
+                                Some(Modal::ConfirmFileUpload { .. }) => Some(
+                                    dashboard::Message::CancelFilehostUpload,
+                                ),
+                                Some(Modal::ConfirmImageUpload { .. }) => {
+                                    Some(dashboard::Message::CancelImageUpload)
+                                }
+                                _ => None,
+                            };
                             self.modal = None;
-                            if cancel_upload
+                            if let Some(cancel_upload) = cancel_upload
                                 && let Screen::Dashboard(_) = &self.screen
                             {
                                 return Task::batch(vec![
-                                        command.map(Message::Modal),
-                                        Task::done(Message::Dashboard(
-                                            dashboard::Message::CancelFilehostUpload,
-                                        )),
-                                    ]);
+                                    command.map(Message::Modal),
+                                    Task::done(Message::Dashboard(
+                                        cancel_upload,
+                                    )),
+                                ]);
                             }
                         }
                         modal::Event::ConfirmFileUpload => {
@@ -1078,6 +1231,17 @@ impl Halloy {
                                 ]);
                             }
                         }
+                        modal::Event::ConfirmImageUpload => {
+                            self.modal = None;
+                            if let Screen::Dashboard(_) = &self.screen {
+                                return Task::batch(vec![
+                                    command.map(Message::Modal),
+                                    Task::done(Message::Dashboard(
+                                        dashboard::Message::ProceedWithImageUpload,
+                                    )),
+                                ]);
+                            }
+                        }
                         modal::Event::AcceptNewServer => {
                             if let Some(Modal::ServerConnect {
                                 server,
@@ -1389,6 +1553,7 @@ impl Halloy {
                         &self.version,
                         &self.config,
                         &self.theme,
+                        self.spell_checker.as_ref(),
                     )
                     .map(Message::Dashboard),
                 Screen::Help(help) => help.view(&self.theme).map(Message::Help),
@@ -1433,6 +1598,7 @@ impl Halloy {
                         &self.version,
                         &self.config,
                         &self.theme,
+                        self.spell_checker.as_ref(),
                     )
                     .map(Message::Dashboard),
             )
@@ -1521,6 +1687,15 @@ impl Halloy {
                 let runtime_task = (self.config.runtime != updated.runtime)
                     .then(|| configure_runtime(updated.runtime));
 
+                let spell_check_task =
+                    (self.config.buffer.text_input.spell_check
+                        != updated.buffer.text_input.spell_check)
+                        .then(|| {
+                            load_spell_check(
+                                updated.buffer.text_input.spell_check.clone(),
+                            )
+                        });
+
                 let removed_servers = self
                     .servers
                     .extract_if(|server, _| {
@@ -1595,9 +1770,8 @@ impl Halloy {
 
                     let mut tasks = Vec::new();
 
-                    if let Some(runtime_task) = runtime_task {
-                        tasks.push(runtime_task);
-                    }
+                    tasks.extend(runtime_task);
+                    tasks.extend(spell_check_task);
 
                     tasks.push(
                         dashboard
@@ -1611,7 +1785,9 @@ impl Halloy {
                     return Task::batch(tasks);
                 }
 
-                return runtime_task.unwrap_or_else(Task::none);
+                return Task::batch(
+                    runtime_task.into_iter().chain(spell_check_task),
+                );
             }
             Err(error) => {
                 self.modal = Some(Modal::ReloadConfigurationError(error));
@@ -1878,6 +2054,7 @@ fn handle_client_events(
                         &config.notifications,
                         &Notification::MonitoredOnline(users),
                         server,
+                        dashboard.notification_mutes(),
                         message_window.unwrap_or(main_window.id),
                     );
 
@@ -1895,6 +2072,7 @@ fn handle_client_events(
                         &config.notifications,
                         &Notification::MonitoredOffline(users),
                         server,
+                        dashboard.notification_mutes(),
                         message_window.unwrap_or(main_window.id),
                     );
 
@@ -1990,6 +2168,22 @@ fn handle_client_events(
                     .request_server_icon(clients, server)
                     .map(Message::Dashboard),
             ),
+            Event::ZncPlayback => {
+                if let Some(command) = dashboard
+                    .load_znc_playback_timestamp(clients, server)
+                    .map(|cmd| cmd.map(Message::Dashboard))
+                {
+                    commands.push(command);
+                }
+            }
+            Event::WebPushSubscribe => {
+                if let Some(command) = dashboard
+                    .subscribe_webpush(clients, server)
+                    .map(|cmd| cmd.map(Message::Dashboard))
+                {
+                    commands.push(command);
+                }
+            }
         }
     }
 
@@ -2232,6 +2426,7 @@ fn handle_priv_or_notice(
             commands,
             config,
             notifications,
+            dashboard.notification_mutes(),
             main_window,
         );
     }
@@ -2257,6 +2452,7 @@ fn handle_priv_or_notice(
                 message: msg.text().to_string(),
             },
             server,
+            dashboard.notification_mutes(),
             window.unwrap_or(main_window.id),
         );
         if let Some(req) = request_attention {
@@ -2264,6 +2460,11 @@ fn handle_priv_or_notice(
         }
     }
 
+    // Only announce messages for buffers the user has open
+    if window.is_some() && !msg.blocked && msg.triggers_unread() {
+        notifications.announce(&msg);
+    }
+
     commands.push(
         dashboard
             .record_message(
@@ -2326,6 +2527,7 @@ fn handle_highlight(
                 sound,
             },
             server,
+            dashboard.notification_mutes(),
             message_window.unwrap_or(main_window.id),
         );
 
@@ -2350,6 +2552,7 @@ fn maybe_notify_channel_message(
     commands: &mut Vec<Task<Message>>,
     config: &Config,
     notifications: &mut Notifications,
+    mutes: &data::notification::Mutes,
     main_window: &Window,
 ) {
     if msg.blocked
@@ -2382,6 +2585,7 @@ fn maybe_notify_channel_message(
             message: msg.text().to_string(),
         },
         server,
+        mutes,
         message_window.unwrap_or(main_window.id),
     );
 
@@ -2418,6 +2622,18 @@ fn handle_broadcast(
                 casemapping,
             },
         ),
+        data::client::Broadcast::Netsplit {
+            kind,
+            split,
+            users,
+            sent_time,
+        } => dashboard.broadcast(
+            server,
+            casemapping,
+            config,
+            sent_time,
+            Broadcast::Netsplit { kind, split, users },
+        ),
         data::client::Broadcast::Nickname {
             old_user,
             new_nick,
@@ -2547,6 +2763,7 @@ fn handle_reaction_to_echo(
                 message_text: reaction_to_echo.message_text,
             },
             server,
+            dashboard.notification_mutes(),
             message_window.unwrap_or(main_window.id),
         );
 
@@ -2595,6 +2812,7 @@ fn handle_reply_to_echo(
                 message: reply_to_echo.message.text().to_string(),
             },
             server,
+            dashboard.notification_mutes(),
             message_window.unwrap_or(main_window.id),
         );
     }
@@ -2653,6 +2871,7 @@ fn handle_direct_message(
                 message: msg.text().to_string(),
             },
             server,
+            dashboard.notification_mutes(),
             message_window.unwrap_or(main_window.id),
         );
 
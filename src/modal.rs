@@ -1,7 +1,7 @@
 use std::path::PathBuf;
-use std::time::Instant;
+use std::sync::Arc;
 
-use data::{Image, Server, config};
+use data::{Server, config, fileupload};
 use iced::Task;
 
 use crate::widget::Element;
@@ -9,6 +9,7 @@ use crate::{Theme, open_url, window};
 
 pub mod about;
 pub mod confirm_file_upload;
+pub mod confirm_image_upload;
 pub mod connect_to_server;
 pub mod image_preview;
 pub mod prompt_before_open_url;
@@ -28,8 +29,7 @@ pub enum Modal {
         window: window::Id,
     },
     ImagePreview {
-        image: Image,
-        timer: Option<Instant>,
+        lightbox: image_preview::Lightbox,
         window: window::Id,
     },
     ConfirmFileUpload {
@@ -37,6 +37,10 @@ pub enum Modal {
         has_credentials: bool,
         window: window::Id,
     },
+    ConfirmImageUpload {
+        images: Vec<fileupload::preprocess::Processed>,
+        window: window::Id,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -44,6 +48,7 @@ pub enum Message {
     Cancel,
     OpenURL(String),
     ConfirmFileUpload,
+    ConfirmImageUpload,
     // Modal specific messages
     ServerConnect(ServerConnect),
     About(about::Action),
@@ -54,6 +59,14 @@ pub enum Message {
 pub enum ImagePreview {
     SaveImage(PathBuf),
     SavedImage(Option<PathBuf>),
+    CopyImage(PathBuf),
+    DecodedImage(Option<Arc<arboard::ImageData<'static>>>),
+    Previous,
+    Next,
+    ZoomIn,
+    ZoomOut,
+    Fit,
+    Transform(image_preview::Transform),
 }
 
 #[derive(Debug, Clone)]
@@ -66,6 +79,7 @@ pub enum Event {
     CloseModal,
     AcceptNewServer,
     ConfirmFileUpload,
+    ConfirmImageUpload,
 }
 
 impl Modal {
@@ -75,12 +89,9 @@ impl Modal {
             Modal::ServerConnect { .. } => None,
             Modal::About(..) => None,
             Modal::PromptBeforeOpenUrl { url: _, window } => Some(*window),
-            Modal::ImagePreview {
-                image: _,
-                timer: _,
-                window,
-            } => Some(*window),
-            Modal::ConfirmFileUpload { window, .. } => Some(*window),
+            Modal::ImagePreview { window, .. } => Some(*window),
+            Modal::ConfirmFileUpload { window, .. }
+            | Modal::ConfirmImageUpload { window, .. } => Some(*window),
         }
     }
 
@@ -93,6 +104,9 @@ impl Modal {
             Message::ConfirmFileUpload => {
                 (Task::none(), Some(Event::ConfirmFileUpload))
             }
+            Message::ConfirmImageUpload => {
+                (Task::none(), Some(Event::ConfirmImageUpload))
+            }
             Message::About(action) => {
                 if let Modal::About(about) = self {
                     (about.update(action), None)
@@ -119,47 +133,13 @@ impl Modal {
                 let close = !matches!(self, Modal::ConfirmFileUpload { .. });
                 (Task::none(), close.then_some(Event::CloseModal))
             }
-            Message::ImagePreview(image_preview) => match image_preview {
-                ImagePreview::SaveImage(source) => (
-                    Task::perform(
-                        async move {
-                            if let Some(handle) = rfd::AsyncFileDialog::new()
-                                .set_file_name(
-                                    source
-                                        .file_name()
-                                        .and_then(|n| n.to_str())
-                                        .unwrap_or_default(),
-                                )
-                                .save_file()
-                                .await
-                            {
-                                let destination = handle.path();
-                                tokio::fs::copy(&source, destination)
-                                    .await
-                                    .ok()
-                                    .map(|_| destination.to_path_buf())
-                            } else {
-                                None
-                            }
-                        },
-                        move |path| {
-                            Message::ImagePreview(ImagePreview::SavedImage(
-                                path,
-                            ))
-                        },
-                    ),
-                    None,
-                ),
-                ImagePreview::SavedImage(path) => {
-                    if path.is_some()
-                        && let Modal::ImagePreview { timer, .. } = self
-                    {
-                        *timer = Some(Instant::now());
-                    }
-
+            Message::ImagePreview(message) => {
+                if let Modal::ImagePreview { lightbox, .. } = self {
+                    (lightbox.update(message), None)
+                } else {
                     (Task::none(), None)
                 }
-            },
+            }
         }
     }
 
@@ -180,11 +160,13 @@ impl Modal {
                 has_credentials,
                 window: _,
             } => confirm_file_upload::view(url, *has_credentials, theme),
+            Modal::ConfirmImageUpload { images, window: _ } => {
+                confirm_image_upload::view(images)
+            }
             Modal::ImagePreview {
-                image,
-                timer,
+                lightbox,
                 window: _,
-            } => image_preview::view(image, timer, theme),
+            } => lightbox.view(theme),
         }
     }
 }
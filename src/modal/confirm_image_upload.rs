@@ -0,0 +1,87 @@
+use bytesize::ByteSize;
+use data::fileupload::preprocess::{Processed, Summary};
+use iced::widget::{button, column, container, image, row, scrollable, text};
+use iced::{ContentFit, Length};
+
+use super::Message;
+use crate::theme;
+use crate::widget::Element;
+
+pub fn view(images: &[Processed]) -> Element<'_, Message> {
+    let summary = |summary: &Summary| {
+        format!(
+            "{}×{} {}, {}",
+            summary.width,
+            summary.height,
+            summary.format,
+            ByteSize::b(summary.size)
+        )
+    };
+
+    let previews = column(images.iter().map(|processed| {
+        let name = processed
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        row![
+            container(
+                image(&processed.path)
+                    .content_fit(ContentFit::Contain)
+                    .border_radius(4)
+            )
+            .center_x(96)
+            .center_y(96),
+            column![
+                text(name).style(theme::text::primary),
+                text(summary(&processed.processed)),
+                text(format!("was {}", summary(&processed.original)))
+                    .style(theme::text::secondary),
+                processed.metadata_stripped.then(|| {
+                    text("Location and other metadata removed")
+                        .style(theme::text::secondary)
+                }),
+            ]
+            .spacing(2),
+        ]
+        .spacing(12)
+        .align_y(iced::Alignment::Center)
+        .into()
+    }))
+    .spacing(8);
+
+    container(
+        column![
+            text(if images.len() == 1 {
+                String::from("Upload this image?")
+            } else {
+                format!("Upload these {} images?", images.len())
+            }),
+            scrollable(previews).height(Length::Shrink),
+            row![
+                button(text("Upload"))
+                    .padding(5)
+                    .style(|theme, status| theme::button::secondary(
+                        theme, status, false
+                    ))
+                    .on_press(Message::ConfirmImageUpload),
+                button(text("Cancel"))
+                    .padding(5)
+                    .style(|theme, status| theme::button::secondary(
+                        theme, status, false
+                    ))
+                    .on_press(Message::Cancel),
+            ]
+            .spacing(8),
+        ]
+        .align_x(iced::Alignment::Center)
+        .spacing(12),
+    )
+    .max_width(480)
+    .max_height(480)
+    .width(Length::Shrink)
+    .style(theme::container::tooltip)
+    .padding(25)
+    .into()
+}
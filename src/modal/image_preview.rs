@@ -1,100 +1,561 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use data::Image;
-use iced::widget::{button, center, column, container, row, space};
-use iced::{ContentFit, Length};
+use iced::advanced::{
+    Clipboard, Layout, Renderer as _, Shell, Widget, layout, renderer, widget,
+};
+use iced::widget::{button, center, column, container, row, space, text};
+use iced::{
+    ContentFit, Event, Length, Point, Rectangle, Size, Task, Transformation,
+    Vector, mouse,
+};
 
-use super::Message;
+use super::{ImagePreview, Message};
 use crate::widget::button::transparent_button;
-use crate::widget::{Element, image, tooltip};
+use crate::widget::key_press::{Key, Modifiers, Named};
+use crate::widget::{Element, Renderer, image, key_press, tooltip};
 use crate::{Theme, icon, theme};
 
-pub fn view<'a>(
-    data: &'a Image,
-    timer: &'a Option<Instant>,
+const PADDING: f32 = 50.0;
+const MIN_ZOOM: f32 = 0.25;
+const MAX_ZOOM: f32 = 32.0;
+const ZOOM_STEP: f32 = 1.25;
+
+/// How long the toolbar shows that an image was saved or copied
+const CONFIRMATION: Duration = Duration::from_secs(2);
+
+/// The images of a buffer, viewed one at a time
+#[derive(Debug)]
+pub struct Lightbox {
+    images: Vec<Image>,
+    index: usize,
+    transform: Transform,
+    saved_at: Option<Instant>,
+    copied_at: Option<Instant>,
+}
+
+/// Zoom relative to fitting the image in the window, and the offset of the
+/// image from the window's center
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    zoom: f32,
+    pan: Vector,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            pan: Vector::ZERO,
+        }
+    }
+}
+
+impl Transform {
+    fn zoom_by(self, factor: f32) -> Self {
+        let zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+
+        Self {
+            zoom,
+            pan: self.pan * (zoom / self.zoom),
+        }
+    }
+}
+
+impl Lightbox {
+    pub fn new(images: Vec<Image>, index: usize) -> Self {
+        Self {
+            index: index.min(images.len().saturating_sub(1)),
+            images,
+            transform: Transform::default(),
+            saved_at: None,
+            copied_at: None,
+        }
+    }
+
+    fn image(&self) -> Option<&Image> {
+        self.images.get(self.index)
+    }
+
+    pub fn update(&mut self, message: ImagePreview) -> Task<Message> {
+        match message {
+            ImagePreview::SaveImage(source) => {
+                return Task::perform(save(source), |path| {
+                    Message::ImagePreview(ImagePreview::SavedImage(path))
+                });
+            }
+            ImagePreview::SavedImage(path) => {
+                if path.is_some() {
+                    self.saved_at = Some(Instant::now());
+                }
+            }
+            ImagePreview::CopyImage(source) => {
+                return Task::perform(decode(source), |image| {
+                    Message::ImagePreview(ImagePreview::DecodedImage(image))
+                });
+            }
+            ImagePreview::DecodedImage(image) => {
+                if let Some(image) = image {
+                    match set_clipboard(Arc::unwrap_or_clone(image)) {
+                        Ok(()) => self.copied_at = Some(Instant::now()),
+                        Err(error) => {
+                            log::warn!("Failed to copy image: {error}");
+                        }
+                    }
+                }
+            }
+            ImagePreview::Previous => {
+                self.index = self
+                    .index
+                    .checked_sub(1)
+                    .unwrap_or(self.images.len().saturating_sub(1));
+                self.transform = Transform::default();
+            }
+            ImagePreview::Next => {
+                self.index = (self.index + 1) % self.images.len().max(1);
+                self.transform = Transform::default();
+            }
+            ImagePreview::ZoomIn => {
+                self.transform = self.transform.zoom_by(ZOOM_STEP);
+            }
+            ImagePreview::ZoomOut => {
+                self.transform = self.transform.zoom_by(ZOOM_STEP.recip());
+            }
+            ImagePreview::Fit => {
+                self.transform = Transform::default();
+            }
+            ImagePreview::Transform(transform) => {
+                self.transform = transform;
+            }
+        }
+
+        Task::none()
+    }
+
+    pub fn view<'a>(&'a self, theme: &'a Theme) -> Element<'a, Message> {
+        let Some(data) = self.image() else {
+            return space::horizontal().into();
+        };
+
+        let is_recent = |at: Option<Instant>| {
+            at.is_some_and(|at| at.elapsed() < CONFIRMATION)
+        };
+
+        let navigation = (self.images.len() > 1).then(|| {
+            row![
+                toolbar_button(
+                    text("‹"),
+                    Message::ImagePreview(ImagePreview::Previous),
+                    "Previous image",
+                    theme,
+                ),
+                toolbar_button(
+                    text("›"),
+                    Message::ImagePreview(ImagePreview::Next),
+                    "Next image",
+                    theme,
+                ),
+                text(format!("{} / {}", self.index + 1, self.images.len()))
+                    .style(theme::text::secondary),
+            ]
+            .spacing(2)
+            .align_y(iced::Alignment::Center)
+        });
+
+        let actions = row![
+            toolbar_button(
+                text("−"),
+                Message::ImagePreview(ImagePreview::ZoomOut),
+                "Zoom out",
+                theme,
+            ),
+            toolbar_button(
+                icon::plus(),
+                Message::ImagePreview(ImagePreview::ZoomIn),
+                "Zoom in",
+                theme,
+            ),
+            toolbar_button(
+                icon::maximize(),
+                Message::ImagePreview(ImagePreview::Fit),
+                "Fit to window",
+                theme,
+            ),
+            toolbar_button(
+                // Show a checkmark when image was copied to indicate success.
+                if is_recent(self.copied_at) {
+                    icon::checkmark().style(theme::text::success)
+                } else {
+                    icon::copy()
+                },
+                Message::ImagePreview(ImagePreview::CopyImage(
+                    data.path.clone(),
+                )),
+                "Copy image",
+                theme,
+            ),
+            toolbar_button(
+                // Show a checkmark when image was saved to indicate success.
+                if is_recent(self.saved_at) {
+                    icon::checkmark().style(theme::text::success)
+                } else {
+                    icon::file_transfer()
+                },
+                Message::ImagePreview(ImagePreview::SaveImage(
+                    data.path.clone(),
+                )),
+                "Save image",
+                theme,
+            ),
+            toolbar_button(
+                icon::share(),
+                Message::OpenURL(data.url.to_string()),
+                "Open in browser",
+                theme,
+            ),
+            toolbar_button(icon::cancel(), Message::Cancel, "Close", theme),
+        ]
+        .spacing(2);
+
+        let content = container(transparent_button(
+            column![
+                container(row![navigation, space::horizontal(), actions])
+                    .padding(6),
+                Zoomable {
+                    content: image::from_data(data, false, ContentFit::Fill),
+                    transform: self.transform,
+                    on_change: Box::new(|transform| {
+                        Message::ImagePreview(ImagePreview::Transform(
+                            transform,
+                        ))
+                    }),
+                },
+            ],
+            Message::Cancel,
+        ));
+
+        let content = key_press(
+            content,
+            Key::Named(Named::ArrowLeft),
+            Modifiers::default(),
+            Message::ImagePreview(ImagePreview::Previous),
+        );
+
+        key_press(
+            content,
+            Key::Named(Named::ArrowRight),
+            Modifiers::default(),
+            Message::ImagePreview(ImagePreview::Next),
+        )
+    }
+}
+
+fn toolbar_button<'a>(
+    content: impl Into<Element<'a, Message>>,
+    on_press: Message,
+    label: &'a str,
     theme: &'a Theme,
 ) -> Element<'a, Message> {
-    container(transparent_button(
-        column![
-            container(row![
-                space::horizontal(),
-                container(
-                    row![
-                        tooltip(
-                            button(center(
-                                // Show a checkmark when image was saved to indicate success.
-                                match timer {
-                                    Some(timer)
-                                        if timer.elapsed()
-                                            < Duration::from_secs(2) =>
-                                        icon::checkmark()
-                                            .style(theme::text::success),
-                                    _ => icon::file_transfer(),
-                                }
-                            ))
-                            .padding(5)
-                            .width(22)
-                            .height(22)
-                            .on_press(Message::ImagePreview(
-                                super::ImagePreview::SaveImage(
-                                    data.path.clone()
-                                )
-                            ))
-                            .style(
-                                move |theme, status| {
-                                    theme::button::secondary(
-                                        theme, status, false,
-                                    )
-                                }
-                            ),
-                            Some("Save image"),
-                            tooltip::Position::Bottom,
-                            theme,
-                        ),
-                        tooltip(
-                            button(center(icon::share()))
-                                .padding(5)
-                                .width(22)
-                                .height(22)
-                                .on_press(Message::OpenURL(
-                                    data.url.to_string()
-                                ))
-                                .style(move |theme, status| {
-                                    theme::button::secondary(
-                                        theme, status, false,
-                                    )
-                                }),
-                            Some("Open in browser"),
-                            tooltip::Position::Bottom,
-                            theme,
-                        ),
-                        tooltip(
-                            button(center(icon::cancel()))
-                                .padding(5)
-                                .width(22)
-                                .height(22)
-                                .on_press(Message::Cancel)
-                                .style(move |theme, status| {
-                                    theme::button::secondary(
-                                        theme, status, false,
-                                    )
-                                }),
-                            Some("Close"),
-                            tooltip::Position::Bottom,
-                            theme,
-                        )
-                    ]
-                    .spacing(2)
-                )
-            ])
-            .padding(6),
-            container(image::from_data(data, false, ContentFit::Contain))
-                .padding(50)
-                .center_x(Length::Fill)
-                .center_y(Length::Fill)
-                .style(theme::container::none)
-        ],
-        Message::Cancel,
-    ))
-    .into()
+    tooltip(
+        button(center(content))
+            .padding(5)
+            .width(22)
+            .height(22)
+            .on_press(on_press)
+            .style(move |theme, status| {
+                theme::button::secondary(theme, status, false)
+            }),
+        Some(label),
+        tooltip::Position::Bottom,
+        theme,
+    )
+}
+
+async fn save(source: PathBuf) -> Option<PathBuf> {
+    let handle = rfd::AsyncFileDialog::new()
+        .set_file_name(
+            source
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default(),
+        )
+        .save_file()
+        .await?;
+
+    let destination = handle.path();
+
+    tokio::fs::copy(&source, destination)
+        .await
+        .ok()
+        .map(|_| destination.to_path_buf())
+}
+
+thread_local! {
+    /// Clipboard contents are served by the process which set them on some
+    /// platforms, so the clipboard is kept around after copying an image
+    static CLIPBOARD: RefCell<Option<arboard::Clipboard>> =
+        const { RefCell::new(None) };
+}
+
+async fn decode(source: PathBuf) -> Option<Arc<arboard::ImageData<'static>>> {
+    let result =
+        tokio::task::spawn_blocking(move || decode_blocking(&source)).await;
+
+    match result {
+        Ok(Ok(image)) => Some(Arc::new(image)),
+        Ok(Err(error)) => {
+            log::warn!("Failed to decode image: {error}");
+            None
+        }
+        Err(error) => {
+            log::warn!("Failed to decode image: {error}");
+            None
+        }
+    }
+}
+
+fn decode_blocking(
+    source: &Path,
+) -> Result<arboard::ImageData<'static>, ::image::ImageError> {
+    let rgba = ::image::ImageReader::open(source)?
+        .with_guessed_format()?
+        .decode()?
+        .into_rgba8();
+
+    Ok(arboard::ImageData {
+        width: rgba.width() as usize,
+        height: rgba.height() as usize,
+        bytes: Cow::Owned(rgba.into_raw()),
+    })
+}
+
+fn set_clipboard(
+    image: arboard::ImageData<'static>,
+) -> Result<(), arboard::Error> {
+    CLIPBOARD.with_borrow_mut(|clipboard| {
+        let clipboard = match clipboard {
+            Some(clipboard) => clipboard,
+            None => clipboard.insert(arboard::Clipboard::new()?),
+        };
+
+        clipboard.set_image(image)
+    })
+}
+
+/// Shows its content scaled and offset by a [`Transform`], which is changed
+/// by scrolling to zoom and dragging to pan
+struct Zoomable<'a, Message> {
+    content: Element<'a, Message>,
+    transform: Transform,
+    on_change: Box<dyn Fn(Transform) -> Message + 'a>,
+}
+
+#[derive(Debug, Default)]
+struct ZoomableState {
+    /// Cursor position and pan when dragging started
+    drag: Option<(Point, Vector)>,
+}
+
+impl<Message> Zoomable<'_, Message> {
+    /// Where the content is drawn, and at which scale
+    fn placement(&self, layout: Layout<'_>) -> (Rectangle, f32) {
+        let available = layout.bounds().shrink(PADDING);
+        let content = layout
+            .children()
+            .next()
+            .map_or(Size::ZERO, |content| content.bounds().size());
+
+        let fit = if content.width > 0.0 && content.height > 0.0 {
+            (available.width / content.width)
+                .min(available.height / content.height)
+                .max(0.0)
+        } else {
+            1.0
+        };
+
+        let scale = fit * self.transform.zoom;
+        let size = content * scale;
+        let center = available.center() + self.transform.pan;
+
+        (
+            Rectangle::new(
+                center - Vector::new(size.width / 2.0, size.height / 2.0),
+                size,
+            ),
+            scale,
+        )
+    }
+}
+
+impl<Message> Widget<Message, Theme, Renderer> for Zoomable<'_, Message> {
+    fn size(&self) -> Size<Length> {
+        Size::new(Length::Fill, Length::Fill)
+    }
+
+    fn tag(&self) -> widget::tree::Tag {
+        widget::tree::Tag::of::<ZoomableState>()
+    }
+
+    fn state(&self) -> widget::tree::State {
+        widget::tree::State::new(ZoomableState::default())
+    }
+
+    fn children(&self) -> Vec<widget::Tree> {
+        vec![widget::Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut widget::Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut widget::Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        // The content is laid out at its natural size and scaled when drawn
+        let content = self.content.as_widget_mut().layout(
+            &mut tree.children[0],
+            renderer,
+            &layout::Limits::new(Size::ZERO, Size::INFINITY),
+        );
+
+        layout::Node::with_children(
+            limits.resolve(Length::Fill, Length::Fill, Size::ZERO),
+            vec![content],
+        )
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut widget::Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_mut::<ZoomableState>();
+        let (target, _) = self.placement(layout);
+
+        match event {
+            Event::Mouse(mouse::Event::WheelScrolled { delta }) => {
+                let Some(position) = cursor.position_over(layout.bounds())
+                else {
+                    return;
+                };
+
+                let steps = match delta {
+                    mouse::ScrollDelta::Lines { y, .. } => *y,
+                    mouse::ScrollDelta::Pixels { y, .. } => *y / 50.0,
+                };
+
+                let zoomed = self.transform.zoom_by(ZOOM_STEP.powf(steps));
+
+                // Keep the point under the cursor in place
+                let ratio = zoomed.zoom / self.transform.zoom;
+                let from_center = position - target.center();
+
+                shell.publish((self.on_change)(Transform {
+                    zoom: zoomed.zoom,
+                    pan: self.transform.pan + from_center * (1.0 - ratio),
+                }));
+                shell.capture_event();
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                // Clicking outside of the image closes the lightbox
+                if let Some(position) = cursor.position_over(target) {
+                    state.drag = Some((position, self.transform.pan));
+                    shell.capture_event();
+                }
+            }
+            Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                if let Some((origin, pan)) = state.drag {
+                    shell.publish((self.on_change)(Transform {
+                        pan: pan + (*position - origin),
+                        ..self.transform
+                    }));
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                if state.drag.take().is_some() {
+                    shell.capture_event();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &widget::Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let state = tree.state.downcast_ref::<ZoomableState>();
+        let (target, _) = self.placement(layout);
+
+        if state.drag.is_some() {
+            mouse::Interaction::Grabbing
+        } else if cursor.is_over(target) {
+            mouse::Interaction::Grab
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &widget::Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let Some(content_layout) = layout.children().next() else {
+            return;
+        };
+
+        let (target, scale) = self.placement(layout);
+        let origin = content_layout.bounds().position();
+
+        renderer.with_layer(layout.bounds(), |renderer| {
+            renderer.with_transformation(
+                Transformation::translate(target.x, target.y)
+                    * Transformation::scale(scale)
+                    * Transformation::translate(-origin.x, -origin.y),
+                |renderer| {
+                    self.content.as_widget().draw(
+                        &tree.children[0],
+                        renderer,
+                        theme,
+                        style,
+                        content_layout,
+                        mouse::Cursor::Unavailable,
+                        &content_layout.bounds(),
+                    );
+                },
+            );
+        });
+    }
+}
+
+impl<'a, Message> From<Zoomable<'a, Message>> for Element<'a, Message>
+where
+    Message: 'a,
+{
+    fn from(zoomable: Zoomable<'a, Message>) -> Self {
+        Element::new(zoomable)
+    }
 }
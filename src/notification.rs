@@ -1,16 +1,19 @@
 use std::collections::HashMap;
 
-use chrono::{DateTime, TimeDelta, Utc};
+use chrono::{DateTime, Local, TimeDelta, Utc};
 use data::audio::Sound;
 use data::config::{self, notification};
+use data::notification::Mutes;
 use data::target::join_targets;
 use data::user::Nick;
 use data::{Config, Notification, Server, User};
 use iced::Task;
 
+use self::screen_reader::ScreenReader;
 pub use self::toast::prepare;
 use crate::{audio, window};
 
+mod screen_reader;
 mod toast;
 
 #[derive(PartialEq, Eq, Hash, Clone)]
@@ -71,6 +74,7 @@ impl From<&Notification> for NotificationDelayKey {
 pub struct Notifications {
     recent_notifications: HashMap<NotificationDelayKey, DateTime<Utc>>,
     sounds: HashMap<String, Sound>,
+    screen_reader: Option<ScreenReader>,
 }
 
 impl Notifications {
@@ -83,9 +87,25 @@ impl Notifications {
                 ),
             );
 
+        let screen_reader = config
+            .accessibility
+            .screen_reader
+            .then(ScreenReader::new)
+            .flatten();
+
         Self {
             recent_notifications: HashMap::new(),
             sounds,
+            screen_reader,
+        }
+    }
+
+    /// Reads `message` aloud if screen reader announcements are enabled.
+    pub fn announce(&mut self, message: &data::Message) {
+        if let Some(screen_reader) = &mut self.screen_reader
+            && let Some(text) = screen_reader::describe(message)
+        {
+            screen_reader.announce(text);
         }
     }
 
@@ -94,8 +114,27 @@ impl Notifications {
         config: &config::Notifications,
         notification: &Notification,
         server: &Server,
+        mutes: &Mutes,
         window_id: window::Id,
     ) -> Option<Task<Message>> {
+        if mutes.is_notification_muted(notification, server, Utc::now()) {
+            return None;
+        }
+
+        let (user, channel) = notification.source();
+
+        if config.quiet_hours.as_ref().is_some_and(|quiet_hours| {
+            quiet_hours.is_quiet(
+                Local::now().time(),
+                user,
+                channel,
+                server,
+                notification.casemapping(),
+            )
+        }) {
+            return None;
+        }
+
         let request_attention = match notification {
             Notification::Connected => {
                 self.execute(
@@ -213,7 +252,13 @@ impl Notifications {
                 casemapping,
                 message,
             } => {
-                if config.direct_message.should_notify(
+                let direct_message = config.direct_message(
+                    user.nickname(),
+                    server,
+                    *casemapping,
+                );
+
+                if direct_message.should_notify(
                     user,
                     None,
                     server,
@@ -223,7 +268,7 @@ impl Notifications {
                         String,
                         Option<String>,
                         String,
-                    ) = if config.direct_message.show_content {
+                    ) = if direct_message.show_content {
                         (
                             user.nickname().as_str().to_owned(),
                             Some(format!("{server}")),
@@ -238,7 +283,7 @@ impl Notifications {
                     };
 
                     self.execute(
-                        &config.direct_message,
+                        direct_message,
                         notification,
                         title.as_str(),
                         subtitle.as_deref(),
@@ -246,7 +291,7 @@ impl Notifications {
                         None,
                     );
 
-                    config.direct_message.request_attention
+                    direct_message.request_attention
                 } else {
                     false
                 }
@@ -259,7 +304,14 @@ impl Notifications {
                 description,
                 sound,
             } => {
-                if config.highlight.should_notify(
+                let highlight = config.highlight(
+                    user.nickname(),
+                    Some(channel),
+                    server,
+                    *casemapping,
+                );
+
+                if highlight.should_notify(
                     user,
                     Some(channel),
                     server,
@@ -267,9 +319,9 @@ impl Notifications {
                 ) {
                     // Description is expected to be expanded by the calling
                     // routine when show_content is true
-                    if config.highlight.show_content {
+                    if highlight.show_content {
                         self.execute(
-                            &config.highlight,
+                            highlight,
                             notification,
                             &format!("{} {description}", user.nickname()),
                             Some(if cfg!(target_os = "macos") {
@@ -282,10 +334,10 @@ impl Notifications {
                             sound.as_deref(),
                         );
 
-                        config.highlight.request_attention
+                        highlight.request_attention
                     } else {
                         self.execute(
-                            &config.highlight,
+                            highlight,
                             notification,
                             user.nickname().as_str(),
                             None,
@@ -293,7 +345,7 @@ impl Notifications {
                             sound.as_deref(),
                         );
 
-                        config.highlight.request_attention
+                        highlight.request_attention
                     }
                 } else {
                     false
@@ -353,7 +405,14 @@ impl Notifications {
             } => {
                 let channel_option = reaction.target.clone().to_channel();
                 let channel = channel_option.as_ref();
-                if config.reaction.should_notify(
+                let reaction_config = config.reaction(
+                    reaction.inner.sender.as_nickref(),
+                    channel,
+                    server,
+                    *casemapping,
+                );
+
+                if reaction_config.should_notify(
                     &User::from(reaction.inner.sender.clone()),
                     channel,
                     server,
@@ -362,7 +421,7 @@ impl Notifications {
                     let react_sent_in = match channel {
                         Some(channel) => {
                             if cfg!(target_os = "macos")
-                                || !config.reaction.show_content
+                                || !reaction_config.show_content
                             {
                                 format!("{channel} ({server})")
                             } else {
@@ -371,7 +430,7 @@ impl Notifications {
                         }
                         None => {
                             if cfg!(target_os = "macos")
-                                || !config.reaction.show_content
+                                || !reaction_config.show_content
                             {
                                 format!("query ({server})")
                             } else {
@@ -384,7 +443,7 @@ impl Notifications {
                         String,
                         Option<String>,
                         String,
-                    ) = if config.reaction.show_content {
+                    ) = if reaction_config.show_content {
                         (
                             reaction.inner.sender.to_string(),
                             Some(react_sent_in.to_string()),
@@ -402,7 +461,7 @@ impl Notifications {
                     };
 
                     self.execute(
-                        &config.reaction,
+                        reaction_config,
                         notification,
                         title.as_str(),
                         subtitle.as_deref(),
@@ -410,7 +469,7 @@ impl Notifications {
                         None,
                     );
 
-                    config.reaction.request_attention
+                    reaction_config.request_attention
                 } else {
                     false
                 }
@@ -421,15 +480,22 @@ impl Notifications {
                 casemapping,
                 message,
             } => {
-                if config.highlight.should_notify(
+                let highlight = config.highlight(
+                    user.nickname(),
+                    Some(channel),
+                    server,
+                    *casemapping,
+                );
+
+                if highlight.should_notify(
                     user,
                     Some(channel),
                     server,
                     *casemapping,
                 ) {
-                    if config.highlight.show_content {
+                    if highlight.show_content {
                         self.execute(
-                            &config.highlight,
+                            highlight,
                             notification,
                             &format!("{} replied to you", user.nickname()),
                             Some(if cfg!(target_os = "macos") {
@@ -443,7 +509,7 @@ impl Notifications {
                         );
                     } else {
                         self.execute(
-                            &config.highlight,
+                            highlight,
                             notification,
                             user.nickname().as_str(),
                             None,
@@ -452,7 +518,7 @@ impl Notifications {
                         );
                     }
 
-                    config.highlight.request_attention
+                    highlight.request_attention
                 } else {
                     false
                 }
@@ -0,0 +1,161 @@
+use data::message::{Source, Target};
+
+/// Speaks messages through the platform accessibility bus.
+///
+/// Halloy exposes a single live region; every announcement replaces its
+/// content, which screen readers pick up and read aloud.
+pub struct ScreenReader {
+    #[cfg(all(unix, not(target_os = "macos")))]
+    adapter: accesskit_unix::Adapter,
+    #[cfg(all(unix, not(target_os = "macos")))]
+    next_id: u64,
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+impl ScreenReader {
+    pub fn new() -> Option<Self> {
+        Some(Self {
+            adapter: accesskit_unix::Adapter::new(
+                tree::Activation,
+                tree::Noop,
+                tree::Noop,
+            ),
+            next_id: tree::FIRST_MESSAGE,
+        })
+    }
+
+    pub fn announce(&mut self, text: String) {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        // Only builds the update when an assistive technology is listening
+        self.adapter
+            .update_if_active(|| tree::announcement(id, text));
+    }
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+impl ScreenReader {
+    pub fn new() -> Option<Self> {
+        log::warn!(
+            "screen reader announcements are only supported on Linux and BSD"
+        );
+
+        None
+    }
+
+    pub fn announce(&mut self, _text: String) {}
+}
+
+/// What to read aloud for `message`, or `None` if it isn't spoken by a user.
+pub fn describe(message: &data::Message) -> Option<String> {
+    let text = message.text();
+
+    match &message.target {
+        Target::Channel {
+            channel,
+            source: Source::User(user),
+        } => Some(format!("{} in {channel}: {text}", user.nickname())),
+        Target::Channel {
+            channel,
+            source: Source::Action(_),
+        } => Some(format!("{channel}: {text}")),
+        Target::Query {
+            source: Source::User(user),
+            ..
+        } => Some(format!("{}: {text}", user.nickname())),
+        Target::Query {
+            source: Source::Action(_),
+            ..
+        } => Some(text.into_owned()),
+        _ => None,
+    }
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+mod tree {
+    use accesskit::{
+        ActionHandler, ActionRequest, ActivationHandler, DeactivationHandler,
+        Live, Node, NodeId, Role, Tree, TreeId, TreeUpdate,
+    };
+
+    const WINDOW: NodeId = NodeId(0);
+    const LOG: NodeId = NodeId(1);
+    pub const FIRST_MESSAGE: u64 = 2;
+
+    pub struct Activation;
+
+    impl ActivationHandler for Activation {
+        fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+            let mut window = Node::new(Role::Window);
+            window.set_label("Halloy");
+            window.push_child(LOG);
+
+            Some(TreeUpdate {
+                nodes: vec![(WINDOW, window), (LOG, log())],
+                tree: Some(Tree::new(WINDOW)),
+                tree_id: TreeId::ROOT,
+                focus: WINDOW,
+            })
+        }
+    }
+
+    pub struct Noop;
+
+    impl ActionHandler for Noop {
+        fn do_action(&mut self, _request: ActionRequest) {}
+    }
+
+    impl DeactivationHandler for Noop {
+        fn deactivate_accessibility(&mut self) {}
+    }
+
+    fn log() -> Node {
+        let mut log = Node::new(Role::Log);
+        log.set_live(Live::Polite);
+        log
+    }
+
+    /// Replaces the log's content with a single new message node. A fresh
+    /// id makes it an addition, which is what triggers the announcement.
+    pub fn announcement(id: u64, text: String) -> TreeUpdate {
+        let id = NodeId(id);
+
+        let mut log = log();
+        log.push_child(id);
+
+        let mut message = Node::new(Role::Label);
+        message.set_live(Live::Polite);
+        message.set_label(text);
+
+        TreeUpdate {
+            nodes: vec![(LOG, log), (id, message)],
+            tree: None,
+            tree_id: TreeId::ROOT,
+            focus: WINDOW,
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn announcement_replaces_log_content() {
+            let update = announcement(7, "alice: hi".to_owned());
+
+            let (_, log) =
+                update.nodes.iter().find(|(id, _)| *id == LOG).unwrap();
+            assert_eq!(log.children(), [NodeId(7)]);
+
+            let (_, message) = update
+                .nodes
+                .iter()
+                .find(|(id, _)| *id == NodeId(7))
+                .unwrap();
+            assert_eq!(message.label(), Some("alice: hi"));
+            assert_eq!(message.live(), Some(Live::Polite));
+            assert!(update.tree.is_none());
+        }
+    }
+}
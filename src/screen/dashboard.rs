@@ -16,9 +16,12 @@ use data::history::ReadMarker;
 use data::history::filter::Filter;
 use data::history::manager::EchoEvent;
 use data::history::reroute::RerouteRules;
-use data::isupport::{self, ChatHistorySubcommand, MessageReference};
+use data::isupport::{
+    self, ChatHistoryState, ChatHistorySubcommand, MessageReference,
+};
 use data::message::{self, Broadcast};
 use data::rate_limit::TokenPriority;
+use data::spell_check::Checker;
 use data::target::{self, Target};
 use data::user::Nick;
 use data::{
@@ -35,6 +38,7 @@ use self::command_bar::CommandBar;
 use self::modal::{reaction as reaction_modal, redaction as redaction_modal};
 use self::pane::Pane;
 use self::sidebar::Sidebar;
+use self::switcher::Switcher;
 use self::theme_editor::ThemeEditor;
 use crate::buffer::{self, Buffer};
 use crate::widget::{
@@ -51,6 +55,7 @@ mod command_bar;
 pub mod modal;
 pub mod pane;
 pub mod sidebar;
+mod switcher;
 mod theme_editor;
 
 const FOCUS_HISTORY_LEN: usize = 8;
@@ -65,6 +70,9 @@ pub struct Dashboard {
     last_changed: Option<Instant>,
     command_bar: Option<CommandBar>,
     command_bar_window: Option<window::Id>,
+    switcher: Option<Switcher>,
+    switcher_window: Option<window::Id>,
+    recent_buffers: data::buffer::switcher::Recent,
     file_transfers: file_transfer::Manager,
     theme_editor: Option<ThemeEditor>,
     notifications: notification::Notifications,
@@ -74,6 +82,13 @@ pub struct Dashboard {
     typing_animation: Option<buffer::typing::Animation>,
     http_client: Option<Arc<reqwest::Client>>,
     buffer_settings: dashboard::BufferSettings,
+    sidebar_layout: dashboard::sidebar::Layout,
+    workspaces: dashboard::workspace::Workspaces,
+    workspaces_load: WorkspacesLoad,
+    workspace: Option<String>,
+    mutes: data::notification::Mutes,
+    scheduled: data::schedule::Scheduled,
+    outbox: data::outbox::Outbox,
     pub filehost: filehost::Manager,
 }
 
@@ -87,7 +102,13 @@ pub enum Message {
     ),
     History(history::manager::Message),
     DashboardSaved(Result<(), data::dashboard::Error>),
+    WorkspacesLoaded(
+        Result<dashboard::workspace::Workspaces, data::dashboard::Error>,
+        Option<String>,
+    ),
+    WorkspacesSaved(Result<(), data::dashboard::Error>),
     Task(command_bar::Message),
+    Switcher(switcher::Message),
     Shortcut(shortcut::Command),
     FileTransfer(file_transfer::task::Update),
     SendFileSelected(Server, User, Option<PathBuf>),
@@ -101,6 +122,8 @@ pub enum Message {
     Filehost(filehost::Message),
     ProceedWithFilehostUpload,
     CancelFilehostUpload,
+    ProceedWithImageUpload,
+    CancelImageUpload,
 }
 
 #[derive(Debug)]
@@ -117,7 +140,7 @@ pub enum Event {
         system_information: Option<iced::system::Information>,
     },
     OpenServer(String),
-    ImagePreview(Image),
+    ImagePreview(Vec<Image>, usize),
     ToggleFullscreen,
     Remove(Server),
     PromptBeforeFileUpload {
@@ -125,9 +148,32 @@ pub enum Event {
         has_credentials: bool,
         window: window::Id,
     },
+    ConfirmImageUpload {
+        images: Vec<data::fileupload::preprocess::Processed>,
+        window: window::Id,
+    },
     EchoEvents(Server, Vec<EchoEvent>),
 }
 
+impl From<filehost::Event> for Event {
+    fn from(event: filehost::Event) -> Self {
+        match event {
+            filehost::Event::PromptBeforeUpload {
+                upload_url,
+                has_credentials,
+                window,
+            } => Event::PromptBeforeFileUpload {
+                upload_url,
+                has_credentials,
+                window,
+            },
+            filehost::Event::ConfirmImages { images, window } => {
+                Event::ConfirmImageUpload { images, window }
+            }
+        }
+    }
+}
+
 impl Dashboard {
     pub fn empty(
         main_window: &Window,
@@ -154,6 +200,9 @@ impl Dashboard {
             last_changed: None,
             command_bar: None,
             command_bar_window: None,
+            switcher: None,
+            switcher_window: None,
+            recent_buffers: data::buffer::switcher::Recent::default(),
             file_transfers: file_transfer::Manager::default(),
             theme_editor: None,
             notifications: notification::Notifications::new(config),
@@ -163,6 +212,13 @@ impl Dashboard {
             typing_animation: None,
             http_client: http_client_from_config(config).map(Arc::new),
             buffer_settings: dashboard::BufferSettings::default(),
+            sidebar_layout: dashboard::sidebar::Layout::default(),
+            workspaces: dashboard::workspace::Workspaces::default(),
+            workspaces_load: WorkspacesLoad::Pending,
+            workspace: None,
+            mutes: data::notification::Mutes::default(),
+            scheduled: data::schedule::Scheduled::default(),
+            outbox: data::outbox::Outbox::default(),
             filehost: filehost::Manager::new(),
         };
 
@@ -172,10 +228,20 @@ impl Dashboard {
                 .preload_drafts(data::input::load_drafts_sync());
         }
 
+        if config.buffer.text_input.history.persist {
+            dashboard.history.preload_input_history(
+                data::input::load_history_sync(),
+                &config.buffer.text_input.history,
+            );
+        }
+
         let command = dashboard.track(None);
         let sidebar_task = sidebar_task.map(Message::Sidebar);
 
-        (dashboard, Task::batch(vec![command, sidebar_task]))
+        (
+            dashboard,
+            Task::batch(vec![command, sidebar_task, load_workspaces(config)]),
+        )
     }
 
     pub fn restore(
@@ -186,7 +252,11 @@ impl Dashboard {
         let (mut dashboard, task) =
             Dashboard::from_data(dashboard, config, main_window);
 
-        let tasks = Task::batch(vec![task, dashboard.track(None)]);
+        let tasks = Task::batch(vec![
+            task,
+            dashboard.track(None),
+            load_workspaces(config),
+        ]);
 
         (dashboard, tasks)
     }
@@ -284,6 +354,7 @@ impl Dashboard {
                         | Buffer::Server(_)
                         | Buffer::Query(_)
                         | Buffer::Highlights(_)
+                        | Buffer::ServerNotices(_)
                 ) {
                     pane.buffer.data().and_then(history::Kind::from_buffer)
                 } else {
@@ -334,6 +405,7 @@ impl Dashboard {
         version: &Version,
         config: &Config,
         main_window: &Window,
+        spell_checker: Option<&Arc<Checker>>,
     ) -> (Task<Message>, Option<Event>) {
         match message {
             Message::Pane(window, message) => {
@@ -380,6 +452,7 @@ impl Dashboard {
                                 &mut self.file_transfers,
                                 main_window,
                                 config,
+                                spell_checker,
                             );
 
                             let task = command.map(move |message| {
@@ -546,6 +619,7 @@ impl Dashboard {
                                         &mut self.file_transfers,
                                         main_window,
                                         config,
+                                        spell_checker,
                                     );
 
                                     let task = self.refocus_pane().chain(
@@ -774,6 +848,81 @@ impl Dashboard {
                     sidebar::Event::Remove(server) => {
                         (Task::none(), Some(Event::Remove(server)))
                     }
+                    sidebar::Event::Pin(buffer) => {
+                        self.sidebar_layout.pin(buffer);
+                        self.last_changed = Some(Instant::now());
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::Release(buffer) => {
+                        self.sidebar_layout.release(&buffer);
+                        self.last_changed = Some(Instant::now());
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::AddToGroup(buffer, index) => {
+                        self.sidebar_layout.add_to_group(buffer, index);
+                        self.last_changed = Some(Instant::now());
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::NewGroup(buffer) => {
+                        let name = format!(
+                            "Group {}",
+                            self.sidebar_layout.groups.len() + 1
+                        );
+                        let index =
+                            self.sidebar_layout.new_group(name.clone(), buffer);
+                        self.last_changed = Some(Instant::now());
+
+                        (
+                            self.side_menu
+                                .edit_group(index, name)
+                                .map(Message::Sidebar),
+                            None,
+                        )
+                    }
+                    sidebar::Event::RenameGroup(index, name) => {
+                        self.sidebar_layout.rename_group(index, name);
+                        self.last_changed = Some(Instant::now());
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::RemoveGroup(index) => {
+                        self.sidebar_layout.remove_group(index);
+                        self.last_changed = Some(Instant::now());
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::Mute(buffer, duration) => {
+                        self.mutes.mute(&buffer, duration);
+                        self.last_changed = Some(Instant::now());
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::Unmute(buffer) => {
+                        self.mutes.unmute(&buffer);
+                        self.last_changed = Some(Instant::now());
+
+                        (Task::none(), None)
+                    }
+                    sidebar::Event::Move(buffer, target) => {
+                        let listed = match &target {
+                            dashboard::sidebar::Target::Buffer(target) => {
+                                sidebar::server_buffers(
+                                    target.server(),
+                                    clients,
+                                    &self.history,
+                                )
+                            }
+                            dashboard::sidebar::Target::Group(_) => vec![],
+                        };
+
+                        self.sidebar_layout.move_to(buffer, target, listed);
+                        self.last_changed = Some(Instant::now());
+
+                        (Task::none(), None)
+                    }
                 };
 
                 let window = main_window.id;
@@ -953,6 +1102,45 @@ impl Dashboard {
             Message::DashboardSaved(Err(error)) => {
                 log::warn!("error saving dashboard: {error}");
             }
+            Message::WorkspacesLoaded(Ok(workspaces), on_launch) => {
+                // Keep workspaces saved before loading finished
+                let saved = std::mem::replace(&mut self.workspaces, workspaces);
+                let changed = !saved.is_empty();
+                self.workspaces.extend(saved);
+
+                self.workspaces_load = WorkspacesLoad::Loaded;
+
+                let save = if changed {
+                    self.save_workspaces()
+                } else {
+                    Task::none()
+                };
+
+                if let Some(name) = on_launch {
+                    return (
+                        Task::batch([
+                            save,
+                            self.switch_workspace(&name, clients, config),
+                        ]),
+                        None,
+                    );
+                }
+
+                return (save, None);
+            }
+            Message::WorkspacesLoaded(Err(error), _) => {
+                log::error!(
+                    "failed to load workspaces, changes to them won't be saved: {error}"
+                );
+
+                self.workspaces_load = WorkspacesLoad::Failed;
+            }
+            Message::WorkspacesSaved(Ok(())) => {
+                log::debug!("workspaces saved");
+            }
+            Message::WorkspacesSaved(Err(error)) => {
+                log::warn!("error saving workspaces: {error}");
+            }
             Message::Task(message) => {
                 let Some(command_bar) = &mut self.command_bar else {
                     return (Task::none(), None);
@@ -1007,6 +1195,31 @@ impl Dashboard {
                     None => {}
                 }
             }
+            Message::Switcher(message) => {
+                let Some(switcher) = &mut self.switcher else {
+                    return (Task::none(), None);
+                };
+
+                match switcher.update(message) {
+                    Some(switcher::Event::Selected(buffer)) => {
+                        self.close_switcher();
+
+                        return (
+                            self.open_buffer(
+                                data::Buffer::Upstream(buffer),
+                                BufferAction::ReplacePane,
+                                clients,
+                                config,
+                            ),
+                            None,
+                        );
+                    }
+                    Some(switcher::Event::Unfocused) => {
+                        return (self.toggle_switcher(clients), None);
+                    }
+                    None => {}
+                }
+            }
             Message::Shortcut(shortcut) => {
                 use shortcut::Command::*;
 
@@ -1168,6 +1381,9 @@ impl Dashboard {
                     ToggleSidebar => {
                         self.side_menu.toggle_visibility();
                     }
+                    BufferSwitcher => {
+                        return (self.toggle_switcher(clients), None);
+                    }
                     CommandBar => {
                         return (
                             self.toggle_command_bar(
@@ -1230,6 +1446,47 @@ impl Dashboard {
                             None,
                         );
                     }
+                    Find => {
+                        return (
+                            self.get_focused_with_history_mut().map_or_else(
+                                Task::none,
+                                |(window, pane, state, history)| {
+                                    state.buffer.find(history, config).map(
+                                        move |message| {
+                                            Message::Pane(
+                                                window,
+                                                pane::Message::Buffer(
+                                                    pane, message,
+                                                ),
+                                            )
+                                        },
+                                    )
+                                },
+                            ),
+                            None,
+                        );
+                    }
+                    SearchInputHistory => {
+                        return (
+                            self.get_focused_with_history_mut().map_or_else(
+                                Task::none,
+                                |(window, pane, state, history)| {
+                                    state
+                                        .buffer
+                                        .search_input_history(history)
+                                        .map(move |message| {
+                                            Message::Pane(
+                                                window,
+                                                pane::Message::Buffer(
+                                                    pane, message,
+                                                ),
+                                            )
+                                        })
+                                },
+                            ),
+                            None,
+                        );
+                    }
                     ToggleFullscreen => {
                         return (
                             window::toggle_fullscreen(),
@@ -1340,6 +1597,23 @@ impl Dashboard {
 
                         return (task, None);
                     }
+                    Run(line) => {
+                        return (self.run_in_focused_buffer(line), None);
+                    }
+                    CycleNextWorkspace | CyclePreviousWorkspace => {
+                        let forward = matches!(shortcut, CycleNextWorkspace);
+
+                        if let Some(name) = self
+                            .workspaces
+                            .cycle(self.workspace.as_deref(), forward)
+                            .map(ToString::to_string)
+                        {
+                            return (
+                                self.switch_workspace(&name, clients, config),
+                                None,
+                            );
+                        }
+                    }
                     CycleNextUnreadBuffer => {
                         let all_buffers =
                             all_buffers_with_has_unread(clients, &self.history);
@@ -1567,6 +1841,35 @@ impl Dashboard {
                         TokenPriority::High,
                     );
                 }
+                client::Message::ZncPlayback(server, since) => {
+                    clients.send_znc_playback(&server, since);
+                }
+                client::Message::ZncPlaybackTimestampUpdated(
+                    server,
+                    timestamp,
+                    Ok(()),
+                ) => {
+                    log::debug!(
+                        "updated ZNC playback timestamp for {server} to {timestamp}"
+                    );
+                }
+                client::Message::ZncPlaybackTimestampUpdated(
+                    server,
+                    timestamp,
+                    Err(error),
+                ) => {
+                    log::warn!(
+                        "failed to update ZNC playback timestamp for {server} to {timestamp}: {error}"
+                    );
+                }
+                client::Message::WebPushSubscribed(server, Ok(keys)) => {
+                    clients.register_webpush(&server, &keys);
+                }
+                client::Message::WebPushSubscribed(server, Err(error)) => {
+                    log::warn!(
+                        "failed to load push subscription keys for {server}: {error}"
+                    );
+                }
             },
             Message::LoadPreview((url, Ok(preview))) => {
                 log::trace!("Preview loaded for {url}");
@@ -1596,10 +1899,7 @@ impl Dashboard {
                 return (self.focus_pane(window, pane), None);
             }
             Message::Filehost(msg) => {
-                return (
-                    self.handle_filehost_message(msg, clients, config),
-                    None,
-                );
+                return self.handle_filehost_message(msg, clients, config);
             }
             Message::ProceedWithFilehostUpload => {
                 let task = self
@@ -1612,6 +1912,19 @@ impl Dashboard {
                 let task = self.filehost.cancel().map(Message::Filehost);
                 return (task, None);
             }
+            Message::ProceedWithImageUpload => {
+                let (task, event) = self.filehost.proceed_with_images(
+                    clients,
+                    self.http_client.clone(),
+                    &config.proxy,
+                );
+
+                return (task.map(Message::Filehost), event.map(Event::from));
+            }
+            Message::CancelImageUpload => {
+                let task = self.filehost.cancel_images().map(Message::Filehost);
+                return (task, None);
+            }
         }
 
         (Task::none(), None)
@@ -1625,6 +1938,7 @@ impl Dashboard {
         version: &'a Version,
         config: &'a Config,
         theme: &'a Theme,
+        spell_checker: Option<&'a Arc<Checker>>,
     ) -> Element<'a, Message> {
         if let Some(state) = self.panes.popout.get(&window) {
             let pane_gap = config.pane.gap.outer;
@@ -1648,6 +1962,8 @@ impl Dashboard {
                         self.typing_animation.as_ref(),
                         clients,
                         &self.file_transfers,
+                        &self.scheduled,
+                        &self.outbox,
                         &self.history,
                         &self.previews,
                         &self.side_menu,
@@ -1655,6 +1971,7 @@ impl Dashboard {
                         theme,
                         settings,
                         window != self.main_window(),
+                        spell_checker,
                     )
                 })
                 .spacing(config.pane.gap.inner)
@@ -1687,6 +2004,7 @@ impl Dashboard {
         version: &'a Version,
         config: &'a Config,
         theme: &'a Theme,
+        spell_checker: Option<&'a Arc<Checker>>,
     ) -> Element<'a, Message> {
         let pane_grid: Element<_> =
             PaneGrid::new(&self.panes.main, |id, pane, maximized| {
@@ -1708,6 +2026,8 @@ impl Dashboard {
                     self.typing_animation.as_ref(),
                     clients,
                     &self.file_transfers,
+                    &self.scheduled,
+                    &self.outbox,
                     &self.history,
                     &self.previews,
                     &self.side_menu,
@@ -1715,6 +2035,7 @@ impl Dashboard {
                     theme,
                     settings,
                     false,
+                    spell_checker,
                 )
             })
             .on_click(pane::Message::PaneClicked)
@@ -1756,8 +2077,11 @@ impl Dashboard {
                 &self.panes,
                 self.focus,
                 &self.server_icons,
+                &self.sidebar_layout,
+                &self.mutes,
                 config,
                 &self.file_transfers,
+                &self.scheduled,
                 version,
                 theme,
             )
@@ -1841,11 +2165,34 @@ impl Dashboard {
                         version,
                         config,
                         self.main_window(),
+                        &self.workspaces,
+                        self.workspace.as_deref(),
                     )
                     .map(Message::Task),
                 anchored_overlay::Anchor::BelowTopCentered,
                 10.0,
             )
+        } else if self.switcher_window == Some(window)
+            && let Some(switcher) = self.switcher.as_ref()
+        {
+            let background = anchored_overlay(
+                base,
+                container(
+                    Space::new().width(Length::Fill).height(Length::Fill),
+                )
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .style(theme::container::transparent_overlay),
+                anchored_overlay::Anchor::BelowTopCentered,
+                0.0,
+            );
+
+            anchored_overlay(
+                background,
+                switcher.view(config).map(Message::Switcher),
+                anchored_overlay::Anchor::BelowTopCentered,
+                10.0,
+            )
         } else {
             // Align `base` into same view tree shape
             // as `anchored_overlay` to prevent diff
@@ -1905,7 +2252,12 @@ impl Dashboard {
         base: Element<'a, Message>,
         config: &'a Config,
     ) -> Element<'a, Message> {
-        shortcut(base, config.keyboard.shortcuts(), Message::Shortcut)
+        let buffer = self
+            .get_focused()
+            .and_then(|(_, _, state)| state.buffer.upstream())
+            .map(config::keys::BufferKind::from);
+
+        shortcut(base, config.keyboard.shortcuts(buffer), Message::Shortcut)
     }
 
     pub fn handle_buffer_event(
@@ -2472,8 +2824,36 @@ impl Dashboard {
                     )),
                 );
             }
-            buffer::Event::ImagePreview(image) => {
-                return (Task::none(), Some(Event::ImagePreview(image)));
+            buffer::Event::ImagePreview(image, urls) => {
+                // Other images previewed in the buffer, for browsing through
+                // them in the lightbox
+                let mut images: Vec<Image> = vec![];
+
+                for url in urls {
+                    if let Some(preview::State::Loaded(data::Preview::Image(
+                        other,
+                    ))) = self.previews.get(&url)
+                        && !images.iter().any(|image| image.url == other.url)
+                    {
+                        images.push(other.clone());
+                    }
+                }
+
+                let index = match images
+                    .iter()
+                    .position(|other| other.url == image.url)
+                {
+                    Some(index) => index,
+                    None => {
+                        images = vec![image];
+                        0
+                    }
+                };
+
+                return (
+                    Task::none(),
+                    Some(Event::ImagePreview(images, index)),
+                );
             }
             buffer::Event::ExpandMessage(server_time, hash) => {
                 if let Some(kind) =
@@ -2544,9 +2924,47 @@ impl Dashboard {
             buffer::Event::OpenServer(server) => {
                 return (Task::none(), Some(Event::OpenServer(server)));
             }
+            buffer::Event::Workspace(workspace) => {
+                let task = match workspace {
+                    data::command::Workspace::Switch(name) => {
+                        self.switch_workspace(&name, clients, config)
+                    }
+                    data::command::Workspace::Save(name) => {
+                        self.save_workspace(name)
+                    }
+                    data::command::Workspace::Delete(name) => {
+                        self.delete_workspace(&name)
+                    }
+                };
+
+                return (task, None);
+            }
             buffer::Event::Reconnect(server) => {
                 controllers.connect(&server);
             }
+            buffer::Event::Schedule(buffer, text, at) => {
+                self.scheduled.add(buffer, text, at);
+                self.last_changed = Some(Instant::now());
+            }
+            buffer::Event::CancelScheduled(id) => {
+                if self.scheduled.cancel(id) {
+                    self.last_changed = Some(Instant::now());
+                }
+            }
+            buffer::Event::Queue(buffer, lines) => {
+                let now = Utc::now();
+
+                for line in lines {
+                    self.outbox.queue(buffer.clone(), line, now);
+                }
+
+                self.last_changed = Some(Instant::now());
+            }
+            buffer::Event::CancelQueued(id) => {
+                if self.outbox.cancel(id) {
+                    self.last_changed = Some(Instant::now());
+                }
+            }
             buffer::Event::FilehostUpload {
                 server,
                 target,
@@ -2589,30 +3007,10 @@ impl Dashboard {
                     abort_registrations,
                 };
 
-                let (task, event) = self.filehost.upload(
-                    pending,
-                    clients,
-                    self.http_client.clone(),
-                    &config.proxy,
-                );
-
-                let task = task.map(Message::Filehost);
-
-                if let Some(filehost::Event::PromptBeforeUpload {
-                    upload_url,
-                    has_credentials,
-                    window,
-                }) = event
-                {
-                    return (
-                        task,
-                        Some(Event::PromptBeforeFileUpload {
-                            upload_url,
-                            has_credentials,
-                            window,
-                        }),
-                    );
-                }
+                let task = self
+                    .filehost
+                    .prepare(pending, &config.filehost.images)
+                    .map(Message::Filehost);
 
                 return (task, None);
             }
@@ -2626,8 +3024,8 @@ impl Dashboard {
         msg: filehost::Message,
         clients: &client::Map,
         config: &Config,
-    ) -> Task<Message> {
-        match msg {
+    ) -> (Task<Message>, Option<Event>) {
+        let task = match msg {
             filehost::Message::UploadDone {
                 window,
                 pane_id,
@@ -2705,7 +3103,21 @@ impl Dashboard {
                 Task::none()
             }
             filehost::Message::KnownSaved(Ok(())) => Task::none(),
-        }
+            filehost::Message::Prepared(batch, processed) => {
+                let (task, event) = self.filehost.prepared(
+                    batch,
+                    processed,
+                    config.filehost.images.confirm,
+                    clients,
+                    self.http_client.clone(),
+                    &config.proxy,
+                );
+
+                return (task.map(Message::Filehost), event.map(Event::from));
+            }
+        };
+
+        (task, None)
     }
 
     pub fn handle_event(
@@ -2737,6 +3149,8 @@ impl Dashboard {
                         config,
                         theme,
                     )
+                } else if self.switcher_window == Some(window) {
+                    self.toggle_switcher(clients)
                 } else {
                     context_menu::close(convert::identity).map(
                         move |any_closed| {
@@ -2893,6 +3307,21 @@ impl Dashboard {
                     None,
                 ),
             },
+            command_bar::Command::Workspace(workspace) => match workspace {
+                command_bar::Workspace::Switch(name) => {
+                    (self.switch_workspace(&name, clients, config), None)
+                }
+                command_bar::Workspace::Save(name) => {
+                    (self.save_workspace(name), None)
+                }
+                command_bar::Workspace::SaveNew => {
+                    let name = self.new_workspace_name();
+                    (self.save_workspace(name), None)
+                }
+                command_bar::Workspace::Delete(name) => {
+                    (self.delete_workspace(&name), None)
+                }
+            },
         }
     }
 
@@ -3354,6 +3783,18 @@ impl Dashboard {
         message: data::Message,
         config: &Config,
     ) -> Task<Message> {
+        self.resend(clients, kind, message, config)
+            .map_or_else(Task::none, |(_, task)| task)
+    }
+
+    /// Sends the message again, returning it as it was sent.
+    fn resend(
+        &mut self,
+        clients: &mut client::Map,
+        kind: history::Kind,
+        message: data::Message,
+        config: &Config,
+    ) -> Option<(data::Message, Task<Message>)> {
         if let Some(buffer) = data::Buffer::from(kind).upstream()
             && let Some(user) = clients
                 .nickname(buffer.server())
@@ -3446,10 +3887,10 @@ impl Dashboard {
                     clients.send(buffer, encoded, TokenPriority::User)
                 };
 
-                return Task::batch(
+                let task = Task::batch(
                     self.history
                         .record_input_message(
-                            message,
+                            message.clone(),
                             labeled_response_context.clone(),
                             buffer.server(),
                             casemapping,
@@ -3459,10 +3900,305 @@ impl Dashboard {
                         .map(Task::future),
                 )
                 .map(Message::History);
+
+                return Some((message, task));
             }
         }
 
-        Task::none()
+        None
+    }
+
+    /// Sends the scheduled messages that are due, once their server is
+    /// connected and their channel is joined.
+    pub fn send_scheduled_messages(
+        &mut self,
+        clients: &mut data::client::Map,
+        config: &Config,
+    ) -> Task<Message> {
+        if self.scheduled.is_empty() {
+            return Task::none();
+        }
+
+        let due = self.scheduled.take_due(Utc::now(), |buffer| {
+            data::schedule::can_send(buffer, clients)
+        });
+
+        if due.is_empty() {
+            return Task::none();
+        }
+
+        self.last_changed = Some(Instant::now());
+
+        let mut tasks = vec![];
+
+        for message in due {
+            match self.send_line(
+                &message.buffer,
+                &message.text,
+                clients,
+                config,
+            ) {
+                Ok(task) => tasks.push(task),
+                Err(error) => {
+                    log::warn!(
+                        "[{}] failed to send scheduled message: {error}",
+                        message.buffer.server()
+                    );
+
+                    self.scheduled.fail(message, error.to_string());
+                }
+            }
+        }
+
+        Task::batch(tasks)
+    }
+
+    /// Sends the messages queued while disconnected, in order, once their
+    /// server is connected and their channel is joined.
+    pub fn send_outbox_messages(
+        &mut self,
+        clients: &mut data::client::Map,
+        config: &Config,
+    ) -> Task<Message> {
+        if self.outbox.is_empty() {
+            return Task::none();
+        }
+
+        let now = Utc::now();
+
+        if self.outbox.confirm_retried(now, |buffer, message| {
+            self.history.is_echoed(
+                &history::Kind::from_input_buffer(buffer.clone()),
+                message,
+            )
+        }) {
+            self.last_changed = Some(Instant::now());
+        }
+
+        let sendable = self.outbox.take_sendable(now, |message| {
+            let server = message.buffer.server();
+
+            // Hold unconfirmed messages while chathistory may still replay
+            // their echo
+            data::schedule::can_send(&message.buffer, clients)
+                && (matches!(message.content, data::outbox::Content::Input(_))
+                    || message.buffer.target().is_none_or(|target| {
+                        !matches!(
+                            clients.get_chathistory_state(server, &target),
+                            Some(ChatHistoryState::PendingRequest)
+                        )
+                    }))
+        });
+
+        if sendable.is_empty() {
+            return Task::none();
+        }
+
+        self.last_changed = Some(Instant::now());
+
+        let mut tasks = vec![];
+
+        for message in sendable {
+            match &message.content {
+                data::outbox::Content::Input(text) => {
+                    match self.send_line(&message.buffer, text, clients, config)
+                    {
+                        Ok(task) => tasks.push(task),
+                        Err(error) => {
+                            log::warn!(
+                                "[{}] failed to send queued message: {error}",
+                                message.buffer.server()
+                            );
+
+                            self.outbox.fail(message, error.to_string());
+                        }
+                    }
+                }
+                data::outbox::Content::Unconfirmed(unconfirmed) => {
+                    let kind = history::Kind::from_input_buffer(
+                        message.buffer.clone(),
+                    );
+
+                    if self.history.is_echoed(&kind, unconfirmed) {
+                        log::debug!(
+                            "[{}] queued message was delivered, not sending it again",
+                            message.buffer.server()
+                        );
+                        continue;
+                    }
+
+                    // Replaced by the message sent again
+                    let _ = self.history.remove_message(
+                        kind.clone(),
+                        unconfirmed.server_time,
+                        unconfirmed.hash,
+                        false,
+                    );
+
+                    match self.resend(
+                        clients,
+                        kind,
+                        unconfirmed.as_ref().clone(),
+                        config,
+                    ) {
+                        Some((resent, task)) => {
+                            tasks.push(task);
+
+                            self.outbox.retried(message, resent, now);
+                        }
+                        None => {
+                            log::warn!(
+                                "[{}] failed to send unconfirmed message again",
+                                message.buffer.server()
+                            );
+
+                            self.outbox.fail(
+                                message,
+                                "couldn't be sent again".to_string(),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        Task::batch(tasks)
+    }
+
+    /// Queues the messages sent to the server since the last echo it sent
+    /// back before it disconnected, which were not confirmed by their echo,
+    /// to send them again once it's connected.  Must be called before the
+    /// client is marked disconnected, while its capabilities are still known.
+    pub fn requeue_unconfirmed_messages(
+        &mut self,
+        server: &Server,
+        clients: &data::client::Map,
+        config: &Config,
+    ) {
+        if !clients.get_server_supports_echoes(server) {
+            return;
+        }
+
+        let Some(server_config) = config.servers.get(server) else {
+            return;
+        };
+
+        let casemapping = clients.get_server_casemapping_or_default(server);
+        let confirm_message_delivery = &server_config.confirm_message_delivery;
+
+        // Echoes arrive in the order messages were sent, so the unconfirmed
+        // ones sent before the last echo were lost rather than still waiting
+        let since = self
+            .history
+            .last_echo(server)
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+
+        let unconfirmed = self.history.unconfirmed_messages(
+            server,
+            since,
+            |kind| match kind {
+                history::Kind::Channel(server, channel) => {
+                    confirm_message_delivery.is_target_channel_included(
+                        channel,
+                        server,
+                        casemapping,
+                    )
+                }
+                history::Kind::Query(server, query) => confirm_message_delivery
+                    .is_target_query_included(query, server, casemapping),
+                _ => false,
+            },
+        );
+
+        if unconfirmed.is_empty() {
+            return;
+        }
+
+        for (kind, message) in unconfirmed {
+            if let Some(buffer) = data::Buffer::from(kind).upstream() {
+                self.outbox.requeue(buffer.clone(), message);
+            }
+        }
+
+        self.last_changed = Some(Instant::now());
+    }
+
+    /// Sends a line as if it was typed into the input of the buffer.
+    fn send_line(
+        &mut self,
+        buffer: &buffer::Upstream,
+        text: &str,
+        clients: &mut data::client::Map,
+        config: &Config,
+    ) -> Result<Task<Message>, data::input::Error> {
+        let input = data::schedule::parse(buffer, text, clients, config)?;
+
+        let Some(encoded) = input.encoded() else {
+            return Ok(Task::none());
+        };
+
+        let labeled_response_context =
+            clients.send(buffer, encoded, TokenPriority::User);
+
+        let Some(user) = clients
+            .nickname(buffer.server())
+            .map(|nick| User::from(nick.to_owned()))
+        else {
+            return Ok(Task::none());
+        };
+
+        let (user, channel_users) =
+            if let buffer::Upstream::Channel(server, channel) = buffer {
+                (
+                    clients
+                        .resolve_user_attributes(server, channel, &user)
+                        .cloned()
+                        .unwrap_or(user),
+                    clients.get_channel_users(server, channel),
+                )
+            } else {
+                (user, None)
+            };
+        let chantypes =
+            clients.get_server_chantypes_or_default(buffer.server());
+        let statusmsg =
+            clients.get_server_statusmsg_or_default(buffer.server());
+        let casemapping =
+            clients.get_server_casemapping_or_default(buffer.server());
+        let supports_echoes =
+            clients.get_server_supports_echoes(buffer.server());
+
+        let messages = input
+            .messages(
+                user,
+                channel_users,
+                buffer.server(),
+                chantypes,
+                statusmsg,
+                casemapping,
+                supports_echoes,
+                self.history.get_reroute_rules(),
+            )
+            .unwrap_or_default();
+
+        let mut tasks = vec![];
+
+        for message in messages {
+            tasks.extend(
+                self.history
+                    .record_input_message(
+                        message,
+                        labeled_response_context.clone(),
+                        buffer.server(),
+                        casemapping,
+                        config,
+                    )
+                    .into_iter()
+                    .map(Task::future),
+            );
+        }
+
+        Ok(Task::batch(tasks).map(Message::History))
     }
 
     pub fn record_reaction(
@@ -3848,6 +4584,36 @@ impl Dashboard {
             .map(|task| Task::perform(task, Message::Client))
     }
 
+    pub fn load_znc_playback_timestamp(
+        &self,
+        clients: &data::client::Map,
+        server: &Server,
+    ) -> Option<Task<Message>> {
+        clients
+            .load_znc_playback_timestamp(server)
+            .map(|task| Task::perform(task, Message::Client))
+    }
+
+    pub fn subscribe_webpush(
+        &self,
+        clients: &data::client::Map,
+        server: &Server,
+    ) -> Option<Task<Message>> {
+        clients
+            .subscribe_webpush(server)
+            .map(|task| Task::perform(task, Message::Client))
+    }
+
+    pub fn overwrite_znc_playback_timestamp(
+        &self,
+        clients: &data::client::Map,
+        server: &Server,
+    ) -> Option<Task<Message>> {
+        clients
+            .overwrite_znc_playback_timestamp(server)
+            .map(|task| Task::perform(task, Message::Client))
+    }
+
     pub fn get_focused(&self) -> Option<(window::Id, pane_grid::Pane, &Pane)> {
         let Focus { window, pane } = self.focus;
         self.panes
@@ -3912,6 +4678,14 @@ impl Dashboard {
         window: window::Id,
         pane: pane_grid::Pane,
     ) -> Task<Message> {
+        if let Some(buffer) = self
+            .panes
+            .get(window, pane)
+            .and_then(|pane| pane.buffer.upstream())
+        {
+            self.recent_buffers.push(buffer.clone());
+        }
+
         if (self.focus != Focus { window, pane })
             || self.focus_history.is_empty()
         {
@@ -4079,6 +4853,10 @@ impl Dashboard {
                 self.close_command_bar();
             }
 
+            if self.switcher_window == Some(window) {
+                self.close_switcher();
+            }
+
             tasks.push(
                 window::close(window)
                     .chain(self.focus_window(self.main_window())),
@@ -4196,6 +4974,146 @@ impl Dashboard {
         )
     }
 
+    /// Replaces the open panes with the ones saved in the workspace `name`.
+    fn switch_workspace(
+        &mut self,
+        name: &str,
+        clients: &mut data::client::Map,
+        config: &Config,
+    ) -> Task<Message> {
+        let Some(workspace) = self.workspaces.get(name).cloned() else {
+            log::warn!("workspace {name} does not exist");
+            return Task::none();
+        };
+
+        for (_, _, state) in self.panes.iter() {
+            mark_as_read_on_buffer_close(
+                &state.buffer,
+                &mut self.history,
+                clients,
+                config,
+            );
+        }
+
+        let mut tasks = vec![];
+
+        for (window, _) in std::mem::take(&mut self.panes.popout) {
+            if self.command_bar_window == Some(window) {
+                self.close_command_bar();
+            }
+
+            if self.switcher_window == Some(window) {
+                self.close_switcher();
+            }
+
+            tasks.push(window::close(window));
+        }
+
+        self.panes.main = pane_grid::State::with_configuration(configuration(
+            workspace.pane,
+            clients,
+            &self.history,
+            config,
+        ));
+        self.focus_history.clear();
+
+        let main_window = self.main_window();
+        let focus = self
+            .panes
+            .iter()
+            .find_map(|(window, pane, state)| {
+                (workspace.focus_buffer.is_some()
+                    && state.buffer.data() == workspace.focus_buffer)
+                    .then_some(Focus { window, pane })
+            })
+            .or_else(|| {
+                self.panes.main.iter().next().map(|(pane, _)| Focus {
+                    window: main_window,
+                    pane: *pane,
+                })
+            });
+
+        for pane in workspace.popout_panes {
+            // Popouts are only a single pane
+            if let pane_grid::Configuration::Pane(pane) =
+                configuration(pane, clients, &self.history, config)
+                && let Some(buffer) = pane.buffer.data()
+            {
+                tasks.push(self.open_buffer(
+                    buffer,
+                    BufferAction::NewWindow,
+                    clients,
+                    config,
+                ));
+            }
+        }
+
+        self.workspace = Some(workspace.name);
+        self.last_changed = Some(Instant::now());
+
+        tasks.push(self.track(Some(&*clients)));
+
+        if let Some(Focus { window, pane }) = focus {
+            tasks.push(self.focus_pane(window, pane));
+        }
+
+        Task::batch(tasks)
+    }
+
+    /// Saves the open panes as the workspace `name`, replacing any workspace
+    /// with the same name.
+    fn save_workspace(&mut self, name: String) -> Task<Message> {
+        self.workspaces.insert(dashboard::workspace::Workspace::new(
+            name.clone(),
+            data::Dashboard::from(&*self),
+        ));
+        self.workspace = Some(name);
+        self.last_changed = Some(Instant::now());
+
+        self.save_workspaces()
+    }
+
+    fn delete_workspace(&mut self, name: &str) -> Task<Message> {
+        if self.workspaces.remove(name).is_none() {
+            return Task::none();
+        }
+
+        if self.workspace.as_deref() == Some(name) {
+            self.workspace = None;
+            self.last_changed = Some(Instant::now());
+        }
+
+        self.save_workspaces()
+    }
+
+    /// Writes the workspaces once they have been loaded, so saving can't
+    /// replace workspaces that were not read (yet).
+    fn save_workspaces(&self) -> Task<Message> {
+        match self.workspaces_load {
+            WorkspacesLoad::Loaded => Task::perform(
+                self.workspaces.clone().save(),
+                Message::WorkspacesSaved,
+            ),
+            // Saved along with the loaded workspaces
+            WorkspacesLoad::Pending => Task::none(),
+            WorkspacesLoad::Failed => {
+                log::error!(
+                    "not saving workspaces, as the saved ones failed to load"
+                );
+
+                Task::none()
+            }
+        }
+    }
+
+    /// Name for a workspace saved without one.
+    fn new_workspace_name(&self) -> String {
+        (1..)
+            .map(|n| format!("Workspace {n}"))
+            .find(|name| self.workspaces.get(name).is_none())
+            .unwrap_or_default()
+    }
+
     pub fn tick(
         &mut self,
         now: Instant,
@@ -4206,6 +5124,10 @@ impl Dashboard {
             self.typing_animation = None;
         }
 
+        if self.mutes.remove_expired(Utc::now()) {
+            self.last_changed = Some(Instant::now());
+        }
+
         let history_ticks = Task::batch(
             self.history
                 .tick(now.into(), clients)
@@ -4225,6 +5147,21 @@ impl Dashboard {
             })
             .flatten();
 
+        let input_history_save = config
+            .buffer
+            .text_input
+            .history
+            .persist
+            .then(|| {
+                self.history
+                    .maybe_save_input_history(
+                        now.into(),
+                        &config.buffer.text_input.history,
+                    )
+                    .map(|fut| Task::perform(fut, Message::History))
+            })
+            .flatten();
+
         if let Some(last_changed) = self.last_changed
             && now.duration_since(last_changed) >= SAVE_AFTER
         {
@@ -4239,6 +5176,7 @@ impl Dashboard {
                 ]
                 .into_iter()
                 .chain(draft_save)
+                .chain(input_history_save)
                 .collect::<Vec<_>>(),
             );
         }
@@ -4247,6 +5185,7 @@ impl Dashboard {
             [history_ticks]
                 .into_iter()
                 .chain(draft_save)
+                .chain(input_history_save)
                 .collect::<Vec<_>>(),
         )
     }
@@ -4311,6 +5250,8 @@ impl Dashboard {
         version: &Version,
         config: &Config,
     ) {
+        self.close_switcher();
+
         self.command_bar_window = Some(self.focus.window);
         self.command_bar = Some(CommandBar::new(
             servers,
@@ -4321,6 +5262,8 @@ impl Dashboard {
             self.focus,
             self.buffer_resize_action(),
             self.main_window(),
+            &self.workspaces,
+            self.workspace.as_deref(),
         ));
     }
 
@@ -4329,6 +5272,54 @@ impl Dashboard {
         self.command_bar_window = None;
     }
 
+    pub fn toggle_switcher(&mut self, clients: &client::Map) -> Task<Message> {
+        if self.switcher_window == Some(self.focus.window) {
+            self.close_switcher();
+            // Refocus the pane so text input gets refocused
+            let Focus { window, pane } = self.focus;
+            self.focus_pane(window, pane)
+        } else {
+            self.close_switcher();
+            self.open_switcher(clients);
+            Task::none()
+        }
+    }
+
+    fn open_switcher(&mut self, clients: &client::Map) {
+        self.close_command_bar();
+
+        let entries = all_buffers(clients, &self.history)
+            .into_iter()
+            .filter(|buffer| !matches!(buffer, buffer::Upstream::Server(_)))
+            .map(|buffer| {
+                let kind = history::Kind::from_input_buffer(buffer.clone());
+
+                data::buffer::switcher::Entry {
+                    has_unread: self.history.has_unread(&kind),
+                    has_highlight: self.history.has_highlight(&kind),
+                    buffer,
+                }
+            })
+            .collect();
+
+        let current = self
+            .panes
+            .get(self.focus.window, self.focus.pane)
+            .and_then(|pane| pane.buffer.upstream());
+
+        self.switcher_window = Some(self.focus.window);
+        self.switcher = Some(Switcher::new(data::buffer::switcher::rank(
+            entries,
+            &self.recent_buffers,
+            current,
+        )));
+    }
+
+    fn close_switcher(&mut self) {
+        self.switcher = None;
+        self.switcher_window = None;
+    }
+
     fn buffer_resize_action(&self) -> data::buffer::Resize {
         let can_resize_buffer =
             self.focus.window == self.main_window() && self.panes.len() > 1;
@@ -4373,6 +5364,7 @@ impl Dashboard {
                 },
             },
             server,
+            &self.mutes,
             request_attention_window,
         );
 
@@ -4442,54 +5434,18 @@ impl Dashboard {
         config: &Config,
         main_window: &Window,
     ) -> (Self, Task<Message>) {
-        use pane_grid::Configuration;
-
-        fn configuration(
-            pane: data::Pane,
-            clients: &data::client::Map,
-            history: &history::Manager,
-            config: &Config,
-        ) -> Configuration<Pane> {
-            match pane {
-                data::Pane::Split { axis, ratio, a, b } => {
-                    Configuration::Split {
-                        axis: match axis {
-                            data::pane::Axis::Horizontal => {
-                                pane_grid::Axis::Horizontal
-                            }
-                            data::pane::Axis::Vertical => {
-                                pane_grid::Axis::Vertical
-                            }
-                        },
-                        ratio,
-                        a: Box::new(configuration(
-                            *a, clients, history, config,
-                        )),
-                        b: Box::new(configuration(
-                            *b, clients, history, config,
-                        )),
-                    }
-                }
-                data::Pane::Buffer { buffer } => {
-                    Configuration::Pane(Pane::new(Buffer::from_data(
-                        buffer,
-                        clients,
-                        history,
-                        Size::default(),
-                        config,
-                    )))
-                }
-                data::Pane::Empty => {
-                    Configuration::Pane(Pane::new(Buffer::empty()))
-                }
-            }
-        }
-
         let mut history = history::Manager::default();
         if config.buffer.text_input.persist {
             history.preload_drafts(data::input::load_drafts_sync());
         }
 
+        if config.buffer.text_input.history.persist {
+            history.preload_input_history(
+                data::input::load_history_sync(),
+                &config.buffer.text_input.history,
+            );
+        }
+
         let panes = Panes {
             main_window: main_window.id,
             main: pane_grid::State::with_configuration(configuration(
@@ -4529,6 +5485,9 @@ impl Dashboard {
             last_changed: None,
             command_bar: None,
             command_bar_window: None,
+            switcher: None,
+            switcher_window: None,
+            recent_buffers: data::buffer::switcher::Recent::default(),
             file_transfers: file_transfer::Manager::default(),
             theme_editor: None,
             notifications: notification::Notifications::new(config),
@@ -4538,6 +5497,13 @@ impl Dashboard {
             typing_animation: None,
             http_client: http_client_from_config(config).map(Arc::new),
             buffer_settings: data.buffer_settings.clone(),
+            sidebar_layout: data.sidebar.clone(),
+            workspaces: dashboard::workspace::Workspaces::default(),
+            workspaces_load: WorkspacesLoad::Pending,
+            workspace: data.workspace.clone(),
+            mutes: data.mutes.clone(),
+            scheduled: data.scheduled.clone(),
+            outbox: data.outbox.clone(),
             filehost: filehost::Manager::new(),
         };
 
@@ -4545,7 +5511,7 @@ impl Dashboard {
 
         for pane in data.popout_panes {
             // Popouts are only a single pane
-            let Configuration::Pane(pane) = configuration(
+            let pane_grid::Configuration::Pane(pane) = configuration(
                 pane,
                 &data::client::Map::default(),
                 &dashboard.history,
@@ -4574,6 +5540,10 @@ impl Dashboard {
         &self.history
     }
 
+    pub fn notification_mutes(&self) -> &data::notification::Mutes {
+        &self.mutes
+    }
+
     pub fn get_filters(&mut self) -> &mut Vec<Filter> {
         self.history.get_filters()
     }
@@ -4605,6 +5575,30 @@ impl Dashboard {
         }
     }
 
+    /// Runs `line` in the focused buffer as if it was sent from its input.
+    fn run_in_focused_buffer(&self, line: String) -> Task<Message> {
+        let Focus { window, pane } = self.focus;
+
+        let Some(pane_state) = self.panes.get(window, pane) else {
+            return Task::none();
+        };
+
+        let msg = match &pane_state.buffer {
+            Buffer::Channel(_) => {
+                buffer::Message::Channel(buffer::channel::Message::Run(line))
+            }
+            Buffer::Query(_) => {
+                buffer::Message::Query(buffer::query::Message::Run(line))
+            }
+            Buffer::Server(_) => {
+                buffer::Message::Server(buffer::server::Message::Run(line))
+            }
+            _ => return Task::none(),
+        };
+
+        Task::done(Message::Pane(window, pane::Message::Buffer(pane, msg)))
+    }
+
     pub fn get_reroute_rules(&self) -> &RerouteRules {
         self.history.get_reroute_rules()
     }
@@ -4722,7 +5716,8 @@ impl Dashboard {
             mark_as_read(kind, &mut self.history, clients, TokenPriority::High);
         });
 
-        let history = self.history.exit(clients);
+        let history = self.history.exit(clients, config);
+        let znc_playback = clients.save_znc_playback_timestamps();
         let last_changed = self.last_changed.take();
         let dashboard = data::Dashboard::from(&*self);
 
@@ -4739,6 +5734,8 @@ impl Dashboard {
                     }
                 }
 
+                znc_playback.await;
+
                 history.await
             },
             Message::History,
@@ -4990,6 +5987,56 @@ pub struct Focus {
     pub pane: pane_grid::Pane,
 }
 
+fn configuration(
+    pane: data::Pane,
+    clients: &data::client::Map,
+    history: &history::Manager,
+    config: &Config,
+) -> pane_grid::Configuration<Pane> {
+    match pane {
+        data::Pane::Split { axis, ratio, a, b } => {
+            pane_grid::Configuration::Split {
+                axis: match axis {
+                    data::pane::Axis::Horizontal => pane_grid::Axis::Horizontal,
+                    data::pane::Axis::Vertical => pane_grid::Axis::Vertical,
+                },
+                ratio,
+                a: Box::new(configuration(*a, clients, history, config)),
+                b: Box::new(configuration(*b, clients, history, config)),
+            }
+        }
+        data::Pane::Buffer { buffer } => {
+            pane_grid::Configuration::Pane(Pane::new(Buffer::from_data(
+                buffer,
+                clients,
+                history,
+                Size::default(),
+                config,
+            )))
+        }
+        data::Pane::Empty => {
+            pane_grid::Configuration::Pane(Pane::new(Buffer::empty()))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WorkspacesLoad {
+    Pending,
+    Loaded,
+    Failed,
+}
+
+/// Loads the saved workspaces, switching to the one configured to be
+/// opened on launch once they're loaded.
+fn load_workspaces(config: &Config) -> Task<Message> {
+    let on_launch = config.pane.workspace_on_launch.clone();
+
+    Task::perform(dashboard::workspace::Workspaces::load(), move |result| {
+        Message::WorkspacesLoaded(result, on_launch)
+    })
+}
+
 impl<'a> From<&'a Dashboard> for data::Dashboard {
     fn from(dashboard: &'a Dashboard) -> Self {
         use pane_grid::Node;
@@ -5031,6 +6078,11 @@ impl<'a> From<&'a Dashboard> for data::Dashboard {
                 .map(|state| from_layout(state, state.layout().clone()))
                 .collect(),
             buffer_settings: dashboard.buffer_settings.clone(),
+            sidebar: dashboard.sidebar_layout.clone(),
+            workspace: dashboard.workspace.clone(),
+            mutes: dashboard.mutes.clone(),
+            scheduled: dashboard.scheduled.clone(),
+            outbox: dashboard.outbox.clone(),
             focus_buffer: dashboard.panes.iter().find_map(|(w, p, state)| {
                 (w == focus.window && p == focus.pane)
                     .then_some(state.buffer.data())
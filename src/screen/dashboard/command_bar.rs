@@ -1,3 +1,4 @@
+use data::dashboard::workspace::Workspaces;
 use data::{Config, buffer, client, server};
 use iced::Length;
 use iced::widget::{column, container, text};
@@ -29,6 +30,8 @@ impl CommandBar {
         focus: Focus,
         resize_buffer: data::buffer::Resize,
         main_window: window::Id,
+        workspaces: &Workspaces,
+        workspace: Option<&str>,
     ) -> Self {
         let state = combo_box::State::new(Command::list(
             servers,
@@ -39,6 +42,8 @@ impl CommandBar {
             resize_buffer,
             version,
             main_window,
+            workspaces,
+            workspace,
         ));
         state.focus();
 
@@ -67,6 +72,8 @@ impl CommandBar {
         version: &data::Version,
         config: &'a Config,
         main_window: window::Id,
+        workspaces: &Workspaces,
+        workspace: Option<&str>,
     ) -> Element<'a, Message> {
         // 1px larger than default
         let font_size =
@@ -108,6 +115,8 @@ impl CommandBar {
                         resize_buffer,
                         version,
                         main_window,
+                        workspaces,
+                        workspace,
                     )
                     .iter()
                     .map(|command| {
@@ -142,6 +151,7 @@ pub enum Command {
     Buffer(Buffer),
     Configuration(Configuration),
     Theme(Theme),
+    Workspace(Workspace),
 }
 
 #[derive(Debug, Clone)]
@@ -192,6 +202,14 @@ pub enum Theme {
     OpenThemesWebsite,
 }
 
+#[derive(Debug, Clone)]
+pub enum Workspace {
+    Switch(String),
+    Save(String),
+    SaveNew,
+    Delete(String),
+}
+
 impl Command {
     pub fn list(
         servers: &server::Map,
@@ -202,6 +220,8 @@ impl Command {
         resize_buffer: buffer::Resize,
         version: &data::Version,
         main_window: window::Id,
+        workspaces: &Workspaces,
+        workspace: Option<&str>,
     ) -> Vec<Self> {
         let servers = Server::list(clients, servers)
             .into_iter()
@@ -217,6 +237,10 @@ impl Command {
 
         let themes = Theme::list(config).into_iter().map(Command::Theme);
 
+        let workspaces = Workspace::list(workspaces, workspace)
+            .into_iter()
+            .map(Command::Workspace);
+
         let version = Version::list(version).into_iter().map(Command::Version);
 
         let application =
@@ -226,6 +250,7 @@ impl Command {
             .chain(application)
             .chain(buffers)
             .chain(servers)
+            .chain(workspaces)
             .chain(configs)
             .chain(themes)
             .collect()
@@ -241,6 +266,9 @@ impl std::fmt::Display for Command {
                 write!(f, "Configuration: {config}")
             }
             Command::Theme(theme) => write!(f, "Theme: {theme}"),
+            Command::Workspace(workspace) => {
+                write!(f, "Workspace: {workspace}")
+            }
             Command::Version(application) => {
                 write!(f, "Version: {application}")
             }
@@ -357,6 +385,30 @@ impl Theme {
     }
 }
 
+impl Workspace {
+    fn list(workspaces: &Workspaces, current: Option<&str>) -> Vec<Self> {
+        let mut list = workspaces
+            .names()
+            .filter(|name| Some(*name) != current)
+            .map(|name| Workspace::Switch(name.to_string()))
+            .collect::<Vec<_>>();
+
+        if let Some(current) = current {
+            list.push(Workspace::Save(current.to_string()));
+        }
+
+        list.push(Workspace::SaveNew);
+
+        list.extend(
+            workspaces
+                .names()
+                .map(|name| Workspace::Delete(name.to_string())),
+        );
+
+        list
+    }
+}
+
 impl std::fmt::Display for Application {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -406,11 +458,17 @@ impl std::fmt::Display for Buffer {
                 buffer::Internal::FileTransfers => {
                     write!(f, "Open file transfers")
                 }
+                buffer::Internal::ScheduledMessages => {
+                    write!(f, "Open scheduled messages")
+                }
                 buffer::Internal::Logs => write!(f, "Open logs"),
                 buffer::Internal::Highlights => write!(f, "Open highlights"),
                 buffer::Internal::ChannelDiscovery(_) => {
                     write!(f, "Open Channel discovery")
                 }
+                buffer::Internal::ServerNotices(server) => {
+                    write!(f, "Open server notices on {server}")
+                }
             },
             Buffer::Replace(buffer) => match buffer {
                 buffer::Upstream::Server(server) => {
@@ -461,3 +519,18 @@ impl std::fmt::Display for Theme {
         }
     }
 }
+
+impl std::fmt::Display for Workspace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Workspace::Switch(name) => write!(f, "Switch to {name}"),
+            Workspace::Save(name) => {
+                write!(f, "Save current layout to {name}")
+            }
+            Workspace::SaveNew => {
+                write!(f, "Save current layout as new workspace")
+            }
+            Workspace::Delete(name) => write!(f, "Delete {name}"),
+        }
+    }
+}
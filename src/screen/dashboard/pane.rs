@@ -1,5 +1,8 @@
+use std::sync::Arc;
+
+use data::spell_check::Checker;
 use data::user::{ChannelUsers, User};
-use data::{Config, file_transfer, history, preview};
+use data::{Config, file_transfer, history, outbox, preview, schedule};
 use iced::widget::text::Wrapping;
 use iced::widget::{button, center, column, container, pane_grid, row, text};
 use iced::{Length, Padding, Size, Task, padding};
@@ -60,6 +63,8 @@ impl Pane {
         typing_animation: Option<&'a buffer::typing::Animation>,
         clients: &'a data::client::Map,
         file_transfers: &'a file_transfer::Manager,
+        scheduled: &'a schedule::Scheduled,
+        outbox: &'a outbox::Outbox,
         history: &'a history::Manager,
         previews: &'a preview::Collection,
         sidebar: &'a sidebar::Sidebar,
@@ -67,6 +72,7 @@ impl Pane {
         theme: &'a Theme,
         settings: Option<&'a buffer::Settings>,
         is_popout: bool,
+        spell_checker: Option<&'a Arc<Checker>>,
     ) -> widget::Content<'a, Message> {
         let title: Element<'a, Message> = match &self.buffer {
             Buffer::Empty => text("").into(),
@@ -129,6 +135,10 @@ impl Pane {
                 .wrapping(Wrapping::None)
                 .ellipsis(text::Ellipsis::End)
                 .into(),
+            Buffer::ScheduledMessages(_) => text("Scheduled Messages")
+                .wrapping(Wrapping::None)
+                .ellipsis(text::Ellipsis::End)
+                .into(),
             Buffer::ChannelDiscovery(state) => {
                 let base = "Channel Discovery";
                 if let Some(server) = state.server.as_ref() {
@@ -163,6 +173,12 @@ impl Pane {
                 .wrapping(Wrapping::None)
                 .ellipsis(text::Ellipsis::End)
                 .into(),
+            Buffer::ServerNotices(state) => {
+                text(format!("Server Notices @ {}", state.server))
+                    .wrapping(Wrapping::None)
+                    .ellipsis(text::Ellipsis::End)
+                    .into()
+            }
         };
 
         let title_bar = self.title_bar.view(
@@ -188,6 +204,8 @@ impl Pane {
                 typing_animation,
                 clients,
                 file_transfers,
+                scheduled,
+                outbox,
                 history,
                 previews,
                 settings,
@@ -195,6 +213,7 @@ impl Pane {
                 theme,
                 is_focused,
                 sidebar,
+                spell_checker,
             )
             .map(move |msg| Message::Buffer(id, msg));
 
@@ -256,7 +275,12 @@ impl Pane {
             }),
             Buffer::Logs(_) => Some(history::Resource::logs()),
             Buffer::Highlights(_) => Some(history::Resource::highlights()),
-            Buffer::ChannelDiscovery(_) | Buffer::FileTransfers(_) => None,
+            Buffer::ServerNotices(state) => Some(history::Resource {
+                kind: history::Kind::ServerNotices(state.server.clone()),
+            }),
+            Buffer::ChannelDiscovery(_)
+            | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_) => None,
         }
     }
 
@@ -269,9 +293,11 @@ impl Pane {
             Buffer::Empty
             | Buffer::Server(_)
             | Buffer::FileTransfers(_)
+            | Buffer::ScheduledMessages(_)
             | Buffer::Logs(_)
             | Buffer::Highlights(_)
-            | Buffer::ChannelDiscovery(_) => vec![],
+            | Buffer::ChannelDiscovery(_)
+            | Buffer::ServerNotices(_) => vec![],
         }
     }
 }
@@ -696,6 +722,9 @@ impl From<Pane> for data::Pane {
             Buffer::FileTransfers(_) => {
                 data::Buffer::Internal(buffer::Internal::FileTransfers)
             }
+            Buffer::ScheduledMessages(_) => {
+                data::Buffer::Internal(buffer::Internal::ScheduledMessages)
+            }
             Buffer::Logs(_) => data::Buffer::Internal(buffer::Internal::Logs),
             Buffer::Highlights(_) => {
                 data::Buffer::Internal(buffer::Internal::Highlights)
@@ -703,6 +732,9 @@ impl From<Pane> for data::Pane {
             Buffer::ChannelDiscovery(state) => data::Buffer::Internal(
                 buffer::Internal::ChannelDiscovery(state.server.clone()),
             ),
+            Buffer::ServerNotices(state) => data::Buffer::Internal(
+                buffer::Internal::ServerNotices(state.server),
+            ),
         };
 
         data::Pane::Buffer { buffer }
@@ -1,16 +1,19 @@
 use std::iter;
 use std::time::Duration;
 
+use chrono::{Local, TimeDelta, Utc};
 use data::config::{self, Config, sidebar};
+use data::dashboard::sidebar::{Group, Layout, Target};
 use data::dashboard::{BufferAction, BufferFocusedAction};
+use data::notification::Mutes;
 use data::{
-    Version, buffer, file_transfer, history, isupport, server, server_icon,
-    target,
+    Version, buffer, file_transfer, history, isupport, schedule, server,
+    server_icon, target,
 };
 use iced::widget::text::{LineHeight, Shaping};
 use iced::widget::{
-    Column, Row, Scrollable, Space, button, column, container, pane_grid, row,
-    rule, scrollable, space, stack,
+    self, Column, Row, Scrollable, Space, button, column, container, operation,
+    pane_grid, row, rule, scrollable, space, stack, text_input,
 };
 use iced::{
     Alignment, Border, ContentFit, Length, Padding, Task, mouse, padding,
@@ -19,7 +22,7 @@ use tokio::time;
 
 use super::{Focus, Panes, Server};
 use crate::widget::{
-    Element, Text, TextExt, context_menu, double_pass, image, text,
+    Element, Text, TextExt, context_menu, double_pass, draggable, image, text,
 };
 use crate::{Theme, font, icon, platform_specific, theme, window};
 
@@ -56,6 +59,20 @@ pub enum Message {
     Connect(Server),
     Remove(Server),
     SystemInformation(iced::system::Information),
+    Pin(buffer::Upstream),
+    Release(buffer::Upstream),
+    Mute(buffer::Upstream, Option<TimeDelta>),
+    Unmute(buffer::Upstream),
+    AddToGroup(buffer::Upstream, usize),
+    NewGroup(buffer::Upstream),
+    EditGroup(usize, String),
+    GroupNameChanged(String),
+    GroupNameSubmitted,
+    RemoveGroup(usize),
+    DragStarted(buffer::Upstream),
+    DragEntered(Target),
+    DragExited(Target),
+    Dropped,
 }
 
 #[derive(Debug, Clone)]
@@ -86,6 +103,15 @@ pub enum Event {
     QuitApplication,
     Connect(Server),
     Remove(Server),
+    Pin(buffer::Upstream),
+    Release(buffer::Upstream),
+    Mute(buffer::Upstream, Option<TimeDelta>),
+    Unmute(buffer::Upstream),
+    AddToGroup(buffer::Upstream, usize),
+    NewGroup(buffer::Upstream),
+    RenameGroup(usize, String),
+    RemoveGroup(usize),
+    Move(buffer::Upstream, Target),
 }
 
 #[derive(Clone)]
@@ -93,6 +119,21 @@ pub struct Sidebar {
     pub hidden: bool,
     reloading_config: bool,
     system_information: Option<iced::system::Information>,
+    drag: Option<Drag>,
+    editing_group: Option<EditingGroup>,
+}
+
+#[derive(Debug, Clone)]
+struct Drag {
+    buffer: buffer::Upstream,
+    over: Option<Target>,
+}
+
+#[derive(Debug, Clone)]
+struct EditingGroup {
+    index: usize,
+    name: String,
+    input_id: widget::Id,
 }
 
 impl Sidebar {
@@ -102,6 +143,8 @@ impl Sidebar {
                 hidden: false,
                 reloading_config: false,
                 system_information: None,
+                drag: None,
+                editing_group: None,
             },
             iced::system::information().map(Message::SystemInformation),
         )
@@ -111,6 +154,19 @@ impl Sidebar {
         self.hidden = !self.hidden;
     }
 
+    /// Shows a text input in place of the group's header to rename it.
+    pub fn edit_group(&mut self, index: usize, name: String) -> Task<Message> {
+        let input_id = widget::Id::unique();
+
+        self.editing_group = Some(EditingGroup {
+            index,
+            name,
+            input_id: input_id.clone(),
+        });
+
+        operation::focus(input_id)
+    }
+
     pub fn update(
         &mut self,
         message: Message,
@@ -204,6 +260,76 @@ impl Sidebar {
                     system_information,
                 }),
             ),
+            Message::Pin(buffer) => (Task::none(), Some(Event::Pin(buffer))),
+            Message::Release(buffer) => {
+                (Task::none(), Some(Event::Release(buffer)))
+            }
+            Message::Mute(buffer, duration) => {
+                (Task::none(), Some(Event::Mute(buffer, duration)))
+            }
+            Message::Unmute(buffer) => {
+                (Task::none(), Some(Event::Unmute(buffer)))
+            }
+            Message::AddToGroup(buffer, index) => {
+                (Task::none(), Some(Event::AddToGroup(buffer, index)))
+            }
+            Message::NewGroup(buffer) => {
+                (Task::none(), Some(Event::NewGroup(buffer)))
+            }
+            Message::EditGroup(index, name) => {
+                (self.edit_group(index, name), None)
+            }
+            Message::GroupNameChanged(name) => {
+                if let Some(editing) = &mut self.editing_group {
+                    editing.name = name;
+                }
+
+                (Task::none(), None)
+            }
+            Message::GroupNameSubmitted => {
+                let event = self.editing_group.take().and_then(|editing| {
+                    let name = editing.name.trim();
+
+                    (!name.is_empty()).then(|| {
+                        Event::RenameGroup(editing.index, name.to_string())
+                    })
+                });
+
+                (Task::none(), event)
+            }
+            Message::RemoveGroup(index) => {
+                self.editing_group = None;
+
+                (Task::none(), Some(Event::RemoveGroup(index)))
+            }
+            Message::DragStarted(buffer) => {
+                self.drag = Some(Drag { buffer, over: None });
+
+                (Task::none(), None)
+            }
+            Message::DragEntered(target) => {
+                if let Some(drag) = &mut self.drag {
+                    drag.over = Some(target);
+                }
+
+                (Task::none(), None)
+            }
+            Message::DragExited(target) => {
+                if let Some(drag) = &mut self.drag
+                    && drag.over.as_ref() == Some(&target)
+                {
+                    drag.over = None;
+                }
+
+                (Task::none(), None)
+            }
+            Message::Dropped => {
+                let event = self.drag.take().and_then(|drag| {
+                    drag.over.map(|target| Event::Move(drag.buffer, target))
+                });
+
+                (Task::none(), event)
+            }
         }
     }
 
@@ -212,6 +338,7 @@ impl Sidebar {
         config: &'a Config,
         history: &'a history::Manager,
         file_transfers: &'a file_transfer::Manager,
+        scheduled: &'a schedule::Scheduled,
         version: &'a Version,
         theme: &'a Theme,
     ) -> Element<'a, Message> {
@@ -230,7 +357,11 @@ impl Sidebar {
         .padding(5)
         .width(Length::Shrink);
 
-        let menu = Menu::list(version.is_old(), config.file_transfer.enabled);
+        let menu = Menu::list(
+            version.is_old(),
+            config.file_transfer.enabled,
+            !scheduled.is_empty(),
+        );
 
         let logs_has_unread = history.has_unread(&history::Kind::Logs);
 
@@ -342,6 +473,14 @@ impl Sidebar {
                                     buffer::Internal::FileTransfers,
                                 ),
                             ),
+                            Menu::ScheduledMessages => context_button(
+                                text("Scheduled Messages"),
+                                None,
+                                icon::share(),
+                                Message::ToggleInternalBuffer(
+                                    buffer::Internal::ScheduledMessages,
+                                ),
+                            ),
                             Menu::Highlights => context_button(
                                 text("Highlights"),
                                 Some(&keyboard.highlights),
@@ -462,8 +601,11 @@ impl Sidebar {
         panes: &'a Panes,
         focus: Focus,
         server_icons: &'a server_icon::Manager,
+        layout: &'a Layout,
+        mutes: &'a Mutes,
         config: &'a Config,
         file_transfers: &'a file_transfer::Manager,
+        scheduled: &'a schedule::Scheduled,
         version: &'a Version,
         theme: &'a Theme,
     ) -> Option<Element<'a, Message>> {
@@ -478,6 +620,7 @@ impl Sidebar {
                         config,
                         history,
                         file_transfers,
+                        scheduled,
                         version,
                         theme,
                     )
@@ -490,33 +633,79 @@ impl Sidebar {
                 buffers.push(space::horizontal().width(4).into());
             }
 
-            for server in servers.keys() {
+            let separator = || -> Element<'a, Message> {
+                if config.sidebar.position.is_horizontal() {
+                    space::horizontal()
+                        .width(config.sidebar.spacing.server)
+                        .into()
+                } else {
+                    space::vertical()
+                        .height(config.sidebar.spacing.server)
+                        .into()
+                }
+            };
+
+            let button = |buffer: buffer::Upstream, connected: bool| {
+                let server = buffer.server();
                 let server_has_unread = history.server_has_unread(server);
                 let supports_detach =
                     clients.get_server_supports_detach(server);
                 let casemapping =
                     clients.get_server_casemapping_or_default(server);
+                let is_drop_target = self.drag.as_ref().is_some_and(|drag| {
+                    drag.over.as_ref().is_some_and(|target| {
+                        *target == Target::Buffer(buffer.clone())
+                    })
+                });
+                let kind = history::Kind::from_input_buffer(buffer.clone());
+
+                upstream_buffer_button(
+                    config,
+                    panes,
+                    focus,
+                    server_icons,
+                    buffer,
+                    kind,
+                    connected,
+                    server_has_unread,
+                    supports_detach,
+                    casemapping,
+                    history,
+                    layout,
+                    mutes,
+                    is_drop_target,
+                    width,
+                    theme,
+                )
+            };
 
-                let button = |buffer: buffer::Upstream,
-                              kind: history::Kind,
-                              connected: bool| {
-                    upstream_buffer_button(
-                        config,
-                        panes,
-                        focus,
-                        server_icons,
-                        buffer,
-                        kind,
-                        connected,
-                        server_has_unread,
-                        supports_detach,
-                        casemapping,
-                        history,
-                        width,
-                        theme,
-                    )
-                };
+            // Pinned and grouped buffers are listed above the servers, as long
+            // as their server is still in the sidebar.
+            let placed = |buffer: &buffer::Upstream| {
+                clients.state(buffer.server()).map(|_| {
+                    let connected =
+                        server_buffers(buffer.server(), clients, history)
+                            .contains(buffer);
+
+                    button(buffer.clone(), connected)
+                })
+            };
+
+            let pinned =
+                layout.pinned.iter().filter_map(&placed).collect::<Vec<_>>();
 
+            if !pinned.is_empty() {
+                buffers.extend(pinned);
+                buffers.push(separator());
+            }
+
+            for (index, group) in layout.groups.iter().enumerate() {
+                buffers.push(self.group_header(index, group, config, theme));
+                buffers.extend(group.buffers.iter().filter_map(&placed));
+                buffers.push(separator());
+            }
+
+            for server in servers.keys() {
                 if let Some(state) = clients.state(server) {
                     client_enumeration += 1;
 
@@ -525,70 +714,28 @@ impl Sidebar {
                             // Disconnected server.
                             buffers.push(button(
                                 buffer::Upstream::Server(server.clone()),
-                                history::Kind::Server(server.clone()),
                                 false,
                             ));
                         }
-                        data::client::State::Ready(connection) => {
+                        data::client::State::Ready(_) => {
                             // Connected server.
                             buffers.push(button(
                                 buffer::Upstream::Server(server.clone()),
-                                history::Kind::Server(server.clone()),
                                 true,
                             ));
 
-                            // Channels from the connected server.
-                            for channel in connection.channels() {
-                                buffers.push(button(
-                                    buffer::Upstream::Channel(
-                                        server.clone(),
-                                        channel.clone(),
-                                    ),
-                                    history::Kind::Channel(
-                                        server.clone(),
-                                        channel.clone(),
-                                    ),
-                                    true,
-                                ));
-                            }
-
-                            // Queries from the connected server.
-                            let queries = history.get_unique_queries(server);
-                            for query in queries {
-                                let query = clients
-                                    .resolve_query(server, query)
-                                    .unwrap_or(query);
-
-                                buffers.push(button(
-                                    buffer::Upstream::Query(
-                                        server.clone(),
-                                        query.clone(),
-                                    ),
-                                    history::Kind::Query(
-                                        server.clone(),
-                                        query.clone(),
-                                    ),
-                                    true,
-                                ));
+                            // Channels and queries from the connected server.
+                            for buffer in layout.arrange(server_buffers(
+                                server, clients, history,
+                            )) {
+                                buffers.push(button(buffer, true));
                             }
 
                             // Separator between servers.
-                            if config.sidebar.position.is_horizontal() {
-                                if client_enumeration < clients.len() {
-                                    buffers.push(
-                                        space::horizontal()
-                                            .width(
-                                                config.sidebar.spacing.server,
-                                            )
-                                            .into(),
-                                    );
-                                }
-                            } else {
-                                buffers.push(
-                                    space::vertical()
-                                        .height(config.sidebar.spacing.server)
-                                        .into(),
-                                );
+                            if !config.sidebar.position.is_horizontal()
+                                || client_enumeration < clients.len()
+                            {
+                                buffers.push(separator());
                             }
                         }
                     }
@@ -708,6 +855,125 @@ impl Sidebar {
     }
 }
 
+impl Sidebar {
+    fn group_header<'a>(
+        &'a self,
+        index: usize,
+        group: &'a Group,
+        config: &'a Config,
+        theme: &'a Theme,
+    ) -> Element<'a, Message> {
+        let font_size =
+            config.sidebar.font_size.or(config.font.size).map(f32::from);
+
+        if let Some(editing) = &self.editing_group
+            && editing.index == index
+        {
+            return text_input("Group name", &editing.name)
+                .id(editing.input_id.clone())
+                .on_input(Message::GroupNameChanged)
+                .on_submit(Message::GroupNameSubmitted)
+                .size(font_size.unwrap_or(theme::TEXT_SIZE))
+                .padding(config.sidebar.padding.buffer)
+                .width(if config.sidebar.position.is_horizontal() {
+                    Length::Fixed(120.0)
+                } else {
+                    Length::Fill
+                })
+                .style(theme::text_input::primary)
+                .into();
+        }
+
+        let header = container(
+            text(&group.name)
+                .line_height(LineHeight::Relative(1.0))
+                .size_maybe(font_size)
+                .style(theme::text::secondary)
+                .font_maybe(theme::font_style::secondary(theme).map(font::get))
+                .shaping(Shaping::Advanced),
+        )
+        .padding(config.sidebar.padding.buffer);
+
+        let is_drop_target = self.drag.as_ref().is_some_and(|drag| {
+            drag.over.as_ref() == Some(&Target::Group(index))
+        });
+
+        let header = if is_drop_target {
+            header.style(theme::container::sidebar_drop_target)
+        } else {
+            header
+        };
+
+        let header = context_menu(
+            context_menu::MouseButton::default(),
+            context_menu::Anchor::Cursor,
+            context_menu::ToggleBehavior::KeepOpen,
+            None,
+            header,
+            vec![GroupEntry::Rename, GroupEntry::Remove],
+            move |entry, length| {
+                let (content, message) = match entry {
+                    GroupEntry::Rename => (
+                        "Rename group",
+                        Message::EditGroup(index, group.name.clone()),
+                    ),
+                    GroupEntry::Remove => {
+                        ("Remove group", Message::RemoveGroup(index))
+                    }
+                };
+
+                button(text(content))
+                    .width(length)
+                    .padding(config.context_menu.padding.entry)
+                    .style(|theme, status| {
+                        theme::button::primary(theme, status, false)
+                    })
+                    .on_press(message)
+                    .into()
+            },
+        );
+
+        draggable(
+            header,
+            None,
+            Message::DragEntered(Target::Group(index)),
+            Message::DragExited(Target::Group(index)),
+            Message::Dropped,
+        )
+    }
+}
+
+/// The channels and queries listed under a connected server, in their default
+/// order.
+pub fn server_buffers(
+    server: &Server,
+    clients: &data::client::Map,
+    history: &history::Manager,
+) -> Vec<buffer::Upstream> {
+    let Some(data::client::State::Ready(connection)) = clients.state(server)
+    else {
+        return vec![];
+    };
+
+    let channels = connection.channels().map(|channel| {
+        buffer::Upstream::Channel(server.clone(), channel.clone())
+    });
+
+    let queries = history.get_unique_queries(server).into_iter().map(|query| {
+        let query = clients.resolve_query(server, query).unwrap_or(query);
+
+        buffer::Upstream::Query(server.clone(), query.clone())
+    });
+
+    channels.chain(queries).collect()
+}
+
+#[derive(Debug, Clone, Copy)]
+enum GroupEntry {
+    Rename,
+    Remove,
+}
+
 #[derive(Debug, Clone, Copy)]
 enum Menu {
     RefreshConfig,
@@ -717,6 +983,7 @@ enum Menu {
     ChannelDiscovery,
     Logs,
     FileTransfers,
+    ScheduledMessages,
     Version,
     Update,
     HorizontalRule,
@@ -726,7 +993,11 @@ enum Menu {
 }
 
 impl Menu {
-    fn list(has_new_version: bool, file_transfer_enabled: bool) -> Vec<Self> {
+    fn list(
+        has_new_version: bool,
+        file_transfer_enabled: bool,
+        has_scheduled_messages: bool,
+    ) -> Vec<Self> {
         let mut list = vec![Self::Version];
 
         if has_new_version {
@@ -743,6 +1014,10 @@ impl Menu {
             list.push(Self::FileTransfers);
         }
 
+        if has_scheduled_messages {
+            list.push(Self::ScheduledMessages);
+        }
+
         list.extend([
             Self::ChannelDiscovery,
             Self::Highlights,
@@ -766,6 +1041,7 @@ enum Entry {
     CloseAllQueries,
     MarkAsRead,
     MarkServerAsRead,
+    ServerNotices,
     NewPane,
     Popout,
     Replace,
@@ -773,6 +1049,14 @@ enum Entry {
     Detach,
     Leave,
     Remove,
+    Pin,
+    Unpin,
+    AddToGroup(usize),
+    NewGroup,
+    RemoveFromGroup,
+    MuteFor(i64),
+    Mute,
+    Unmute,
 }
 
 impl Entry {
@@ -783,6 +1067,8 @@ impl Entry {
         focus: Focus,
         connected: bool,
         supports_detach: bool,
+        layout: &Layout,
+        muted: bool,
     ) -> Vec<Self> {
         use Entry::*;
         use itertools::Itertools;
@@ -790,7 +1076,7 @@ impl Entry {
         itertools::chain!(
             match buffer {
                 buffer::Upstream::Server(_) => if connected {
-                    vec![CloseAllQueries, MarkServerAsRead]
+                    vec![CloseAllQueries, MarkServerAsRead, ServerNotices]
                 } else {
                     vec![Connect, Remove]
                 }
@@ -821,6 +1107,32 @@ impl Entry {
             } else {
                 vec![]
             },
+            match buffer {
+                buffer::Upstream::Server(_) => vec![],
+                buffer::Upstream::Channel(_, _)
+                | buffer::Upstream::Query(_, _) => {
+                    let group = layout.group_of(buffer);
+
+                    iter::once(if layout.is_pinned(buffer) {
+                        Unpin
+                    } else {
+                        Pin
+                    })
+                    .chain(
+                        (0..layout.groups.len())
+                            .filter(|index| Some(*index) != group)
+                            .map(AddToGroup),
+                    )
+                    .chain(iter::once(NewGroup))
+                    .chain(group.map(|_| RemoveFromGroup))
+                    .collect_vec()
+                }
+            },
+            if muted {
+                vec![Unmute]
+            } else {
+                vec![MuteFor(1), MuteFor(8), Mute]
+            },
         )
         .sorted()
         .collect_vec()
@@ -839,6 +1151,9 @@ fn upstream_buffer_button<'a>(
     supports_detach: bool,
     casemapping: isupport::CaseMap,
     history: &'a history::Manager,
+    layout: &'a Layout,
+    mutes: &'a Mutes,
+    is_drop_target: bool,
     width: Length,
     theme: &'a Theme,
 ) -> Element<'a, Message> {
@@ -846,6 +1161,8 @@ fn upstream_buffer_button<'a>(
         (state.buffer.upstream() == Some(&buffer)).then_some((window_id, pane))
     });
 
+    let muted_until = mutes.muted_until(&buffer, Utc::now());
+
     let has_unread = if config.sidebar.unread_indicator.show_on_open_buffers
         || open.is_none()
     {
@@ -1194,6 +1511,24 @@ fn upstream_buffer_button<'a>(
         }
     }
 
+    if let Some(until) = muted_until {
+        let badge = match until {
+            Some(until) => format!(
+                "muted until {}",
+                until.with_timezone(&Local).format("%H:%M")
+            ),
+            None => "muted".to_string(),
+        };
+
+        content = content.push(Space::new().width(6));
+        content = content.push(
+            text(badge)
+                .line_height(LineHeight::Relative(1.0))
+                .size(theme::TEXT_SIZE - 2.0)
+                .style(theme::text::secondary),
+        );
+    }
+
     let base =
         button(content.width(width).padding(Padding::default().bottom(1)))
             .style(move |theme, status| {
@@ -1263,9 +1598,17 @@ fn upstream_buffer_button<'a>(
         focus,
         connected,
         supports_detach,
+        layout,
+        muted_until.is_some(),
     );
 
-    if entries.is_empty() {
+    // Server buffers keep their position; only channels and queries can be
+    // dragged.
+    let on_drag = (!matches!(buffer, buffer::Upstream::Server(_)))
+        .then(|| Message::DragStarted(buffer.clone()));
+    let target = Target::Buffer(buffer.clone());
+
+    let element: Element<'a, Message> = if entries.is_empty() {
         base.into()
     } else {
         context_menu(
@@ -1276,6 +1619,18 @@ fn upstream_buffer_button<'a>(
             base,
             entries,
             move |entry, length| {
+                let entry_button =
+                    |content: Text<'a>, message: Option<Message>| {
+                        button(content)
+                            .width(length)
+                            .padding(config.context_menu.padding.entry)
+                            .style(|theme, status| {
+                                theme::button::primary(theme, status, false)
+                            })
+                            .on_press_maybe(message)
+                            .into()
+                    };
+
                 let (content, message) = match entry {
                     Entry::CloseAllQueries => {
                         let queries = history
@@ -1306,6 +1661,14 @@ fn upstream_buffer_button<'a>(
                             None
                         },
                     ),
+                    Entry::ServerNotices => (
+                        "Open server notices",
+                        Some(Message::ToggleInternalBuffer(
+                            buffer::Internal::ServerNotices(
+                                buffer.server().clone(),
+                            ),
+                        )),
+                    ),
                     Entry::MarkAsRead => (
                         if matches!(&buffer, buffer::Upstream::Server(_)) {
                             "Mark server buffer as read"
@@ -1358,6 +1721,51 @@ fn upstream_buffer_button<'a>(
                         "Remove server from sidebar",
                         Some(Message::Remove(buffer.server().clone())),
                     ),
+                    Entry::Pin => {
+                        ("Pin to top", Some(Message::Pin(buffer.clone())))
+                    }
+                    Entry::Unpin => {
+                        ("Unpin", Some(Message::Release(buffer.clone())))
+                    }
+                    Entry::AddToGroup(index) => {
+                        let name = layout
+                            .groups
+                            .get(index)
+                            .map_or("group", |group| group.name.as_str());
+
+                        return entry_button(
+                            text(format!("Move to {name}")),
+                            Some(Message::AddToGroup(buffer.clone(), index)),
+                        );
+                    }
+                    Entry::NewGroup => (
+                        "Move to new group",
+                        Some(Message::NewGroup(buffer.clone())),
+                    ),
+                    Entry::RemoveFromGroup => (
+                        "Remove from group",
+                        Some(Message::Release(buffer.clone())),
+                    ),
+                    Entry::MuteFor(hours) => {
+                        return entry_button(
+                            text(if hours == 1 {
+                                "Mute for 1 hour".to_string()
+                            } else {
+                                format!("Mute for {hours} hours")
+                            }),
+                            Some(Message::Mute(
+                                buffer.clone(),
+                                Some(TimeDelta::hours(hours)),
+                            )),
+                        );
+                    }
+                    Entry::Mute => (
+                        "Mute until unmuted",
+                        Some(Message::Mute(buffer.clone(), None)),
+                    ),
+                    Entry::Unmute => {
+                        ("Unmute", Some(Message::Unmute(buffer.clone())))
+                    }
                     Entry::Context => {
                         return container(
                             row![
@@ -1423,16 +1831,25 @@ fn upstream_buffer_button<'a>(
                     },
                 };
 
-                button(text(content))
-                    .width(length)
-                    .padding(config.context_menu.padding.entry)
-                    .style(|theme, status| {
-                        theme::button::primary(theme, status, false)
-                    })
-                    .on_press_maybe(message)
-                    .into()
+                entry_button(text(content), message)
             },
         )
         .into()
-    }
+    };
+
+    let element = if is_drop_target {
+        container(element)
+            .style(theme::container::sidebar_drop_target)
+            .into()
+    } else {
+        element
+    };
+
+    draggable(
+        element,
+        on_drag,
+        Message::DragEntered(target.clone()),
+        Message::DragExited(target),
+        Message::Dropped,
+    )
 }
@@ -0,0 +1,75 @@
+use data::Config;
+use data::buffer::switcher::Entry;
+use iced::Length;
+use iced::widget::container;
+
+use crate::theme;
+use crate::widget::{Element, combo_box, key_press};
+
+#[derive(Debug, Clone)]
+pub struct Switcher {
+    state: combo_box::State<Entry>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Selected(Entry),
+    Unfocused,
+    Ignored,
+}
+
+pub enum Event {
+    Selected(data::buffer::Upstream),
+    Unfocused,
+}
+
+impl Switcher {
+    pub fn new(entries: Vec<Entry>) -> Self {
+        // Markers aren't part of the buffer names searched for
+        let state = combo_box::State::with_matcher(entries, Entry::name);
+        state.focus();
+
+        Self { state }
+    }
+
+    pub fn update(&mut self, message: Message) -> Option<Event> {
+        match message {
+            Message::Selected(entry) => Some(Event::Selected(entry.buffer)),
+            Message::Unfocused => Some(Event::Unfocused),
+            Message::Ignored => None,
+        }
+    }
+
+    pub fn view<'a>(&'a self, config: &'a Config) -> Element<'a, Message> {
+        // 1px larger than default
+        let font_size =
+            config.font.size.map_or(theme::TEXT_SIZE, f32::from) + 1.0;
+        let line_height = theme::line_height(&config.font);
+
+        let combo_box = combo_box(
+            &self.state,
+            "Jump to channel or query...",
+            None,
+            Message::Selected,
+        )
+        .on_close(Message::Unfocused)
+        .size(font_size)
+        .line_height(line_height)
+        .padding([8, 8]);
+
+        // Capture ESC so we can close the combobox manually from application
+        // and prevent undesired effects
+        let combo_box = key_press(
+            combo_box,
+            key_press::Key::Named(key_press::Named::Escape),
+            key_press::Modifiers::default(),
+            Message::Ignored,
+        );
+
+        container(combo_box)
+            .padding(1)
+            .style(theme::container::tooltip)
+            .width(Length::Fixed(480.0))
+            .into()
+    }
+}
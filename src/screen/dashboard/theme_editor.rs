@@ -1,13 +1,13 @@
 use std::path::PathBuf;
 use std::time::Duration;
 
-use data::appearance::theme::FontStyle;
+use data::appearance::theme::{FontStyle, import};
 use data::{Config, url};
 use futures::TryFutureExt;
 use iced::Length::*;
 use iced::alignment::Vertical;
 use iced::widget::text::LineHeight;
-use iced::widget::{button, center, column, container, row, text_input};
+use iced::widget::{button, center, column, container, row, text, text_input};
 use iced::{Color, Length, Padding, Task, Vector, alignment, clipboard};
 use strum::IntoEnumIterator;
 use tokio::time;
@@ -32,6 +32,8 @@ pub enum Message {
     Component(Component),
     HexInput(String),
     Save,
+    Import,
+    FixContrast,
     Apply,
     Discard,
     Revert,
@@ -41,6 +43,9 @@ pub enum Message {
     SavePath(Option<PathBuf>),
     Saved(Result<(), String>),
     ClearSaveResult,
+    ImportPath(Option<PathBuf>),
+    Imported(Result<import::Imported, String>),
+    ClearImportResult,
     ClearCopy,
 }
 
@@ -51,6 +56,7 @@ pub struct ThemeEditor {
     component: Component,
     hex_input: Option<String>,
     save_result: Option<bool>,
+    import_result: Option<bool>,
     copied: bool,
 }
 
@@ -61,7 +67,7 @@ impl ThemeEditor {
     ) -> (Self, Task<window::Id>) {
         let (window, task) = window::open(window::Settings {
             // Just big enough to show all components in combobox
-            size: iced::Size::new(555.0, 300.0),
+            size: iced::Size::new(555.0, 340.0),
             resizable: false,
             position: main_window
                 .position
@@ -82,6 +88,7 @@ impl ThemeEditor {
                 component: Component::Text(Text::Primary),
                 hex_input: None,
                 save_result: None,
+                import_result: None,
                 copied: false,
             },
             task,
@@ -149,6 +156,48 @@ impl ThemeEditor {
 
                 return (Task::perform(task, Message::SavePath), None);
             }
+            Message::Import => {
+                let task = async move {
+                    rfd::AsyncFileDialog::new()
+                        .add_filter(
+                            "Color schemes",
+                            &["yaml", "yml", "itermcolors", "toml", "conf"],
+                        )
+                        .pick_file()
+                        .await
+                        .map(|handle| handle.path().to_path_buf())
+                };
+
+                return (Task::perform(task, Message::ImportPath), None);
+            }
+            Message::FixContrast => {
+                self.hex_input = None;
+
+                let mut styles = *theme.styles();
+
+                for component in contrast_issues(&styles) {
+                    if let Some(color) = component.color(&styles)
+                        && let Some(background) =
+                            component.contrast_background(&styles)
+                    {
+                        let font_style =
+                            component.font_style(&styles).flatten();
+
+                        component.update(
+                            &mut styles,
+                            Some(theme::fix_contrast(
+                                color,
+                                background,
+                                theme::MINIMUM_CONTRAST,
+                            )),
+                            font_style,
+                        );
+                    }
+                }
+
+                *theme = theme
+                    .preview(data::Theme::new("Custom Theme".into(), styles));
+            }
             Message::Apply => {
                 // Keep theme in preview mode, it'll get overwritten the next time they
                 // change theme in-app
@@ -248,6 +297,48 @@ impl ThemeEditor {
             Message::ClearSaveResult => {
                 self.save_result = None;
             }
+            Message::ImportPath(None) => {}
+            Message::ImportPath(Some(path)) => {
+                log::debug!("Importing theme from {path:?}");
+
+                return (
+                    Task::perform(
+                        import::import_file(path, None)
+                            .map_err(|e| e.to_string()),
+                        Message::Imported,
+                    ),
+                    None,
+                );
+            }
+            Message::Imported(Err(err)) => {
+                log::error!("Failed to import theme: {err}");
+                self.import_result = Some(false);
+
+                return (
+                    Task::perform(time::sleep(Duration::from_secs(2)), |()| {
+                        Message::ClearImportResult
+                    }),
+                    None,
+                );
+            }
+            Message::Imported(Ok(imported)) => {
+                log::debug!("Theme imported to {:?}", imported.path);
+                self.hex_input = None;
+                self.import_result = Some(true);
+
+                *theme = theme
+                    .preview(data::Theme::new(imported.name, imported.styles));
+
+                return (
+                    Task::perform(time::sleep(Duration::from_secs(2)), |()| {
+                        Message::ClearImportResult
+                    }),
+                    Some(Event::ReloadThemes),
+                );
+            }
+            Message::ClearImportResult => {
+                self.import_result = None;
+            }
             Message::ClearCopy => {
                 self.copied = false;
             }
@@ -311,6 +402,10 @@ impl ThemeEditor {
             Some(is_success) => status_button(is_success),
             None => secondary_button("Save to Disk", Message::Save),
         };
+        let import = match self.import_result {
+            Some(is_success) => status_button(is_success),
+            None => secondary_button("Import...", Message::Import),
+        };
         let apply =
             secondary_button("Apply Colors & Font Styles", Message::Apply);
 
@@ -327,6 +422,48 @@ impl ThemeEditor {
             theme,
         );
 
+        // Contrast of the selected component against its background
+        let contrast = self.component.contrast_background(theme.styles()).map(
+            |background| {
+                let ratio = theme::contrast_ratio(color, background);
+
+                text(format!("{ratio:.1}:1")).style(
+                    if ratio < theme::MINIMUM_CONTRAST {
+                        theme::text::error
+                    } else {
+                        theme::text::secondary
+                    },
+                )
+            },
+        );
+
+        let issues = contrast_issues(theme.styles());
+        let audit = (!issues.is_empty()).then(|| {
+            // Cycle through the flagged components
+            let next = issues
+                .iter()
+                .position(|component| *component == self.component)
+                .and_then(|index| issues.get(index + 1))
+                .unwrap_or(&issues[0]);
+
+            row![
+                container(
+                    text(format!(
+                        "{} {} below {}:1 contrast",
+                        issues.len(),
+                        if issues.len() == 1 { "color" } else { "colors" },
+                        theme::MINIMUM_CONTRAST,
+                    ))
+                    .style(theme::text::error)
+                )
+                .width(Fill),
+                secondary_button("Select Next", Message::Component(*next)),
+                secondary_button("Fix Contrast", Message::FixContrast),
+            ]
+            .align_y(Vertical::Center)
+            .spacing(4)
+        });
+
         let color_picker = color_picker(color, Message::Color);
 
         let font_style_pick_list = font_style.map(|font_style| {
@@ -345,6 +482,7 @@ impl ThemeEditor {
             row![
                 container(component).width(Fill),
                 container(hex_input).width(80),
+                contrast,
                 font_style_pick_list,
                 undo,
                 copy,
@@ -353,7 +491,8 @@ impl ThemeEditor {
             .align_y(Vertical::Center)
             .spacing(4),
             color_picker,
-            row![apply, save].spacing(4),
+            audit,
+            row![apply, save, import].spacing(4),
         ]
         .spacing(8);
 
@@ -425,6 +564,19 @@ fn status_button<'a>(is_success: bool) -> Element<'a, Message> {
     .into()
 }
 
+/// Text components below the minimum contrast against their background.
+fn contrast_issues(styles: &Styles) -> Vec<Component> {
+    components()
+        .filter_map(|component| {
+            let color = component.color(styles)?;
+            let background = component.contrast_background(styles)?;
+            let ratio = theme::contrast_ratio(color, background);
+
+            (ratio < theme::MINIMUM_CONTRAST).then_some(component)
+        })
+        .collect()
+}
+
 fn components() -> impl Iterator<Item = Component> {
     General::iter()
         .map(Component::General)
@@ -473,6 +625,28 @@ impl Component {
         }
     }
 
+    /// Background that text drawn in this component's color is read
+    /// against, or `None` when it isn't a text color.
+    fn contrast_background(&self, styles: &Styles) -> Option<Color> {
+        match self {
+            Component::General(General::HorizontalRuleText)
+            | Component::Text(_)
+            | Component::Buffer(
+                Buffer::Action
+                | Buffer::Code
+                | Buffer::Nickname
+                | Buffer::NicknameOffline
+                | Buffer::ServerMessages(_)
+                | Buffer::Timestamp
+                | Buffer::Topic
+                | Buffer::Url
+                | Buffer::BacklogRuleText
+                | Buffer::DateRuleText,
+            ) => Some(styles.buffer.background),
+            _ => None,
+        }
+    }
+
     fn font_style(&self, styles: &Styles) -> Option<Option<FontStyle>> {
         match self {
             Component::General(_) => None,
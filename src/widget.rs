@@ -10,6 +10,7 @@ pub use self::combo_box::combo_box;
 pub use self::context_menu::context_menu;
 pub use self::decorate::decorate;
 pub use self::double_pass::double_pass;
+pub use self::draggable::draggable;
 pub use self::font_style_pick_list::font_style_pick_list;
 pub use self::key_press::key_press;
 pub use self::message_content::message_content;
@@ -32,6 +33,7 @@ pub mod context_menu;
 pub mod decorate;
 pub mod double_click;
 pub mod double_pass;
+pub mod draggable;
 pub mod font_style_pick_list;
 pub mod key_press;
 pub mod message_content;
@@ -236,11 +236,30 @@ where
     /// Creates a new [`State`] for a [`ComboBox`] with the given list of options
     /// and selected value.
     pub fn with_selection(options: Vec<T>, selection: Option<&T>) -> Self {
-        let value = selection.map(T::to_string).unwrap_or_default();
-
         // Pre-build "matcher" strings ahead of time so that search is fast
         let option_matchers = build_matchers(&options);
 
+        Self::with_matchers(options, option_matchers, selection)
+    }
+
+    /// Creates a new [`State`] for a [`ComboBox`] whose options are searched
+    /// by `matcher`, rather than by how they are displayed.
+    pub fn with_matcher(
+        options: Vec<T>,
+        matcher: impl Fn(&T) -> String,
+    ) -> Self {
+        let option_matchers = options.iter().map(matcher).collect();
+
+        Self::with_matchers(options, option_matchers, None)
+    }
+
+    fn with_matchers(
+        options: Vec<T>,
+        option_matchers: Vec<String>,
+        selection: Option<&T>,
+    ) -> Self {
+        let value = selection.map(T::to_string).unwrap_or_default();
+
         let filtered_options = Filtered::new(
             search(&options, &option_matchers, &value)
                 .cloned()
@@ -855,8 +874,9 @@ where
     }
 
     // exact match score first, then substring match score, then number of
-    // substring matches, then higher fuzzy match score
-    hits.sort_unstable_by_key(|h| {
+    // substring matches, then higher fuzzy match score (stable, so that ties
+    // keep the order the options were given in)
+    hits.sort_by_key(|h| {
         (Reverse(h.1), Reverse(h.2), Reverse(h.3), Reverse(h.4))
     });
 
@@ -0,0 +1,120 @@
+use iced::advanced::widget::Tree;
+use iced::advanced::{Clipboard, Layout, Shell, mouse};
+use iced::{Point, event};
+
+use crate::Element;
+use crate::widget::{Renderer, decorate};
+
+/// How far the cursor has to travel while pressed before a drag starts, so
+/// regular clicks still reach the content.
+const THRESHOLD: f32 = 6.0;
+
+/// Makes `content` a drag source and a drop target.  `on_drag` is published
+/// once the content is dragged, `on_enter` and `on_exit` when the cursor
+/// crosses it, and `on_drop` when a drag started here is released.  Pass
+/// `None` for `on_drag` to only accept drops.
+pub fn draggable<'a, Message>(
+    content: impl Into<Element<'a, Message>>,
+    on_drag: Option<Message>,
+    on_enter: Message,
+    on_exit: Message,
+    on_drop: Message,
+) -> Element<'a, Message>
+where
+    Message: Clone + 'a,
+{
+    decorate(content)
+        .update(
+            move |state: &mut Internal,
+                  inner: &mut Element<'a, Message>,
+                  tree: &mut Tree,
+                  event: &iced::Event,
+                  layout: Layout<'_>,
+                  cursor: mouse::Cursor,
+                  renderer: &Renderer,
+                  clipboard: &mut dyn Clipboard,
+                  shell: &mut Shell<'_, Message>,
+                  viewport: &iced::Rectangle| {
+                inner.as_widget_mut().update(
+                    tree, event, layout, cursor, renderer, clipboard, shell,
+                    viewport,
+                );
+
+                // The content (usually a button) captures presses, so they
+                // are tracked regardless of capture status.
+                match event {
+                    event::Event::Mouse(mouse::Event::ButtonPressed {
+                        button: mouse::Button::Left,
+                        ..
+                    }) => {
+                        if on_drag.is_some()
+                            && let Some(position) =
+                                cursor.position_over(layout.bounds())
+                        {
+                            state.pressed_at = Some(position);
+                        }
+                    }
+                    event::Event::Mouse(mouse::Event::CursorMoved {
+                        position,
+                    }) => {
+                        if let Some(pressed_at) = state.pressed_at
+                            && !state.dragging
+                            && pressed_at.distance(*position) > THRESHOLD
+                            && let Some(on_drag) = &on_drag
+                        {
+                            state.dragging = true;
+                            shell.publish(on_drag.clone());
+                        }
+
+                        let is_hovered = cursor.is_over(layout.bounds());
+
+                        if is_hovered != state.is_hovered {
+                            state.is_hovered = is_hovered;
+
+                            shell.publish(if is_hovered {
+                                on_enter.clone()
+                            } else {
+                                on_exit.clone()
+                            });
+                        }
+                    }
+                    event::Event::Mouse(mouse::Event::ButtonReleased(
+                        mouse::Button::Left,
+                    )) => {
+                        if state.dragging {
+                            shell.publish(on_drop.clone());
+                        }
+
+                        state.pressed_at = None;
+                        state.dragging = false;
+                    }
+                    _ => {}
+                }
+            },
+        )
+        .mouse_interaction(
+            move |state: &Internal,
+                  inner: &Element<'a, Message>,
+                  tree: &Tree,
+                  layout: Layout<'_>,
+                  cursor: mouse::Cursor,
+                  viewport: &iced::Rectangle,
+                  renderer: &Renderer| {
+                if state.dragging {
+                    mouse::Interaction::Grabbing
+                } else {
+                    inner.as_widget().mouse_interaction(
+                        tree, layout, cursor, viewport, renderer,
+                    )
+                }
+            },
+        )
+        .into()
+}
+
+#[derive(Debug, Clone, Default)]
+struct Internal {
+    pressed_at: Option<Point>,
+    dragging: bool,
+    is_hovered: bool,
+}
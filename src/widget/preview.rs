@@ -1,8 +1,18 @@
+use std::time::Instant;
+
+use data::config::preview::Playback;
+use data::preview::code;
 use data::{Config, Preview, preview};
-use iced::widget::{column, container, text};
-use iced::{ContentFit, Padding};
+use iced::advanced::{
+    Clipboard, Layout, Shell, Widget, layout, renderer, widget,
+};
+use iced::widget::{column, container, row, stack, text};
+use iced::{
+    ContentFit, Event, Length, Padding, Rectangle, Size, alignment, mouse,
+    window,
+};
 
-use super::Element;
+use super::{Column, Element, Renderer, Text};
 use crate::widget::image;
 use crate::{Theme, font, theme};
 
@@ -17,51 +27,382 @@ pub fn preview_content<'a, M: 'a>(
             title,
             description,
             ..
-        }) => container(
+        }) => card(
             column![
-                text(title)
-                    .shaping(text::Shaping::Advanced)
-                    .style(theme::text::primary)
-                    .font_maybe(
-                        theme::font_style::primary(theme).map(font::get)
-                    ),
+                title_text(title, theme),
                 description.as_ref().map(|description| {
-                    container(
-                        text(description)
-                            .shaping(text::Shaping::Advanced)
-                            .wrapping(text::Wrapping::WordOrGlyph)
-                            .style(theme::text::secondary)
-                            .font_maybe(
-                                theme::font_style::secondary(theme)
-                                    .map(font::get),
-                            ),
-                    )
-                    .clip(false)
-                    .max_height(config.preview.card.description_max_height)
+                    description_text(description, config, theme)
                 }),
-                config.preview.card.show_image.then_some(
-                    container(image::from_data(
+                config.preview.card.show_image.then_some(card_image_content(
+                    image::from_data(
                         card_image,
                         config.preview.card.round_image_corners,
                         ContentFit::ScaleDown,
-                    ))
-                    .padding(Padding::default().top(8))
-                    .max_height(config.preview.card.image_max_height),
-                ),
-            ]
-            .spacing(8)
-            .max_width(config.preview.card.max_width),
-        )
+                    ),
+                    config,
+                )),
+            ],
+            config,
+        ),
+
+        Preview::Image(img) => container(image_content(img, config))
+            .max_width(config.preview.image.max_width)
+            .max_height(config.preview.image.max_height)
+            .into(),
+
+        Preview::Video(video) => card(
+            column![
+                title_text(&video.title, theme),
+                video.description.as_ref().map(|description| {
+                    description_text(description, config, theme)
+                }),
+                config.preview.card.show_image.then_some(card_image_content(
+                    stack![
+                        image::from_data(
+                            &video.poster,
+                            config.preview.card.round_image_corners,
+                            ContentFit::ScaleDown,
+                        ),
+                        video.duration_label().map(|duration| {
+                            container(
+                                container(
+                                    text(duration)
+                                        .size(theme::TEXT_SIZE - 2.0)
+                                        .style(theme::text::primary),
+                                )
+                                .padding([2, 4])
+                                .style(theme::container::tooltip),
+                            )
+                            .padding(6)
+                            .width(Length::Fill)
+                            .height(Length::Fill)
+                            .align_x(alignment::Horizontal::Right)
+                            .align_y(alignment::Vertical::Bottom)
+                        }),
+                    ],
+                    config,
+                )),
+            ],
+            config,
+        ),
+
+        Preview::Code(code) => {
+            let header = match &code.kind {
+                code::Kind::Repository { .. } => code.host.clone(),
+                code::Kind::Issue { .. } | code::Kind::PullRequest { .. } => {
+                    format!("{}/{}", code.host, code.repository)
+                }
+            };
+
+            let footer: Option<Element<'a, M>> = match &code.kind {
+                code::Kind::Repository { description } => {
+                    description.as_ref().map(|description| {
+                        description_text(description, config, theme)
+                    })
+                }
+                code::Kind::Issue { number, state } => {
+                    Some(code_state(*state, format!("Issue #{number}"), theme))
+                }
+                code::Kind::PullRequest { number, state } => Some(code_state(
+                    *state,
+                    format!("Pull request #{number}"),
+                    theme,
+                )),
+            };
+
+            card(
+                column![
+                    secondary_text(header, theme),
+                    title_text(&code.title, theme),
+                    footer,
+                ],
+                config,
+            )
+        }
+
+        Preview::Snippet(snippet) => card(
+            column![
+                secondary_text(&snippet.source, theme),
+                text(&snippet.text)
+                    .shaping(text::Shaping::Advanced)
+                    .wrapping(text::Wrapping::WordOrGlyph)
+                    .style(theme::text::primary),
+                snippet.truncated.then_some(secondary_text("…", theme)),
+            ],
+            config,
+        ),
+
+        Preview::Embed(embed) => card(
+            column![
+                embed
+                    .provider_name
+                    .as_ref()
+                    .map(|provider_name| secondary_text(provider_name, theme)),
+                title_text(&embed.title, theme),
+                embed.author_name.as_ref().map(|author_name| {
+                    secondary_text(format!("by {author_name}"), theme)
+                }),
+                embed
+                    .thumbnail
+                    .as_ref()
+                    .filter(|_| config.preview.card.show_image)
+                    .map(|thumbnail| {
+                        card_image_content(
+                            image::from_data(
+                                thumbnail,
+                                config.preview.card.round_image_corners,
+                                ContentFit::ScaleDown,
+                            ),
+                            config,
+                        )
+                    }),
+            ],
+            config,
+        ),
+    }
+}
+
+fn card<'a, M: 'a>(
+    content: Column<'a, M>,
+    config: &'a Config,
+) -> Element<'a, M> {
+    container(content.spacing(8).max_width(config.preview.card.max_width))
         .padding(8)
-        .into(),
-
-        Preview::Image(img) => container(image::from_data(
-            img,
-            config.preview.image.round_corners,
-            ContentFit::ScaleDown,
-        ))
-        .max_width(config.preview.image.max_width)
-        .max_height(config.preview.image.max_height)
-        .into(),
+        .into()
+}
+
+fn card_image_content<'a, M: 'a>(
+    content: impl Into<Element<'a, M>>,
+    config: &'a Config,
+) -> Element<'a, M> {
+    container(content)
+        .padding(Padding::default().top(8))
+        .max_height(config.preview.card.image_max_height)
+        .into()
+}
+
+fn title_text<'a>(title: &'a str, theme: &'a Theme) -> Text<'a> {
+    text(title)
+        .shaping(text::Shaping::Advanced)
+        .style(theme::text::primary)
+        .font_maybe(theme::font_style::primary(theme).map(font::get))
+}
+
+fn secondary_text<'a>(
+    content: impl text::IntoFragment<'a>,
+    theme: &'a Theme,
+) -> Text<'a> {
+    text(content)
+        .shaping(text::Shaping::Advanced)
+        .style(theme::text::secondary)
+        .font_maybe(theme::font_style::secondary(theme).map(font::get))
+}
+
+fn description_text<'a, M: 'a>(
+    description: &'a str,
+    config: &'a Config,
+    theme: &'a Theme,
+) -> Element<'a, M> {
+    container(
+        text(description)
+            .shaping(text::Shaping::Advanced)
+            .wrapping(text::Wrapping::WordOrGlyph)
+            .style(theme::text::secondary)
+            .font_maybe(theme::font_style::secondary(theme).map(font::get)),
+    )
+    .clip(false)
+    .max_height(config.preview.card.description_max_height)
+    .into()
+}
+
+fn code_state<'a, M: 'a>(
+    state: code::State,
+    label: String,
+    theme: &'a Theme,
+) -> Element<'a, M> {
+    let style = match state {
+        code::State::Open => theme::text::success,
+        code::State::Closed => theme::text::error,
+        code::State::Merged => theme::text::action,
+        code::State::Draft => theme::text::tertiary,
+    };
+
+    row![
+        text(state.to_string())
+            .style(style)
+            .font_maybe(theme::font_style::primary(theme).map(font::get)),
+        secondary_text(label, theme),
+    ]
+    .spacing(6)
+    .into()
+}
+
+fn image_content<'a, M: 'a>(
+    img: &'a data::Image,
+    config: &'a Config,
+) -> Element<'a, M> {
+    let playback = config.preview.image.animation.playback;
+    let round_corners = config.preview.image.round_corners;
+
+    if playback != Playback::Never
+        && let Some(animation) = &img.animation
+        && let Some(frames) = &animation.frames
+    {
+        return Animated {
+            animation,
+            playback,
+            frames: frames
+                .iter()
+                .map(|frame| {
+                    iced::widget::image(frame.clone())
+                        .border_radius(if round_corners { 4 } else { 0 })
+                        .content_fit(ContentFit::ScaleDown)
+                        .into()
+                })
+                .collect(),
+        }
+        .into();
+    }
+
+    image::from_data(img, round_corners, ContentFit::ScaleDown)
+}
+
+/// Plays the decoded frames of an animated image, redrawing itself at each
+/// frame's delay while visible
+struct Animated<'a, Message> {
+    animation: &'a data::image::Animation,
+    playback: Playback,
+    frames: Vec<Element<'a, Message>>,
+}
+
+#[derive(Debug, Default)]
+struct AnimatedState {
+    started_at: Option<Instant>,
+    frame: usize,
+    is_hovered: bool,
+}
+
+impl<Message> Widget<Message, Theme, Renderer> for Animated<'_, Message> {
+    fn size(&self) -> Size<Length> {
+        self.frames[0].as_widget().size()
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.frames[0].as_widget().size_hint()
+    }
+
+    fn tag(&self) -> widget::tree::Tag {
+        widget::tree::Tag::of::<AnimatedState>()
+    }
+
+    fn state(&self) -> widget::tree::State {
+        widget::tree::State::new(AnimatedState::default())
+    }
+
+    fn children(&self) -> Vec<widget::Tree> {
+        self.frames.iter().map(widget::Tree::new).collect()
+    }
+
+    fn diff(&self, tree: &mut widget::Tree) {
+        tree.diff_children(self.frames.as_slice());
+    }
+
+    fn layout(
+        &mut self,
+        tree: &mut widget::Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        // Every frame has the same dimensions
+        self.frames[0].as_widget_mut().layout(
+            &mut tree.children[0],
+            renderer,
+            limits,
+        )
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut widget::Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_mut::<AnimatedState>();
+
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { .. })
+                if self.playback == Playback::OnHover =>
+            {
+                let is_hovered = cursor.is_over(layout.bounds());
+
+                if is_hovered != state.is_hovered {
+                    state.is_hovered = is_hovered;
+                    state.started_at = None;
+                    state.frame = 0;
+
+                    shell.request_redraw();
+                }
+            }
+            Event::Window(window::Event::RedrawRequested(now)) => {
+                let is_playing = match self.playback {
+                    Playback::Autoplay => true,
+                    Playback::OnHover => state.is_hovered,
+                    Playback::Never => false,
+                };
+
+                // Scrolling the preview back into view redraws it, which
+                // resumes playback
+                if !is_playing || !viewport.intersects(&layout.bounds()) {
+                    return;
+                }
+
+                let started_at = *state.started_at.get_or_insert(*now);
+                let (frame, next) = self
+                    .animation
+                    .frame_at(now.saturating_duration_since(started_at));
+
+                state.frame = frame.min(self.frames.len() - 1);
+
+                shell.request_redraw_at(*now + next);
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &widget::Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let frame = tree.state.downcast_ref::<AnimatedState>().frame;
+
+        self.frames[frame].as_widget().draw(
+            &tree.children[frame],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+}
+
+impl<'a, Message> From<Animated<'a, Message>> for Element<'a, Message>
+where
+    Message: 'a,
+{
+    fn from(animated: Animated<'a, Message>) -> Self {
+        Element::new(animated)
     }
 }
@@ -17,7 +17,7 @@ where
 {
     decorate(base)
         .update(
-            move |modifiers: &mut shortcut::Modifiers,
+            move |state: &mut State,
                   inner: &mut Element<'a, Message>,
                   tree: &mut Tree,
                   event: &iced::Event,
@@ -27,42 +27,54 @@ where
                   clipboard: &mut dyn Clipboard,
                   shell: &mut Shell<'_, Message>,
                   viewport: &iced::Rectangle| {
-                match &event {
-                    Event::Keyboard(keyboard::Event::KeyPressed {
-                        key,
-                        physical_key,
-                        modifiers,
-                        text,
-                        ..
-                    }) => {
-                        // Treat numpad keys as character keys when numlock is
-                        // on (i.e. text.is_some())
-                        let key_bind = if is_numpad(physical_key)
-                            && let Some(text) = text
-                        {
-                            shortcut::KeyBind::from((
-                                keyboard::Key::Character(text.clone()),
-                                *modifiers,
-                            ))
-                        } else {
-                            shortcut::KeyBind::from((key.clone(), *modifiers))
-                        };
+                if let Event::Keyboard(keyboard::Event::KeyPressed {
+                    key,
+                    physical_key,
+                    modifiers,
+                    text,
+                    ..
+                }) = &event
+                    // Pressing a modifier on its own doesn't interrupt a chord
+                    && !is_modifier(key)
+                {
+                    // Treat numpad keys as character keys when numlock is
+                    // on (i.e. text.is_some())
+                    let key_bind = if is_numpad(physical_key)
+                        && let Some(text) = text
+                    {
+                        shortcut::KeyBind::from((
+                            keyboard::Key::Character(text.clone()),
+                            *modifiers,
+                        ))
+                    } else {
+                        shortcut::KeyBind::from((key.clone(), *modifiers))
+                    };
 
-                        if let Some(command) = shortcuts
-                            .iter()
-                            .find_map(|shortcut| shortcut.execute(&key_bind))
-                        {
+                    state.pending.push(key_bind);
+
+                    match shortcut::matching(&shortcuts, &state.pending) {
+                        shortcut::Matched::Command(command) => {
+                            state.pending.clear();
                             shell.publish((on_press)(command));
                             shell.capture_event();
                             return;
                         }
+                        shortcut::Matched::Pending => {
+                            shell.capture_event();
+                            return;
+                        }
+                        shortcut::Matched::None => {
+                            let in_chord = state.pending.len() > 1;
+
+                            state.pending.clear();
+
+                            // The key that breaks a chord only cancels it
+                            if in_chord {
+                                shell.capture_event();
+                                return;
+                            }
+                        }
                     }
-                    Event::Keyboard(keyboard::Event::ModifiersChanged(
-                        new_modifiers,
-                    )) => {
-                        *modifiers = (*new_modifiers).into();
-                    }
-                    _ => {}
                 }
 
                 inner.as_widget_mut().update(
@@ -73,3 +85,21 @@ where
         )
         .into()
 }
+
+#[derive(Debug, Clone, Default)]
+struct State {
+    /// Keys of a chord pressed so far.
+    pending: Vec<shortcut::KeyBind>,
+}
+
+fn is_modifier(key: &keyboard::Key) -> bool {
+    matches!(
+        key,
+        keyboard::Key::Named(
+            keyboard::key::Named::Shift
+                | keyboard::key::Named::Control
+                | keyboard::key::Named::Alt
+                | keyboard::key::Named::Super
+        )
+    )
+}